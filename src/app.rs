@@ -1,13 +1,24 @@
-use crate::models::{AppMode, KubeResource, KubeResourceEvent, PendingAction, ResourceType};
+use crate::audit::AuditEntry;
+use crate::cert::CertInfo;
+use crate::config::{Config, PluginOutput};
+use crate::k8s::watcher::PaneStore;
+use crate::models::{
+    AccessCheck, AppMode, ConfigValue, EditorRequest, Endpoint, FilterMode, KubeResource,
+    KubeResourceEvent, LogStreamEnd, ManifestObject, MetricSeries, NamespaceSummary, Notification,
+    PendingAction, ResourceEdit, ResourceType, SecretConsumer, SecretDiffRow, SecretForm,
+    SecretOwner, WatchHealth,
+};
 use crate::state::AppState;
+use crate::utils::NameMatcher;
 use k8s_openapi::api::{
     apps::v1::Deployment,
-    core::v1::{Pod, Secret},
+    core::v1::{ConfigMap, Pod, Secret},
 };
 use kube::Client;
 use kube::runtime::reflector::Store;
+use ratatui::layout::Rect;
 use ratatui::widgets::{ListState, TableState};
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::io::Read;
 use std::sync::Arc;
 use std::time::Instant;
@@ -20,8 +31,303 @@ pub struct ShellSession {
     _master: Box<dyn portable_pty::MasterPty + Send>,
 }
 
+/// Spawned one-shot operations still running, labelled for the status bar.
+#[derive(Debug, Default, Clone)]
+pub struct TaskRegistry(Arc<std::sync::Mutex<TaskList>>);
+
+#[derive(Debug, Default)]
+struct TaskList {
+    next_id: u64,
+    running: std::collections::BTreeMap<u64, RunningTask>,
+    /// Per object, the last mutation queued on it.
+    tails: HashMap<String, (u64, tokio::sync::watch::Receiver<()>)>,
+}
+
+#[derive(Debug)]
+struct RunningTask {
+    label: String,
+    global: bool,
+    /// Set right after spawning; a task can finish before that.
+    handle: Option<AbortHandle>,
+    mutation: Option<Mutation>,
+    /// Waiting for an earlier mutation of the same object.
+    queued: bool,
+}
+
+/// What a mutating task does, and to which objects (see `App::object_key`).
+#[derive(Debug)]
+struct Mutation {
+    verb: String,
+    objects: Vec<String>,
+}
+
+/// Unregisters a task when its future completes or is dropped by an abort.
+struct TaskGuard(TaskRegistry, u64);
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        let mut list = self.0.lock();
+        list.running.remove(&self.1);
+        list.tails.retain(|_, (id, _)| *id != self.1);
+    }
+}
+
+impl TaskRegistry {
+    fn lock(&self) -> std::sync::MutexGuard<'_, TaskList> {
+        self.0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Runs `fut` on its own task, listed under `label` until it finishes.
+    pub fn spawn<F>(&self, label: impl Into<String>, fut: F)
+    where
+        F: std::future::Future + Send + 'static,
+    {
+        self.start(label.into(), false, None, fut);
+    }
+
+    /// Runs a mutation of `objects` after the earlier ones touching them.
+    pub fn mutate<F>(
+        &self,
+        label: impl Into<String>,
+        verb: impl Into<String>,
+        objects: Vec<String>,
+        fut: F,
+    ) -> Result<(), String>
+    where
+        F: std::future::Future + Send + 'static,
+    {
+        let verb = verb.into();
+        if let Some(clash) = objects.iter().find_map(|o| self.in_flight(&verb, o)) {
+            return Err(clash);
+        }
+        let mutation = Mutation { verb, objects };
+        self.start(label.into(), false, Some(mutation), fut);
+        Ok(())
+    }
+
+    /// The label of a queued or running `verb` mutation of `object`.
+    pub fn in_flight(&self, verb: &str, object: &str) -> Option<String> {
+        self.lock()
+            .running
+            .values()
+            .find(|t| {
+                t.mutation
+                    .as_ref()
+                    .is_some_and(|m| m.verb == verb && m.objects.iter().any(|o| o == object))
+            })
+            .map(|t| t.label.clone())
+    }
+
+    /// Like `spawn`, for work that isn't tied to the current context.
+    pub fn spawn_global<F>(&self, label: impl Into<String>, fut: F)
+    where
+        F: std::future::Future + Send + 'static,
+    {
+        self.start(label.into(), true, None, fut);
+    }
+
+    fn start<F>(&self, label: String, global: bool, mutation: Option<Mutation>, fut: F)
+    where
+        F: std::future::Future + Send + 'static,
+    {
+        let (done, _) = tokio::sync::watch::channel(());
+        let (id, after) = {
+            let mut list = self.lock();
+            list.next_id += 1;
+            let id = list.next_id;
+            let mut after = Vec::new();
+            for object in mutation.iter().flat_map(|m| &m.objects) {
+                let tail = (id, done.subscribe());
+                if let Some((_, previous)) = list.tails.insert(object.clone(), tail) {
+                    after.push(previous);
+                }
+            }
+            let task = RunningTask {
+                label,
+                global,
+                handle: None,
+                mutation,
+                queued: !after.is_empty(),
+            };
+            list.running.insert(id, task);
+            (id, after)
+        };
+        let guard = TaskGuard(self.clone(), id);
+        let handle = tokio::spawn(async move {
+            let _done = done;
+            if !after.is_empty() {
+                for mut previous in after {
+                    while previous.changed().await.is_ok() {}
+                }
+                if let Some(task) = guard.0.lock().running.get_mut(&id) {
+                    task.queued = false;
+                }
+            }
+            let _guard = guard;
+            fut.await;
+        })
+        .abort_handle();
+        if let Some(task) = self.lock().running.get_mut(&id) {
+            task.handle = Some(handle);
+        }
+    }
+
+    /// Labels of the running tasks, oldest first.
+    pub fn labels(&self) -> Vec<String> {
+        self.lock()
+            .running
+            .values()
+            .map(|t| match t.queued {
+                true => format!("{} (queued)", t.label),
+                false => t.label.clone(),
+            })
+            .collect()
+    }
+
+    /// Aborts the tasks bound to the context being left.
+    pub fn cancel_context_tasks(&self) {
+        self.cancel(|t| !t.global);
+    }
+
+    pub fn cancel_all(&self) {
+        self.cancel(|_| true);
+    }
+
+    /// Aborts the tasks listed under `label`; false when there were none.
+    pub fn cancel_labelled(&self, label: &str) -> bool {
+        let found = self.lock().running.values().any(|t| t.label == label);
+        self.cancel(|t| t.label == label);
+        found
+    }
+
+    fn cancel(&self, which: impl Fn(&RunningTask) -> bool) {
+        let handles: Vec<AbortHandle> = self
+            .lock()
+            .running
+            .values()
+            .filter(|t| which(t))
+            .filter_map(|t| t.handle.clone())
+            .collect();
+        // Outside the lock: an aborted task's guard takes it.
+        for handle in handles {
+            handle.abort();
+        }
+    }
+}
+
+/// The object a describe view was opened for.
+#[derive(Debug, Clone)]
+pub struct DescribeTarget {
+    /// `kubectl` kind, e.g. `pod`.
+    pub kind: &'static str,
+    pub name: String,
+    pub namespace: String,
+    pub context: String,
+}
+
+/// Screen regions from the last draw, used to route mouse clicks.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HitAreas {
+    /// The tab bar row.
+    pub tabs: Rect,
+    /// The resource table, borders included.
+    pub table: Rect,
+    /// The open modal; clicks outside it close it.
+    pub modal: Option<Rect>,
+}
+
+/// Text area sizes of the scrollable views as last drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    /// Body rows of the resource table.
+    pub table_rows: usize,
+    pub log_rows: usize,
+    pub describe_rows: usize,
+    /// Columns and rows of wrapped text in the value pager.
+    pub pager: (usize, usize),
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self {
+            table_rows: 20,
+            log_rows: 20,
+            describe_rows: 20,
+            pager: (78, 20),
+        }
+    }
+}
+
+/// What a tab keeps while another tab is shown.
+#[derive(Debug, Default, Clone)]
+pub struct TabState {
+    pub filter_query: String,
+    pub status_filter: HashSet<String>,
+    /// Name of the selected row; indices do not survive a reload.
+    pub selected: Option<String>,
+    pub column_offset: usize,
+}
+
+/// `--tab`, `--filter` and `--status`, applied once the first context is set up.
+#[derive(Debug, Default, Clone)]
+pub struct Startup {
+    pub tab: Option<ResourceType>,
+    pub filter: Option<String>,
+    pub status: Vec<String>,
+}
+
+/// A resource remembered by `m` + letter, found again by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mark {
+    pub context: String,
+    pub namespace: String,
+    pub tab: ResourceType,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkAction {
+    Set,
+    Jump,
+}
+
+/// Second pane of the split view: the active tab in another context/namespace.
+pub struct SplitPane {
+    pub context: String,
+    pub namespace: String,
+    pub client: Client,
+    pub store: Option<PaneStore>,
+    pub watcher: Option<AbortHandle>,
+    /// Whether the pane reads the main store rather than its own watch.
+    pub shared: bool,
+    pub items: Vec<KubeResource>,
+    pub error: Option<String>,
+    pub stale: bool,
+}
+
+impl Drop for SplitPane {
+    fn drop(&mut self) {
+        if let Some(handle) = self.watcher.take() {
+            handle.abort();
+        }
+    }
+}
+
 pub(crate) const MAX_LOG_LINES: usize = 10_000;
-pub(crate) const LOG_CHROME_LINES: usize = 6;
+const CLUSTER_PROBE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+/// Probe interval while the API server is unreachable.
+const CLUSTER_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+/// Minimum time between re-authentication attempts.
+const REAUTH_COOLDOWN: std::time::Duration = std::time::Duration::from_secs(60);
+pub const DESCRIBE_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+const MAX_NOTIFICATIONS: usize = 200;
+/// Above this many items, the filter waits for a pause in typing.
+const FILTER_DEBOUNCE_ABOVE: usize = 2000;
+const FILTER_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(150);
+const DEFAULT_DETAIL_WIDTH: u16 = 40;
+const DETAIL_WIDTH_STEP: u16 = 5;
 
 pub(crate) fn contains_ascii_ci(haystack: &str, needle_lower: &str) -> bool {
     if needle_lower.is_empty() {
@@ -33,19 +339,91 @@ pub(crate) fn contains_ascii_ci(haystack: &str, needle_lower: &str) -> bool {
         .any(|w| w.eq_ignore_ascii_case(needle_lower.as_bytes()))
 }
 
+pub(crate) fn decode_secret_data(secret: &Secret) -> Vec<(String, ConfigValue)> {
+    secret
+        .data
+        .iter()
+        .flatten()
+        .map(|(k, v)| (k.clone(), ConfigValue::from_bytes(v.0.clone())))
+        .collect()
+}
+
+/// Merges `data` and `binaryData` into one key-sorted list.
+pub(crate) fn configmap_entries(cm: &ConfigMap) -> Vec<(String, ConfigValue)> {
+    let mut entries: Vec<(String, ConfigValue)> = cm
+        .data
+        .iter()
+        .flatten()
+        .map(|(k, v)| (k.clone(), ConfigValue::Text(v.clone())))
+        .collect();
+    entries.extend(
+        cm.binary_data
+            .iter()
+            .flatten()
+            .map(|(k, v)| (k.clone(), ConfigValue::from_bytes(v.0.clone()))),
+    );
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries
+}
+
+/// Controllers whose generated secrets get overwritten on every sync.
+const SECRET_OWNER_KINDS: &[&str] = &["ExternalSecret", "SealedSecret"];
+
+pub(crate) fn secret_owner(secret: &Secret) -> Option<SecretOwner> {
+    secret
+        .metadata
+        .owner_references
+        .iter()
+        .flatten()
+        .find(|o| SECRET_OWNER_KINDS.contains(&o.kind.as_str()))
+        .map(|o| SecretOwner {
+            kind: o.kind.clone(),
+            name: o.name.clone(),
+            api_version: o.api_version.clone(),
+        })
+}
+
+/// Parsed `tls.crt` for `kubernetes.io/tls` secrets; `None` for other types.
+pub(crate) fn secret_cert_info(secret: &Secret) -> Option<Result<Vec<CertInfo>, String>> {
+    if secret.type_.as_deref() != Some("kubernetes.io/tls") {
+        return None;
+    }
+    let crt = secret.data.as_ref().and_then(|d| d.get("tls.crt"));
+    Some(match crt {
+        Some(bytes) => crate::cert::parse_pem_chain(&bytes.0),
+        None => Err("secret has no tls.crt".to_string()),
+    })
+}
+
+/// Sets the terminal's clipboard with an OSC52 escape.
+pub(crate) fn write_osc52(text: &str) -> std::io::Result<()> {
+    use std::io::{IsTerminal, Write};
+    let mut out = std::io::stdout();
+    if !out.is_terminal() {
+        return Err(std::io::Error::other("stdout is not a terminal"));
+    }
+    let in_tmux = std::env::var_os("TMUX").is_some();
+    out.write_all(crate::utils::osc52_sequence(text, in_tmux).as_bytes())?;
+    out.flush()
+}
+
 pub struct App {
     pub client: Client,
     pub current_namespace: String,
 
     pub mode: AppMode,
+    /// Views beneath `mode`, innermost last; `pop_mode` returns to the top.
+    pub mode_stack: Vec<AppMode>,
     pub active_tab: ResourceType,
     pub should_quit: bool,
 
     pub pod_store: Option<Store<Pod>>,
     pub deployment_store: Option<Store<Deployment>>,
     pub secret_store: Option<Store<Secret>>,
+    pub configmap_store: Option<Store<ConfigMap>>,
     pub current_context: String,
     pub pending_context: Option<String>,
+    pub pending_editor: Option<EditorRequest>,
 
     pub event_tx: UnboundedSender<KubeResourceEvent>,
 
@@ -55,20 +433,88 @@ pub struct App {
     pub filter_query: String,
     pub selected_indices: HashSet<usize>,
 
-    pub selected_secret_decoded: Option<Vec<(String, String)>>,
+    pub selected_secret_decoded: Option<Vec<(String, ConfigValue)>>,
+    pub decoded_secret_name: String,
+    pub secret_certs: Option<Result<Vec<CertInfo>, String>>,
+    pub secret_owner: Option<SecretOwner>,
+    pub secret_owner_status: Option<String>,
+    pub split: Option<SplitPane>,
+    /// Watch of pods and deployments for the context's webhooks.
+    pub webhook_watcher: Option<AbortHandle>,
+    pub resource_edit: Option<ResourceEdit>,
+    pub manifest_plan: Vec<ManifestObject>,
+    /// Graphs of the metrics panel; `None` while they load.
+    pub metrics: Option<Vec<MetricSeries>>,
+    pub metrics_deployment: bool,
+    pub metrics_target: String,
+    pub hit_areas: HitAreas,
+    /// One-row areas of the last frame to turn into OSC 8 hyperlinks.
+    pub drawn_links: Vec<(Rect, String)>,
+    pub viewport: Viewport,
+    /// Extra columns (node, IP, images, ...) on the workload tabs.
+    pub wide: bool,
+    /// Columns scrolled past in wide mode; the name column stays pinned.
+    pub column_offset: usize,
+    /// Filter, cursor and scroll of the tabs not currently shown.
+    pub tab_states: HashMap<ResourceType, TabState>,
+    /// Row to select once the tab's items load, by name.
+    pub restore_selection: Option<String>,
+    /// Command-line view options; win over the context's configured tab.
+    pub startup: Startup,
+    /// Row where `V` visual selection started; moving extends the range.
+    pub visual_anchor: Option<usize>,
+    /// Rows selected before visual mode began; the range is added to them.
+    pub visual_base: HashSet<usize>,
+    pub tasks: TaskRegistry,
+    pub watch_health: WatchHealth,
+    /// Set by `R`; the event loop restarts the watcher and relists.
+    pub refresh_requested: bool,
+    /// Set on a 401; the event loop rebuilds the client for the context.
+    pub reauth_requested: bool,
+    /// Session of each context that authenticates with `tsh`.
+    pub teleport_status: HashMap<String, crate::k8s::teleport::LoginStatus>,
+    /// Context to run `tsh kube login` for.
+    pub tsh_login: Option<String>,
+    pub reauth_at: Option<Instant>,
+    /// When the watcher last delivered anything, to spot a stalled watch.
+    pub last_watch_event: Option<Instant>,
+    pub split_input: String,
     pub log_buffer: VecDeque<String>,
     pub log_task: Option<AbortHandle>,
     pub log_scroll_offset: Option<usize>,
 
     pub available_contexts: Vec<String>,
+    pub filtered_contexts: Vec<String>,
+    pub context_input: String,
+    pub context_typing: bool,
+    pub context_missing: bool,
+    pub cluster_version: Option<String>,
+    pub cluster_reachable: Option<bool>,
+    pub cluster_probe_at: Option<Instant>,
+    pub cluster_unreachable_since: Option<Instant>,
+    pub cluster_error: Option<String>,
+    pub warm_clients: HashMap<String, Client>,
+    pub access_checks: Vec<AccessCheck>,
+    pub read_only: bool,
+    pub namespace_summary: Option<NamespaceSummary>,
+    pub command_input: String,
+    pub command_history_pos: Option<usize>,
+    pub filter_history_pos: Option<usize>,
+    /// When a debounced filter recomputation is due (large lists only).
+    pub filter_due: Option<Instant>,
+    /// Tab, mode and query `filtered_items` was last computed for.
+    pub filter_applied: Option<(ResourceType, FilterMode, String)>,
     pub available_namespaces: Vec<String>,
     pub filtered_namespaces: Vec<String>,
     pub namespace_input: String,
     pub namespace_typing: bool,
+    pub namespace_create_input: String,
     pub popup_state: ListState,
 
     pub last_error: Option<String>,
     pub last_success: Option<String>,
+    /// Every footer message, oldest first, capped at `MAX_NOTIFICATIONS`.
+    pub notifications: VecDeque<Notification>,
     pub message_time: Option<Instant>,
     pub is_loading: bool,
     pub loading_since: Option<Instant>,
@@ -77,13 +523,46 @@ pub struct App {
     pub secret_scroll: usize,
     pub secret_table_state: TableState,
     pub secret_revealed: bool,
+    pub secret_show_base64: bool,
+    pub secret_edit_input: String,
+    pub export_path_input: String,
+    pub export_clean: bool,
+    pub secret_diff_input: String,
+    pub secret_diff_target: String,
+    pub secret_diff: Vec<SecretDiffRow>,
+    pub configmap_name: String,
+    pub configmap_entries: Option<Vec<(String, ConfigValue)>>,
+    pub configmap_scroll: usize,
+    pub configmap_value_scroll: u16,
+    pub configmap_table_state: TableState,
+    pub pager_title: String,
+    pub pager_text: String,
+    pub pager_scroll: usize,
+    pub pager_query: String,
+    pub pager_search_input: String,
+    pub secret_usage_name: String,
+    pub secret_usage: Vec<SecretConsumer>,
+    pub secret_usage_scroll: usize,
 
     pub scale_input: String,
+    pub secret_form: SecretForm,
 
     pub pending_action: Option<PendingAction>,
+    /// Text typed into a confirm modal that needs more than `y`.
+    pub confirm_input: String,
+    /// Vim-style count typed before a motion key (`5j`).
+    pub pending_count: Option<usize>,
 
     pub describe_content: Vec<String>,
     pub describe_scroll: usize,
+    /// What the describe view shows, so it can be re-run.
+    pub describe_target: Option<DescribeTarget>,
+    /// Re-run describe every `DESCRIBE_REFRESH_INTERVAL` while it's open.
+    pub describe_auto_refresh: bool,
+    pub describe_refreshed_at: Option<Instant>,
+    /// Confirmed search, lowercase; matches are highlighted.
+    pub describe_query: String,
+    pub describe_search_input: String,
 
     pub shell_session: Option<ShellSession>,
     pub shell_title: String,
@@ -91,6 +570,7 @@ pub struct App {
     pub clipboard_clear_task: Option<AbortHandle>,
 
     pub log_pod_name: String,
+    pub log_end: Option<LogStreamEnd>,
     pub log_namespace: String,
     pub log_tail_lines: i64,
     pub log_loading_history: bool,
@@ -102,6 +582,18 @@ pub struct App {
     pub status_filter_items: Vec<(String, usize)>,
     pub status_filter_selected: HashSet<usize>,
     pub status_filter_state: ListState,
+    pub action_menu_state: ListState,
+    /// `kubectl` plugins found on PATH, as the words that invoke them.
+    pub kubectl_plugins: Option<Vec<String>>,
+    pub plugin_menu_state: ListState,
+    pub link_menu_state: ListState,
+    /// URLs of the `O` popup.
+    pub endpoints: Vec<Endpoint>,
+    pub endpoint_menu_state: ListState,
+    /// Set by `m` or `'`; the next letter names the mark.
+    pub pending_mark: Option<MarkAction>,
+    /// Resources marked with `m` + letter in this session.
+    pub marks: HashMap<char, Mark>,
 
     pub log_search_query: String,
     pub log_search_input: String,
@@ -109,11 +601,36 @@ pub struct App {
     pub log_search_pending: bool,
 
     pub app_state: AppState,
+    pub config: Config,
 }
 
 impl App {
+    /// Reads the config file and installs its theme and symbols.
+    pub fn load_config() -> (Config, Option<String>) {
+        let (config, config_error) = match Config::load() {
+            Ok(config) => (config, None),
+            Err(e) => (Config::default(), Some(format!("Config error: {e}"))),
+        };
+        let config_error = match config.theme.build() {
+            Ok(theme) => {
+                crate::ui::theme::init(theme);
+                config_error
+            }
+            Err(e) => config_error.or(Some(format!("Config error: {e}"))),
+        };
+        let config_error = match config.symbols.build() {
+            Ok(symbols) => {
+                crate::ui::symbols::init(symbols);
+                config_error
+            }
+            Err(e) => config_error.or(Some(format!("Config error: {e}"))),
+        };
+        (config, config_error)
+    }
+
     pub async fn new(
         client: Client,
+        (config, config_error): (Config, Option<String>),
     ) -> anyhow::Result<(
         Self,
         tokio::sync::mpsc::UnboundedReceiver<KubeResourceEvent>,
@@ -122,95 +639,279 @@ impl App {
             crate::k8s::config::get_context_namespace().unwrap_or_else(|_| "default".to_string());
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
 
-        Ok((
-            Self {
-                client,
-                current_namespace: namespace,
-                mode: AppMode::List,
-                active_tab: ResourceType::Pod,
-                should_quit: false,
-                pod_store: None,
-                deployment_store: None,
-                secret_store: None,
-                event_tx: tx,
-                items: Vec::new(),
-                filtered_items: Vec::new(),
-                table_state: TableState::default(),
-                filter_query: String::new(),
-                selected_indices: HashSet::new(),
-                selected_secret_decoded: None,
-                log_buffer: VecDeque::new(),
-                log_task: None,
-                log_scroll_offset: None,
-                current_context: "default".into(),
-                pending_context: None,
-                available_contexts: Vec::new(),
-                available_namespaces: Vec::new(),
-                filtered_namespaces: Vec::new(),
-                namespace_input: String::new(),
-                namespace_typing: false,
-                popup_state: ListState::default(),
-                last_error: None,
-                last_success: None,
-                message_time: None,
-                is_loading: true,
-                loading_since: Some(Instant::now()),
-                dirty: true,
-                secret_scroll: 0,
-                secret_table_state: TableState::default(),
-                secret_revealed: false,
-                scale_input: String::new(),
-                pending_action: None,
-                describe_content: Vec::new(),
-                describe_scroll: 0,
-                shell_session: None,
-                shell_title: String::new(),
-                clipboard_clear_task: None,
-                log_pod_name: String::new(),
-                log_namespace: String::new(),
-                log_tail_lines: 100,
-                log_loading_history: false,
-                log_generation: 0,
-                log_history_exhausted: false,
-                log_history_task: None,
-                status_filter: HashSet::new(),
-                status_filter_items: Vec::new(),
-                status_filter_selected: HashSet::new(),
-                status_filter_state: ListState::default(),
-                log_search_query: String::new(),
-                log_search_input: String::new(),
-                log_search_match_line: None,
-                log_search_pending: false,
-                app_state: AppState::load(),
-            },
-            rx,
-        ))
+        let mut app = Self {
+            client,
+            current_namespace: namespace,
+            mode: AppMode::List,
+            mode_stack: Vec::new(),
+            active_tab: ResourceType::Pod,
+            should_quit: false,
+            pod_store: None,
+            deployment_store: None,
+            secret_store: None,
+            configmap_store: None,
+            event_tx: tx,
+            items: Vec::new(),
+            filtered_items: Vec::new(),
+            table_state: TableState::default(),
+            filter_query: String::new(),
+            selected_indices: HashSet::new(),
+            selected_secret_decoded: None,
+            decoded_secret_name: String::new(),
+            secret_certs: None,
+            secret_owner: None,
+            secret_owner_status: None,
+            split: None,
+            webhook_watcher: None,
+            resource_edit: None,
+            manifest_plan: Vec::new(),
+            metrics: None,
+            metrics_deployment: false,
+            metrics_target: String::new(),
+            hit_areas: HitAreas::default(),
+            drawn_links: Vec::new(),
+            viewport: Viewport::default(),
+            wide: false,
+            column_offset: 0,
+            tab_states: HashMap::new(),
+            restore_selection: None,
+            startup: Startup::default(),
+            visual_anchor: None,
+            visual_base: HashSet::new(),
+            tasks: TaskRegistry::default(),
+            watch_health: WatchHealth::Syncing,
+            refresh_requested: false,
+            reauth_requested: false,
+            teleport_status: HashMap::new(),
+            tsh_login: None,
+            reauth_at: None,
+            last_watch_event: None,
+            split_input: String::new(),
+            log_buffer: VecDeque::new(),
+            log_task: None,
+            log_scroll_offset: None,
+            current_context: "default".into(),
+            pending_context: None,
+            pending_editor: None,
+            available_contexts: Vec::new(),
+            filtered_contexts: Vec::new(),
+            context_input: String::new(),
+            context_typing: false,
+            context_missing: false,
+            cluster_version: None,
+            cluster_reachable: None,
+            cluster_probe_at: None,
+            cluster_unreachable_since: None,
+            cluster_error: None,
+            warm_clients: HashMap::new(),
+            access_checks: Vec::new(),
+            read_only: false,
+            namespace_summary: None,
+            command_input: String::new(),
+            command_history_pos: None,
+            filter_history_pos: None,
+            filter_due: None,
+            filter_applied: None,
+            available_namespaces: Vec::new(),
+            filtered_namespaces: Vec::new(),
+            namespace_input: String::new(),
+            namespace_typing: false,
+            namespace_create_input: String::new(),
+            popup_state: ListState::default(),
+            last_error: None,
+            last_success: None,
+            notifications: VecDeque::new(),
+            message_time: None,
+            is_loading: true,
+            loading_since: Some(Instant::now()),
+            dirty: true,
+            secret_scroll: 0,
+            secret_table_state: TableState::default(),
+            secret_revealed: false,
+            secret_show_base64: false,
+            secret_edit_input: String::new(),
+            export_path_input: String::new(),
+            export_clean: true,
+            secret_diff_input: String::new(),
+            secret_diff_target: String::new(),
+            secret_diff: Vec::new(),
+            configmap_name: String::new(),
+            configmap_entries: None,
+            configmap_scroll: 0,
+            configmap_value_scroll: 0,
+            configmap_table_state: TableState::default(),
+            pager_title: String::new(),
+            pager_text: String::new(),
+            pager_scroll: 0,
+            pager_query: String::new(),
+            pager_search_input: String::new(),
+            secret_usage_name: String::new(),
+            secret_usage: Vec::new(),
+            secret_usage_scroll: 0,
+            scale_input: String::new(),
+            secret_form: SecretForm::default(),
+            pending_action: None,
+            confirm_input: String::new(),
+            pending_count: None,
+            describe_content: Vec::new(),
+            describe_scroll: 0,
+            describe_target: None,
+            describe_auto_refresh: false,
+            describe_refreshed_at: None,
+            describe_query: String::new(),
+            describe_search_input: String::new(),
+            shell_session: None,
+            shell_title: String::new(),
+            clipboard_clear_task: None,
+            log_pod_name: String::new(),
+            log_end: None,
+            log_namespace: String::new(),
+            log_tail_lines: 100,
+            log_loading_history: false,
+            log_generation: 0,
+            log_history_exhausted: false,
+            log_history_task: None,
+            status_filter: HashSet::new(),
+            status_filter_items: Vec::new(),
+            status_filter_selected: HashSet::new(),
+            status_filter_state: ListState::default(),
+            action_menu_state: ListState::default(),
+            kubectl_plugins: None,
+            plugin_menu_state: ListState::default(),
+            link_menu_state: ListState::default(),
+            endpoints: Vec::new(),
+            endpoint_menu_state: ListState::default(),
+            pending_mark: None,
+            marks: HashMap::new(),
+            log_search_query: String::new(),
+            log_search_input: String::new(),
+            log_search_match_line: None,
+            log_search_pending: false,
+            app_state: AppState::load(),
+            config,
+        };
+        if let Some(e) = config_error {
+            app.set_error(e);
+        }
+        Ok((app, rx))
     }
 
     pub fn next_tab(&mut self) {
-        self.active_tab = match self.active_tab {
-            ResourceType::Pod => ResourceType::Deployment,
-            ResourceType::Deployment => ResourceType::Secret,
-            ResourceType::Secret => ResourceType::Pod,
-        };
-        self.reset_tab_state();
+        self.step_tab(1);
     }
 
     pub fn prev_tab(&mut self) {
-        self.active_tab = match self.active_tab {
-            ResourceType::Pod => ResourceType::Secret,
-            ResourceType::Deployment => ResourceType::Pod,
-            ResourceType::Secret => ResourceType::Deployment,
+        self.step_tab(ResourceType::ALL.len() - 1);
+    }
+
+    fn step_tab(&mut self, step: usize) {
+        let tabs = ResourceType::ALL;
+        let i = tabs.iter().position(|t| *t == self.active_tab).unwrap_or(0);
+        self.select_tab(tabs[(i + step) % tabs.len()]);
+    }
+
+    /// Switches to `tab`, stashing this tab's filter, cursor and column scroll.
+    pub fn select_tab(&mut self, tab: ResourceType) {
+        if self.active_tab == tab {
+            return;
+        }
+        let state = TabState {
+            filter_query: std::mem::take(&mut self.filter_query),
+            status_filter: std::mem::take(&mut self.status_filter),
+            selected: self.get_selected_resource().map(|r| r.name().to_string()),
+            column_offset: self.column_offset,
         };
+        self.tab_states.insert(self.active_tab, state);
+        self.active_tab = tab;
         self.reset_tab_state();
     }
 
-    fn reset_tab_state(&mut self) {
+    /// Clears the list for a reload of the active tab.
+    pub fn reset_tab_state(&mut self) {
+        let state = self.tab_states.remove(&self.active_tab).unwrap_or_default();
         self.items.clear();
         self.filtered_items.clear();
+        self.filter_applied = None;
+        self.filter_due = None;
         self.table_state.select(None);
         self.selected_indices.clear();
+        self.visual_anchor = None;
+        self.filter_query = state.filter_query;
+        self.status_filter = state.status_filter;
+        self.column_offset = state.column_offset;
+        self.restore_selection = state.selected;
+    }
+
+    /// Applies the command-line view options; later calls do nothing.
+    pub fn apply_startup(&mut self) {
+        let startup = std::mem::take(&mut self.startup);
+        if let Some(tab) = startup.tab {
+            self.select_tab(tab);
+        }
+        if let Some(query) = startup.filter {
+            self.filter_query = query;
+        }
+        if !startup.status.is_empty() {
+            self.status_filter = startup.status.into_iter().collect();
+        }
+        self.update_filter();
+    }
+
+    /// Marks the selected resource as `key`.
+    pub fn set_mark(&mut self, key: char) {
+        let Some(name) = self.get_selected_resource().map(|r| r.name().to_string()) else {
+            self.set_error("Nothing selected to mark".to_string());
+            return;
+        };
+        self.set_success(format!("Marked '{name}' as '{key}'"));
+        self.marks.insert(
+            key,
+            Mark {
+                context: self.current_context.clone(),
+                namespace: self.current_namespace.clone(),
+                tab: self.active_tab,
+                name,
+            },
+        );
+    }
+
+    /// Goes to the tab and namespace of mark `key` and selects its resource.
+    pub fn jump_to_mark(&mut self, key: char) {
+        let Some(mark) = self.marks.get(&key).cloned() else {
+            self.set_error(format!("Mark '{key}' is not set"));
+            return;
+        };
+        if mark.context != self.current_context {
+            self.set_error(format!(
+                "Mark '{key}' is in context '{}'",
+                self.context_label(&mark.context)
+            ));
+            return;
+        }
+        self.select_tab(mark.tab);
+        if mark.namespace != self.current_namespace {
+            self.switch_namespace(mark.namespace);
+            self.reset_tab_state();
+        }
+        self.filter_query.clear();
         self.status_filter.clear();
+        self.update_filter();
+        match self
+            .filtered_items
+            .iter()
+            .position(|r| r.name() == mark.name)
+        {
+            Some(i) => self.table_state.select(Some(i)),
+            None => self.restore_selection = Some(mark.name),
+        }
+    }
+
+    /// Asks the event loop to drop the watcher and relist the active tab.
+    pub fn request_refresh(&mut self) {
+        self.restore_selection = self.get_selected_resource().map(|r| r.name().to_string());
+        self.selected_indices.clear();
+        self.visual_anchor = None;
+        self.refresh_requested = true;
     }
 
     pub fn get_selected_resource(&self) -> Option<&KubeResource> {
@@ -221,84 +922,463 @@ impl App {
 
     pub fn decode_selected_secret(&mut self) {
         if let Some(KubeResource::Secret(s)) = self.get_selected_resource().cloned() {
-            if let Some(data) = &s.data {
-                let decoded: Vec<(String, String)> = data
-                    .iter()
-                    .map(|(k, v)| {
-                        let val = String::from_utf8(v.0.clone())
-                            .unwrap_or_else(|_| "<binary>".to_string());
-                        (k.clone(), val)
-                    })
-                    .collect();
-                self.selected_secret_decoded = Some(decoded);
-            } else {
-                self.selected_secret_decoded = Some(vec![]);
+            self.decoded_secret_name = s.metadata.name.clone().unwrap_or_default();
+            self.selected_secret_decoded = Some(decode_secret_data(&s));
+            self.secret_certs = secret_cert_info(&s);
+            self.secret_owner = secret_owner(&s);
+            self.secret_owner_status = None;
+            if let Some(owner) = self.secret_owner.clone() {
+                let client = self.client.clone();
+                let ns = self.current_namespace.clone();
+                let tx = self.event_tx.clone();
+                let name = self.decoded_secret_name.clone();
+                self.tasks.spawn("secret owner status", async move {
+                    let status =
+                        match crate::k8s::actions::fetch_owner_status(client, &ns, &owner).await {
+                            Ok(status) => status,
+                            Err(e) => format!("status unavailable: {e}"),
+                        };
+                    let _ = tx.send(KubeResourceEvent::SecretOwnerStatus(name, status));
+                });
             }
         }
     }
 
-    pub fn stream_logs(&mut self, pod_name: &str, namespace: &str) {
-        self.abort_log_stream();
-        self.log_buffer.clear();
-        self.log_scroll_offset = None;
-        self.log_tail_lines = 100;
-        self.log_loading_history = false;
-        self.log_generation += 1;
-        self.log_history_exhausted = false;
-        self.log_search_query.clear();
-        self.log_search_input.clear();
-        self.log_search_match_line = None;
-        self.log_search_pending = false;
-        self.log_pod_name = pod_name.to_owned();
-        self.log_namespace = namespace.to_owned();
-        self.mode = AppMode::LogView;
+    /// Re-decodes the open secret from the latest watcher state.
+    pub fn refresh_decoded_secret(&mut self) {
+        if self.selected_secret_decoded.is_none() {
+            return;
+        }
+        let fresh = self.items.iter().find_map(|item| match item {
+            KubeResource::Secret(s) if item.name() == self.decoded_secret_name => {
+                Some((decode_secret_data(s), secret_cert_info(s)))
+            }
+            _ => None,
+        });
+        if let Some((decoded, certs)) = fresh {
+            self.secret_scroll = self.secret_scroll.min(decoded.len().saturating_sub(1));
+            self.selected_secret_decoded = Some(decoded);
+            self.secret_certs = certs;
+        }
+    }
 
-        let abort = crate::k8s::actions::stream_pod_logs(
-            self.client.clone(),
-            namespace,
-            pod_name,
-            self.event_tx.clone(),
-            self.log_tail_lines,
-        );
-        self.log_task = Some(abort);
+    pub fn open_selected_configmap(&mut self) {
+        if let Some(KubeResource::ConfigMap(c)) = self.get_selected_resource().cloned() {
+            self.configmap_name = c.metadata.name.clone().unwrap_or_default();
+            self.configmap_entries = Some(configmap_entries(&c));
+        }
     }
 
-    pub fn load_more_history(&mut self) {
-        if self.log_loading_history || self.log_history_exhausted {
+    /// Counterpart of `refresh_decoded_secret` for the ConfigMap modal.
+    pub fn refresh_configmap_view(&mut self) {
+        if self.configmap_entries.is_none() {
             return;
         }
-        if self.log_tail_lines >= MAX_LOG_LINES as i64 {
-            self.log_history_exhausted = true;
-            return;
+        let fresh = self.items.iter().find_map(|item| match item {
+            KubeResource::ConfigMap(c) if item.name() == self.configmap_name => {
+                Some(configmap_entries(c))
+            }
+            _ => None,
+        });
+        if let Some(entries) = fresh {
+            self.configmap_scroll = self.configmap_scroll.min(entries.len().saturating_sub(1));
+            self.configmap_entries = Some(entries);
         }
-        self.log_loading_history = true;
-        self.log_tail_lines += 100;
-        let handle = crate::k8s::actions::fetch_log_history(
-            self.client.clone(),
-            &self.log_namespace,
-            &self.log_pod_name,
-            self.log_tail_lines,
-            self.log_generation,
-            self.event_tx.clone(),
-        );
-        self.log_history_task = Some(handle);
     }
 
-    pub fn merge_log_history(&mut self, generation: u64, lines: Vec<String>) {
-        if generation != self.log_generation {
-            self.log_loading_history = false;
-            return;
+    /// Splits `namespace` or `context/namespace` input, defaulting to the current context.
+    fn parse_target(&mut self, target: &str) -> Option<(String, String)> {
+        let (context, namespace) = match target.trim().split_once('/') {
+            Some((ctx, ns)) => (ctx.to_string(), ns.to_string()),
+            None => (self.current_context.clone(), target.trim().to_string()),
+        };
+        if namespace.is_empty() || context.is_empty() {
+            self.set_error("Enter a namespace or context/namespace".to_string());
+            return None;
         }
+        Some((context, namespace))
+    }
 
-        if lines.len() < self.log_tail_lines as usize {
-            self.log_history_exhausted = true;
-        }
+    /// Connects to `target` (`ns` or `ctx/ns`) in the background and opens the split pane.
+    pub fn request_split(&mut self, target: &str) {
+        let Some((context, namespace)) = self.parse_target(target) else {
+            return;
+        };
+        let same_context = context == self.current_context;
+        let client = self.client.clone();
+        let tx = self.event_tx.clone();
+        self.tasks
+            .spawn(format!("connect split pane to {target}"), async move {
+                let client = if same_context {
+                    client
+                } else {
+                    match crate::k8s::config::create_client_with_context(&context).await {
+                        Ok(c) => c,
+                        Err(e) => {
+                            let _ = tx.send(KubeResourceEvent::Error(format!(
+                                "Split failed: context '{context}': {e}"
+                            )));
+                            return;
+                        }
+                    }
+                };
+                let _ = tx.send(KubeResourceEvent::SplitReady(client, context, namespace));
+            });
+    }
 
-        let overlap_idx = self
-            .log_buffer
-            .front()
-            .and_then(|first| lines.iter().rposition(|l| l == first))
-            .unwrap_or(lines.len());
+    pub fn open_split(&mut self, client: Client, context: String, namespace: String) {
+        self.split = Some(SplitPane {
+            context,
+            namespace,
+            client,
+            store: None,
+            watcher: None,
+            shared: false,
+            items: Vec::new(),
+            error: None,
+            stale: false,
+        });
+        self.restart_split_watcher();
+    }
+
+    /// Re-watches the split pane for the active tab.
+    pub fn restart_split_watcher(&mut self) {
+        let tab = self.active_tab;
+        let tx = self.event_tx.clone();
+        let main_store = self.main_store();
+        let Some(pane) = &mut self.split else {
+            return;
+        };
+        if let Some(handle) = pane.watcher.take() {
+            handle.abort();
+        }
+        let same_view =
+            pane.context == self.current_context && pane.namespace == self.current_namespace;
+        match main_store.filter(|_| same_view) {
+            Some(store) => {
+                pane.items = store.items();
+                pane.store = Some(store);
+                pane.shared = true;
+            }
+            None => {
+                let (store, handle) = crate::k8s::watcher::spawn_pane_watcher(
+                    pane.client.clone(),
+                    &pane.namespace,
+                    tab,
+                    self.config.field_selector(tab),
+                    tx,
+                );
+                pane.store = Some(store);
+                pane.watcher = Some(handle);
+                pane.shared = false;
+                pane.items.clear();
+            }
+        }
+        pane.error = None;
+        pane.stale = false;
+    }
+
+    /// Whether the split pane depends on the main watch.
+    pub fn split_follows_main(&self) -> bool {
+        self.split.as_ref().is_some_and(|p| {
+            p.shared || (p.context == self.current_context && p.namespace == self.current_namespace)
+        })
+    }
+
+    /// The main list's store, once its watch has been created.
+    fn main_store(&self) -> Option<PaneStore> {
+        match self.active_tab {
+            ResourceType::Pod => self.pod_store.clone().map(PaneStore::Pod),
+            ResourceType::Deployment => self.deployment_store.clone().map(PaneStore::Deployment),
+            ResourceType::Secret => self.secret_store.clone().map(PaneStore::Secret),
+            ResourceType::ConfigMap => self.configmap_store.clone().map(PaneStore::ConfigMap),
+        }
+    }
+
+    pub fn refresh_split_if_stale(&mut self) {
+        if let Some(pane) = &mut self.split
+            && pane.stale
+        {
+            pane.items = pane.store.as_ref().map(|s| s.items()).unwrap_or_default();
+            pane.stale = false;
+        }
+    }
+
+    /// Compares the selected secret with the same-named secret at `target`.
+    pub fn diff_selected_secret(&mut self, target: &str) {
+        let Some(KubeResource::Secret(local)) = self.get_selected_resource().cloned() else {
+            self.set_error("No secret selected".to_string());
+            return;
+        };
+        let name = local.metadata.name.clone().unwrap_or_default();
+        let Some((context, namespace)) = self.parse_target(target) else {
+            return;
+        };
+        let left = local.data.clone().unwrap_or_default();
+        let same_context = context == self.current_context;
+        let client = self.client.clone();
+        let tx = self.event_tx.clone();
+        let label = format!("{context}/{namespace}");
+        self.tasks
+            .spawn(format!("diff against {label}"), async move {
+                let client = if same_context {
+                    client
+                } else {
+                    match crate::k8s::config::create_client_with_context(&context).await {
+                        Ok(c) => c,
+                        Err(e) => {
+                            let _ = tx.send(KubeResourceEvent::Error(format!(
+                                "Diff failed: context '{context}': {e}"
+                            )));
+                            return;
+                        }
+                    }
+                };
+                let secrets: kube::Api<Secret> = kube::Api::namespaced(client, &namespace);
+                let _ = tx.send(match secrets.get_opt(&name).await {
+                    Ok(Some(remote)) => {
+                        let right = remote.data.clone().unwrap_or_default();
+                        KubeResourceEvent::SecretDiffReady(
+                            format!("{name} vs {label}"),
+                            crate::utils::diff_secret_data(&left, &right),
+                        )
+                    }
+                    Ok(None) => {
+                        KubeResourceEvent::Error(format!("Secret '{name}' not found in {label}"))
+                    }
+                    Err(e) => KubeResourceEvent::Error(format!("Diff failed: {e}")),
+                });
+            });
+    }
+
+    /// Looks up pods and deployments referencing the selected secret.
+    pub fn load_namespace_summary(&mut self) {
+        let client = self.client.clone();
+        let ns = self.current_namespace.clone();
+        let tx = self.event_tx.clone();
+        self.set_success(format!("Summarizing '{ns}'..."));
+        self.tasks.spawn("namespace summary", async move {
+            let _ = tx.send(
+                match crate::k8s::actions::fetch_namespace_summary(client, &ns).await {
+                    Ok(summary) => KubeResourceEvent::NamespaceSummaryReady(ns, summary),
+                    Err(e) => KubeResourceEvent::Error(format!("Namespace summary failed: {e}")),
+                },
+            );
+        });
+    }
+
+    pub fn review_access(&mut self) {
+        let client = self.client.clone();
+        let ns = self.current_namespace.clone();
+        let tx = self.event_tx.clone();
+        self.set_success(format!("Checking permissions in '{ns}'..."));
+        self.tasks.spawn("access review", async move {
+            let checks =
+                crate::k8s::actions::review_access(client, &ns, crate::models::ACCESS_CHECKS).await;
+            let _ = tx.send(KubeResourceEvent::AccessReviewReady(ns, checks));
+        });
+    }
+
+    pub fn find_selected_secret_usage(&mut self) {
+        let Some(KubeResource::Secret(s)) = self.get_selected_resource() else {
+            self.set_error("No secret selected".to_string());
+            return;
+        };
+        let name = s.metadata.name.clone().unwrap_or_default();
+        let client = self.client.clone();
+        let ns = self.current_namespace.clone();
+        let tx = self.event_tx.clone();
+        self.set_success(format!("Looking for consumers of '{name}'..."));
+        self.tasks.spawn("secret usage", async move {
+            let _ = tx.send(
+                match crate::k8s::actions::find_secret_consumers(client, &ns, &name).await {
+                    Ok(consumers) => KubeResourceEvent::SecretUsageReady(name, consumers),
+                    Err(e) => KubeResourceEvent::Error(format!("Usage lookup failed: {e}")),
+                },
+            );
+        });
+    }
+
+    fn patch_secret_value(&mut self, secret: String, key: String, value: String) {
+        let client = self.client.clone();
+        let ns = self.current_namespace.clone();
+        let tx = self.event_tx.clone();
+        let object = self.object_key("secret", &secret);
+        let audit = self.audit("patch", format!("secret/{secret} key {key}"));
+        self.mutate(
+            format!("patch secret {secret}"),
+            format!("patch {key}"),
+            vec![object],
+            async move {
+                let result = crate::k8s::actions::patch_secret_key(
+                    client,
+                    &ns,
+                    &secret,
+                    &key,
+                    value.into_bytes(),
+                )
+                .await;
+                audit.record(&result);
+                let _ = tx.send(match result {
+                    Ok(()) => KubeResourceEvent::Success(format!("Updated '{key}' in '{secret}'")),
+                    Err(e) => KubeResourceEvent::Error(format!("Update '{key}' failed: {e}")),
+                });
+            },
+        );
+    }
+
+    fn create_secret(&mut self, name: String, type_: String, entries: Vec<(String, String)>) {
+        let client = self.client.clone();
+        let ns = self.current_namespace.clone();
+        let tx = self.event_tx.clone();
+        let object = self.object_key("secret", &name);
+        let audit = self.audit("create", format!("secret/{name}"));
+        self.mutate(
+            format!("create secret {name}"),
+            "create",
+            vec![object],
+            async move {
+                let result =
+                    crate::k8s::actions::create_secret(client, &ns, &name, &type_, &entries).await;
+                audit.record(&result);
+                let _ = tx.send(match result {
+                    Ok(()) => KubeResourceEvent::Success(format!("Secret '{name}' created")),
+                    Err(e) => KubeResourceEvent::Error(format!("Create '{name}' failed: {e}")),
+                });
+            },
+        );
+    }
+
+    fn create_namespace(&mut self, name: String) {
+        let client = self.client.clone();
+        let tx = self.event_tx.clone();
+        let object = self.object_key("namespace", &name);
+        let audit = self.audit("create", format!("namespace/{name}"));
+        self.mutate(
+            format!("create namespace {name}"),
+            "create",
+            vec![object],
+            async move {
+                let result = crate::k8s::actions::create_namespace(client, &name).await;
+                audit.record(&result);
+                let _ = tx.send(match result {
+                    Ok(()) => KubeResourceEvent::NamespaceCreated(name),
+                    Err(e) => {
+                        KubeResourceEvent::Error(format!("Create namespace '{name}' failed: {e}"))
+                    }
+                });
+            },
+        );
+    }
+
+    /// `TaskRegistry::mutate`, telling the user when it was refused.
+    pub fn mutate<F>(
+        &mut self,
+        label: impl Into<String>,
+        verb: impl Into<String>,
+        objects: Vec<String>,
+        fut: F,
+    ) where
+        F: std::future::Future + Send + 'static,
+    {
+        if let Err(clash) = self.tasks.mutate(label, verb, objects, fut) {
+            self.set_error(format!("'{clash}' is already in progress"));
+        }
+    }
+
+    pub fn stream_logs(&mut self, pod_name: &str, namespace: &str) {
+        self.abort_log_stream();
+        self.log_buffer.clear();
+        self.log_scroll_offset = None;
+        self.log_tail_lines = 100;
+        self.log_loading_history = false;
+        self.log_generation += 1;
+        self.log_history_exhausted = false;
+        self.log_search_query.clear();
+        self.log_search_input.clear();
+        self.log_search_match_line = None;
+        self.log_search_pending = false;
+        self.log_pod_name = pod_name.to_owned();
+        self.log_end = None;
+        self.log_namespace = namespace.to_owned();
+        self.push_mode(AppMode::LogView);
+
+        let abort = crate::k8s::actions::stream_pod_logs(
+            self.client.clone(),
+            namespace,
+            pod_name,
+            self.event_tx.clone(),
+            kube::api::LogParams {
+                follow: true,
+                tail_lines: Some(self.log_tail_lines),
+                ..Default::default()
+            },
+        );
+        self.log_task = Some(abort);
+    }
+
+    /// Records why the followed stream stopped.
+    pub fn apply_log_end(&mut self, pod_name: &str, end: LogStreamEnd) {
+        if self.log_task.is_none() || pod_name != self.log_pod_name {
+            return;
+        }
+        if let LogStreamEnd::PodTerminated {
+            replacement: Some(name),
+        } = &end
+        {
+            self.set_success(format!("Pod terminated, press R to follow {name}"));
+        }
+        self.log_end = Some(end);
+    }
+
+    /// Follows the pod that replaced the terminated one.
+    pub fn reattach_logs(&mut self) {
+        let Some(LogStreamEnd::PodTerminated {
+            replacement: Some(name),
+        }) = self.log_end.clone()
+        else {
+            return;
+        };
+        let namespace = self.log_namespace.clone();
+        self.stream_logs(&name, &namespace);
+    }
+
+    pub fn load_more_history(&mut self) {
+        if self.log_loading_history || self.log_history_exhausted {
+            return;
+        }
+        if self.log_tail_lines >= MAX_LOG_LINES as i64 {
+            self.log_history_exhausted = true;
+            return;
+        }
+        self.log_loading_history = true;
+        self.log_tail_lines += 100;
+        let handle = crate::k8s::actions::fetch_log_history(
+            self.client.clone(),
+            &self.log_namespace,
+            &self.log_pod_name,
+            self.log_tail_lines,
+            self.log_generation,
+            self.event_tx.clone(),
+        );
+        self.log_history_task = Some(handle);
+    }
+
+    pub fn merge_log_history(&mut self, generation: u64, lines: Vec<String>) {
+        if generation != self.log_generation {
+            self.log_loading_history = false;
+            return;
+        }
+
+        if lines.len() < self.log_tail_lines as usize {
+            self.log_history_exhausted = true;
+        }
+
+        let overlap_idx = self
+            .log_buffer
+            .front()
+            .and_then(|first| lines.iter().rposition(|l| l == first))
+            .unwrap_or(lines.len());
 
         let available = MAX_LOG_LINES.saturating_sub(self.log_buffer.len());
         let prepend_count = overlap_idx.min(available);
@@ -310,201 +1390,1558 @@ impl App {
             return;
         }
 
-        let start = overlap_idx - prepend_count;
-        for line in lines[start..overlap_idx].iter().rev() {
-            self.log_buffer.push_front(line.clone());
+        let start = overlap_idx - prepend_count;
+        for line in lines[start..overlap_idx].iter().rev() {
+            self.log_buffer.push_front(line.clone());
+        }
+
+        if let Some(offset) = &mut self.log_scroll_offset {
+            *offset += prepend_count;
+        }
+        if let Some(m) = &mut self.log_search_match_line {
+            *m += prepend_count;
+        }
+
+        self.log_loading_history = false;
+        self.resolve_pending_search(prepend_count);
+    }
+
+    fn resolve_pending_search(&mut self, new_line_count: usize) {
+        if !self.log_search_pending {
+            return;
+        }
+        self.log_search_pending = false;
+        if new_line_count == 0 {
+            if self.log_history_exhausted {
+                self.set_error("Not found".to_string());
+            }
+            return;
+        }
+        let needle = &self.log_search_query;
+        if needle.is_empty() {
+            return;
+        }
+        for idx in (0..new_line_count).rev() {
+            if contains_ascii_ci(&self.log_buffer[idx], needle) {
+                self.log_search_match_line = Some(idx);
+                let visible = self.viewport.log_rows;
+                self.scroll_to_line(idx, visible);
+                return;
+            }
+        }
+        if self.log_history_exhausted {
+            self.set_error("Not found".to_string());
+        } else {
+            self.set_success(format!(
+                "Not found in {} loaded lines, press n to load more",
+                self.log_buffer.len()
+            ));
+        }
+    }
+
+    pub fn abort_log_stream(&mut self) {
+        if let Some(handle) = self.log_task.take() {
+            handle.abort();
+        }
+        if let Some(handle) = self.log_history_task.take() {
+            handle.abort();
+        }
+        self.log_search_pending = false;
+    }
+
+    pub fn load_namespaces(&self) {
+        let client = self.client.clone();
+        let current_ns = self.current_namespace.clone();
+        let ctx = self.current_context.clone();
+        let tx = self.event_tx.clone();
+        self.tasks.spawn("list namespaces", async move {
+            use k8s_openapi::api::core::v1::Namespace;
+            use kube::Api;
+            use kube::api::ListParams;
+            let ns_api: Api<Namespace> = Api::all(client);
+            if let Ok(ns_list) = ns_api.list(&ListParams::default()).await {
+                let namespaces: Vec<String> = ns_list
+                    .iter()
+                    .map(|n| n.metadata.name.clone().unwrap_or_default())
+                    .collect();
+                let _ = tx.send(KubeResourceEvent::NamespacesLoaded(namespaces));
+                return;
+            }
+
+            if let Ok(output) = tokio::process::Command::new("kubectl")
+                .args([
+                    "get",
+                    "namespaces",
+                    "--context",
+                    &ctx,
+                    "-o",
+                    "jsonpath={.items[*].metadata.name}",
+                ])
+                .envs(crate::k8s::config::kubectl_env())
+                .output()
+                .await
+                && output.status.success()
+            {
+                let text = String::from_utf8_lossy(&output.stdout);
+                let namespaces: Vec<String> = text
+                    .split_whitespace()
+                    .map(|s| s.to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+                if !namespaces.is_empty() {
+                    let _ = tx.send(KubeResourceEvent::NamespacesLoaded(namespaces));
+                    return;
+                }
+            }
+
+            let _ = tx.send(KubeResourceEvent::NamespacesLoaded(vec![current_ns]));
+        });
+    }
+
+    /// Identifies an object for `TaskRegistry::mutate`.
+    pub fn object_key(&self, kind: &str, name: &str) -> String {
+        match kind {
+            "namespace" => format!("{}/{kind}/{name}", self.current_context),
+            _ => format!(
+                "{}/{}/{kind}/{name}",
+                self.current_context, self.current_namespace
+            ),
+        }
+    }
+
+    /// The configured alias for `context`, or the context name itself.
+    pub fn context_label<'a>(&'a self, context: &'a str) -> &'a str {
+        self.config.context_alias(context).unwrap_or(context)
+    }
+
+    pub fn update_context_filter(&mut self) {
+        let query = self.context_input.to_lowercase();
+        let matches = |ctx: &str| {
+            ctx.to_lowercase().contains(&query)
+                || self
+                    .config
+                    .context_alias(ctx)
+                    .is_some_and(|a| a.to_lowercase().contains(&query))
+        };
+        self.filtered_contexts = self
+            .available_contexts
+            .iter()
+            .filter(|ctx| query.is_empty() || matches(ctx))
+            .cloned()
+            .collect();
+        self.popup_state
+            .select((!self.filtered_contexts.is_empty()).then_some(0));
+    }
+
+    pub fn update_namespace_filter(&mut self) {
+        if self.namespace_input.is_empty() {
+            self.filtered_namespaces
+                .clone_from(&self.available_namespaces);
+        } else {
+            let matcher = NameMatcher::new(self.app_state.filter_mode, &self.namespace_input);
+            let mut scored: Vec<(i64, &String)> = self
+                .available_namespaces
+                .iter()
+                .filter_map(|ns| matcher.score(ns).map(|s| (s, ns)))
+                .collect();
+            if matcher.ranks() {
+                scored.sort_by_key(|(s, _)| std::cmp::Reverse(*s));
+            }
+            self.filtered_namespaces = scored.into_iter().map(|(_, ns)| ns.clone()).collect();
+        }
+        let favorites = self.app_state.get_favorites(&self.current_context);
+        self.filtered_namespaces
+            .sort_by_key(|ns| !favorites.contains(ns));
+        if self.filtered_namespaces.is_empty() {
+            self.popup_state.select(None);
+        } else {
+            self.popup_state.select(Some(0));
+        }
+    }
+
+    /// Switches to `ns`, remembering the namespace being left for `-`.
+    pub fn switch_namespace(&mut self, ns: String) {
+        let ctx = self.current_context.clone();
+        if ns != self.current_namespace {
+            self.app_state
+                .set_previous_namespace(&ctx, &self.current_namespace);
+        }
+        self.current_namespace = ns.clone();
+        self.app_state.add_namespace(&ctx, &ns);
+        if !self.available_namespaces.contains(&ns) {
+            self.available_namespaces.push(ns);
+            self.available_namespaces.sort();
+        }
+        self.app_state.save();
+    }
+
+    /// Moves to the next (or previous) known namespace of this context.
+    pub fn cycle_namespace(&mut self, forward: bool) {
+        let len = self.available_namespaces.len();
+        if len == 0 {
+            return;
+        }
+        let next = match self
+            .available_namespaces
+            .iter()
+            .position(|ns| *ns == self.current_namespace)
+        {
+            Some(i) if forward => (i + 1) % len,
+            Some(i) => (i + len - 1) % len,
+            None => 0,
+        };
+        let ns = self.available_namespaces[next].clone();
+        if ns != self.current_namespace {
+            self.switch_namespace(ns);
+        }
+    }
+
+    /// Detail pane width in percent, kept between 20 and 80.
+    pub fn detail_width(&self) -> u16 {
+        self.app_state
+            .detail_width
+            .unwrap_or(DEFAULT_DETAIL_WIDTH)
+            .clamp(20, 80)
+    }
+
+    pub fn toggle_detail_pane(&mut self) {
+        self.app_state.detail_pane = !self.app_state.detail_pane;
+        self.app_state.save();
+    }
+
+    /// Widens or narrows the detail pane, showing it if hidden.
+    pub fn resize_detail_pane(&mut self, grow: bool) {
+        let width = self.detail_width();
+        let width = if grow {
+            width + DETAIL_WIDTH_STEP
+        } else {
+            width - DETAIL_WIDTH_STEP
+        };
+        self.app_state.detail_width = Some(width.clamp(20, 80));
+        self.app_state.detail_pane = true;
+        self.app_state.save();
+    }
+
+    /// Recently used contexts worth pre-warming, most recent first.
+    pub fn prewarm_candidates(&self) -> Vec<String> {
+        self.app_state
+            .recent_contexts
+            .iter()
+            .filter(|ctx| **ctx != self.current_context && self.available_contexts.contains(ctx))
+            .filter(|ctx| !self.warm_clients.contains_key(*ctx))
+            .take(self.config.prewarm_contexts)
+            .cloned()
+            .collect()
+    }
+
+    /// Builds clients for recent contexts in the background.
+    pub fn prewarm_clients(&self) {
+        for context in self.prewarm_candidates() {
+            let tx = self.event_tx.clone();
+            self.tasks
+                .spawn_global(format!("prewarm {context}"), async move {
+                    let warm = async {
+                        let client =
+                            crate::k8s::config::create_client_with_context(&context).await?;
+                        crate::k8s::actions::fetch_server_version(client.clone()).await?;
+                        anyhow::Ok(client)
+                    };
+                    match warm.await {
+                        Ok(client) => {
+                            let _ = tx.send(KubeResourceEvent::ClientWarmed(context, client));
+                        }
+                        Err(e) => tracing::debug!("Pre-warming '{context}' failed: {e}"),
+                    }
+                });
+        }
+    }
+
+    /// Checks API reachability and the server version in the background.
+    pub fn probe_cluster(&mut self) {
+        self.cluster_probe_at = Some(Instant::now());
+        let client = self.client.clone();
+        let context = self.current_context.clone();
+        let tx = self.event_tx.clone();
+        self.tasks.spawn("cluster probe", async move {
+            let result = crate::k8s::actions::fetch_server_version(client).await;
+            if let Err(e) = &result
+                && e.downcast_ref()
+                    .is_some_and(crate::k8s::client::is_unauthorized)
+            {
+                let _ = tx.send(KubeResourceEvent::Unauthorized(format!(
+                    "API server rejected credentials: {e}"
+                )));
+            }
+            let result = result.map_err(|e| e.to_string());
+            let _ = tx.send(KubeResourceEvent::ClusterProbe(context, result));
+        });
+    }
+
+    /// Asks the event loop to rebuild the client, at most once per `REAUTH_COOLDOWN`.
+    pub fn request_reauth(&mut self) {
+        if self
+            .reauth_at
+            .is_some_and(|t| t.elapsed() < REAUTH_COOLDOWN)
+        {
+            return;
+        }
+        self.reauth_at = Some(Instant::now());
+        self.reauth_requested = true;
+    }
+
+    /// Asks the event loop to renew `context`'s Teleport session.
+    pub fn tsh_login(&mut self, context: String) {
+        if crate::k8s::config::teleport_login(&context).is_none() {
+            self.set_error(format!(
+                "Context '{}' doesn't authenticate with tsh",
+                self.context_label(&context)
+            ));
+            return;
+        }
+        self.tsh_login = Some(context);
+    }
+
+    /// Reads the Teleport sessions from `tsh status` in the background.
+    pub fn refresh_teleport_status(&self) {
+        let tx = self.event_tx.clone();
+        self.tasks.spawn_global("tsh status", async move {
+            let logins = crate::k8s::config::teleport_logins();
+            if logins.is_empty() {
+                return;
+            }
+            let status = match crate::k8s::teleport::status().await {
+                Ok(status) => status,
+                Err(e) => {
+                    tracing::debug!("{e}");
+                    return;
+                }
+            };
+            let now = jiff::Timestamp::now();
+            let sessions = logins
+                .into_iter()
+                .map(|(context, login)| (context, login.status(&status, now)))
+                .collect();
+            let _ = tx.send(KubeResourceEvent::TeleportStatus(sessions));
+        });
+    }
+
+    /// Swaps in a freshly authenticated client for the current context.
+    pub fn reauthenticated(&mut self, client: Client) {
+        self.warm_clients.remove(&self.current_context);
+        let mut restart_split = false;
+        if let Some(pane) = &mut self.split
+            && pane.context == self.current_context
+        {
+            pane.client = client.clone();
+            restart_split = !pane.shared;
+        }
+        self.client = client;
+        self.refresh_requested = true;
+        if restart_split {
+            self.restart_split_watcher();
+        }
+        self.cluster_probe_at = None;
+        self.set_success(format!("Re-authenticated with '{}'", self.current_context));
+    }
+
+    /// Runs `kubectl describe` for `target` in the background.
+    pub fn describe(&mut self, target: DescribeTarget, refresh: bool) {
+        if !refresh {
+            self.describe_auto_refresh = false;
+            self.describe_query.clear();
+        }
+        self.describe_refreshed_at = Some(Instant::now());
+        self.describe_target = Some(target.clone());
+        let tx = self.event_tx.clone();
+        let DescribeTarget {
+            kind,
+            name,
+            namespace,
+            context,
+        } = target;
+        self.tasks.spawn(format!("describe {name}"), async move {
+            match tokio::process::Command::new("kubectl")
+                .args([
+                    "describe",
+                    kind,
+                    &name,
+                    "-n",
+                    &namespace,
+                    "--context",
+                    &context,
+                ])
+                .envs(crate::k8s::config::kubectl_env())
+                .output()
+                .await
+            {
+                Ok(output) if output.status.success() => {
+                    let text = String::from_utf8_lossy(&output.stdout);
+                    let lines: Vec<String> = text.lines().map(|l| l.to_string()).collect();
+                    let _ = tx.send(KubeResourceEvent::DescribeReady(lines, refresh));
+                }
+                Ok(output) => {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    let _ = tx.send(KubeResourceEvent::Error(format!(
+                        "Describe failed: {}",
+                        stderr.trim()
+                    )));
+                }
+                Err(e) => {
+                    let _ = tx.send(KubeResourceEvent::Error(format!("Describe failed: {e}")));
+                }
+            }
+        });
+    }
+
+    /// Whether auto-refresh should re-run the open describe view now.
+    pub fn describe_refresh_due(&self) -> bool {
+        self.describe_auto_refresh
+            && matches!(
+                self.mode,
+                AppMode::DescribeView | AppMode::DescribeSearchInput
+            )
+            && self
+                .describe_refreshed_at
+                .is_none_or(|t| t.elapsed() >= DESCRIBE_REFRESH_INTERVAL)
+    }
+
+    pub fn refresh_describe(&mut self) {
+        if let Some(target) = self.describe_target.clone() {
+            self.describe(target, true);
+        }
+    }
+
+    /// Shows a describe result, or refreshes the open view's text.
+    pub fn show_describe(&mut self, lines: Vec<String>, refresh: bool) {
+        if !refresh {
+            self.describe_content = lines;
+            self.describe_scroll = 0;
+            self.push_mode(AppMode::DescribeView);
+        } else if matches!(
+            self.mode,
+            AppMode::DescribeView | AppMode::DescribeSearchInput
+        ) {
+            self.describe_scroll = self.describe_scroll.min(lines.len().saturating_sub(1));
+            self.describe_content = lines;
+        }
+    }
+
+    /// Scrolls the describe view to the next (or previous) matching line.
+    pub fn describe_search(&mut self, forward: bool, include_current: bool) {
+        let n = self.describe_content.len();
+        if self.describe_query.is_empty() || n == 0 {
+            return;
+        }
+        let current = self.describe_scroll.min(n - 1);
+        let first = if include_current { 0 } else { 1 };
+        let hit = (first..first + n)
+            .map(|step| {
+                if forward {
+                    (current + step) % n
+                } else {
+                    (current + n - step % n) % n
+                }
+            })
+            .find(|&i| contains_ascii_ci(&self.describe_content[i], &self.describe_query));
+        match hit {
+            Some(i) => self.describe_scroll = i,
+            None => self.set_error(format!("Pattern not found: {}", self.describe_query)),
+        }
+    }
+
+    pub fn cluster_probe_due(&self) -> bool {
+        let interval = if self.cluster_reachable == Some(false) {
+            CLUSTER_RETRY_INTERVAL
+        } else {
+            CLUSTER_PROBE_INTERVAL
+        };
+        self.cluster_probe_at
+            .is_none_or(|t| t.elapsed() >= interval)
+    }
+
+    pub fn apply_cluster_probe(&mut self, context: &str, result: Result<String, String>) {
+        if context != self.current_context {
+            return;
+        }
+        match result {
+            Ok(version) => {
+                if let Some(since) = self.cluster_unreachable_since.take() {
+                    let secs = since.elapsed().as_secs() as i64;
+                    self.record_notification(
+                        false,
+                        &format!(
+                            "API server reachable again after {}",
+                            crate::utils::format_secs(secs)
+                        ),
+                    );
+                }
+                self.cluster_version = Some(version);
+                self.cluster_reachable = Some(true);
+            }
+            Err(e) => {
+                // The header banner stays up while this lasts; the history
+                // gets the first failure only.
+                if self.cluster_reachable != Some(false) {
+                    self.record_notification(true, &format!("API server unreachable: {e}"));
+                    self.cluster_unreachable_since = Some(Instant::now());
+                }
+                self.cluster_error = Some(e);
+                self.cluster_reachable = Some(false);
+            }
+        }
+    }
+
+    /// Applies a re-read kubeconfig.
+    pub fn reload_contexts(&mut self, contexts: Vec<String>) {
+        let missing = !contexts.contains(&self.current_context);
+        if missing && !self.context_missing {
+            self.set_error(format!(
+                "Context '{}' was removed from kubeconfig",
+                self.current_context
+            ));
+        } else if !missing {
+            let added = contexts
+                .iter()
+                .filter(|c| !self.available_contexts.contains(c))
+                .count();
+            if added > 0 {
+                self.set_success(format!("kubeconfig reloaded: {added} new context(s)"));
+            }
+        }
+        self.context_missing = missing;
+        self.available_contexts = self
+            .app_state
+            .order_contexts(&contexts, &self.current_context);
+        if self.mode == AppMode::ContextSelect {
+            self.update_context_filter();
+        }
+    }
+
+    /// Applies the current context's preferences from the config file.
+    pub fn apply_context_prefs(&mut self) {
+        let Some(prefs) = self.config.context(&self.current_context) else {
+            self.read_only = false;
+            return;
+        };
+        self.read_only = prefs.read_only;
+        if let Some(ns) = prefs.namespace.clone() {
+            self.current_namespace = ns;
+        }
+        if let Some(tab) = prefs.tab() {
+            self.select_tab(tab);
+        }
+    }
+
+    /// Records the current context as most recently used.
+    pub fn record_context_switch(&mut self) {
+        let ctx = self.current_context.clone();
+        self.context_missing = false;
+        self.app_state.record_context(&ctx);
+        self.app_state.save();
+        self.available_contexts = self
+            .app_state
+            .order_contexts(&self.available_contexts, &ctx);
+    }
+
+    /// Pins or unpins the highlighted namespace in the popup.
+    pub fn toggle_favorite_namespace(&mut self) {
+        let Some(ns) = self
+            .popup_state
+            .selected()
+            .and_then(|i| self.filtered_namespaces.get(i).cloned())
+        else {
+            return;
+        };
+        let ctx = self.current_context.clone();
+        let pinned = self.app_state.toggle_favorite(&ctx, &ns);
+        self.app_state.save();
+        self.update_namespace_filter();
+        let idx = self.filtered_namespaces.iter().position(|n| *n == ns);
+        self.popup_state.select(idx);
+        self.set_success(if pinned {
+            format!("Pinned namespace '{ns}'")
+        } else {
+            format!("Unpinned namespace '{ns}'")
+        });
+    }
+
+    pub fn set_error(&mut self, msg: String) {
+        self.record_notification(true, &msg);
+        self.last_error = Some(msg);
+        self.last_success = None;
+        self.message_time = Some(Instant::now());
+    }
+
+    pub fn set_success(&mut self, msg: String) {
+        self.record_notification(false, &msg);
+        self.last_success = Some(msg);
+        self.last_error = None;
+        self.message_time = Some(Instant::now());
+    }
+
+    pub fn record_notification(&mut self, error: bool, message: &str) {
+        let at = jiff::Zoned::now();
+        if let Some(last) = self.notifications.back_mut()
+            && last.error == error
+            && last.message == message
+        {
+            last.at = at;
+            last.repeats += 1;
+            return;
+        }
+        if self.notifications.len() == MAX_NOTIFICATIONS {
+            self.notifications.pop_front();
+        }
+        self.notifications.push_back(Notification {
+            at,
+            error,
+            message: message.to_owned(),
+            repeats: 1,
+        });
+    }
+
+    /// An audit entry for `action` on `target` in the current context and namespace.
+    pub fn audit(&self, action: &str, target: String) -> AuditEntry {
+        let namespace = match target.starts_with("namespace/") {
+            true => "",
+            false => self.current_namespace.as_str(),
+        };
+        AuditEntry::new(&self.current_context, namespace, action, target)
+    }
+
+    pub fn open_audit_log(&mut self) {
+        let path = crate::audit::audit_path();
+        let entries = crate::audit::read_recent(&path, crate::audit::AUDIT_VIEW_LIMIT);
+        let text = if entries.is_empty() {
+            format!("No actions recorded yet in {}", path.display())
+        } else {
+            entries
+                .iter()
+                .map(AuditEntry::line)
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        self.open_pager(format!("Audit log [{}]", entries.len()), text);
+    }
+
+    /// Notification history for the pager, newest first.
+    pub fn notification_log(&self) -> String {
+        if self.notifications.is_empty() {
+            return "No notifications yet".to_string();
+        }
+        self.notifications
+            .iter()
+            .rev()
+            .map(|n| {
+                let repeats = if n.repeats > 1 {
+                    format!(" (x{})", n.repeats)
+                } else {
+                    String::new()
+                };
+                format!(
+                    "{}  {}  {}{repeats}",
+                    n.at.strftime("%Y-%m-%d %H:%M:%S"),
+                    if n.error { "ERROR" } else { "OK   " },
+                    n.message
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Opens the most recent error in the pager with its full text.
+    pub fn open_last_error(&mut self) {
+        let Some(n) = self.notifications.iter().rev().find(|n| n.error) else {
+            self.set_success("No errors so far".to_string());
+            return;
+        };
+        let title = format!("Error at {}", n.at.strftime("%H:%M:%S"));
+        let text = crate::utils::error_detail(&n.message);
+        self.open_pager(title, text);
+    }
+
+    /// Returns whether a message was cleared.
+    pub fn clear_stale_messages(&mut self) -> bool {
+        let mut cleared = false;
+        if let Some(t) = self.message_time {
+            let elapsed = t.elapsed().as_secs();
+            if self.last_success.is_some() && elapsed >= 5 {
+                self.last_success = None;
+                if self.last_error.is_none() {
+                    self.message_time = None;
+                }
+                cleared = true;
+            }
+            if let Some(err) = &self.last_error
+                && !err.starts_with("Access denied")
+                && elapsed >= 15
+            {
+                self.last_error = None;
+                self.message_time = None;
+                cleared = true;
+            }
+        }
+        cleared
+    }
+
+    /// Whether the current context, or the namespace shown in it, is protected.
+    pub fn protected(&self) -> bool {
+        self.protects(&self.current_namespace)
+    }
+
+    fn protects(&self, namespace: &str) -> bool {
+        self.config
+            .context(&self.current_context)
+            .is_some_and(|c| c.protects(namespace))
+    }
+
+    /// Whether the pending action is destructive and aimed at a protected target.
+    pub fn confirm_protected(&self) -> bool {
+        match &self.pending_action {
+            Some(
+                PendingAction::DeleteNamespace { name } | PendingAction::CreateNamespace { name },
+            ) => self.protects(name),
+            Some(PendingAction::ApplyEdit { namespace, .. }) => self.protects(namespace),
+            Some(PendingAction::ApplyManifest { namespaces, .. }) => {
+                self.protected() || namespaces.iter().any(|ns| self.protects(ns))
+            }
+            Some(PendingAction::RunPlugin { index, .. }) => {
+                self.protected() && self.config.plugins.get(*index).is_some_and(|p| p.dangerous)
+            }
+            Some(PendingAction::EditManagedSecret { .. }) | None => false,
+            Some(_) => self.protected(),
+        }
+    }
+
+    /// Runs a write, or first asks for the context name when it is protected.
+    pub fn write(&mut self, action: PendingAction) {
+        self.pending_action = Some(action);
+        if self.confirm_protected() {
+            self.confirm_input.clear();
+            self.push_mode(AppMode::Confirm);
+        } else if let Some(action) = self.pending_action.take() {
+            self.run_write(action);
+        }
+    }
+
+    /// Carries out a write passed to `write`.
+    pub fn run_write(&mut self, action: PendingAction) {
+        match action {
+            PendingAction::CreateSecret {
+                name,
+                type_,
+                entries,
+            } => self.create_secret(name, type_, entries),
+            PendingAction::PatchSecretKey { secret, key, value } => {
+                self.patch_secret_value(secret, key, value)
+            }
+            PendingAction::CreateNamespace { name } => self.create_namespace(name),
+            PendingAction::ApplyEdit { .. } => self.apply_resource_edit(),
+            PendingAction::ApplyManifest { .. } => self.apply_manifest_plan(),
+            _ => {}
+        }
+    }
+
+    /// What must be typed to confirm the pending action, when `y` is not enough.
+    pub fn typed_confirmation(&self) -> Option<String> {
+        if self.confirm_protected() {
+            return Some(self.current_context.clone());
+        }
+        let flagged = self
+            .config
+            .context(&self.current_context)
+            .is_some_and(|c| c.type_to_confirm);
+        match self.pending_action.as_ref()? {
+            PendingAction::DeleteResource { count, names, .. }
+                if flagged
+                    || self
+                        .config
+                        .type_to_confirm_above
+                        .is_some_and(|above| *count > above) =>
+            {
+                if *count == 1 {
+                    names.first().cloned()
+                } else {
+                    Some(count.to_string())
+                }
+            }
+            PendingAction::DeleteNamespace { name } if flagged => Some(name.clone()),
+            _ => None,
+        }
+    }
+
+    /// Cycles substring, fuzzy and regex matching.
+    pub fn cycle_filter_mode(&mut self) {
+        self.app_state.filter_mode = self.app_state.filter_mode.next();
+        self.app_state.save();
+        if self.mode == AppMode::NamespaceSelect {
+            self.update_namespace_filter();
+        } else {
+            self.update_filter();
+        }
+    }
+
+    /// Starts visual selection at the cursor, or ends it.
+    pub fn toggle_visual(&mut self) {
+        if self.visual_anchor.take().is_some() {
+            return;
+        }
+        if let Some(i) = self.table_state.selected() {
+            self.visual_anchor = Some(i);
+            self.visual_base = self.selected_indices.clone();
+            self.extend_visual();
+        }
+    }
+
+    /// Re-selects the rows between the anchor and the cursor.
+    pub fn extend_visual(&mut self) {
+        let (Some(anchor), Some(cursor)) = (self.visual_anchor, self.table_state.selected()) else {
+            return;
+        };
+        let range = anchor.min(cursor)..=anchor.max(cursor);
+        self.selected_indices = self.visual_base.iter().copied().chain(range).collect();
+    }
+
+    /// Opens `mode` on top of the current view.
+    pub fn push_mode(&mut self, mode: AppMode) {
+        if self.mode == mode {
+            return;
+        }
+        if self.mode == AppMode::List {
+            self.mode_stack.clear();
+        }
+        self.mode_stack.push(self.mode);
+        self.mode = mode;
+    }
+
+    /// Closes the current view, returning to the one it was opened from.
+    pub fn pop_mode(&mut self) {
+        self.mode = self.mode_stack.pop().unwrap_or(AppMode::List);
+    }
+
+    /// Opens `text` in the full-screen pager; `q` returns to the current mode.
+    pub fn open_pager(&mut self, title: String, text: String) {
+        self.pager_title = title;
+        self.pager_text = text;
+        self.pager_scroll = 0;
+        self.pager_query.clear();
+        self.pager_search_input.clear();
+        self.push_mode(AppMode::ValuePager);
+    }
+
+    /// Moves the pager to the next (or previous) row matching the query.
+    pub fn pager_search(&mut self, width: usize, forward: bool, include_current: bool) {
+        if self.pager_query.is_empty() {
+            return;
+        }
+        let rows = crate::utils::wrap_rows(&self.pager_text, width);
+        let n = rows.len();
+        let first = if include_current { 0 } else { 1 };
+        let hit = (first..first + n)
+            .map(|step| {
+                if forward {
+                    (self.pager_scroll + step) % n
+                } else {
+                    (self.pager_scroll + n - step % n) % n
+                }
+            })
+            .find(|&i| contains_ascii_ci(&rows[i], &self.pager_query));
+        match hit {
+            Some(i) => self.pager_scroll = i,
+            None => self.set_error(format!("Pattern not found: {}", self.pager_query)),
+        }
+    }
+
+    /// Copies sensitive text to the clipboard and wipes it again after 15s.
+    pub fn copy_to_clipboard(&mut self, label: &str, text: String) {
+        let Some(via_osc52) = self.write_clipboard(&text) else {
+            return;
+        };
+        if let Some(handle) = self.clipboard_clear_task.take() {
+            handle.abort();
+        }
+        let how = if via_osc52 {
+            " via terminal (OSC52)"
+        } else {
+            ""
+        };
+        self.set_success(format!("Copied {label} to clipboard{how} (clears in 15s)"));
+        let tx = self.event_tx.clone();
+        let handle = tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_secs(15)).await;
+            if via_osc52 {
+                // Written by the event loop so it can't interleave with a frame.
+                let _ = tx.send(KubeResourceEvent::ClearOsc52Clipboard);
+            } else if let Ok(mut cb) = arboard::Clipboard::new() {
+                let _ = cb.set_text(String::new());
+            }
+        });
+        self.clipboard_clear_task = Some(handle.abort_handle());
+    }
+
+    /// Copies text that isn't sensitive and leaves it on the clipboard.
+    pub fn copy_text(&mut self, label: &str, text: String) {
+        let Some(via_osc52) = self.write_clipboard(&text) else {
+            return;
+        };
+        let how = if via_osc52 {
+            " via terminal (OSC52)"
+        } else {
+            ""
+        };
+        self.set_success(format!("Copied {label} to clipboard{how}: {text}"));
+    }
+
+    /// Whether the text went out via OSC52; None after reporting an error.
+    fn write_clipboard(&mut self, text: &str) -> Option<bool> {
+        match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(text)) {
+            Ok(()) => Some(false),
+            Err(e) => match write_osc52(text) {
+                Ok(()) => Some(true),
+                Err(_) => {
+                    self.set_error(format!("Clipboard error: {e}"));
+                    None
+                }
+            },
+        }
+    }
+
+    /// The `kubectl` command equivalent to action `key` of the `.` menu.
+    pub fn action_kubectl(&self, key: char) -> Option<String> {
+        let item = self.get_selected_resource()?;
+        let name = item.name().to_string();
+        let kind = item.resource_type().singular();
+        let args: Vec<String> = match (item, key) {
+            (KubeResource::Pod(_), 'l') => vec!["logs".into(), "-f".into(), name],
+            (KubeResource::Pod(_), 's') => {
+                ["exec", "-it", &name, "--", "sh"].map(Into::into).into()
+            }
+            (KubeResource::Deployment(d), 'S') => {
+                let replicas = d.spec.as_ref().and_then(|s| s.replicas).unwrap_or(1);
+                vec![
+                    "scale".into(),
+                    format!("deployment/{name}"),
+                    format!("--replicas={replicas}"),
+                ]
+            }
+            (KubeResource::Deployment(_), 'r') => vec![
+                "rollout".into(),
+                "restart".into(),
+                format!("deployment/{name}"),
+            ],
+            (_, 'd') => vec!["describe".into(), kind.into(), name],
+            (_, 'e') => vec!["edit".into(), kind.into(), name],
+            (_, 'D') => vec!["delete".into(), kind.into(), name],
+            (KubeResource::Secret(_) | KubeResource::ConfigMap(_), 'x') => {
+                ["get", kind, &name, "-o", "yaml"].map(Into::into).into()
+            }
+            _ => return None,
+        };
+        Some(crate::utils::kubectl_line(
+            &self.current_context,
+            Some(&self.current_namespace),
+            &args,
+        ))
+    }
+
+    /// The kubectl plugins of the `.` menu; none in read-only contexts.
+    pub fn menu_kubectl_plugins(&mut self) -> &[String] {
+        if self.read_only {
+            return &[];
+        }
+        self.kubectl_plugins.get_or_insert_with(|| {
+            std::env::var_os("PATH")
+                .map(|path| crate::utils::find_kubectl_plugins(&path))
+                .unwrap_or_default()
+        })
+    }
+
+    /// The arguments to `kubectl` that run `plugin` on the selected row.
+    fn kubectl_plugin_args(&self, plugin: &str) -> Option<Vec<String>> {
+        let name = self.get_selected_resource()?.name();
+        let mut args: Vec<String> = plugin.split(' ').map(str::to_string).collect();
+        args.push(name.to_string());
+        Some(args)
+    }
+
+    /// Runs `kubectl <plugin> <name>` in the embedded terminal.
+    pub fn run_kubectl_plugin(&mut self, plugin: &str) {
+        let Some(args) = self.kubectl_plugin_args(plugin) else {
+            self.set_error("No resource selected".to_string());
+            return;
+        };
+        let mut cmd = portable_pty::CommandBuilder::new("kubectl");
+        cmd.args(&args);
+        cmd.args([
+            "-n",
+            &self.current_namespace,
+            "--context",
+            &self.current_context,
+        ]);
+        self.shell_title = format!("kubectl {}", args.join(" "));
+        self.spawn_pty_session(cmd);
+    }
+
+    /// The command line `run_kubectl_plugin` runs, for sharing.
+    pub fn kubectl_plugin_line(&self, plugin: &str) -> Option<String> {
+        let args = self.kubectl_plugin_args(plugin)?;
+        Some(crate::utils::kubectl_line(
+            &self.current_context,
+            Some(&self.current_namespace),
+            &args,
+        ))
+    }
+
+    pub fn start_shell(&mut self, pod_name: &str, namespace: &str) {
+        use portable_pty::CommandBuilder;
+        let mut cmd = CommandBuilder::new("kubectl");
+        cmd.args([
+            "exec",
+            "-it",
+            pod_name,
+            "-n",
+            namespace,
+            "--context",
+            &self.current_context,
+            "--",
+            "sh",
+        ]);
+        self.shell_title = format!("Shell: {pod_name}");
+        self.spawn_pty_session(cmd);
+    }
+
+    /// Runs plugin `index` of the config against the selected row.
+    pub fn run_plugin(&mut self, index: usize) {
+        let Some(plugin) = self.config.plugins.get(index).cloned() else {
+            return;
+        };
+        let Some(name) = self.get_selected_resource().map(|r| r.name().to_owned()) else {
+            self.set_error("No resource selected".to_string());
+            return;
+        };
+        let kind = self.active_tab.plural().trim_end_matches('s');
+        let (command, args) = plugin.command_line(&[
+            ("NAME", &name),
+            ("NAMESPACE", &self.current_namespace),
+            ("CONTEXT", &self.current_context),
+            ("KIND", kind),
+        ]);
+        let audit = plugin
+            .dangerous
+            .then(|| self.audit("plugin", format!("{kind}/{name} ({})", plugin.name)));
+        let title = format!("{}: {name}", plugin.name);
+        match plugin.output {
+            PluginOutput::Terminal => {
+                if let Some(audit) = audit {
+                    audit.record_as("opened in terminal");
+                }
+                let mut cmd = portable_pty::CommandBuilder::new(command);
+                cmd.args(args);
+                self.shell_title = title;
+                self.spawn_pty_session(cmd);
+            }
+            PluginOutput::Capture => {
+                let tx = self.event_tx.clone();
+                self.tasks
+                    .spawn(format!("plugin {}", plugin.name), async move {
+                        let output = tokio::process::Command::new(&command)
+                            .args(&args)
+                            .envs(crate::k8s::config::kubectl_env())
+                            .stdin(std::process::Stdio::null())
+                            .kill_on_drop(true)
+                            .output()
+                            .await;
+                        let result = match &output {
+                            Ok(out) if out.status.success() => Ok(()),
+                            Ok(out) => Err(out.status.to_string()),
+                            Err(e) => Err(e.to_string()),
+                        };
+                        if let Some(audit) = audit {
+                            audit.record(&result);
+                        }
+                        let _ = tx.send(match output {
+                            Ok(out) => {
+                                let mut text = String::from_utf8_lossy(&out.stdout).into_owned();
+                                text.push_str(&String::from_utf8_lossy(&out.stderr));
+                                if let Err(status) = result {
+                                    text.push_str(&format!("\n[{status}]"));
+                                }
+                                KubeResourceEvent::PluginOutput(title, text)
+                            }
+                            Err(e) => KubeResourceEvent::Error(format!(
+                                "Plugin '{}' failed: {e}",
+                                plugin.name
+                            )),
+                        });
+                    });
+            }
+        }
+    }
+
+    /// Opens link `index` of this tab with the selected row filled in.
+    pub fn open_link(&mut self, index: usize) {
+        let Some(name) = self.get_selected_resource().map(|r| r.name().to_owned()) else {
+            self.set_error("No resource selected".to_string());
+            return;
+        };
+        let links = self.config.links(&self.current_context, self.active_tab);
+        let Some(link) = links.get(index) else {
+            return;
+        };
+        let url = link.url(&[
+            ("NAME", &name),
+            ("NAMESPACE", &self.current_namespace),
+            ("CONTEXT", &self.current_context),
+            ("KIND", self.active_tab.plural().trim_end_matches('s')),
+        ]);
+        let label = link.name.clone();
+        self.open_url(label, url);
+    }
+
+    /// Opens `url` in the browser, reporting it as `label`.
+    pub fn open_url(&mut self, label: String, url: String) {
+        let tx = self.event_tx.clone();
+        self.tasks.spawn(format!("open {label}"), async move {
+            let _ = tx.send(match crate::utils::browser_command(&url).status().await {
+                Ok(status) if status.success() => {
+                    KubeResourceEvent::Success(format!("Opened {label}"))
+                }
+                Ok(status) => KubeResourceEvent::Error(format!("Could not open {url}: {status}")),
+                Err(e) => KubeResourceEvent::Error(format!("Could not open {url}: {e}")),
+            });
+        });
+    }
+
+    /// `O`: looks up the URLs that reach the selected pod or deployment.
+    pub fn find_endpoints(&mut self) {
+        let (name, labels) = match self.get_selected_resource() {
+            Some(KubeResource::Pod(p)) => (p.metadata.name.clone(), p.metadata.labels.clone()),
+            Some(KubeResource::Deployment(d)) => (
+                d.metadata.name.clone(),
+                d.spec
+                    .as_ref()
+                    .and_then(|s| s.template.metadata.as_ref())
+                    .and_then(|m| m.labels.clone()),
+            ),
+            _ => {
+                self.set_error("No pod or deployment selected".to_string());
+                return;
+            }
+        };
+        let name = name.unwrap_or_default();
+        let client = self.client.clone();
+        let namespace = self.current_namespace.clone();
+        let tx = self.event_tx.clone();
+        self.tasks
+            .spawn(format!("endpoints of {name}"), async move {
+                let labels = labels.unwrap_or_default();
+                let _ = tx.send(
+                    match crate::k8s::actions::fetch_endpoints(client, &namespace, &labels).await {
+                        Ok(endpoints) => KubeResourceEvent::EndpointsReady(name, endpoints),
+                        Err(e) => {
+                            KubeResourceEvent::Error(format!("Finding endpoints of {name}: {e}"))
+                        }
+                    },
+                );
+            });
+    }
+
+    pub fn endpoints_found(&mut self, name: String, endpoints: Vec<Endpoint>) {
+        // Dropped when the user has moved on.
+        if self.mode != AppMode::List
+            || self.get_selected_resource().map(|r| r.name()) != Some(name.as_str())
+        {
+            return;
+        }
+        if endpoints.is_empty() {
+            self.set_error(format!(
+                "No ingress host or external service address reaches {name}"
+            ));
+            return;
+        }
+        self.endpoints = endpoints;
+        self.endpoint_menu_state.select(Some(0));
+        self.push_mode(AppMode::EndpointMenu);
+    }
+
+    /// Writes the selected rows as `<kind>-<name>.yaml` files.
+    pub fn export_manifests(&mut self) {
+        let items: Vec<KubeResource> = if self.selected_indices.is_empty() {
+            self.get_selected_resource().cloned().into_iter().collect()
+        } else {
+            let mut indices: Vec<usize> = self.selected_indices.iter().copied().collect();
+            indices.sort_unstable();
+            indices
+                .into_iter()
+                .filter_map(|i| self.filtered_items.get(i).cloned())
+                .collect()
+        };
+        let dir = crate::utils::expand_home(self.export_path_input.trim());
+        let kind = self.active_tab.plural().trim_end_matches('s');
+        // Secrets carry their data, so every file is private.
+        let result = std::fs::create_dir_all(&dir).and_then(|()| {
+            items.iter().try_for_each(|item| {
+                let yaml = crate::utils::manifest_yaml(item.to_json(), self.export_clean)
+                    .map_err(std::io::Error::other)?;
+                let path = dir.join(format!("{kind}-{}.yaml", item.name()));
+                crate::utils::write_private_file(&path, yaml.as_bytes())
+            })
+        });
+        match result {
+            Ok(()) => self.set_success(format!(
+                "Wrote {} manifest(s) to {}",
+                items.len(),
+                dir.display()
+            )),
+            Err(e) => self.set_error(format!("Export failed: {e}")),
         }
+    }
 
-        if let Some(offset) = &mut self.log_scroll_offset {
-            *offset += prepend_count;
+    /// `:export`: writes the filtered table as CSV, or TSV for a `.tsv` path.
+    pub fn export_table(&mut self, path: Option<String>) {
+        let path = path.unwrap_or_else(|| {
+            format!(
+                "{}-{}.csv",
+                self.active_tab.plural(),
+                self.current_namespace
+            )
+        });
+        let separator = match path.ends_with(".tsv") {
+            true => '\t',
+            false => ',',
+        };
+        let rows = crate::ui::components::table_text(
+            self.active_tab,
+            &self.filtered_items,
+            self.config.columns(self.active_tab),
+            self.wide,
+        );
+        let text = crate::utils::delimited(&rows, separator);
+        match std::fs::write(crate::utils::expand_home(&path), text) {
+            Ok(()) => self.set_success(format!("Wrote {} row(s) to {path}", rows.len() - 1)),
+            Err(e) => self.set_error(format!("Export to '{path}' failed: {e}")),
         }
-        if let Some(m) = &mut self.log_search_match_line {
-            *m += prepend_count;
+    }
+
+    /// Opens the metrics panel for the selected pod or deployment.
+    pub fn open_metrics(&mut self) {
+        let Some(name) = self.get_selected_resource().map(|r| r.name().to_owned()) else {
+            self.set_error("No resource selected".to_string());
+            return;
+        };
+        if self.prometheus().is_none() {
+            self.set_error(format!(
+                "No Prometheus configured for context '{}'",
+                self.context_label(&self.current_context)
+            ));
+            return;
         }
+        self.metrics_target = name;
+        self.metrics_deployment = self.active_tab == ResourceType::Deployment;
+        self.push_mode(AppMode::MetricsView);
+        self.refresh_metrics();
+    }
 
-        self.log_loading_history = false;
-        self.resolve_pending_search(prepend_count);
+    fn prometheus(&self) -> Option<&crate::config::PrometheusConfig> {
+        self.config
+            .context(&self.current_context)?
+            .prometheus
+            .as_ref()
     }
 
-    fn resolve_pending_search(&mut self, new_line_count: usize) {
-        if !self.log_search_pending {
+    pub fn refresh_metrics(&mut self) {
+        let Some(config) = self.prometheus().cloned() else {
             return;
-        }
-        self.log_search_pending = false;
-        if new_line_count == 0 {
-            if self.log_history_exhausted {
-                self.set_error("Not found".to_string());
-            }
+        };
+        self.metrics = None;
+        let name = self.metrics_target.clone();
+        let vars = vec![
+            ("NAMESPACE", self.current_namespace.clone()),
+            ("NAME", name.clone()),
+            (
+                "PODS",
+                crate::prometheus::pods_regex(&name, self.metrics_deployment),
+            ),
+        ];
+        let client = self.client.clone();
+        let tx = self.event_tx.clone();
+        self.tasks.spawn(format!("metrics {name}"), async move {
+            let series = crate::prometheus::fetch_series(client, config, vars).await;
+            let _ = tx.send(KubeResourceEvent::MetricsReady(name, series));
+        });
+    }
+
+    /// `W`: notifies once the selected pod or deployment settles; again cancels.
+    pub fn toggle_watch(&mut self) {
+        let Some(item) = self.get_selected_resource() else {
+            self.set_error("No resource selected".to_string());
             return;
-        }
-        let needle = &self.log_search_query;
-        if needle.is_empty() {
+        };
+        let kind = match item {
+            KubeResource::Pod(_) => "pod",
+            KubeResource::Deployment(_) => "deployment",
+            _ => return,
+        };
+        let name = item.name().to_owned();
+        let label = format!("watch {}", self.object_key(kind, &name));
+        if self.tasks.cancel_labelled(&label) {
+            self.set_success(format!("Stopped watching {kind}/{name}"));
             return;
         }
-        for idx in (0..new_line_count).rev() {
-            if contains_ascii_ci(&self.log_buffer[idx], needle) {
-                self.log_search_match_line = Some(idx);
-                let visible = self.log_visible_height();
-                self.scroll_to_line(idx, visible);
-                return;
+        let client = self.client.clone();
+        let namespace = self.current_namespace.clone();
+        let place = format!("{}/{namespace}", self.context_label(&self.current_context));
+        let watching = format!(
+            "Watching {kind}/{name}; you'll be notified when it is {}",
+            match kind {
+                "pod" => "Ready, crash-looping or done",
+                _ => "rolled out",
             }
-        }
-        if self.log_history_exhausted {
-            self.set_error("Not found".to_string());
-        } else {
-            self.set_success(format!(
-                "Not found in {} loaded lines, press n to load more",
-                self.log_buffer.len()
-            ));
-        }
+        );
+        let tx = self.event_tx.clone();
+        self.tasks.spawn_global(label, async move {
+            let met = match kind {
+                "pod" => {
+                    let condition = crate::notify::pod_condition;
+                    crate::k8s::watcher::wait_for::<Pod>(client, &namespace, &name, condition).await
+                }
+                _ => {
+                    let condition = crate::notify::rollout_condition;
+                    crate::k8s::watcher::wait_for::<Deployment>(
+                        client, &namespace, &name, condition,
+                    )
+                    .await
+                }
+            };
+            let event = match met {
+                Ok(met) => {
+                    let message = format!("{kind}/{name} {met} ({place})");
+                    crate::notify::desktop(message.clone());
+                    KubeResourceEvent::Success(message)
+                }
+                Err(e) => KubeResourceEvent::Error(format!("Watch of {kind}/{name}: {e}")),
+            };
+            let _ = tx.send(event);
+        });
+        self.set_success(watching);
     }
 
-    pub fn abort_log_stream(&mut self) {
-        if let Some(handle) = self.log_task.take() {
-            handle.abort();
+    /// `:apply`: reads the manifest at `path`, or opens `$EDITOR`.
+    pub fn start_manifest_apply(&mut self, path: Option<String>) {
+        if self.read_only {
+            self.set_error(format!(
+                "Context '{}' is read-only",
+                self.context_label(&self.current_context)
+            ));
+            return;
         }
-        if let Some(handle) = self.log_history_task.take() {
-            handle.abort();
+        match path {
+            None => self.pending_editor = Some(EditorRequest::Manifest),
+            Some(path) => match std::fs::read_to_string(crate::utils::expand_home(&path)) {
+                Ok(text) => self.plan_manifest(&text),
+                Err(e) => self.set_error(format!("Read '{path}' failed: {e}")),
+            },
         }
-        self.log_search_pending = false;
     }
 
-    pub fn load_namespaces(&self) {
-        let client = self.client.clone();
-        let current_ns = self.current_namespace.clone();
-        let ctx = self.current_context.clone();
-        let tx = self.event_tx.clone();
-        tokio::spawn(async move {
-            use k8s_openapi::api::core::v1::Namespace;
-            use kube::Api;
-            use kube::api::ListParams;
-            let ns_api: Api<Namespace> = Api::all(client);
-            if let Ok(ns_list) = ns_api.list(&ListParams::default()).await {
-                let namespaces: Vec<String> = ns_list
-                    .iter()
-                    .map(|n| n.metadata.name.clone().unwrap_or_default())
-                    .collect();
-                let _ = tx.send(KubeResourceEvent::NamespacesLoaded(namespaces));
+    /// Dry-runs the manifest `text` on the server.
+    pub fn plan_manifest(&mut self, text: &str) {
+        let objects = match crate::k8s::actions::parse_manifest(text) {
+            Ok(objects) => objects,
+            Err(e) => {
+                self.set_error(format!("Invalid manifest: {e}"));
                 return;
             }
-
-            if let Ok(output) = tokio::process::Command::new("kubectl")
-                .args([
-                    "get",
-                    "namespaces",
-                    "--context",
-                    &ctx,
-                    "-o",
-                    "jsonpath={.items[*].metadata.name}",
-                ])
-                .output()
-                .await
-                && output.status.success()
-            {
-                let text = String::from_utf8_lossy(&output.stdout);
-                let namespaces: Vec<String> = text
-                    .split_whitespace()
-                    .map(|s| s.to_string())
-                    .filter(|s| !s.is_empty())
-                    .collect();
-                if !namespaces.is_empty() {
-                    let _ = tx.send(KubeResourceEvent::NamespacesLoaded(namespaces));
-                    return;
-                }
-            }
-
-            let _ = tx.send(KubeResourceEvent::NamespacesLoaded(vec![current_ns]));
-        });
+        };
+        let client = self.client.clone();
+        let tx = self.event_tx.clone();
+        let namespace = self.current_namespace.clone();
+        self.tasks
+            .spawn(format!("dry-run {} object(s)", objects.len()), async move {
+                let plan = crate::k8s::actions::plan_manifest(client, objects, &namespace).await;
+                let _ = tx.send(KubeResourceEvent::ManifestPlanned(plan));
+            });
     }
 
-    pub fn update_namespace_filter(&mut self) {
-        if self.namespace_input.is_empty() {
-            self.filtered_namespaces
-                .clone_from(&self.available_namespaces);
-        } else {
-            let query = self.namespace_input.to_lowercase();
-            self.filtered_namespaces = self
-                .available_namespaces
-                .iter()
-                .filter(|ns| ns.to_lowercase().contains(&query))
-                .cloned()
-                .collect();
-        }
-        if self.filtered_namespaces.is_empty() {
-            self.popup_state.select(None);
-        } else {
-            self.popup_state.select(Some(0));
+    /// Previews a dry-run manifest object by object; `y` applies it.
+    pub fn show_manifest_plan(&mut self, plan: Vec<ManifestObject>) {
+        let mut lines = Vec::new();
+        for item in &plan {
+            let state = match &item.plan {
+                Err(_) => "dry run failed",
+                Ok(_) if item.created => "created",
+                Ok(diff) if diff.is_empty() => "unchanged",
+                Ok(_) => "configured",
+            };
+            lines.push(format!("== {} ({state})", item.title()));
+            match &item.plan {
+                Ok(diff) => lines.extend(diff.iter().cloned()),
+                Err(e) => lines.push(format!("! {e}")),
+            }
+            lines.push(String::new());
         }
+        let title = format!("Apply {} object(s), server dry run", plan.len());
+        self.open_pager(title, lines.join("\n"));
+        self.mode = AppMode::ApplyDiff;
+        self.manifest_plan = plan;
     }
 
-    pub fn set_error(&mut self, msg: String) {
-        self.last_error = Some(msg);
-        self.last_success = None;
-        self.message_time = Some(Instant::now());
+    /// Applies the previewed manifest and reports each object.
+    pub fn apply_manifest_plan(&mut self) {
+        let plan = std::mem::take(&mut self.manifest_plan);
+        self.pager_text.clear();
+        self.pop_mode();
+        if plan.is_empty() {
+            return;
+        }
+        let client = self.client.clone();
+        let tx = self.event_tx.clone();
+        let context = self.current_context.clone();
+        self.tasks
+            .spawn(format!("apply {} object(s)", plan.len()), async move {
+                let mut results = Vec::with_capacity(plan.len());
+                for item in plan {
+                    let result = match &item.plan {
+                        Err(e) => Err(format!("not applied, dry run failed: {e}")),
+                        Ok(_) => {
+                            let result =
+                                crate::k8s::actions::apply_manifest_object(client.clone(), &item)
+                                    .await
+                                    .map_err(|e| e.to_string());
+                            let namespace = item.namespace.as_deref().unwrap_or_default();
+                            AuditEntry::new(&context, namespace, "apply", item.label.clone())
+                                .record(&result);
+                            result
+                        }
+                    };
+                    results.push((item.title(), result));
+                }
+                let _ = tx.send(KubeResourceEvent::BulkResult("Apply".to_string(), results));
+            });
     }
 
-    pub fn set_success(&mut self, msg: String) {
-        self.last_success = Some(msg);
-        self.last_error = None;
-        self.message_time = Some(Instant::now());
+    /// Fetches an object's YAML for `e`; the editor opens once it arrives.
+    pub fn edit_resource(&mut self, kind: &'static str, name: &str, namespace: &str) {
+        let edit = ResourceEdit {
+            kind,
+            name: name.to_owned(),
+            namespace: namespace.to_owned(),
+            original: String::new(),
+            edited: String::new(),
+            error: None,
+        };
+        let client = self.client.clone();
+        let tx = self.event_tx.clone();
+        self.tasks
+            .spawn(format!("fetch {kind}/{name}"), async move {
+                fetch_edit(client, edit, false, tx).await;
+            });
     }
 
-    pub fn clear_stale_messages(&mut self) {
-        if let Some(t) = self.message_time {
-            let elapsed = t.elapsed().as_secs();
-            if self.last_success.is_some() && elapsed >= 5 {
-                self.last_success = None;
-                if self.last_error.is_none() {
-                    self.message_time = None;
-                }
+    /// Takes the editor's result for `edit`.
+    pub fn resource_edited(&mut self, mut edit: ResourceEdit, saved: std::io::Result<String>) {
+        let saved = match saved {
+            Ok(text) => strip_edit_header(&text),
+            Err(e) => {
+                self.set_error(format!("Editor failed: {e}"));
+                return;
             }
-            if let Some(err) = &self.last_error
-                && !err.starts_with("Access denied")
-                && elapsed >= 15
-            {
-                self.last_error = None;
-                self.message_time = None;
+        };
+        if saved == edit.original {
+            self.set_success(format!("'{}' unchanged", edit.name));
+            return;
+        }
+        if edit.error.is_some() && saved == edit.edited {
+            self.set_success(format!("Edit of '{}' discarded", edit.name));
+            return;
+        }
+        edit.edited = saved;
+        match crate::k8s::actions::validate_edit(&edit) {
+            Ok(_) => {
+                edit.error = None;
+                self.show_edit_diff(edit);
+            }
+            Err(e) => {
+                edit.error = Some(e);
+                self.pending_editor = Some(EditorRequest::Resource(edit));
             }
         }
     }
 
-    pub fn start_shell(&mut self, pod_name: &str, namespace: &str) {
-        use portable_pty::CommandBuilder;
-        let mut cmd = CommandBuilder::new("kubectl");
-        cmd.args([
-            "exec",
-            "-it",
-            pod_name,
-            "-n",
-            namespace,
-            "--context",
-            &self.current_context,
-            "--",
-            "sh",
-        ]);
-        self.shell_title = format!("Shell: {pod_name}");
-        self.spawn_pty_session(cmd);
+    /// Previews an edit as a diff against the server's version; `y` applies.
+    pub fn show_edit_diff(&mut self, edit: ResourceEdit) {
+        let mut lines = Vec::new();
+        if let Some(note) = &edit.error {
+            lines.push(format!("! {note}"));
+            lines.push(String::new());
+        }
+        lines.extend(crate::utils::diff_lines(&edit.original, &edit.edited, 3));
+        let title = format!("Changes to {}/{}", edit.kind, edit.name);
+        self.open_pager(title, lines.join("\n"));
+        self.mode = AppMode::EditDiff;
+        self.resource_edit = Some(edit);
     }
 
-    pub fn start_kubectl_edit(&mut self, kind: &str, name: &str, namespace: &str) {
-        use portable_pty::CommandBuilder;
-        let mut cmd = CommandBuilder::new("kubectl");
-        cmd.args([
-            "edit",
-            kind,
-            name,
-            "-n",
-            namespace,
-            "--context",
+    /// Applies the previewed edit.
+    pub fn apply_resource_edit(&mut self) {
+        let Some(edit) = self.resource_edit.take() else {
+            return;
+        };
+        self.pager_text.clear();
+        self.pop_mode();
+        let client = self.client.clone();
+        let tx = self.event_tx.clone();
+        let object = self.object_key(edit.kind, &edit.name);
+        let audit = AuditEntry::new(
             &self.current_context,
-        ]);
-        self.shell_title = format!("Edit: {kind}/{name}");
-        self.spawn_pty_session(cmd);
+            &edit.namespace,
+            "apply",
+            format!("{}/{}", edit.kind, edit.name),
+        );
+        self.mutate(
+            format!("apply {}/{}", edit.kind, edit.name),
+            "apply",
+            vec![object],
+            async move {
+                use crate::k8s::actions::ApplyError;
+                let result = crate::k8s::actions::apply_edit(client.clone(), &edit).await;
+                audit.record_as(match &result {
+                    Ok(()) => "ok",
+                    Err(ApplyError::Conflict) => "conflict, changed on the server",
+                    Err(ApplyError::Invalid(e) | ApplyError::Other(e)) => e,
+                });
+                match result {
+                    Ok(()) => {
+                        let _ = tx.send(KubeResourceEvent::Success(format!(
+                            "Applied changes to '{}'",
+                            edit.name
+                        )));
+                    }
+                    Err(ApplyError::Conflict) => fetch_edit(client, edit, true, tx).await,
+                    Err(ApplyError::Invalid(e)) => {
+                        let edit = ResourceEdit {
+                            error: Some(e),
+                            ..edit
+                        };
+                        let _ = tx.send(KubeResourceEvent::EditReady(edit, false));
+                    }
+                    Err(ApplyError::Other(e)) => {
+                        let _ = tx.send(KubeResourceEvent::Error(format!(
+                            "Apply '{}' failed: {e}",
+                            edit.name
+                        )));
+                    }
+                }
+            },
+        );
     }
 
-    fn spawn_pty_session(&mut self, cmd: portable_pty::CommandBuilder) {
+    fn spawn_pty_session(&mut self, mut cmd: portable_pty::CommandBuilder) {
         use portable_pty::{PtySize, native_pty_system};
 
+        if let Some((key, path)) = crate::k8s::config::kubectl_env() {
+            cmd.env(key, path);
+        }
         let (cols, rows) = crossterm::terminal::size().unwrap_or((80, 24));
         let pty_rows = (rows * 80 / 100).saturating_sub(2).max(10);
         let pty_cols = (cols * 80 / 100).saturating_sub(2).max(40);
@@ -577,7 +3014,7 @@ impl App {
             parser,
             _master: pair.master,
         });
-        self.mode = AppMode::ShellView;
+        self.push_mode(AppMode::ShellView);
     }
 
     pub fn push_log_line(&mut self, line: String) {
@@ -591,7 +3028,7 @@ impl App {
     }
 
     pub fn log_search_next(&mut self) {
-        let visible = self.log_visible_height();
+        let visible = self.viewport.log_rows;
         self.log_search_next_with_height(visible);
     }
 
@@ -626,7 +3063,7 @@ impl App {
     }
 
     pub fn log_search_prev(&mut self) {
-        let visible = self.log_visible_height();
+        let visible = self.viewport.log_rows;
         self.log_search_prev_with_height(visible);
     }
 
@@ -654,12 +3091,6 @@ impl App {
         self.set_error("No more matches".to_string());
     }
 
-    fn log_visible_height(&self) -> usize {
-        crossterm::terminal::size()
-            .map(|(_, h)| (h as usize).saturating_sub(LOG_CHROME_LINES))
-            .unwrap_or(20)
-    }
-
     fn scroll_to_line(&mut self, idx: usize, visible: usize) {
         let len = self.log_buffer.len();
         let centered = idx.saturating_sub(visible / 2);
@@ -688,18 +3119,144 @@ impl App {
                         .collect();
                 }
             }
-            ResourceType::Secret => {
-                if let Some(store) = &self.secret_store {
-                    self.items = store
-                        .state()
-                        .iter()
-                        .map(|s| KubeResource::Secret(Arc::clone(s)))
-                        .collect();
-                }
+            ResourceType::Secret => {
+                if let Some(store) = &self.secret_store {
+                    self.items = store
+                        .state()
+                        .iter()
+                        .map(|s| KubeResource::Secret(Arc::clone(s)))
+                        .collect();
+                }
+            }
+            ResourceType::ConfigMap => {
+                if let Some(store) = &self.configmap_store {
+                    self.items = store
+                        .state()
+                        .iter()
+                        .map(|c| KubeResource::ConfigMap(Arc::clone(c)))
+                        .collect();
+                }
+            }
+        }
+        self.items.sort_by(|a, b| a.name().cmp(b.name()));
+        self.update_filter();
+        self.items_changed();
+    }
+
+    /// Re-watches pods and deployments for the context's webhooks.
+    pub fn restart_webhook_watcher(&mut self) {
+        if let Some(handle) = self.webhook_watcher.take() {
+            handle.abort();
+        }
+        if self
+            .config
+            .context(&self.current_context)
+            .is_some_and(|c| !c.webhooks.is_empty())
+        {
+            self.webhook_watcher = Some(crate::k8s::watcher::spawn_webhook_watcher(
+                self.client.clone(),
+                &self.current_namespace,
+                self.event_tx.clone(),
+            ));
+        }
+    }
+
+    /// Posts `target`'s crash loop or failed rollout to the context's webhooks.
+    pub fn post_webhooks(&self, event: crate::config::WebhookEvent, target: &str, what: &str) {
+        let Some(config) = self.config.context(&self.current_context) else {
+            return;
+        };
+        let body = crate::notify::webhook_body(
+            &self.current_context,
+            &self.current_namespace,
+            target,
+            what,
+        );
+        for webhook in config.webhooks.iter().filter(|w| w.fires_on(event)) {
+            self.tasks.spawn_global(
+                format!("webhook {}", crate::notify::webhook_host(&webhook.url)),
+                crate::notify::webhook(webhook.url.clone(), body.clone()),
+            );
+        }
+    }
+
+    /// Applies one watch event to the list in place. Returns whether anything changed.
+    pub fn apply_item_change(&mut self, item: KubeResource, deleted: bool) -> bool {
+        if item.resource_type() != self.active_tab {
+            return false;
+        }
+        let pos = self.items.binary_search_by(|r| r.name().cmp(item.name()));
+        match (pos, deleted) {
+            (Ok(i), true) => {
+                self.items.remove(i);
+            }
+            (Ok(i), false) => self.items[i] = item.clone(),
+            (Err(i), false) => self.items.insert(i, item.clone()),
+            (Err(_), true) => return false,
+        }
+
+        let matcher = NameMatcher::new(self.app_state.filter_mode, &self.filter_query);
+        // Ranked results aren't ordered by name, and a pending debounce
+        // means the results are for an older query: rebuild those.
+        if matcher.ranks() || self.filter_due.is_some() {
+            self.update_filter();
+            return true;
+        }
+        let keep = !deleted && self.filter_score(&matcher, &item).is_some();
+        let pos = self
+            .filtered_items
+            .binary_search_by(|r| r.name().cmp(item.name()));
+        match (pos, keep) {
+            (Ok(i), true) => self.filtered_items[i] = item,
+            (Ok(i), false) => {
+                self.filtered_items.remove(i);
+                self.shift_rows(i, false);
             }
+            (Err(i), true) => {
+                self.filtered_items.insert(i, item);
+                self.shift_rows(i, true);
+            }
+            (Err(_), false) => {}
         }
-        self.items.sort_by(|a, b| a.name().cmp(b.name()));
-        self.update_filter();
+        true
+    }
+
+    /// Keeps row indices on their rows after a row was inserted or removed at `at`.
+    fn shift_rows(&mut self, at: usize, inserted: bool) {
+        let shift = |i: usize| match i {
+            i if i < at => Some(i),
+            i if inserted => Some(i + 1),
+            i if i == at => None,
+            i => Some(i - 1),
+        };
+        self.selected_indices = self
+            .selected_indices
+            .iter()
+            .filter_map(|&i| shift(i))
+            .collect();
+        self.visual_anchor = self.visual_anchor.and_then(shift);
+        if let Some(selected) = self.table_state.selected() {
+            let last = self.filtered_items.len().saturating_sub(1);
+            self.table_state
+                .select(Some(shift(selected).unwrap_or(at).min(last)));
+        }
+    }
+
+    /// Follow-up once `items` changed.
+    pub fn items_changed(&mut self) {
+        if let Some(pane) = &mut self.split
+            && pane.shared
+        {
+            pane.items = pane.store.as_ref().map(|s| s.items()).unwrap_or_default();
+        }
+        if !self.items.is_empty()
+            && let Some(name) = self.restore_selection.take()
+            && let Some(i) = self.filtered_items.iter().position(|r| r.name() == name)
+        {
+            self.table_state.select(Some(i));
+        }
+        self.refresh_decoded_secret();
+        self.refresh_configmap_view();
     }
 
     #[cfg(test)]
@@ -720,11 +3277,13 @@ impl App {
             client,
             current_namespace: "default".to_string(),
             mode: AppMode::List,
+            mode_stack: Vec::new(),
             active_tab: ResourceType::Pod,
             should_quit: false,
             pod_store: None,
             deployment_store: None,
             secret_store: None,
+            configmap_store: None,
             event_tx: tx,
             items: Vec::new(),
             filtered_items: Vec::new(),
@@ -732,19 +3291,70 @@ impl App {
             filter_query: String::new(),
             selected_indices: HashSet::new(),
             selected_secret_decoded: None,
+            decoded_secret_name: String::new(),
+            secret_certs: None,
+            secret_owner: None,
+            secret_owner_status: None,
+            split: None,
+            webhook_watcher: None,
+            resource_edit: None,
+            manifest_plan: Vec::new(),
+            metrics: None,
+            metrics_deployment: false,
+            metrics_target: String::new(),
+            hit_areas: HitAreas::default(),
+            drawn_links: Vec::new(),
+            viewport: Viewport::default(),
+            wide: false,
+            column_offset: 0,
+            tab_states: HashMap::new(),
+            restore_selection: None,
+            startup: Startup::default(),
+            visual_anchor: None,
+            visual_base: HashSet::new(),
+            tasks: TaskRegistry::default(),
+            watch_health: WatchHealth::Syncing,
+            refresh_requested: false,
+            reauth_requested: false,
+            teleport_status: HashMap::new(),
+            tsh_login: None,
+            reauth_at: None,
+            last_watch_event: None,
+            split_input: String::new(),
             log_buffer: VecDeque::new(),
             log_task: None,
             log_scroll_offset: None,
             current_context: "test-context".into(),
             pending_context: None,
+            pending_editor: None,
             available_contexts: vec!["ctx1".into(), "ctx2".into()],
+            filtered_contexts: vec!["ctx1".into(), "ctx2".into()],
+            context_input: String::new(),
+            context_typing: false,
+            context_missing: false,
+            cluster_version: None,
+            cluster_reachable: None,
+            cluster_probe_at: None,
+            cluster_unreachable_since: None,
+            cluster_error: None,
+            warm_clients: HashMap::new(),
+            access_checks: Vec::new(),
+            read_only: false,
+            namespace_summary: None,
+            command_input: String::new(),
+            command_history_pos: None,
+            filter_history_pos: None,
+            filter_due: None,
+            filter_applied: None,
             available_namespaces: vec!["default".into(), "kube-system".into()],
             filtered_namespaces: vec!["default".into(), "kube-system".into()],
             namespace_input: String::new(),
             namespace_typing: false,
+            namespace_create_input: String::new(),
             popup_state: ListState::default(),
             last_error: None,
             last_success: None,
+            notifications: VecDeque::new(),
             message_time: None,
             is_loading: false,
             loading_since: None,
@@ -752,14 +3362,43 @@ impl App {
             secret_scroll: 0,
             secret_table_state: TableState::default(),
             secret_revealed: false,
+            secret_show_base64: false,
+            secret_edit_input: String::new(),
+            export_path_input: String::new(),
+            export_clean: true,
+            secret_diff_input: String::new(),
+            secret_diff_target: String::new(),
+            secret_diff: Vec::new(),
+            configmap_name: String::new(),
+            configmap_entries: None,
+            configmap_scroll: 0,
+            configmap_value_scroll: 0,
+            configmap_table_state: TableState::default(),
+            pager_title: String::new(),
+            pager_text: String::new(),
+            pager_scroll: 0,
+            pager_query: String::new(),
+            pager_search_input: String::new(),
+            secret_usage_name: String::new(),
+            secret_usage: Vec::new(),
+            secret_usage_scroll: 0,
             scale_input: String::new(),
+            secret_form: SecretForm::default(),
             pending_action: None,
+            confirm_input: String::new(),
+            pending_count: None,
             describe_content: Vec::new(),
             describe_scroll: 0,
+            describe_target: None,
+            describe_auto_refresh: false,
+            describe_refreshed_at: None,
+            describe_query: String::new(),
+            describe_search_input: String::new(),
             shell_session: None,
             shell_title: String::new(),
             clipboard_clear_task: None,
             log_pod_name: String::new(),
+            log_end: None,
             log_namespace: String::new(),
             log_tail_lines: 100,
             log_loading_history: false,
@@ -770,11 +3409,20 @@ impl App {
             status_filter_items: Vec::new(),
             status_filter_selected: HashSet::new(),
             status_filter_state: ListState::default(),
+            action_menu_state: ListState::default(),
+            kubectl_plugins: Some(Vec::new()),
+            plugin_menu_state: ListState::default(),
+            link_menu_state: ListState::default(),
+            endpoints: Vec::new(),
+            endpoint_menu_state: ListState::default(),
+            pending_mark: None,
+            marks: HashMap::new(),
             log_search_query: String::new(),
             log_search_input: String::new(),
             log_search_match_line: None,
             log_search_pending: false,
             app_state: AppState::default(),
+            config: Config::default(),
         }
     }
 
@@ -803,34 +3451,162 @@ impl App {
             .collect();
     }
 
+    /// Recomputes the filter after a keystroke, or once typing pauses.
+    pub fn filter_typed(&mut self) {
+        if self.items.len() > FILTER_DEBOUNCE_ABOVE {
+            self.filter_due = Some(Instant::now() + FILTER_DEBOUNCE);
+        } else {
+            self.refine_filter();
+        }
+    }
+
+    /// Runs a debounced recomputation once due. Returns whether the list changed.
+    pub fn flush_filter(&mut self, force: bool) -> bool {
+        match self.filter_due {
+            Some(due) if force || Instant::now() >= due => {
+                self.refine_filter();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Like `update_filter`, but narrows the previous results when it can.
+    pub fn refine_filter(&mut self) {
+        let mode = self.app_state.filter_mode;
+        let narrows = matches!(
+            &self.filter_applied,
+            Some((tab, applied_mode, applied))
+                if *tab == self.active_tab
+                    && *applied_mode == mode
+                    && mode != FilterMode::Regex
+                    && !applied.is_empty()
+                    && self.filter_query.starts_with(applied.as_str())
+        );
+        if !narrows {
+            self.update_filter();
+            return;
+        }
+        self.selected_indices.clear();
+        self.visual_anchor = None;
+        self.filter_due = None;
+        let matcher = NameMatcher::new(mode, &self.filter_query);
+        let mut scored: Vec<(i64, KubeResource)> = std::mem::take(&mut self.filtered_items)
+            .into_iter()
+            .filter_map(|item| matcher.score(item.name()).map(|s| (s, item)))
+            .collect();
+        if matcher.ranks() {
+            scored.sort_by_key(|(s, _)| std::cmp::Reverse(*s));
+        }
+        self.filtered_items = scored.into_iter().map(|(_, item)| item).collect();
+        self.filter_applied = Some((self.active_tab, mode, self.filter_query.clone()));
+    }
+
+    /// The item's match score, or `None` when the filters exclude it.
+    fn filter_score(&self, matcher: &NameMatcher, item: &KubeResource) -> Option<i64> {
+        if let KubeResource::Pod(p) = item
+            && self.active_tab == ResourceType::Pod
+            && !self.status_filter.is_empty()
+            && !self.status_filter.contains(Self::pod_phase(p))
+        {
+            return None;
+        }
+        matcher.score(item.name())
+    }
+
     pub fn update_filter(&mut self) {
         self.selected_indices.clear();
+        self.visual_anchor = None;
+        self.filter_due = None;
+        self.filter_applied = Some((
+            self.active_tab,
+            self.app_state.filter_mode,
+            self.filter_query.clone(),
+        ));
         let has_status = self.active_tab == ResourceType::Pod && !self.status_filter.is_empty();
         let has_query = !self.filter_query.is_empty();
 
         if !has_status && !has_query {
             self.filtered_items.clone_from(&self.items);
         } else {
-            let query = self.filter_query.to_lowercase();
             self.filtered_items = self
-                .items
-                .iter()
-                .filter(|item| {
-                    if has_status
-                        && let KubeResource::Pod(p) = item
-                        && !self.status_filter.contains(Self::pod_phase(p))
-                    {
-                        return false;
-                    }
-                    if has_query {
-                        return item.name().to_lowercase().contains(&query);
-                    }
-                    true
-                })
+                .filter_items(&self.items)
+                .into_iter()
                 .cloned()
                 .collect();
         }
     }
+
+    /// `items` through the list's name and status filters.
+    pub fn filter_items<'a>(&self, items: &'a [KubeResource]) -> Vec<&'a KubeResource> {
+        let matcher = NameMatcher::new(self.app_state.filter_mode, &self.filter_query);
+        let mut scored: Vec<(i64, &KubeResource)> = items
+            .iter()
+            .filter_map(|item| self.filter_score(&matcher, item).map(|s| (s, item)))
+            .collect();
+        if matcher.ranks() {
+            scored.sort_by_key(|(s, _)| std::cmp::Reverse(*s));
+        }
+        scored.into_iter().map(|(_, item)| item).collect()
+    }
+}
+
+/// What the editor opens with for `:apply` without a file.
+pub const MANIFEST_TEMPLATE: &str = "# Paste the manifests to apply below, then save and quit for a server\n# dry run and diff. Save it unchanged to cancel.\n";
+
+/// Prefix of the lines `resource_edited` adds above the YAML.
+const EDIT_NOTE: &str = "# kr: ";
+
+/// The text the editor opens for `edit`, with the last error on top.
+pub fn edit_text(edit: &ResourceEdit) -> String {
+    match &edit.error {
+        Some(e) => format!(
+            "{EDIT_NOTE}{}\n{EDIT_NOTE}Fix the YAML and save, or save it unchanged to discard the edit.\n{}",
+            e.replace('\n', " "),
+            edit.edited
+        ),
+        None => edit.edited.clone(),
+    }
+}
+
+fn strip_edit_header(text: &str) -> String {
+    let mut rest = text;
+    while let Some(line_end) = rest.find('\n')
+        && rest.starts_with(EDIT_NOTE)
+    {
+        rest = &rest[line_end + 1..];
+    }
+    rest.to_string()
+}
+
+/// Fetches the object for `edit`.
+async fn fetch_edit(
+    client: Client,
+    mut edit: ResourceEdit,
+    conflict: bool,
+    tx: tokio::sync::mpsc::UnboundedSender<KubeResourceEvent>,
+) {
+    let yaml = match crate::k8s::actions::fetch_for_edit(client, &edit).await {
+        Ok(yaml) => yaml,
+        Err(e) => {
+            let _ = tx.send(KubeResourceEvent::Error(format!(
+                "Fetch '{}' failed: {e}",
+                edit.name
+            )));
+            return;
+        }
+    };
+    if conflict {
+        edit.edited = crate::k8s::actions::rebase_edit(&edit.edited, &yaml);
+        edit.error = Some(format!(
+            "'{}' changed on the server after it was opened. This is the diff against its current state.",
+            edit.name
+        ));
+    } else {
+        edit.edited.clone_from(&yaml);
+    }
+    edit.original = yaml;
+    let _ = tx.send(KubeResourceEvent::EditReady(edit, conflict));
 }
 
 #[cfg(test)]
@@ -867,6 +3643,8 @@ mod tests {
         app.next_tab();
         assert_eq!(app.active_tab, ResourceType::Secret);
         app.next_tab();
+        assert_eq!(app.active_tab, ResourceType::ConfigMap);
+        app.next_tab();
         assert_eq!(app.active_tab, ResourceType::Pod);
     }
 
@@ -875,6 +3653,8 @@ mod tests {
         let mut app = App::new_test();
         assert_eq!(app.active_tab, ResourceType::Pod);
         app.prev_tab();
+        assert_eq!(app.active_tab, ResourceType::ConfigMap);
+        app.prev_tab();
         assert_eq!(app.active_tab, ResourceType::Secret);
         app.prev_tab();
         assert_eq!(app.active_tab, ResourceType::Deployment);
@@ -1004,8 +3784,34 @@ mod tests {
 
         let decoded = app.selected_secret_decoded.unwrap();
         assert_eq!(decoded.len(), 2);
-        assert!(decoded.iter().any(|(k, v)| k == "user" && v == "admin"));
-        assert!(decoded.iter().any(|(k, v)| k == "pass" && v == "s3cret"));
+        assert!(
+            decoded
+                .iter()
+                .any(|(k, v)| k == "user" && v.text() == Some("admin"))
+        );
+        assert!(
+            decoded
+                .iter()
+                .any(|(k, v)| k == "pass" && v.text() == Some("s3cret"))
+        );
+    }
+
+    #[test]
+    fn decoded_secret_values_are_typed() {
+        let secret = Secret {
+            data: Some(BTreeMap::from([
+                ("blob".to_string(), ByteString(vec![0x30, 0xff])),
+                ("note".to_string(), ByteString(b"<binary>".to_vec())),
+            ])),
+            ..Default::default()
+        };
+        assert_eq!(
+            decode_secret_data(&secret),
+            vec![
+                ("blob".to_string(), ConfigValue::Binary(vec![0x30, 0xff])),
+                ("note".to_string(), ConfigValue::Text("<binary>".into())),
+            ]
+        );
     }
 
     #[tokio::test]
@@ -1457,4 +4263,618 @@ mod tests {
         assert_eq!(app.log_search_match_line, Some(20));
         assert!(!app.log_search_pending);
     }
+
+    #[tokio::test]
+    async fn refresh_decoded_secret_picks_up_new_values() {
+        let mut app = App::new_test();
+        app.active_tab = ResourceType::Secret;
+        app.decoded_secret_name = "creds".into();
+        app.selected_secret_decoded = Some(vec![("user".into(), ConfigValue::Text("old".into()))]);
+        app.items = vec![make_secret("creds", vec![("user", "new")])];
+
+        app.refresh_decoded_secret();
+
+        let decoded = app.selected_secret_decoded.unwrap();
+        assert_eq!(
+            decoded,
+            vec![("user".to_string(), ConfigValue::Text("new".into()))]
+        );
+    }
+
+    #[tokio::test]
+    async fn pager_search_wraps_and_reports_misses() {
+        let mut app = App::new_test();
+        app.open_pager("s/k".into(), "alpha\nbeta\nGamma\nbeta".into());
+        app.pager_query = "beta".into();
+        app.pager_search(80, true, false);
+        assert_eq!(app.pager_scroll, 1);
+        app.pager_search(80, true, false);
+        assert_eq!(app.pager_scroll, 3);
+        app.pager_search(80, true, false);
+        assert_eq!(app.pager_scroll, 1);
+        app.pager_search(80, false, false);
+        assert_eq!(app.pager_scroll, 3);
+
+        app.pager_query = "gamma".into();
+        app.pager_search(80, true, true);
+        assert_eq!(app.pager_scroll, 2);
+        app.pager_search(80, true, true);
+        assert_eq!(app.pager_scroll, 2);
+
+        app.pager_query = "delta".into();
+        app.pager_search(80, true, false);
+        assert_eq!(app.pager_scroll, 2);
+        assert!(app.last_error.as_deref().unwrap().contains("delta"));
+    }
+
+    #[test]
+    fn secret_owner_detects_external_and_sealed_secrets() {
+        use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, OwnerReference};
+        let owned_by = |kind: &str| Secret {
+            metadata: ObjectMeta {
+                owner_references: Some(vec![OwnerReference {
+                    api_version: "external-secrets.io/v1beta1".into(),
+                    kind: kind.into(),
+                    name: "db".into(),
+                    uid: "1".into(),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let owner = secret_owner(&owned_by("ExternalSecret")).unwrap();
+        assert_eq!(owner.kind, "ExternalSecret");
+        assert_eq!(owner.name, "db");
+        assert!(secret_owner(&owned_by("SealedSecret")).is_some());
+        assert!(secret_owner(&owned_by("Deployment")).is_none());
+        assert!(secret_owner(&Secret::default()).is_none());
+    }
+
+    #[tokio::test]
+    async fn context_switch_moves_context_to_top() {
+        let mut app = App::new_test();
+        app.available_contexts = vec!["a".into(), "b".into(), "c".into()];
+        app.current_context = "b".into();
+        app.record_context_switch();
+        app.current_context = "c".into();
+        app.record_context_switch();
+        assert_eq!(app.available_contexts, vec!["c", "b", "a"]);
+        assert_eq!(app.app_state.recent_contexts, vec!["c", "b"]);
+    }
+
+    #[tokio::test]
+    async fn reload_contexts_flags_removed_current_context() {
+        let mut app = App::new_test();
+        app.current_context = "ctx1".into();
+
+        app.reload_contexts(vec!["ctx1".into(), "ctx2".into(), "ctx3".into()]);
+        assert!(!app.context_missing);
+        assert_eq!(app.available_contexts, vec!["ctx1", "ctx2", "ctx3"]);
+        assert!(app.last_success.as_ref().unwrap().contains("1 new context"));
+
+        app.reload_contexts(vec!["ctx2".into()]);
+        assert!(app.context_missing);
+        assert!(
+            app.last_error
+                .as_ref()
+                .unwrap()
+                .contains("'ctx1' was removed")
+        );
+        assert_eq!(app.available_contexts, vec!["ctx2"]);
+    }
+
+    #[tokio::test]
+    async fn cluster_probe_updates_header_state() {
+        let mut app = App::new_test();
+        assert!(app.cluster_probe_due());
+
+        app.apply_cluster_probe("other", Ok("v1.30.0".into()));
+        assert!(app.cluster_version.is_none());
+
+        app.apply_cluster_probe("test-context", Ok("v1.30.0".into()));
+        assert_eq!(app.cluster_version.as_deref(), Some("v1.30.0"));
+        assert_eq!(app.cluster_reachable, Some(true));
+
+        app.apply_cluster_probe("test-context", Err("timeout".into()));
+        assert_eq!(app.cluster_reachable, Some(false));
+        assert_eq!(app.cluster_version.as_deref(), Some("v1.30.0"));
+        assert!(app.cluster_unreachable_since.is_some());
+        app.cluster_probe_at = Some(Instant::now());
+        assert!(!app.cluster_probe_due());
+        app.cluster_probe_at = Some(Instant::now() - CLUSTER_RETRY_INTERVAL);
+        assert!(app.cluster_probe_due());
+
+        app.apply_cluster_probe("test-context", Err("timeout".into()));
+        app.apply_cluster_probe("test-context", Ok("v1.30.0".into()));
+        assert!(app.cluster_unreachable_since.is_none());
+        let history: Vec<_> = app.notifications.iter().map(|n| n.error).collect();
+        assert_eq!(history, [true, false]);
+    }
+
+    #[tokio::test]
+    async fn context_alias_labels_and_filters() {
+        let mut app = App::new_test();
+        app.config = Config::parse("contexts:\n  ctx2:\n    alias: staging\n").unwrap();
+        assert_eq!(app.context_label("ctx2"), "staging");
+        assert_eq!(app.context_label("ctx1"), "ctx1");
+
+        app.context_input = "stag".into();
+        app.update_context_filter();
+        assert_eq!(app.filtered_contexts, vec!["ctx2"]);
+    }
+
+    #[tokio::test]
+    async fn prewarm_candidates_follow_recency() {
+        let mut app = App::new_test();
+        app.available_contexts = vec!["a".into(), "b".into(), "c".into(), "test-context".into()];
+        app.app_state.recent_contexts = vec![
+            "test-context".into(),
+            "c".into(),
+            "gone".into(),
+            "a".into(),
+            "b".into(),
+        ];
+        assert!(app.prewarm_candidates().is_empty());
+
+        app.config.prewarm_contexts = 2;
+        assert_eq!(app.prewarm_candidates(), vec!["c", "a"]);
+
+        let client = app.client.clone();
+        app.warm_clients.insert("c".into(), client);
+        assert_eq!(app.prewarm_candidates(), vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn context_prefs_applied_on_switch() {
+        let mut app = App::new_test();
+        app.config = Config::parse(
+            "contexts:\n  prod:\n    namespace: web\n    tab: secrets\n    readOnly: true\n",
+        )
+        .unwrap();
+        app.current_context = "prod".into();
+        app.apply_context_prefs();
+        assert_eq!(app.current_namespace, "web");
+        assert_eq!(app.active_tab, ResourceType::Secret);
+        assert!(app.read_only);
+
+        app.current_context = "dev".into();
+        app.apply_context_prefs();
+        assert!(!app.read_only);
+        assert_eq!(app.current_namespace, "web");
+    }
+
+    #[tokio::test]
+    async fn detail_pane_resize_is_clamped_and_shows_pane() {
+        let mut app = App::new_test();
+        assert!(!app.app_state.detail_pane);
+        assert_eq!(app.detail_width(), 40);
+        app.resize_detail_pane(true);
+        assert!(app.app_state.detail_pane);
+        assert_eq!(app.detail_width(), 45);
+        for _ in 0..20 {
+            app.resize_detail_pane(false);
+        }
+        assert_eq!(app.detail_width(), 20);
+        app.toggle_detail_pane();
+        assert!(!app.app_state.detail_pane);
+    }
+
+    #[tokio::test]
+    async fn push_mode_restarts_stack_from_list() {
+        let mut app = App::new_test();
+        app.push_mode(AppMode::SecretDecode);
+        app.push_mode(AppMode::ValuePager);
+        assert_eq!(app.mode_stack, vec![AppMode::List, AppMode::SecretDecode]);
+        // A view reset straight to the list must not leave stale entries.
+        app.mode = AppMode::List;
+        app.push_mode(AppMode::DescribeView);
+        assert_eq!(app.mode_stack, vec![AppMode::List]);
+        app.push_mode(AppMode::DescribeView);
+        assert_eq!(app.mode_stack, vec![AppMode::List]);
+        app.pop_mode();
+        app.pop_mode();
+        assert_eq!(app.mode, AppMode::List);
+    }
+
+    #[tokio::test]
+    async fn webhooks_post_through_the_task_registry() {
+        let mut app = App::new_test();
+        app.config = Config::parse(
+            "contexts:\n  test-context:\n    webhooks:\n      - url: http://127.0.0.1:9/a\n      - {url: 'http://alerts.local/kr', events: [rolloutFailed]}\n",
+        )
+        .unwrap();
+        app.post_webhooks(
+            crate::config::WebhookEvent::CrashLoop,
+            "pod/web",
+            "is in CrashLoopBackOff",
+        );
+        assert_eq!(app.tasks.labels(), ["webhook 127.0.0.1"]);
+        app.tasks.cancel_all();
+    }
+
+    #[tokio::test]
+    async fn task_registry_tracks_and_cancels_tasks() {
+        async fn settle(tasks: &TaskRegistry, count: usize) {
+            for _ in 0..100 {
+                if tasks.labels().len() == count {
+                    return;
+                }
+                tokio::task::yield_now().await;
+            }
+            panic!(
+                "expected {count} tasks, found {}",
+                tasks.labels().join(", ")
+            );
+        }
+
+        let tasks = TaskRegistry::default();
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        tasks.spawn("describe api", async move {
+            let _ = rx.await;
+        });
+        tasks.spawn("delete web", std::future::pending::<()>());
+        tasks.spawn_global("prewarm staging", std::future::pending::<()>());
+        assert_eq!(
+            tasks.labels(),
+            ["describe api", "delete web", "prewarm staging"]
+        );
+        tx.send(()).unwrap();
+        settle(&tasks, 2).await;
+
+        tasks.cancel_context_tasks();
+        settle(&tasks, 1).await;
+        assert_eq!(tasks.labels(), ["prewarm staging"]);
+        tasks.cancel_all();
+        settle(&tasks, 0).await;
+    }
+
+    #[tokio::test]
+    async fn mutations_of_an_object_run_in_order_without_duplicates() {
+        let tasks = TaskRegistry::default();
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let log = order.clone();
+        let first = tasks.mutate(
+            "scale web to 3",
+            "scale 3",
+            vec!["web".into()],
+            async move {
+                let _ = rx.await;
+                log.lock().unwrap().push("scale");
+            },
+        );
+        assert!(first.is_ok());
+        let log = order.clone();
+        let second = tasks.mutate("delete web", "delete", vec!["web".into()], async move {
+            log.lock().unwrap().push("delete");
+        });
+        assert!(second.is_ok());
+        let again = tasks.mutate("delete web", "delete", vec!["web".into()], async {});
+        assert_eq!(again, Err("delete web".to_string()));
+        let other = tasks.mutate("delete api", "delete", vec!["api".into()], async {});
+        assert!(other.is_ok());
+
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(tasks.labels(), ["scale web to 3", "delete web (queued)"]);
+        assert!(order.lock().unwrap().is_empty());
+
+        tx.send(()).unwrap();
+        for _ in 0..100 {
+            if tasks.labels().is_empty() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(*order.lock().unwrap(), ["scale", "delete"]);
+        assert!(tasks.in_flight("delete", "web").is_none());
+    }
+
+    #[tokio::test]
+    async fn notifications_are_kept_newest_last_and_collapse_repeats() {
+        let mut app = App::new_test();
+        app.set_error("boom".to_string());
+        app.set_error("boom".to_string());
+        app.set_success("saved".to_string());
+        assert_eq!(app.notifications.len(), 2);
+        assert_eq!(app.notifications[0].repeats, 2);
+        let log = app.notification_log();
+        let lines: Vec<&str> = log.lines().collect();
+        assert!(lines[0].ends_with("OK     saved"));
+        assert!(lines[1].ends_with("ERROR  boom (x2)"));
+
+        for i in 0..MAX_NOTIFICATIONS + 5 {
+            app.set_success(format!("msg {i}"));
+        }
+        assert_eq!(app.notifications.len(), MAX_NOTIFICATIONS);
+        assert_eq!(app.notifications[0].message, "msg 5");
+    }
+
+    #[tokio::test]
+    async fn fuzzy_filter_ranks_by_score() {
+        let mut app = App::new_test();
+        app.items = vec![
+            make_pod("a-pod-init"),
+            make_pod("nginx"),
+            make_pod("api-server"),
+        ];
+        app.filter_query = "api".to_string();
+        app.update_filter();
+        assert_eq!(app.filtered_items.len(), 1);
+
+        app.cycle_filter_mode();
+        let names: Vec<&str> = app.filtered_items.iter().map(|i| i.name()).collect();
+        assert_eq!(names, vec!["api-server", "a-pod-init"]);
+    }
+
+    #[tokio::test]
+    async fn split_pane_items_go_through_the_list_filter() {
+        let mut app = App::new_test();
+        let mut running = Pod::default();
+        running.metadata.name = Some("api-server".into());
+        running.status = Some(k8s_openapi::api::core::v1::PodStatus {
+            phase: Some("Running".into()),
+            ..Default::default()
+        });
+        let pane = vec![
+            make_pod("a-pod-init"),
+            make_pod("nginx"),
+            KubeResource::Pod(Arc::new(running)),
+        ];
+        let names = |app: &App| -> Vec<String> {
+            app.filter_items(&pane)
+                .iter()
+                .map(|i| i.name().to_string())
+                .collect()
+        };
+
+        app.filter_query = "api".into();
+        app.cycle_filter_mode();
+        assert_eq!(names(&app), ["api-server", "a-pod-init"]);
+
+        app.status_filter.insert("Unknown".into());
+        assert_eq!(names(&app), ["a-pod-init"]);
+    }
+
+    #[tokio::test]
+    async fn tabs_keep_their_filter_and_selection() {
+        let mut app = App::new_test();
+        app.items = vec![make_pod("api"), make_pod("web")];
+        app.filter_query = "w".to_string();
+        app.update_filter();
+        app.table_state.select(Some(0));
+
+        app.next_tab();
+        assert_eq!(app.active_tab, ResourceType::Deployment);
+        assert!(app.filter_query.is_empty());
+        assert_eq!(app.restore_selection, None);
+        app.filter_query = "db".to_string();
+
+        app.prev_tab();
+        assert_eq!(app.filter_query, "w");
+        assert_eq!(app.restore_selection.as_deref(), Some("web"));
+        app.select_tab(ResourceType::Deployment);
+        assert_eq!(app.filter_query, "db");
+    }
+
+    #[tokio::test]
+    async fn last_error_opens_in_pager() {
+        let mut app = App::new_test();
+        app.open_last_error();
+        assert_eq!(app.mode, AppMode::List);
+
+        app.set_error("first".to_string());
+        app.set_success("done".to_string());
+        app.open_last_error();
+        assert_eq!(app.mode, AppMode::ValuePager);
+        assert!(app.pager_title.starts_with("Error at "));
+        assert_eq!(app.pager_text, "first");
+    }
+
+    #[tokio::test]
+    async fn apply_startup_sets_tab_and_filters_once() {
+        let mut app = App::new_test();
+        app.startup = Startup {
+            tab: Some(ResourceType::Deployment),
+            filter: Some("api".to_string()),
+            status: vec!["Pending".to_string()],
+        };
+        app.apply_startup();
+        assert_eq!(app.active_tab, ResourceType::Deployment);
+        assert_eq!(app.filter_query, "api");
+        assert!(app.status_filter.contains("Pending"));
+
+        app.filter_query.clear();
+        app.apply_startup();
+        assert_eq!(app.filter_query, "");
+    }
+
+    #[tokio::test]
+    async fn extended_query_narrows_previous_results() {
+        let mut app = App::new_test();
+        app.items = vec![make_pod("api-a"), make_pod("api-b"), make_pod("web")];
+        app.filter_query = "api".into();
+        app.update_filter();
+        // An item the previous results left out stays out when narrowing.
+        app.filtered_items.remove(0);
+        app.filter_query.push_str("-a");
+        app.refine_filter();
+        assert!(app.filtered_items.is_empty());
+
+        // Shortening the query rescans everything.
+        app.filter_query = "api-".into();
+        app.refine_filter();
+        assert_eq!(app.filtered_items.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn typing_waits_for_a_pause_on_large_lists() {
+        let mut app = App::new_test();
+        app.items = (0..=FILTER_DEBOUNCE_ABOVE)
+            .map(|i| make_pod(&format!("pod-{i}")))
+            .collect();
+        app.update_filter();
+        app.filter_query = "pod-7".into();
+        app.filter_typed();
+        assert_eq!(app.filtered_items.len(), app.items.len());
+        assert!(!app.flush_filter(false));
+        assert!(app.flush_filter(true));
+        assert_eq!(app.filtered_items.len(), 111);
+        assert!(app.filter_due.is_none());
+    }
+
+    #[tokio::test]
+    async fn watch_changes_apply_in_place() {
+        let mut app = App::new_test();
+        app.items = vec![make_pod("a"), make_pod("c"), make_pod("e")];
+        app.update_filter();
+        app.table_state.select(Some(1));
+        app.selected_indices.insert(2);
+
+        assert!(app.apply_item_change(make_pod("b"), false));
+        let names: Vec<&str> = app.filtered_items.iter().map(|r| r.name()).collect();
+        assert_eq!(names, ["a", "b", "c", "e"]);
+        assert_eq!(app.table_state.selected(), Some(2));
+        assert!(app.selected_indices.contains(&3));
+
+        assert!(app.apply_item_change(make_pod("c"), true));
+        assert_eq!(app.items.len(), 3);
+        assert_eq!(app.filtered_items[2].name(), "e");
+        assert_eq!(app.table_state.selected(), Some(2));
+        assert!(app.selected_indices.contains(&2));
+
+        assert!(!app.apply_item_change(make_pod("zz"), true));
+        assert!(!app.apply_item_change(make_secret("b", vec![]), false));
+
+        // Rows outside the filter only change `items`.
+        app.filter_query = "e".into();
+        app.update_filter();
+        assert!(app.apply_item_change(make_pod("d"), false));
+        assert_eq!(app.items.len(), 4);
+        assert_eq!(app.filtered_items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn split_on_the_main_namespace_shares_the_main_store() {
+        let mut app = App::new_test();
+        let (store, mut writer) = kube::runtime::reflector::store::<Pod>();
+        app.pod_store = Some(store);
+        let mut pod = Pod::default();
+        pod.metadata.name = Some("api".into());
+        writer.apply_watcher_event(&kube::runtime::watcher::Event::Apply(pod));
+
+        let (context, namespace) = (app.current_context.clone(), app.current_namespace.clone());
+        let client = app.client.clone();
+        app.open_split(client.clone(), context, namespace);
+        let pane = app.split.as_ref().unwrap();
+        assert!(pane.shared);
+        assert!(pane.watcher.is_none());
+        assert_eq!(pane.items.len(), 1);
+
+        let mut pod = Pod::default();
+        pod.metadata.name = Some("web".into());
+        writer.apply_watcher_event(&kube::runtime::watcher::Event::Apply(pod));
+        app.items_changed();
+        assert_eq!(app.split.as_ref().unwrap().items.len(), 2);
+        assert!(app.split_follows_main());
+
+        app.open_split(client, "other".into(), "staging".into());
+        let pane = app.split.as_ref().unwrap();
+        assert!(!pane.shared);
+        assert!(pane.watcher.is_some());
+        assert!(!app.split_follows_main());
+    }
+
+    #[tokio::test]
+    async fn rejected_credentials_request_one_reauth_per_cooldown() {
+        let mut app = App::new_test();
+        app.request_reauth();
+        assert!(std::mem::take(&mut app.reauth_requested));
+        app.request_reauth();
+        assert!(!app.reauth_requested);
+
+        app.reauthenticated(app.client.clone());
+        assert!(app.refresh_requested);
+        assert!(app.cluster_probe_due());
+
+        app.reauth_at = Some(Instant::now() - REAUTH_COOLDOWN);
+        app.request_reauth();
+        assert!(app.reauth_requested);
+    }
+
+    #[tokio::test]
+    async fn saved_edits_are_validated_before_the_diff_preview() {
+        let mut app = App::new_test();
+        let original = "apiVersion: v1\nkind: Pod\nmetadata:\n  name: web\n  namespace: default\nspec:\n  replicas: 1\n";
+        let edit = ResourceEdit {
+            kind: "pod",
+            name: "web".into(),
+            namespace: "default".into(),
+            original: original.into(),
+            edited: original.into(),
+            error: None,
+        };
+
+        let renamed = original.replace("name: web", "name: other");
+        app.resource_edited(edit.clone(), Ok(renamed.clone()));
+        let Some(EditorRequest::Resource(reopened)) = app.pending_editor.take() else {
+            panic!("editor not reopened");
+        };
+        assert!(reopened.error.is_some());
+        assert_eq!(reopened.edited, renamed);
+        assert!(edit_text(&reopened).starts_with(EDIT_NOTE));
+
+        // Saving the annotated text unchanged gives up on the edit.
+        app.resource_edited(reopened.clone(), Ok(edit_text(&reopened)));
+        assert!(app.pending_editor.is_none());
+        assert_eq!(app.mode, AppMode::List);
+
+        let scaled = original.replace("replicas: 1", "replicas: 2");
+        app.resource_edited(reopened, Ok(scaled));
+        assert_eq!(app.mode, AppMode::EditDiff);
+        assert!(app.pager_text.contains("-   replicas: 1\n+   replicas: 2"));
+        assert!(
+            app.resource_edit
+                .as_ref()
+                .is_some_and(|e| e.error.is_none())
+        );
+    }
+
+    #[tokio::test]
+    async fn manifest_plan_previews_each_object() {
+        let mut app = App::new_test();
+        app.plan_manifest("kind: ConfigMap\nmetadata: {name: a}\n");
+        assert!(app.last_error.as_ref().unwrap().contains("document 1"));
+
+        let objects = crate::k8s::actions::parse_manifest(
+            "# comment only\n---\napiVersion: v1\nkind: ConfigMap\nmetadata: {name: a}\n---\napiVersion: v1\nkind: Namespace\nmetadata: {name: b}\n",
+        )
+        .unwrap();
+        assert_eq!(objects.len(), 2);
+        let item =
+            |object: &kube::api::DynamicObject, namespace: Option<&str>, plan| ManifestObject {
+                label: format!("x/{}", object.metadata.name.as_deref().unwrap()),
+                object: object.clone(),
+                resource: None,
+                namespace: namespace.map(String::from),
+                plan,
+                created: false,
+            };
+        app.show_manifest_plan(vec![
+            item(&objects[0], Some("default"), Ok(vec!["+ x".into()])),
+            item(&objects[1], None, Err("forbidden".into())),
+        ]);
+        assert_eq!(app.mode, AppMode::ApplyDiff);
+        assert!(app.pager_text.starts_with(
+            "== x/a -n default (configured)\n+ x\n\n== x/b (dry run failed)\n! forbidden"
+        ));
+        crate::input::handle_input(
+            &mut app,
+            crossterm::event::KeyEvent::from(crossterm::event::KeyCode::Esc),
+        );
+        assert_eq!(app.mode, AppMode::List);
+        assert!(app.manifest_plan.is_empty());
+    }
 }
@@ -0,0 +1,161 @@
+//! Append-only record of the mutating actions taken from kr: one JSON
+//! object per line in `~/.config/kr/audit.log`, browsed with `:audit`.
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Most entries `:audit` shows, newest first.
+pub const AUDIT_VIEW_LIMIT: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// When the action was confirmed, RFC 3339.
+    pub at: String,
+    /// Local user running kr.
+    pub user: String,
+    /// The context's kubeconfig user, when it names one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kube_user: Option<String>,
+    pub context: String,
+    /// Empty for cluster-wide objects such as namespaces.
+    pub namespace: String,
+    /// `delete`, `scale`, `patch`, ...
+    pub action: String,
+    /// `kind/name`, plus any detail such as the replica count.
+    pub target: String,
+    /// `ok`, or the error.
+    pub result: String,
+}
+
+pub fn audit_path() -> PathBuf {
+    // Tests confirm actions too; keep them out of the real log.
+    let mut path = match cfg!(test) {
+        true => std::env::temp_dir(),
+        false => dirs::config_dir().unwrap_or_else(|| PathBuf::from(".")),
+    };
+    path.push("kr");
+    path.push("audit.log");
+    path
+}
+
+impl AuditEntry {
+    pub fn new(context: &str, namespace: &str, action: &str, target: String) -> Self {
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_default();
+        Self {
+            at: jiff::Timestamp::now().to_string(),
+            user,
+            kube_user: None,
+            context: context.to_string(),
+            namespace: namespace.to_string(),
+            action: action.to_string(),
+            target,
+            result: String::new(),
+        }
+    }
+
+    /// Fills in the outcome and appends the entry in the background.
+    pub fn record<E: std::fmt::Display>(self, result: &Result<(), E>) {
+        match result {
+            Ok(()) => self.record_as("ok"),
+            Err(e) => self.record_as(&e.to_string()),
+        }
+    }
+
+    /// Like `record`, for an outcome kr doesn't see itself.
+    pub fn record_as(mut self, result: &str) {
+        self.result = result.to_string();
+        tokio::task::spawn_blocking(move || {
+            self.kube_user = crate::k8s::config::context_user(&self.context);
+            if let Err(e) = append(&audit_path(), &self) {
+                tracing::warn!("Failed to write audit log: {e}");
+            }
+        });
+    }
+
+    /// One line of the `:audit` view.
+    pub fn line(&self) -> String {
+        let who = match &self.kube_user {
+            Some(kube_user) => format!("{} ({kube_user})", self.user),
+            None => self.user.clone(),
+        };
+        let place = match self.namespace.as_str() {
+            "" => self.context.clone(),
+            ns => format!("{}/{ns}", self.context),
+        };
+        let at = self
+            .at
+            .parse::<jiff::Timestamp>()
+            .map(|t| {
+                t.to_zoned(jiff::tz::TimeZone::system())
+                    .strftime("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_else(|_| self.at.clone());
+        format!(
+            "{at}  {who}  {place}  {} {}  {}",
+            self.action, self.target, self.result
+        )
+    }
+}
+
+fn append(path: &Path, entry: &AuditEntry) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    let json = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+    writeln!(file, "{json}")
+}
+
+/// The last `limit` entries that parse, newest first.
+pub fn read_recent(path: &Path, limit: usize) -> Vec<AuditEntry> {
+    let Ok(text) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    text.lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .take(limit)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_and_reads_newest_first() {
+        let path = std::env::temp_dir().join(format!("kr-audit-{}/audit.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut first = AuditEntry::new("prod", "payments", "scale", "deployment/api to 3".into());
+        first.result = "ok".into();
+        let mut second = AuditEntry::new("prod", "", "delete", "namespace/old".into());
+        second.result = "forbidden".into();
+        append(&path, &first).unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut f| writeln!(f, "not json"))
+            .unwrap();
+        append(&path, &second).unwrap();
+
+        assert_eq!(read_recent(&path, 10), [second.clone(), first]);
+        assert_eq!(read_recent(&path, 1), [second.clone()]);
+        assert!(
+            second
+                .line()
+                .ends_with("prod  delete namespace/old  forbidden")
+        );
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
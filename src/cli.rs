@@ -0,0 +1,530 @@
+//! Non-interactive subcommands for scripts: the same rows the TUI tables
+//! show, printed once to stdout, and pod logs.
+
+use crate::app::{App, Startup, secret_owner};
+use crate::models::{FilterMode, KubeResource, KubeResourceEvent, ResourceType};
+use crate::utils::NameMatcher;
+use anyhow::Result;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned columns, as in the TUI.
+    #[default]
+    Table,
+    /// Table plus the columns `w` adds in the TUI.
+    Wide,
+    /// Array of objects with every column, wide ones included.
+    Json,
+    Yaml,
+}
+
+/// `(header, key)` for each column; the key names the field in JSON/YAML.
+fn columns(tab: ResourceType, wide: bool) -> Vec<(&'static str, &'static str)> {
+    let (base, extra): (&[_], &[_]) = match tab {
+        ResourceType::Pod => (
+            &[
+                ("Name", "name"),
+                ("Ready", "ready"),
+                ("Status", "status"),
+                ("Restarts", "restarts"),
+                ("Age", "age"),
+            ],
+            &[
+                ("IP", "ip"),
+                ("Node", "node"),
+                ("QoS", "qos"),
+                ("Images", "images"),
+            ],
+        ),
+        ResourceType::Deployment => (
+            &[
+                ("Name", "name"),
+                ("Ready", "ready"),
+                ("Up-to-date", "upToDate"),
+                ("Available", "available"),
+                ("Age", "age"),
+            ],
+            &[
+                ("Containers", "containers"),
+                ("Images", "images"),
+                ("Selector", "selector"),
+            ],
+        ),
+        ResourceType::Secret => (
+            &[
+                ("Name", "name"),
+                ("Type", "type"),
+                ("Managed By", "managedBy"),
+                ("Data Count", "dataCount"),
+                ("Age", "age"),
+            ],
+            &[],
+        ),
+        ResourceType::ConfigMap => (
+            &[
+                ("Name", "name"),
+                ("Data Count", "dataCount"),
+                ("Age", "age"),
+            ],
+            &[],
+        ),
+    };
+    let mut columns = base.to_vec();
+    if wide {
+        columns.extend_from_slice(extra);
+    }
+    columns
+}
+
+/// One row's cells, in `columns(tab, true)` order.
+fn row(item: &KubeResource) -> Vec<String> {
+    let age = |meta: &k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta| {
+        crate::utils::get_resource_age(meta.creation_timestamp.as_ref())
+    };
+    match item {
+        KubeResource::Pod(p) => {
+            let status = p.status.as_ref();
+            let statuses = status.and_then(|s| s.container_statuses.as_deref());
+            let ready = statuses.map_or(0, |c| c.iter().filter(|cs| cs.ready).count());
+            let restarts: i32 = statuses.map_or(0, |c| c.iter().map(|cs| cs.restart_count).sum());
+            let spec = p.spec.as_ref();
+            let images: Vec<&str> = spec
+                .iter()
+                .flat_map(|s| &s.containers)
+                .filter_map(|c| c.image.as_deref())
+                .collect();
+            vec![
+                item.name().to_string(),
+                format!("{ready}/{}", spec.map_or(0, |s| s.containers.len())),
+                App::pod_phase(p).to_string(),
+                restarts.to_string(),
+                age(&p.metadata),
+                status.and_then(|s| s.pod_ip.clone()).unwrap_or_default(),
+                spec.and_then(|s| s.node_name.clone()).unwrap_or_default(),
+                status.and_then(|s| s.qos_class.clone()).unwrap_or_default(),
+                images.join(","),
+            ]
+        }
+        KubeResource::Deployment(d) => {
+            let status = d.status.as_ref();
+            let spec = d.spec.as_ref();
+            let containers = spec
+                .and_then(|s| s.template.spec.as_ref())
+                .map(|s| s.containers.as_slice())
+                .unwrap_or_default();
+            let selector: Vec<String> = spec
+                .and_then(|s| s.selector.match_labels.as_ref())
+                .iter()
+                .flat_map(|labels| labels.iter().map(|(k, v)| format!("{k}={v}")))
+                .collect();
+            vec![
+                item.name().to_string(),
+                format!(
+                    "{}/{}",
+                    status.and_then(|s| s.ready_replicas).unwrap_or(0),
+                    status.and_then(|s| s.replicas).unwrap_or(0)
+                ),
+                status
+                    .and_then(|s| s.updated_replicas)
+                    .unwrap_or(0)
+                    .to_string(),
+                status
+                    .and_then(|s| s.available_replicas)
+                    .unwrap_or(0)
+                    .to_string(),
+                age(&d.metadata),
+                containers
+                    .iter()
+                    .map(|c| c.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(","),
+                containers
+                    .iter()
+                    .filter_map(|c| c.image.as_deref())
+                    .collect::<Vec<_>>()
+                    .join(","),
+                selector.join(","),
+            ]
+        }
+        KubeResource::Secret(s) => vec![
+            item.name().to_string(),
+            s.type_.clone().unwrap_or_default(),
+            secret_owner(s).map(|o| o.kind).unwrap_or_default(),
+            s.data.as_ref().map_or(0, |d| d.len()).to_string(),
+            age(&s.metadata),
+        ],
+        KubeResource::ConfigMap(c) => {
+            let count = c.data.as_ref().map_or(0, |d| d.len())
+                + c.binary_data.as_ref().map_or(0, |d| d.len());
+            vec![item.name().to_string(), count.to_string(), age(&c.metadata)]
+        }
+    }
+}
+
+/// A row as a map that keeps the column order, unlike `serde_json::Map`.
+struct Object<'a> {
+    keys: &'a [&'a str],
+    values: &'a [String],
+}
+
+impl serde::Serialize for Object<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.keys.len()))?;
+        for (key, value) in self.keys.iter().zip(self.values) {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Formats `items` of `tab` for stdout.
+pub fn render(tab: ResourceType, items: &[KubeResource], format: OutputFormat) -> Result<String> {
+    let wide = format != OutputFormat::Table;
+    let columns = columns(tab, wide);
+    let rows: Vec<Vec<String>> = items
+        .iter()
+        .map(|item| {
+            let mut cells = row(item);
+            cells.truncate(columns.len());
+            cells
+        })
+        .collect();
+    match format {
+        OutputFormat::Table | OutputFormat::Wide => {
+            let mut widths: Vec<usize> = columns.iter().map(|(h, _)| h.len()).collect();
+            for cells in &rows {
+                for (w, cell) in widths.iter_mut().zip(cells) {
+                    *w = (*w).max(cell.chars().count());
+                }
+            }
+            let line = |cells: &[String]| {
+                let padded: Vec<String> = cells
+                    .iter()
+                    .zip(&widths)
+                    .map(|(c, w)| format!("{c:<w$}"))
+                    .collect();
+                padded.join("   ").trim_end().to_string() + "\n"
+            };
+            let headers: Vec<String> = columns.iter().map(|(h, _)| h.to_uppercase()).collect();
+            let mut out = line(&headers);
+            for cells in &rows {
+                out.push_str(&line(cells));
+            }
+            Ok(out)
+        }
+        OutputFormat::Json | OutputFormat::Yaml => {
+            let keys: Vec<&str> = columns.iter().map(|(_, key)| *key).collect();
+            let objects: Vec<Object> = rows
+                .iter()
+                .map(|cells| Object {
+                    keys: &keys,
+                    values: cells,
+                })
+                .collect();
+            Ok(if format == OutputFormat::Json {
+                serde_json::to_string_pretty(&objects)? + "\n"
+            } else {
+                serde_yaml::to_string(&objects)?
+            })
+        }
+    }
+}
+
+/// `kr get <tab>`: lists the tab's resources in `namespace` and prints them.
+pub async fn get(tab: ResourceType, namespace: Option<String>, format: OutputFormat) -> Result<()> {
+    let client = crate::k8s::client::default_client().await?;
+    let namespace = match namespace {
+        Some(ns) => ns,
+        None => crate::k8s::config::get_context_namespace()?,
+    };
+    let items = crate::k8s::actions::list_resources(client, &namespace, tab).await?;
+    print!("{}", render(tab, &items, format)?);
+    Ok(())
+}
+
+/// The first screen of the TUI as plain text, for CI and pipes.
+pub async fn snapshot(startup: Startup) -> Result<()> {
+    let config = crate::config::Config::load().map_err(anyhow::Error::msg)?;
+    let context = crate::k8s::config::get_current_context()?;
+    let prefs = config.context(&context);
+    let tab = startup
+        .tab
+        .or_else(|| prefs.and_then(|p| p.tab()))
+        .unwrap_or(ResourceType::Pod);
+    let namespace = match prefs.and_then(|p| p.namespace.clone()) {
+        Some(ns) => ns,
+        None => crate::k8s::config::get_context_namespace()?,
+    };
+    let client = crate::k8s::client::default_client().await?;
+    let items = crate::k8s::actions::list_resources(client, &namespace, tab).await?;
+    let items = filter_items(items, startup.filter.as_deref(), &startup.status);
+    print!("{}", render(tab, &items, OutputFormat::Table)?);
+    Ok(())
+}
+
+/// Keeps the items matching `filter` and, for pods, `status`.
+fn filter_items(
+    items: Vec<KubeResource>,
+    filter: Option<&str>,
+    status: &[String],
+) -> Vec<KubeResource> {
+    let matcher = NameMatcher::new(FilterMode::Substring, filter.unwrap_or_default());
+    items
+        .into_iter()
+        .filter(|item| matcher.score(item.name()).is_some())
+        .filter(|item| match item {
+            KubeResource::Pod(p) if !status.is_empty() => {
+                status.iter().any(|s| s == App::pod_phase(p))
+            }
+            _ => true,
+        })
+        .collect()
+}
+
+/// `kr logs <pod>`: prints the pod's log to stdout, following it with `follow`.
+pub async fn logs(
+    pod: String,
+    namespace: Option<String>,
+    container: Option<String>,
+    follow: bool,
+    since: Option<String>,
+) -> Result<()> {
+    let since_seconds = match since {
+        Some(text) => Some(
+            crate::utils::parse_duration_secs(&text)
+                .ok_or_else(|| anyhow::anyhow!("invalid --since '{text}' (e.g. 30s, 10m, 2h)"))?,
+        ),
+        None => None,
+    };
+    let client = crate::k8s::client::default_client().await?;
+    let namespace = match namespace {
+        Some(ns) => ns,
+        None => crate::k8s::config::get_context_namespace()?,
+    };
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let lp = kube::api::LogParams {
+        follow,
+        container,
+        since_seconds,
+        ..Default::default()
+    };
+    crate::k8s::actions::stream_pod_logs(client, &namespace, &pod, tx, lp);
+    while let Some(event) = rx.recv().await {
+        match event {
+            KubeResourceEvent::Log(line) => println!("{line}"),
+            KubeResourceEvent::Error(e) => anyhow::bail!(e),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Splits `pod/name` or `svc/name` into kubectl's resource kind and the name.
+fn parse_forward_target(target: &str) -> Result<(&'static str, &str)> {
+    let (kind, name) = match target.split_once('/') {
+        Some((kind, name)) => (kind, name),
+        None => ("pod", target),
+    };
+    let kind = match kind {
+        "pod" | "pods" | "po" => "pod",
+        "svc" | "service" | "services" => "service",
+        other => anyhow::bail!("cannot port-forward to '{other}' (use pod/<name> or svc/<name>)"),
+    };
+    if name.is_empty() {
+        anyhow::bail!("missing name in '{target}'");
+    }
+    Ok((kind, name))
+}
+
+/// Checks `local:remote` (or a single port used for both).
+fn parse_port_mapping(mapping: &str) -> Result<(u16, u16)> {
+    let parse = |port: &str| {
+        port.parse::<u16>()
+            .map_err(|_| anyhow::anyhow!("invalid port '{port}' in '{mapping}'"))
+    };
+    match mapping.split_once(':') {
+        Some((local, remote)) => Ok((parse(local)?, parse(remote)?)),
+        None => parse(mapping).map(|port| (port, port)),
+    }
+}
+
+/// `kr port-forward <pod|svc>/<name> local:remote...`: forwards until Ctrl+C.
+pub async fn port_forward(
+    target: String,
+    ports: Vec<String>,
+    namespace: Option<String>,
+) -> Result<()> {
+    let (kind, name) = parse_forward_target(&target)?;
+    let mut mappings = Vec::new();
+    for mapping in &ports {
+        let (local, remote) = parse_port_mapping(mapping)?;
+        mappings.push(format!("{local}:{remote}"));
+    }
+    let namespace = match namespace {
+        Some(ns) => ns,
+        None => crate::k8s::config::get_context_namespace()?,
+    };
+    let mut child = tokio::process::Command::new("kubectl")
+        .arg("port-forward")
+        .arg(format!("{kind}/{name}"))
+        .args(&mappings)
+        .args(["-n", &namespace])
+        .envs(crate::k8s::config::kubectl_env())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("failed to run kubectl: {e}"))?;
+    tokio::select! {
+        status = child.wait() => {
+            let status = status?;
+            if !status.success() {
+                anyhow::bail!("kubectl port-forward exited with {status}");
+            }
+        }
+        _ = tokio::signal::ctrl_c() => {
+            let _ = child.kill().await;
+            eprintln!("Stopped forwarding to {kind}/{name}");
+        }
+    }
+    Ok(())
+}
+
+/// kubectl arguments for `kr exec`, with a TTY only when stdin is one.
+fn exec_args(
+    pod: &str,
+    namespace: &str,
+    container: Option<&str>,
+    tty: bool,
+    command: &[String],
+) -> Vec<String> {
+    let mut args = vec!["exec".to_string(), "-i".to_string()];
+    if tty {
+        args.push("-t".to_string());
+    }
+    args.extend([pod.to_string(), "-n".to_string(), namespace.to_string()]);
+    if let Some(container) = container {
+        args.extend(["-c".to_string(), container.to_string()]);
+    }
+    args.push("--".to_string());
+    args.extend(command.iter().cloned());
+    args
+}
+
+/// `kr exec <pod> -- <cmd...>`: runs the command and returns its exit code.
+pub fn exec(
+    pod: String,
+    namespace: Option<String>,
+    container: Option<String>,
+    command: Vec<String>,
+) -> Result<i32> {
+    use std::io::IsTerminal;
+    let namespace = match namespace {
+        Some(ns) => ns,
+        None => crate::k8s::config::get_context_namespace()?,
+    };
+    let args = exec_args(
+        &pod,
+        &namespace,
+        container.as_deref(),
+        std::io::stdin().is_terminal(),
+        &command,
+    );
+    let status = std::process::Command::new("kubectl")
+        .args(&args)
+        .envs(crate::k8s::config::kubectl_env())
+        .status()
+        .map_err(|e| anyhow::anyhow!("failed to run kubectl: {e}"))?;
+    Ok(status.code().unwrap_or(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{ConfigMap, Pod};
+    use std::sync::Arc;
+
+    fn configmap(name: &str, keys: &[&str]) -> KubeResource {
+        let mut cm = ConfigMap::default();
+        cm.metadata.name = Some(name.to_string());
+        cm.data = Some(
+            keys.iter()
+                .map(|k| (k.to_string(), String::new()))
+                .collect(),
+        );
+        KubeResource::ConfigMap(Arc::new(cm))
+    }
+
+    #[test]
+    fn renders_aligned_table() {
+        let items = [configmap("app-config", &["a", "b"]), configmap("x", &[])];
+        let out = render(ResourceType::ConfigMap, &items, OutputFormat::Table).unwrap();
+        assert_eq!(
+            out,
+            "NAME         DATA COUNT   AGE\napp-config   2            ?\nx            0            ?\n"
+        );
+    }
+
+    #[test]
+    fn json_and_yaml_include_wide_columns() {
+        let mut pod = Pod::default();
+        pod.metadata.name = Some("api".to_string());
+        let items = [KubeResource::Pod(Arc::new(pod))];
+        let json = render(ResourceType::Pod, &items, OutputFormat::Json).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0]["name"], "api");
+        assert_eq!(parsed[0]["status"], "Unknown");
+        assert_eq!(parsed[0]["node"], "");
+
+        let yaml = render(ResourceType::Pod, &items, OutputFormat::Yaml).unwrap();
+        assert!(yaml.starts_with("- name: api\n"));
+        assert!(yaml.contains("restarts: '0'"));
+    }
+
+    #[test]
+    fn snapshot_filters_by_name_and_status() {
+        use k8s_openapi::api::core::v1::PodStatus;
+        let pod = |name: &str, phase: &str| {
+            let mut pod = Pod::default();
+            pod.metadata.name = Some(name.to_string());
+            pod.status = Some(PodStatus {
+                phase: Some(phase.to_string()),
+                ..Default::default()
+            });
+            KubeResource::Pod(Arc::new(pod))
+        };
+        let items = vec![
+            pod("api-1", "Running"),
+            pod("api-2", "Pending"),
+            pod("web", "Pending"),
+        ];
+        let kept = filter_items(items, Some("API"), &["Pending".to_string()]);
+        let names: Vec<&str> = kept.iter().map(|i| i.name()).collect();
+        assert_eq!(names, ["api-2"]);
+    }
+
+    #[test]
+    fn parses_port_forward_arguments() {
+        assert_eq!(parse_forward_target("svc/api").unwrap(), ("service", "api"));
+        assert_eq!(parse_forward_target("web-0").unwrap(), ("pod", "web-0"));
+        assert!(parse_forward_target("deploy/api").is_err());
+        assert!(parse_forward_target("pod/").is_err());
+
+        assert_eq!(parse_port_mapping("8080:80").unwrap(), (8080, 80));
+        assert_eq!(parse_port_mapping("5432").unwrap(), (5432, 5432));
+        assert!(parse_port_mapping("80:http").is_err());
+        assert!(parse_port_mapping("70000").is_err());
+    }
+
+    #[test]
+    fn exec_args_add_tty_and_container() {
+        let cmd = vec!["ls".to_string(), "-l".to_string()];
+        assert_eq!(
+            exec_args("web", "prod", Some("app"), true, &cmd).join(" "),
+            "exec -i -t web -n prod -c app -- ls -l"
+        );
+        assert_eq!(
+            exec_args("web", "prod", None, false, &cmd).join(" "),
+            "exec -i web -n prod -- ls -l"
+        );
+    }
+}
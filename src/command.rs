@@ -0,0 +1,164 @@
+//! `:` command palette: parsing and Tab completion.
+
+use crate::models::ResourceType;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Quit,
+    Help,
+    /// Switch namespace; `None` opens the namespace popup.
+    Namespace(Option<String>),
+    /// Switch context; `None` opens the context popup.
+    Context(Option<String>),
+    Tab(ResourceType),
+    /// Browse the audit log of performed actions.
+    Audit,
+    /// Apply a manifest file; `None` opens `$EDITOR` to paste one.
+    Apply(Option<String>),
+    /// Write the filtered table to a CSV (or `.tsv`) file.
+    Export(Option<String>),
+    /// Renew a Teleport session with `tsh kube login`.
+    Login(Option<String>),
+}
+
+/// Command names offered by completion; aliases are accepted but not offered.
+const COMMANDS: &[&str] = &[
+    "apply",
+    "audit",
+    "configmaps",
+    "ctx",
+    "deploy",
+    "export",
+    "help",
+    "login",
+    "ns",
+    "pods",
+    "q",
+    "quit",
+    "secrets",
+];
+
+pub fn parse_command(input: &str) -> Result<Command, String> {
+    let mut parts = input.split_whitespace();
+    let Some(name) = parts.next() else {
+        return Err("Empty command".to_string());
+    };
+    let arg = parts.next().map(str::to_string);
+    if parts.next().is_some() {
+        return Err(format!("Too many arguments for ':{name}'"));
+    }
+    let no_arg = |cmd: Command| match &arg {
+        Some(_) => Err(format!("':{name}' takes no argument")),
+        None => Ok(cmd),
+    };
+    match name {
+        "q" | "q!" | "quit" => no_arg(Command::Quit),
+        "help" | "?" => no_arg(Command::Help),
+        "audit" => no_arg(Command::Audit),
+        "apply" => Ok(Command::Apply(arg)),
+        "export" => Ok(Command::Export(arg)),
+        "login" => Ok(Command::Login(arg)),
+        "ns" | "namespace" => Ok(Command::Namespace(arg)),
+        "ctx" | "context" => Ok(Command::Context(arg)),
+        _ => match ResourceType::from_name(name) {
+            Some(tab) => no_arg(Command::Tab(tab)),
+            None => Err(format!("Unknown command ':{name}'")),
+        },
+    }
+}
+
+/// Completes the command name, or the argument of `ns`/`ctx`.
+pub fn complete(input: &str, namespaces: &[String], contexts: &[String]) -> Option<String> {
+    match input.split_once(' ') {
+        None => {
+            let names: Vec<&str> = COMMANDS
+                .iter()
+                .copied()
+                .filter(|c| c.starts_with(input))
+                .collect();
+            let completed = extend(input, &names)?;
+            if names.len() == 1 && matches!(completed.as_str(), "ns" | "ctx" | "login") {
+                Some(format!("{completed} "))
+            } else {
+                Some(completed)
+            }
+        }
+        Some((name, arg)) => {
+            let pool = match name {
+                "ns" | "namespace" => namespaces,
+                "ctx" | "context" | "login" => contexts,
+                _ => return None,
+            };
+            let arg = arg.trim_start();
+            let matches: Vec<&str> = pool
+                .iter()
+                .map(String::as_str)
+                .filter(|c| c.starts_with(arg))
+                .collect();
+            extend(arg, &matches).map(|a| format!("{name} {a}"))
+        }
+    }
+}
+
+fn extend(prefix: &str, matches: &[&str]) -> Option<String> {
+    let first = matches.first()?;
+    let common = matches.iter().skip(1).fold(first.len(), |len, m| {
+        first
+            .bytes()
+            .zip(m.bytes())
+            .take(len)
+            .take_while(|(a, b)| a == b)
+            .count()
+    });
+    (common > prefix.len() || matches.len() == 1).then(|| first[..common].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands_and_aliases() {
+        assert_eq!(parse_command("q"), Ok(Command::Quit));
+        assert_eq!(
+            parse_command(" ns  staging "),
+            Ok(Command::Namespace(Some("staging".into())))
+        );
+        assert_eq!(parse_command("ctx"), Ok(Command::Context(None)));
+        assert_eq!(parse_command("audit"), Ok(Command::Audit));
+        assert_eq!(
+            parse_command("apply ~/app.yaml"),
+            Ok(Command::Apply(Some("~/app.yaml".into())))
+        );
+        assert_eq!(parse_command("export"), Ok(Command::Export(None)));
+        assert_eq!(
+            parse_command("login prod"),
+            Ok(Command::Login(Some("prod".into())))
+        );
+        assert_eq!(
+            parse_command("deploy"),
+            Ok(Command::Tab(ResourceType::Deployment))
+        );
+        assert_eq!(
+            parse_command("cm"),
+            Ok(Command::Tab(ResourceType::ConfigMap))
+        );
+        assert!(parse_command("pods extra").is_err());
+        assert!(parse_command("ns a b").is_err());
+        assert!(parse_command("bogus").is_err());
+        assert!(parse_command("  ").is_err());
+    }
+
+    #[test]
+    fn completes_names_and_arguments() {
+        let ns: Vec<String> = vec!["staging".into(), "stable".into(), "prod".into()];
+        let ctx: Vec<String> = vec!["prod-eu".into()];
+        assert_eq!(complete("de", &ns, &ctx), Some("deploy".into()));
+        assert_eq!(complete("n", &ns, &ctx), Some("ns ".into()));
+        assert_eq!(complete("ns st", &ns, &ctx), Some("ns sta".into()));
+        assert_eq!(complete("ns sta", &ns, &ctx), None);
+        assert_eq!(complete("ns stag", &ns, &ctx), Some("ns staging".into()));
+        assert_eq!(complete("ctx ", &ns, &ctx), Some("ctx prod-eu".into()));
+        assert_eq!(complete("x", &ns, &ctx), None);
+    }
+}
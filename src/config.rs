@@ -0,0 +1,763 @@
+use crate::models::ResourceType;
+use crate::ui::symbols::Symbols;
+use crate::ui::theme::Theme;
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// User configuration read from `~/.config/kr/config.yaml`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Config {
+    /// Settings keyed by kubeconfig context name.
+    pub contexts: HashMap<String, ContextConfig>,
+    /// Number of recent contexts to connect to in the background at startup.
+    pub prewarm_contexts: usize,
+    /// Deleting more than this many resources at once requires typing the count.
+    pub type_to_confirm_above: Option<usize>,
+    /// Milliseconds between housekeeping ticks and spinner frames; 250 when unset.
+    pub tick_ms: Option<u64>,
+    /// Redraws only after input or cluster events, never on the tick alone.
+    pub low_power: bool,
+    /// Upper bound on redraws per second; 30 when unset.
+    pub max_fps: Option<u32>,
+    pub theme: ThemeConfig,
+    pub symbols: SymbolsConfig,
+    /// Extra table columns keyed by tab.
+    pub columns: HashMap<String, Vec<ColumnConfig>>,
+    /// Field selectors keyed by tab, applied by the API server to the watches.
+    pub field_selectors: HashMap<String, String>,
+    /// External commands offered by the `P` menu.
+    pub plugins: Vec<PluginConfig>,
+}
+
+/// A user-defined column whose cells are read from each object by path.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnConfig {
+    pub header: String,
+    /// JSONPath-like expression such as `.spec.nodeName`.
+    pub path: String,
+    /// Column width; defaults to the header's width, at least 12.
+    pub width: Option<u16>,
+}
+
+impl ColumnConfig {
+    pub fn width(&self) -> u16 {
+        self.width
+            .unwrap_or_else(|| (self.header.chars().count() as u16).max(12))
+    }
+}
+
+/// An external command run against the selected row, like a k9s plugin.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginConfig {
+    /// Menu label.
+    pub name: String,
+    /// Key that runs the plugin from the menu.
+    pub key: Option<char>,
+    /// Tabs the plugin is offered on; every tab when empty.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub output: PluginOutput,
+    /// Ask before running.
+    #[serde(default)]
+    pub confirm: bool,
+    /// Changes the cluster; hidden in read-only contexts.
+    #[serde(default)]
+    pub dangerous: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PluginOutput {
+    /// Runs interactively in the embedded terminal.
+    #[default]
+    Terminal,
+    /// Runs in the background and shows stdout and stderr in the pager.
+    Capture,
+}
+
+impl PluginConfig {
+    pub fn applies_to(&self, tab: ResourceType) -> bool {
+        in_scope(&self.scopes, tab)
+    }
+
+    /// The command line with placeholders replaced by `vars`.
+    pub fn command_line(&self, vars: &[(&str, &str)]) -> (String, Vec<String>) {
+        let expand = |s: &str| crate::utils::expand_placeholders(s, vars);
+        (
+            expand(&self.command),
+            self.args.iter().map(|a| expand(a)).collect(),
+        )
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SymbolsConfig {
+    /// `unicode` (default) or `ascii`.
+    pub preset: Option<String>,
+    /// `plain`, `rounded`, `ascii` or `none`.
+    pub border: Option<String>,
+    /// Single character marking selected rows.
+    pub marker: Option<String>,
+    /// Spinner frames as one string, e.g. `|/-\\`.
+    pub spinner: Option<String>,
+}
+
+impl SymbolsConfig {
+    pub fn build(&self) -> Result<Symbols, String> {
+        Symbols::from_config(
+            self.preset.as_deref(),
+            self.border.as_deref(),
+            self.marker.as_deref(),
+            self.spinner.as_deref(),
+        )
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ThemeConfig {
+    /// `dark` (default), `light` or `mono`.
+    pub preset: Option<String>,
+    /// Per-color overrides, e.g. `running: green` or `error: '#ff0000'`.
+    pub colors: HashMap<String, String>,
+    /// Symbols for states and no background highlights; implied by `mono`.
+    pub accessible: bool,
+}
+
+impl ThemeConfig {
+    /// The configured theme; a non-empty `NO_COLOR` forces `mono`.
+    pub fn build(&self) -> Result<Theme, String> {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        self.build_with(no_color)
+    }
+
+    fn build_with(&self, no_color: bool) -> Result<Theme, String> {
+        let preset = if no_color {
+            Some("mono")
+        } else {
+            self.preset.as_deref()
+        };
+        let mut theme = Theme::from_config(preset, &self.colors)?;
+        theme.accessible |= self.accessible;
+        Ok(theme)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ContextConfig {
+    /// Short name shown instead of the full context name.
+    pub alias: Option<String>,
+    /// Namespace to open instead of the kubeconfig default.
+    pub namespace: Option<String>,
+    /// Tab to open: `pods`, `deployments`, `secrets` or `configmaps`.
+    pub tab: Option<String>,
+    /// Blocks every action that changes the cluster.
+    pub read_only: bool,
+    /// Header color, as a name (`red`) or hex (`#ff5f00`).
+    pub accent: Option<String>,
+    /// Every deletion requires typing the resource name (or count).
+    pub type_to_confirm: bool,
+    /// Deletes, scaling and restarts require typing the context name.
+    pub protected: bool,
+    /// Namespaces protected as above in an otherwise unprotected context.
+    pub protected_namespaces: Vec<String>,
+    /// Web pages for the selected row, opened with `o`.
+    pub links: Vec<LinkConfig>,
+    /// Source of the `M` metrics panel.
+    pub prometheus: Option<PrometheusConfig>,
+    /// Slack-compatible incoming webhooks told about crash loops and failed rollouts.
+    pub webhooks: Vec<WebhookConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Conditions posted; every one when empty.
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WebhookEvent {
+    CrashLoop,
+    RolloutFailed,
+}
+
+impl WebhookConfig {
+    pub fn fires_on(&self, event: WebhookEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+/// A context's Prometheus and the graphs the metrics panel draws from it.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrometheusConfig {
+    /// Base URL, e.g. `https://prometheus.example.com`.
+    pub url: Option<String>,
+    /// `namespace/service:port`, reached through the API server's service proxy.
+    pub service: Option<String>,
+    /// PromQL range queries; request rate, error rate and CPU when empty.
+    #[serde(default)]
+    pub queries: Vec<MetricQuery>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetricQuery {
+    pub title: String,
+    pub query: String,
+}
+
+/// Graphs for a Prometheus without configured queries.
+const DEFAULT_QUERIES: &[(&str, &str)] = &[
+    (
+        "Requests/s",
+        r#"sum(rate(http_requests_total{namespace="$NAMESPACE",pod=~"$PODS"}[5m]))"#,
+    ),
+    (
+        "Errors/s (5xx)",
+        r#"sum(rate(http_requests_total{namespace="$NAMESPACE",pod=~"$PODS",code=~"5.."}[5m]))"#,
+    ),
+    (
+        "CPU (cores)",
+        r#"sum(rate(container_cpu_usage_seconds_total{namespace="$NAMESPACE",pod=~"$PODS",container!=""}[5m]))"#,
+    ),
+];
+
+impl PrometheusConfig {
+    pub fn queries(&self) -> Vec<MetricQuery> {
+        if !self.queries.is_empty() {
+            return self.queries.clone();
+        }
+        DEFAULT_QUERIES
+            .iter()
+            .map(|(title, query)| MetricQuery {
+                title: title.to_string(),
+                query: query.to_string(),
+            })
+            .collect()
+    }
+
+    /// `(namespace, service:port)` of `service`.
+    pub fn service(&self) -> Option<(&str, &str)> {
+        let (ns, svc) = self.service.as_deref()?.split_once('/')?;
+        svc.contains(':').then_some((ns, svc))
+    }
+
+    fn check(&self) -> Result<(), String> {
+        match (&self.url, &self.service) {
+            (Some(url), None) if url.starts_with("https://") || url.starts_with("http://") => {
+                Ok(())
+            }
+            (Some(_), None) => Err("prometheus.url must be an http(s) URL".to_string()),
+            (None, Some(_)) if self.service().is_some() => Ok(()),
+            (None, Some(_)) => Err("prometheus.service must be namespace/service:port".to_string()),
+            _ => Err("prometheus needs exactly one of url and service".to_string()),
+        }
+    }
+}
+
+/// A URL template such as a Grafana dashboard for a resource.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkConfig {
+    pub name: String,
+    pub url: String,
+    /// Tabs the link is offered on; every tab when empty.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+impl LinkConfig {
+    pub fn applies_to(&self, tab: ResourceType) -> bool {
+        in_scope(&self.scopes, tab)
+    }
+
+    pub fn url(&self, vars: &[(&str, &str)]) -> String {
+        let encoded: Vec<(&str, String)> = vars
+            .iter()
+            .map(|(k, v)| (*k, crate::utils::percent_encode(v)))
+            .collect();
+        let vars: Vec<(&str, &str)> = encoded.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        crate::utils::expand_placeholders(&self.url, &vars)
+    }
+}
+
+/// Whether `scopes` (tab names or aliases) include `tab`; empty means all.
+fn in_scope(scopes: &[String], tab: ResourceType) -> bool {
+    scopes.is_empty()
+        || scopes
+            .iter()
+            .any(|s| ResourceType::from_name(s) == Some(tab))
+}
+
+fn check_scopes(scopes: &[String], owner: &str) -> Result<(), String> {
+    match scopes.iter().find(|s| ResourceType::from_name(s).is_none()) {
+        Some(scope) => Err(format!("unknown scope '{scope}' in {owner}")),
+        None => Ok(()),
+    }
+}
+
+impl ContextConfig {
+    pub fn tab(&self) -> Option<ResourceType> {
+        ResourceType::from_name(self.tab.as_deref()?)
+    }
+
+    pub fn accent(&self) -> Option<Color> {
+        self.accent.as_deref()?.parse().ok()
+    }
+
+    pub fn protects(&self, namespace: &str) -> bool {
+        self.protected || self.protected_namespaces.iter().any(|n| n == namespace)
+    }
+}
+
+fn config_path() -> PathBuf {
+    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("kr");
+    path.push("config.yaml");
+    path
+}
+
+impl Config {
+    /// A missing file yields the defaults; a malformed one is an error.
+    pub fn load() -> Result<Self, String> {
+        let path = config_path();
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text)
+                .map_err(|e| e.to_string())
+                .and_then(|config| config.check_columns().map(|()| config))
+                .and_then(|config| config.check_field_selectors().map(|()| config))
+                .and_then(|config| config.check_plugins().map(|()| config))
+                .and_then(|config| config.check_links().map(|()| config))
+                .and_then(|config| config.check_prometheus().map(|()| config))
+                .and_then(|config| config.check_webhooks().map(|()| config))
+                .map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, serde_yaml::Error> {
+        if text.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        serde_yaml::from_str(text)
+    }
+
+    /// The custom columns configured for `tab`.
+    pub fn columns(&self, tab: ResourceType) -> &[ColumnConfig] {
+        self.columns
+            .get(tab.plural())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Rejects unknown tabs and unparseable column paths.
+    pub fn check_columns(&self) -> Result<(), String> {
+        for (tab, columns) in &self.columns {
+            if !ResourceType::ALL.iter().any(|t| t.plural() == tab) {
+                return Err(format!("unknown tab '{tab}' in columns"));
+            }
+            for column in columns {
+                crate::utils::parse_json_path(&column.path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The field selector configured for `tab`'s watches.
+    pub fn field_selector(&self, tab: ResourceType) -> Option<&str> {
+        self.field_selectors
+            .get(tab.plural())
+            .map(String::as_str)
+            .filter(|s| !s.trim().is_empty())
+    }
+
+    /// Rejects unknown tabs and malformed field selectors.
+    pub fn check_field_selectors(&self) -> Result<(), String> {
+        for (tab, selector) in &self.field_selectors {
+            if !ResourceType::ALL.iter().any(|t| t.plural() == tab) {
+                return Err(format!("unknown tab '{tab}' in fieldSelectors"));
+            }
+            for term in selector.split(',').filter(|t| !t.trim().is_empty()) {
+                let field = term.split(['=', '!']).next().unwrap_or_default();
+                if !term.contains('=') || field.trim().is_empty() {
+                    return Err(format!("invalid field selector '{term}' for {tab}"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects plugins without a command and unknown scopes.
+    pub fn check_plugins(&self) -> Result<(), String> {
+        for plugin in &self.plugins {
+            if plugin.command.trim().is_empty() {
+                return Err(format!("plugin '{}' has no command", plugin.name));
+            }
+            check_scopes(&plugin.scopes, &format!("plugin '{}'", plugin.name))?;
+        }
+        Ok(())
+    }
+
+    /// Rejects links that are not http(s) URLs and unknown scopes.
+    pub fn check_links(&self) -> Result<(), String> {
+        for (context, config) in &self.contexts {
+            for link in &config.links {
+                if !link.url.starts_with("https://") && !link.url.starts_with("http://") {
+                    return Err(format!(
+                        "link '{}' of context '{context}' is not an http(s) URL",
+                        link.name
+                    ));
+                }
+                check_scopes(&link.scopes, &format!("link '{}'", link.name))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects webhooks that aren't http(s) URLs.
+    pub fn check_webhooks(&self) -> Result<(), String> {
+        for (context, config) in &self.contexts {
+            for webhook in &config.webhooks {
+                if !webhook.url.starts_with("https://") && !webhook.url.starts_with("http://") {
+                    return Err(format!(
+                        "webhook '{}' of context '{context}' is not an http(s) URL",
+                        webhook.url
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects Prometheus settings without exactly one valid source.
+    pub fn check_prometheus(&self) -> Result<(), String> {
+        for (context, config) in &self.contexts {
+            if let Some(prometheus) = &config.prometheus {
+                prometheus
+                    .check()
+                    .map_err(|e| format!("context '{context}': {e}"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The links configured for `context` that apply to `tab`.
+    pub fn links(&self, context: &str, tab: ResourceType) -> Vec<&LinkConfig> {
+        self.context(context)
+            .map(|c| c.links.iter().filter(|l| l.applies_to(tab)).collect())
+            .unwrap_or_default()
+    }
+
+    /// The plugins offered on `tab` with their index in `plugins`.
+    pub fn plugins(&self, tab: ResourceType, read_only: bool) -> Vec<(usize, &PluginConfig)> {
+        self.plugins
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.applies_to(tab) && !(read_only && p.dangerous))
+            .collect()
+    }
+
+    /// The shortest time between two redraws, for 1..120 frames a second.
+    pub fn frame_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(1000 / u64::from(self.max_fps.unwrap_or(30).clamp(1, 120)))
+    }
+
+    /// The tick interval, kept within 50ms..5s.
+    pub fn tick(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.tick_ms.unwrap_or(250).clamp(50, 5000))
+    }
+
+    pub fn context(&self, context: &str) -> Option<&ContextConfig> {
+        self.contexts.get(context)
+    }
+
+    pub fn context_alias(&self, context: &str) -> Option<&str> {
+        self.contexts
+            .get(context)
+            .and_then(|c| c.alias.as_deref())
+            .filter(|a| !a.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_context_aliases() {
+        let config = Config::parse(
+            "contexts:\n  arn:aws:eks:eu-west-1:123:cluster/prod:\n    alias: prod\n  dev: {}\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.context_alias("arn:aws:eks:eu-west-1:123:cluster/prod"),
+            Some("prod")
+        );
+        assert_eq!(config.context_alias("dev"), None);
+        assert_eq!(config.context_alias("unknown"), None);
+    }
+
+    #[test]
+    fn parses_context_preferences() {
+        let config = Config::parse(
+            "contexts:\n  prod:\n    namespace: payments\n    tab: Deployments\n    readOnly: true\n    accent: red\n  dev:\n    tab: nope\n    accent: '#00ff00'\n",
+        )
+        .unwrap();
+        let prod = config.context("prod").unwrap();
+        assert_eq!(prod.namespace.as_deref(), Some("payments"));
+        assert_eq!(prod.tab(), Some(ResourceType::Deployment));
+        assert!(prod.read_only);
+        assert_eq!(prod.accent(), Some(Color::Red));
+        let dev = config.context("dev").unwrap();
+        assert_eq!(dev.tab(), None);
+        assert!(!dev.read_only);
+        assert_eq!(dev.accent(), Some(Color::Rgb(0, 255, 0)));
+    }
+
+    #[test]
+    fn parses_type_to_confirm() {
+        let config =
+            Config::parse("typeToConfirmAbove: 3\ncontexts:\n  prod:\n    typeToConfirm: true\n")
+                .unwrap();
+        assert_eq!(config.type_to_confirm_above, Some(3));
+        assert!(config.context("prod").unwrap().type_to_confirm);
+        assert_eq!(Config::default().type_to_confirm_above, None);
+    }
+
+    #[test]
+    fn parses_protected_contexts_and_namespaces() {
+        let config = Config::parse(
+            "contexts:\n  prod:\n    protected: true\n  dev:\n    protectedNamespaces: [kube-system]\n",
+        )
+        .unwrap();
+        assert!(config.context("prod").unwrap().protects("anything"));
+        let dev = config.context("dev").unwrap();
+        assert!(dev.protects("kube-system"));
+        assert!(!dev.protects("default"));
+    }
+
+    #[test]
+    fn parses_prewarm_count() {
+        assert_eq!(
+            Config::parse("prewarmContexts: 3")
+                .unwrap()
+                .prewarm_contexts,
+            3
+        );
+        assert_eq!(Config::parse("contexts: {}").unwrap().prewarm_contexts, 0);
+    }
+
+    #[test]
+    fn parses_tick_settings() {
+        assert_eq!(
+            Config::default().tick(),
+            std::time::Duration::from_millis(250)
+        );
+        assert!(!Config::default().low_power);
+        let config = Config::parse("tickMs: 1000\nlowPower: true\n").unwrap();
+        assert_eq!(config.tick(), std::time::Duration::from_millis(1000));
+        assert!(config.low_power);
+        let config = Config::parse("tickMs: 1\n").unwrap();
+        assert_eq!(config.tick(), std::time::Duration::from_millis(50));
+        assert_eq!(
+            Config::default().frame_interval(),
+            std::time::Duration::from_millis(33)
+        );
+        let config = Config::parse("maxFps: 0\n").unwrap();
+        assert_eq!(config.frame_interval(), std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn parses_custom_columns() {
+        let config = Config::parse(
+            "columns:\n  pods:\n    - header: Node\n      path: .spec.nodeName\n      width: 20\n",
+        )
+        .unwrap();
+        assert!(config.check_columns().is_ok());
+        let columns = config.columns(ResourceType::Pod);
+        assert_eq!(columns.len(), 1);
+        assert_eq!(columns[0].width(), 20);
+        assert!(config.columns(ResourceType::Secret).is_empty());
+
+        let config = Config::parse("columns:\n  nodes:\n    - {header: X, path: .a}\n").unwrap();
+        assert!(config.check_columns().is_err());
+        let config = Config::parse("columns:\n  pods:\n    - {header: X, path: '.a['}\n").unwrap();
+        assert!(config.check_columns().is_err());
+    }
+
+    #[test]
+    fn parses_field_selectors() {
+        let config = Config::parse(
+            "fieldSelectors:\n  pods: status.phase!=Succeeded,spec.nodeName=node-1\n  secrets: ''\n",
+        )
+        .unwrap();
+        assert!(config.check_field_selectors().is_ok());
+        assert_eq!(
+            config.field_selector(ResourceType::Pod),
+            Some("status.phase!=Succeeded,spec.nodeName=node-1")
+        );
+        assert_eq!(config.field_selector(ResourceType::Secret), None);
+        assert_eq!(config.field_selector(ResourceType::Deployment), None);
+
+        let config = Config::parse("fieldSelectors:\n  nodes: a=b\n").unwrap();
+        assert!(config.check_field_selectors().is_err());
+        let config = Config::parse("fieldSelectors:\n  pods: status.phase\n").unwrap();
+        assert!(config.check_field_selectors().is_err());
+        let config = Config::parse("fieldSelectors:\n  pods: '!=x'\n").unwrap();
+        assert!(config.check_field_selectors().is_err());
+    }
+
+    #[test]
+    fn parses_plugins() {
+        let config = Config::parse(
+            "plugins:\n  - name: Stern\n    key: t\n    scopes: [pods, deploy]\n    command: stern\n    args: [-n, $NAMESPACE, --context, $CONTEXT, $NAME]\n  - name: Images\n    command: kubectl\n    output: capture\n    dangerous: true\n",
+        )
+        .unwrap();
+        assert!(config.check_plugins().is_ok());
+        let stern = &config.plugins[0];
+        assert_eq!(stern.output, PluginOutput::Terminal);
+        assert_eq!(
+            stern.command_line(&[("NAME", "web-1"), ("NAMESPACE", "prod"), ("CONTEXT", "eu")]),
+            (
+                "stern".to_string(),
+                vec!["-n", "prod", "--context", "eu", "web-1"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect()
+            )
+        );
+        assert_eq!(config.plugins(ResourceType::Deployment, false).len(), 2);
+        assert_eq!(config.plugins(ResourceType::Secret, false).len(), 1);
+        assert!(config.plugins(ResourceType::Secret, true).is_empty());
+
+        let config =
+            Config::parse("plugins:\n  - {name: X, command: x, scopes: [nodes]}\n").unwrap();
+        assert!(config.check_plugins().is_err());
+        let config = Config::parse("plugins:\n  - {name: X, command: ''}\n").unwrap();
+        assert!(config.check_plugins().is_err());
+    }
+
+    #[test]
+    fn parses_links() {
+        let config = Config::parse(
+            "contexts:\n  prod:\n    links:\n      - name: Grafana\n        url: https://grafana/d/pods?var-ns=$NAMESPACE&var-pod=$NAME\n        scopes: [pods]\n      - name: Argo\n        url: https://argo/applications?search=$NAME&cluster=$CONTEXT\n",
+        )
+        .unwrap();
+        assert!(config.check_links().is_ok());
+        let links = config.links("prod", ResourceType::Pod);
+        assert_eq!(links.len(), 2);
+        assert_eq!(
+            links[1].url(&[("NAME", "web"), ("CONTEXT", "arn:aws:eks/prod")]),
+            "https://argo/applications?search=web&cluster=arn%3Aaws%3Aeks%2Fprod"
+        );
+        assert_eq!(config.links("prod", ResourceType::Secret).len(), 1);
+        assert!(config.links("dev", ResourceType::Pod).is_empty());
+
+        let config = Config::parse(
+            "contexts:\n  prod:\n    links:\n      - {name: X, url: 'file:///etc'}\n",
+        )
+        .unwrap();
+        assert!(config.check_links().is_err());
+    }
+
+    #[test]
+    fn parses_prometheus_sources() {
+        let config = Config::parse(
+            "contexts:\n  prod:\n    prometheus:\n      service: monitoring/prometheus-k8s:web\n  dev:\n    prometheus:\n      url: http://localhost:9090\n      queries:\n        - {title: Memory, query: 'sum(x)'}\n",
+        )
+        .unwrap();
+        assert!(config.check_prometheus().is_ok());
+        let prod = config.context("prod").unwrap().prometheus.as_ref().unwrap();
+        assert_eq!(prod.service(), Some(("monitoring", "prometheus-k8s:web")));
+        assert_eq!(prod.queries().len(), 3);
+        let dev = config.context("dev").unwrap().prometheus.as_ref().unwrap();
+        assert_eq!(dev.queries()[0].title, "Memory");
+
+        for bad in [
+            "{service: 'prometheus:9090'}",
+            "{url: 'prometheus:9090'}",
+            "{}",
+            "{url: 'http://a', service: 'a/b:1'}",
+        ] {
+            let config =
+                Config::parse(&format!("contexts:\n  prod:\n    prometheus: {bad}\n")).unwrap();
+            assert!(config.check_prometheus().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn parses_webhooks() {
+        let config = Config::parse(
+            "contexts:\n  prod:\n    webhooks:\n      - url: https://hooks.slack.com/services/T0/B0/x\n      - url: http://alerts.local/kr\n        events: [rolloutFailed]\n",
+        )
+        .unwrap();
+        assert!(config.check_webhooks().is_ok());
+        let webhooks = &config.context("prod").unwrap().webhooks;
+        assert!(webhooks[0].fires_on(WebhookEvent::CrashLoop));
+        assert!(!webhooks[1].fires_on(WebhookEvent::CrashLoop));
+        assert!(webhooks[1].fires_on(WebhookEvent::RolloutFailed));
+
+        let config =
+            Config::parse("contexts:\n  prod:\n    webhooks:\n      - url: hooks.slack.com\n")
+                .unwrap();
+        assert!(config.check_webhooks().is_err());
+        assert!(
+            Config::parse(
+                "contexts:\n  prod:\n    webhooks:\n      - {url: 'http://a', events: [oom]}\n"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn parses_symbols() {
+        assert_eq!(Config::default().symbols.build(), Ok(Symbols::unicode()));
+        let config =
+            Config::parse("symbols:\n  preset: ascii\n  border: none\n  spinner: '.o'\n").unwrap();
+        let symbols = config.symbols.build().unwrap();
+        assert_eq!(symbols.marker, "*");
+        assert_eq!(symbols.spinner, ['.', 'o']);
+        assert_eq!(symbols.border, crate::ui::symbols::BorderStyle::None);
+    }
+
+    #[test]
+    fn accessible_theme_and_no_color() {
+        let config = Config::parse("theme:\n  accessible: true\n").unwrap();
+        let theme = config.theme.build_with(false).unwrap();
+        assert!(theme.accessible);
+        assert_eq!(theme.running, Theme::DARK.running);
+        assert_eq!(Config::default().theme.build_with(true), Ok(Theme::MONO));
+    }
+
+    #[test]
+    fn parses_theme() {
+        let config =
+            Config::parse("theme:\n  preset: light\n  colors:\n    pending: '#ffaa00'\n").unwrap();
+        let theme = config.theme.build().unwrap();
+        assert_eq!(theme.pending, Color::Rgb(0xff, 0xaa, 0x00));
+        assert_eq!(theme.text, Theme::LIGHT.text);
+        assert_eq!(Config::default().theme.build_with(false), Ok(Theme::DARK));
+    }
+
+    #[test]
+    fn empty_config_is_default() {
+        assert!(Config::parse("").unwrap().contexts.is_empty());
+        assert!(Config::parse("contexts: [").is_err());
+    }
+}
@@ -1,17 +1,21 @@
 use anyhow::Result;
-use crossterm::event::{Event, EventStream};
+use crossterm::event::{Event, EventStream, KeyCode, KeyModifiers, MouseEventKind};
 use futures::{FutureExt, StreamExt};
 use ratatui::{Terminal, backend::Backend};
 use std::time::Duration;
 use tokio::time;
 
 use crate::app::App;
-use crate::input::handle_input;
+use crate::input::{handle_input, handle_mouse};
 use crate::k8s::watcher::reflect_resources;
-use crate::models::{AppMode, KubeResourceEvent, ResourceType};
+use crate::models::{
+    AppMode, EditorRequest, KubeResource, KubeResourceEvent, PendingAction, ResourceType,
+    WatchHealth,
+};
 use crate::ui::draw;
 use futures::stream::BoxStream;
 use kube::runtime::watcher;
+use std::sync::Arc;
 
 fn is_forbidden(err: &watcher::Error) -> bool {
     match err {
@@ -25,9 +29,24 @@ fn is_forbidden(err: &watcher::Error) -> bool {
     }
 }
 
-fn map_watcher_event<K>(event: Result<watcher::Event<K>, watcher::Error>) -> KubeResourceEvent {
+fn is_unauthorized(err: &watcher::Error) -> bool {
+    match err {
+        watcher::Error::InitialListFailed(e)
+        | watcher::Error::WatchStartFailed(e)
+        | watcher::Error::WatchFailed(e) => crate::k8s::client::is_unauthorized(e),
+        watcher::Error::WatchError(resp) => resp.code == 401,
+        _ => false,
+    }
+}
+
+fn map_watcher_event<K>(
+    event: Result<watcher::Event<K>, watcher::Error>,
+    wrap: fn(Arc<K>) -> KubeResource,
+) -> KubeResourceEvent {
     match event {
         Ok(watcher::Event::InitDone) => KubeResourceEvent::InitialListDone,
+        Ok(watcher::Event::Apply(obj)) => KubeResourceEvent::Applied(wrap(Arc::new(obj))),
+        Ok(watcher::Event::Delete(obj)) => KubeResourceEvent::Deleted(wrap(Arc::new(obj))),
         Ok(_) => KubeResourceEvent::Refresh,
         Err(ref e) if is_forbidden(e) => {
             let msg = match e {
@@ -39,6 +58,9 @@ fn map_watcher_event<K>(event: Result<watcher::Event<K>, watcher::Error>) -> Kub
             };
             KubeResourceEvent::WatcherForbidden(msg)
         }
+        Err(e) if is_unauthorized(&e) => {
+            KubeResourceEvent::Unauthorized(format!("Watcher error: {e}"))
+        }
         Err(e) => KubeResourceEvent::Error(format!("Watcher error: {e}")),
     }
 }
@@ -46,22 +68,28 @@ fn map_watcher_event<K>(event: Result<watcher::Event<K>, watcher::Error>) -> Kub
 fn create_watcher(app: &mut App) -> BoxStream<'static, KubeResourceEvent> {
     let client = app.client.clone();
     let ns = app.current_namespace.clone();
+    let fields = app.config.field_selector(app.active_tab);
 
     match app.active_tab {
         ResourceType::Pod => {
-            let (store, stream) = reflect_resources(client, &ns);
+            let (store, stream) = reflect_resources(client, &ns, fields);
             app.pod_store = Some(store);
-            Box::pin(stream.map(map_watcher_event))
+            Box::pin(stream.map(|e| map_watcher_event(e, KubeResource::Pod)))
         }
         ResourceType::Deployment => {
-            let (store, stream) = reflect_resources(client, &ns);
+            let (store, stream) = reflect_resources(client, &ns, fields);
             app.deployment_store = Some(store);
-            Box::pin(stream.map(map_watcher_event))
+            Box::pin(stream.map(|e| map_watcher_event(e, KubeResource::Deployment)))
         }
         ResourceType::Secret => {
-            let (store, stream) = reflect_resources(client, &ns);
+            let (store, stream) = reflect_resources(client, &ns, fields);
             app.secret_store = Some(store);
-            Box::pin(stream.map(map_watcher_event))
+            Box::pin(stream.map(|e| map_watcher_event(e, KubeResource::Secret)))
+        }
+        ResourceType::ConfigMap => {
+            let (store, stream) = reflect_resources(client, &ns, fields);
+            app.configmap_store = Some(store);
+            Box::pin(stream.map(|e| map_watcher_event(e, KubeResource::ConfigMap)))
         }
     }
 }
@@ -71,44 +99,104 @@ fn handle_watcher_event(
     event: KubeResourceEvent,
     watcher: &mut BoxStream<'static, KubeResourceEvent>,
 ) -> bool {
+    app.last_watch_event = Some(std::time::Instant::now());
     match event {
         KubeResourceEvent::WatcherForbidden(msg) => {
-            let resource_kind = match app.active_tab {
-                ResourceType::Pod => "pods",
-                ResourceType::Deployment => "deployments",
-                ResourceType::Secret => "secrets",
-            };
+            let resource_kind = app.active_tab.plural();
             let short_msg = if msg.is_empty() {
                 format!("Access denied: cannot list {resource_kind}")
             } else {
                 format!("Access denied: {resource_kind} — {msg}")
             };
             app.set_error(short_msg);
+            app.watch_health = WatchHealth::Forbidden;
             app.is_loading = false;
             app.loading_since = None;
             *watcher = Box::pin(futures::stream::pending());
             app.dirty = true;
             false
         }
+        KubeResourceEvent::Unauthorized(msg) => {
+            app.request_reauth();
+            handle_watcher_event(app, KubeResourceEvent::Error(msg), watcher)
+        }
         KubeResourceEvent::Error(msg) => {
-            app.set_error(msg);
+            // Retries are the watcher's job (see `reflect_resources`); only
+            // the first failure of an outage goes to the notification history.
+            app.watch_health = match std::mem::replace(&mut app.watch_health, WatchHealth::Live) {
+                WatchHealth::Reconnecting {
+                    attempts, since, ..
+                } => WatchHealth::Reconnecting {
+                    attempts: attempts + 1,
+                    since,
+                    last_error: msg,
+                },
+                _ => {
+                    app.record_notification(true, &msg);
+                    // Tells a dead API server from a dropped watch on the
+                    // next tick rather than at the next scheduled probe.
+                    app.cluster_probe_at = None;
+                    WatchHealth::Reconnecting {
+                        attempts: 1,
+                        since: std::time::Instant::now(),
+                        last_error: msg,
+                    }
+                }
+            };
             app.dirty = true;
             false
         }
         KubeResourceEvent::InitialListDone => {
+            app.watch_health = WatchHealth::Live;
             app.refresh_items();
             app.is_loading = false;
             app.loading_since = None;
             app.dirty = true;
             false
         }
-        _ => !app.is_loading,
+        event => {
+            if let WatchHealth::Reconnecting { since, .. } = app.watch_health {
+                let secs = since.elapsed().as_secs() as i64;
+                app.record_notification(
+                    false,
+                    &format!(
+                        "Watch reconnected after {}",
+                        crate::utils::format_secs(secs)
+                    ),
+                );
+                app.watch_health = WatchHealth::Live;
+            }
+            // Until the initial list is done the store is complete only at
+            // `InitialListDone`, which rebuilds the whole list.
+            if app.is_loading {
+                return false;
+            }
+            match event {
+                KubeResourceEvent::Applied(item) => app.apply_item_change(item, false),
+                KubeResourceEvent::Deleted(item) => app.apply_item_change(item, true),
+                _ => false,
+            }
+        }
+    }
+}
+
+/// Whether `event` changes what is on screen.
+fn redraws(app: &App, event: &KubeResourceEvent) -> bool {
+    match event {
+        KubeResourceEvent::Log(_) | KubeResourceEvent::LogHistory(..) => {
+            matches!(app.mode, AppMode::LogView | AppMode::LogSearchInput)
+        }
+        KubeResourceEvent::ShellOutput(_) => app.mode == AppMode::ShellView,
+        KubeResourceEvent::ClearOsc52Clipboard => false,
+        _ => true,
     }
 }
 
 fn handle_channel_event(app: &mut App, event: KubeResourceEvent) {
     match event {
         KubeResourceEvent::Refresh
+        | KubeResourceEvent::Applied(_)
+        | KubeResourceEvent::Deleted(_)
         | KubeResourceEvent::InitialListDone
         | KubeResourceEvent::WatcherForbidden(_) => {}
         KubeResourceEvent::Log(line) => {
@@ -117,9 +205,16 @@ fn handle_channel_event(app: &mut App, event: KubeResourceEvent) {
         KubeResourceEvent::LogHistory(generation, lines) => {
             app.merge_log_history(generation, lines);
         }
+        KubeResourceEvent::LogEnded(pod, end) => {
+            app.apply_log_end(&pod, end);
+        }
         KubeResourceEvent::Error(e) => {
             app.set_error(e);
         }
+        KubeResourceEvent::Unauthorized(e) => {
+            app.request_reauth();
+            app.set_error(e);
+        }
         KubeResourceEvent::Success(msg) => {
             app.set_success(msg);
         }
@@ -128,38 +223,275 @@ fn handle_channel_event(app: &mut App, event: KubeResourceEvent) {
                 session.parser.process(&data);
             }
         }
+        KubeResourceEvent::ClearOsc52Clipboard => {
+            let _ = crate::app::write_osc52("");
+        }
         KubeResourceEvent::ShellExited => {
             app.shell_session = None;
             if app.mode == AppMode::ShellView {
-                app.mode = AppMode::List;
+                app.pop_mode();
                 app.set_success("Shell session ended".to_string());
             }
         }
-        KubeResourceEvent::DescribeReady(lines) => {
-            app.describe_content = lines;
-            app.describe_scroll = 0;
-            app.mode = AppMode::DescribeView;
+        KubeResourceEvent::EditReady(edit, true) => app.show_edit_diff(edit),
+        KubeResourceEvent::EditReady(edit, false) => {
+            app.pending_editor = Some(EditorRequest::Resource(edit));
+        }
+        KubeResourceEvent::PluginOutput(title, text) => app.open_pager(title, text),
+        KubeResourceEvent::ManifestPlanned(plan) => app.show_manifest_plan(plan),
+        KubeResourceEvent::EndpointsReady(name, endpoints) => app.endpoints_found(name, endpoints),
+        KubeResourceEvent::MetricsReady(name, series) => {
+            if app.mode == AppMode::MetricsView && app.metrics_target == name {
+                app.metrics = Some(series);
+            }
+        }
+        KubeResourceEvent::DescribeReady(lines, refresh) => {
+            app.show_describe(lines, refresh);
+        }
+        KubeResourceEvent::SecretDiffReady(target, rows) => {
+            app.secret_diff_target = target;
+            app.secret_diff = rows;
+            app.secret_scroll = 0;
+            app.secret_revealed = false;
+            app.push_mode(AppMode::SecretDiff);
+        }
+        KubeResourceEvent::SplitReady(client, context, namespace) => {
+            app.open_split(client, context, namespace);
+        }
+        KubeResourceEvent::SplitRefresh => {
+            if let Some(pane) = &mut app.split {
+                pane.stale = true;
+                pane.error = None;
+            }
+        }
+        KubeResourceEvent::SplitError(msg) => {
+            if let Some(pane) = &mut app.split {
+                pane.error = Some(msg);
+            }
+        }
+        KubeResourceEvent::WebhookFired(event, target, what) => {
+            app.post_webhooks(event, &target, &what);
+        }
+        KubeResourceEvent::SecretOwnerStatus(name, status) => {
+            if name == app.decoded_secret_name {
+                app.secret_owner_status = Some(status);
+            }
+        }
+        KubeResourceEvent::SecretUsageReady(name, consumers) => {
+            app.secret_usage_name = name;
+            app.secret_usage = consumers;
+            app.secret_usage_scroll = 0;
+            app.last_success = None;
+            app.push_mode(AppMode::SecretUsage);
+        }
+        KubeResourceEvent::NamespaceSummaryReady(namespace, summary) => {
+            if namespace == app.current_namespace {
+                app.namespace_summary = Some(summary);
+                app.last_success = None;
+                app.push_mode(AppMode::NamespaceSummary);
+            }
+        }
+        KubeResourceEvent::AccessReviewReady(namespace, checks) => {
+            if namespace == app.current_namespace {
+                app.access_checks = checks;
+                app.last_success = None;
+                app.push_mode(AppMode::AccessReview);
+            }
         }
         KubeResourceEvent::NamespacesLoaded(namespaces) => {
             let ctx = app.current_context.clone();
             app.available_namespaces = app.app_state.merge_namespaces(&ctx, &namespaces);
             app.app_state.save();
         }
+        KubeResourceEvent::ContextsChanged(contexts) => app.reload_contexts(contexts),
+        KubeResourceEvent::TeleportStatus(sessions) => app.teleport_status = sessions,
+        KubeResourceEvent::ClientWarmed(context, client) => {
+            if context != app.current_context {
+                app.warm_clients.insert(context, client);
+            }
+        }
+        KubeResourceEvent::ClusterProbe(context, result) => {
+            app.apply_cluster_probe(&context, result);
+        }
+        KubeResourceEvent::NamespaceCreated(name) => {
+            let ctx = app.current_context.clone();
+            app.app_state.add_namespace(&ctx, &name);
+            app.app_state.save();
+            app.available_namespaces = app.app_state.get_namespaces(&ctx);
+            app.set_success(format!("Namespace '{name}' created"));
+            if app.mode == AppMode::NamespaceSelect {
+                app.update_namespace_filter();
+                let idx = app.filtered_namespaces.iter().position(|ns| *ns == name);
+                app.popup_state.select(idx);
+            }
+        }
+        KubeResourceEvent::NamespaceDeleted(name) => {
+            let ctx = app.current_context.clone();
+            app.app_state.remove_namespace(&ctx, &name);
+            app.app_state.save();
+            app.available_namespaces.retain(|ns| *ns != name);
+            app.set_success(format!("Namespace '{name}' is being deleted"));
+            if app.mode == AppMode::NamespaceSelect {
+                app.update_namespace_filter();
+            }
+        }
+        KubeResourceEvent::BulkResult(action, results) => {
+            let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+            let summary = format!(
+                "{action}: {} succeeded, {failed} failed",
+                results.len() - failed
+            );
+            if failed == 0 {
+                app.set_success(summary.clone());
+            } else {
+                app.set_error(summary.clone());
+            }
+            app.open_pager(summary, crate::utils::format_bulk_report(&results));
+        }
     }
     app.dirty = true;
 }
 
+fn suspend_terminal<B: Backend<Error: Send + Sync + 'static> + std::io::Write>(
+    terminal: &mut Terminal<B>,
+) -> Result<()> {
+    crossterm::terminal::disable_raw_mode()?;
+    crossterm::execute!(
+        terminal.backend_mut(),
+        crossterm::event::DisableMouseCapture,
+        crossterm::terminal::LeaveAlternateScreen,
+        crossterm::cursor::Show
+    )?;
+    Ok(())
+}
+
+fn resume_terminal<B: Backend<Error: Send + Sync + 'static> + std::io::Write>(
+    terminal: &mut Terminal<B>,
+) -> Result<()> {
+    crossterm::execute!(
+        terminal.backend_mut(),
+        crossterm::terminal::EnterAlternateScreen,
+        crossterm::event::EnableMouseCapture,
+        crossterm::cursor::Hide
+    )?;
+    crossterm::terminal::enable_raw_mode()?;
+    terminal.clear()?;
+    Ok(())
+}
+
+/// Rebuilds the client after the API server rejected the credentials of `context`.
+async fn reauthenticate<B: Backend<Error: Send + Sync + 'static> + std::io::Write>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    context: String,
+) -> Result<()> {
+    // Exec plugins (OIDC, cloud CLIs) may want the terminal for MFA.
+    let interactive = crate::k8s::config::needs_interactive_auth(&context);
+    if interactive {
+        suspend_terminal(terminal)?;
+        eprintln!("Credentials for context '{context}' were rejected, re-authenticating...");
+    }
+    let result = crate::k8s::config::create_client_with_context(&context).await;
+    if interactive {
+        resume_terminal(terminal)?;
+    }
+    match result {
+        Ok(client) => app.reauthenticated(client),
+        Err(e) => app.set_error(format!("Re-authentication failed: {e}")),
+    }
+    app.dirty = true;
+    Ok(())
+}
+
+/// Runs `tsh kube login` for `context` on the real terminal, then connects.
+async fn tsh_login<B: Backend<Error: Send + Sync + 'static> + std::io::Write>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    context: String,
+) -> Result<()> {
+    let Some(login) = crate::k8s::config::teleport_login(&context) else {
+        return Ok(());
+    };
+    suspend_terminal(terminal)?;
+    eprintln!("Logging in to context '{context}' with tsh...");
+    // tsh writes the renewed credentials into the kubeconfig kr reads.
+    let status = std::process::Command::new("tsh")
+        .args(login.login_args())
+        .envs(crate::k8s::config::kubectl_env())
+        .status();
+    let result = match status {
+        Ok(s) if s.success() => crate::k8s::config::create_client_with_context(&context)
+            .await
+            .map_err(|e| e.to_string()),
+        Ok(s) => Err(format!("tsh kube login exited with {s}")),
+        Err(e) => Err(format!("tsh kube login failed: {e}")),
+    };
+    resume_terminal(terminal)?;
+    match result {
+        Ok(client) if context == app.current_context => app.reauthenticated(client),
+        Ok(client) => {
+            app.set_success(format!("Logged in to '{}'", app.context_label(&context)));
+            app.warm_clients.insert(context, client);
+        }
+        Err(e) => app.set_error(e),
+    }
+    app.refresh_teleport_status();
+    app.dirty = true;
+    Ok(())
+}
+
+/// Builds the client for the current context behind a "connecting" screen.
+pub async fn connect<B: Backend<Error: Send + Sync + 'static> + std::io::Write>(
+    terminal: &mut Terminal<B>,
+    tick: Duration,
+) -> Result<Option<kube::Client>> {
+    let context = crate::k8s::config::get_current_context().unwrap_or_default();
+    if crate::k8s::config::needs_interactive_auth(&context) {
+        suspend_terminal(terminal)?;
+        eprintln!("Authenticating with context '{context}'...");
+        let result = crate::k8s::client::default_client().await;
+        resume_terminal(terminal)?;
+        return result.map(Some);
+    }
+
+    let mut client = tokio::spawn(crate::k8s::client::default_client());
+    let mut reader = EventStream::new();
+    let mut ticker = time::interval(tick);
+    let started = time::Instant::now();
+    loop {
+        tokio::select! {
+            result = &mut client => return Ok(Some(result??)),
+            _ = ticker.tick() => {
+                let ticks = (started.elapsed().as_millis() / tick.as_millis()) as usize;
+                terminal.draw(|f| crate::ui::draw_connecting(f, &context, ticks))?;
+            }
+            Some(Ok(Event::Key(key))) = reader.next() => {
+                let ctrl_c = key.code == KeyCode::Char('c')
+                    && key.modifiers.contains(KeyModifiers::CONTROL);
+                if ctrl_c || matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+                    client.abort();
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
+
 pub async fn run<B: Backend<Error: Send + Sync + 'static> + std::io::Write>(
     terminal: &mut Terminal<B>,
     mut app: App,
     mut event_rx: tokio::sync::mpsc::UnboundedReceiver<KubeResourceEvent>,
 ) -> Result<()> {
     let mut reader = EventStream::new();
-    let mut ticker = time::interval(Duration::from_millis(250));
+    let mut ticker = time::interval(app.config.tick());
+    let low_power = app.config.low_power;
+    let frame = app.config.frame_interval();
+    let mut last_draw = time::Instant::now() - frame;
 
     let mut current_tab = app.active_tab;
     let mut current_ns = app.current_namespace.clone();
     let mut watcher = create_watcher(&mut app);
+    app.restart_webhook_watcher();
 
     if let Ok(ctxs) = crate::k8s::config::list_contexts() {
         app.available_contexts = ctxs;
@@ -167,6 +499,11 @@ pub async fn run<B: Backend<Error: Send + Sync + 'static> + std::io::Write>(
     if let Ok(ctx) = crate::k8s::config::get_current_context() {
         app.current_context = ctx;
     }
+    app.record_context_switch();
+    app.apply_context_prefs();
+    app.apply_startup();
+    crate::k8s::config::watch_kubeconfig(app.event_tx.clone());
+    app.prewarm_clients();
 
     app.available_namespaces = app.app_state.get_namespaces(&app.current_context);
     if !app.available_namespaces.contains(&app.current_namespace) {
@@ -180,40 +517,53 @@ pub async fn run<B: Backend<Error: Send + Sync + 'static> + std::io::Write>(
     let mut current_ctx = app.current_context.clone();
 
     loop {
-        if app.dirty {
-            terminal.draw(|f| draw(f, &mut app))?;
+        // Changes arriving faster than the frame budget are drawn together
+        // once it allows; see the `sleep_until` branch below.
+        if app.dirty && last_draw.elapsed() >= frame {
+            let frame = terminal.draw(|f| draw(f, &mut app))?;
+            if !app.drawn_links.is_empty() {
+                let _ = crate::ui::write_hyperlinks(frame.buffer, &app.drawn_links);
+            }
             app.dirty = false;
+            last_draw = time::Instant::now();
         }
 
         if app.should_quit {
             app.abort_log_stream();
+            app.tasks.cancel_all();
             return Ok(());
         }
 
         if let Some(new_ctx) = app.pending_context.take() {
-            crossterm::terminal::disable_raw_mode()?;
-            crossterm::execute!(
-                terminal.backend_mut(),
-                crossterm::terminal::LeaveAlternateScreen,
-                crossterm::cursor::Show
-            )?;
-            eprintln!("Authenticating with context '{new_ctx}'...");
-
-            let result = crate::k8s::config::create_client_with_context(&new_ctx).await;
-
-            crossterm::execute!(
-                terminal.backend_mut(),
-                crossterm::terminal::EnterAlternateScreen,
-                crossterm::cursor::Hide
-            )?;
-            crossterm::terminal::enable_raw_mode()?;
-            terminal.clear()?;
+            let result = match app.warm_clients.remove(&new_ctx) {
+                Some(client) => Ok(client),
+                None => {
+                    suspend_terminal(terminal)?;
+                    eprintln!("Authenticating with context '{new_ctx}'...");
+                    let result = crate::k8s::config::create_client_with_context(&new_ctx).await;
+                    resume_terminal(terminal)?;
+                    result
+                }
+            };
 
             match result {
                 Ok(client) => {
-                    app.client = client;
+                    // Results for the old context would land in the new one.
+                    app.tasks.cancel_context_tasks();
+                    let previous = std::mem::replace(&mut app.client, client);
+                    if app.config.prewarm_contexts > 0 {
+                        app.warm_clients
+                            .insert(app.current_context.clone(), previous);
+                    }
                     app.current_namespace = crate::k8s::config::get_namespace_for_context(&new_ctx);
                     app.current_context = new_ctx.clone();
+                    app.record_context_switch();
+                    app.apply_context_prefs();
+                    app.cluster_version = None;
+                    app.cluster_reachable = None;
+                    app.cluster_unreachable_since = None;
+                    app.cluster_error = None;
+                    app.probe_cluster();
 
                     app.available_namespaces = app.app_state.get_namespaces(&new_ctx);
                     if !app.available_namespaces.contains(&app.current_namespace) {
@@ -222,6 +572,11 @@ pub async fn run<B: Backend<Error: Send + Sync + 'static> + std::io::Write>(
                     }
                     app.load_namespaces();
                 }
+                Err(e) if crate::k8s::config::teleport_login(&new_ctx).is_some() => {
+                    app.set_error(format!(
+                        "Context switch failed: {e} (`:login {new_ctx}` renews the tsh session)"
+                    ));
+                }
                 Err(e) => {
                     app.set_error(format!("Context switch failed: {e}"));
                 }
@@ -229,10 +584,76 @@ pub async fn run<B: Backend<Error: Send + Sync + 'static> + std::io::Write>(
             app.dirty = true;
         }
 
+        if std::mem::take(&mut app.reauth_requested) {
+            let ctx = app.current_context.clone();
+            // `tsh kube credentials` can't renew an expired session itself.
+            if crate::k8s::config::teleport_login(&ctx).is_some() {
+                app.tsh_login = Some(ctx);
+            } else {
+                reauthenticate(terminal, &mut app, ctx).await?;
+            }
+        }
+
+        if let Some(ctx) = app.tsh_login.take() {
+            tsh_login(terminal, &mut app, ctx).await?;
+        }
+
+        if let Some(request) = app.pending_editor.take() {
+            suspend_terminal(terminal)?;
+            match request {
+                EditorRequest::SecretValue { secret, key, value } => {
+                    let result = crate::utils::edit_in_editor(&value, ".txt");
+                    resume_terminal(terminal)?;
+                    match result {
+                        Ok(edited) if edited == value => {
+                            app.set_success(format!("'{key}' unchanged"));
+                        }
+                        Ok(edited) => app.write(PendingAction::PatchSecretKey {
+                            secret,
+                            key,
+                            value: edited,
+                        }),
+                        Err(e) => app.set_error(format!("Editor failed: {e}")),
+                    }
+                }
+                EditorRequest::Resource(edit) => {
+                    let result =
+                        crate::utils::edit_in_editor(&crate::app::edit_text(&edit), ".yaml");
+                    resume_terminal(terminal)?;
+                    app.resource_edited(edit, result);
+                }
+                EditorRequest::Manifest => {
+                    let template = crate::app::MANIFEST_TEMPLATE;
+                    let result = crate::utils::edit_in_editor(template, ".yaml");
+                    resume_terminal(terminal)?;
+                    match result {
+                        Ok(text) if text == template => {
+                            app.set_success("Apply cancelled".to_string());
+                        }
+                        Ok(text) => app.plan_manifest(&text),
+                        Err(e) => app.set_error(format!("Editor failed: {e}")),
+                    }
+                }
+                EditorRequest::Pager(text) => {
+                    let result = crate::utils::page(&text);
+                    resume_terminal(terminal)?;
+                    if let Err(e) = result {
+                        app.set_error(format!("Pager failed: {e}"));
+                    }
+                }
+            }
+            app.dirty = true;
+        }
+
         if app.active_tab != current_tab
             || app.current_namespace != current_ns
             || app.current_context != current_ctx
+            || std::mem::take(&mut app.refresh_requested)
         {
+            let tab_changed = app.active_tab != current_tab;
+            let view_changed = app.current_namespace != current_ns
+                || app.current_context != current_ctx
+                || !tab_changed;
             current_tab = app.active_tab;
             current_ns = app.current_namespace.clone();
             current_ctx = app.current_context.clone();
@@ -242,8 +663,11 @@ pub async fn run<B: Backend<Error: Send + Sync + 'static> + std::io::Write>(
             app.pod_store = None;
             app.deployment_store = None;
             app.secret_store = None;
+            app.configmap_store = None;
             app.is_loading = true;
             app.loading_since = Some(std::time::Instant::now());
+            app.watch_health = WatchHealth::Syncing;
+            app.last_watch_event = None;
             if app
                 .last_error
                 .as_ref()
@@ -254,36 +678,63 @@ pub async fn run<B: Backend<Error: Send + Sync + 'static> + std::io::Write>(
             }
 
             watcher = create_watcher(&mut app);
+            if view_changed {
+                app.restart_webhook_watcher();
+            }
+            if tab_changed || app.split_follows_main() {
+                app.restart_split_watcher();
+            }
             app.refresh_items();
             app.dirty = true;
         }
 
         tokio::select! {
             _ = ticker.tick() => {
-                app.clear_stale_messages();
-                app.dirty = true;
+                let cleared = app.clear_stale_messages() | app.flush_filter(false);
+                if app.cluster_probe_due() {
+                    app.probe_cluster();
+                }
+                if app.describe_refresh_due() {
+                    app.refresh_describe();
+                }
+                // Only the spinner and the elapsed-time labels change on
+                // their own; those need at most a frame a second.
+                let animating = app.is_loading || last_draw.elapsed() >= Duration::from_secs(1);
+                app.dirty |= cleared || (animating && !low_power);
             }
+            _ = time::sleep_until(last_draw + frame), if app.dirty => {}
             Some(Ok(event)) = reader.next() => {
-               if let Event::Key(key) = event {
-                   handle_input(&mut app, key);
-                   app.dirty = true;
+               match event {
+                   Event::Key(key) => {
+                       handle_input(&mut app, key);
+                       app.dirty = true;
+                   }
+                   Event::Mouse(mouse) if mouse.kind != MouseEventKind::Moved => {
+                       handle_mouse(&mut app, mouse);
+                       app.dirty = true;
+                   }
+                   _ => {}
                }
             }
             Some(event) = watcher.next() => {
-                let mut needs_refresh = handle_watcher_event(&mut app, event, &mut watcher);
+                let mut changed = handle_watcher_event(&mut app, event, &mut watcher);
                 while let Some(Some(event)) = watcher.next().now_or_never() {
-                    needs_refresh |= handle_watcher_event(&mut app, event, &mut watcher);
+                    changed |= handle_watcher_event(&mut app, event, &mut watcher);
                 }
-                if needs_refresh {
-                    app.refresh_items();
+                if changed {
+                    app.items_changed();
                     app.dirty = true;
                 }
             }
             Some(event) = event_rx.recv() => {
+                let mut visible = redraws(&app, &event);
                 handle_channel_event(&mut app, event);
                 while let Ok(event) = event_rx.try_recv() {
+                    visible |= redraws(&app, &event);
                     handle_channel_event(&mut app, event);
                 }
+                app.refresh_split_if_stale();
+                app.dirty |= visible;
             }
         }
     }
@@ -350,10 +801,23 @@ mod tests {
         assert!(!is_forbidden(&err));
     }
 
+    #[test]
+    fn map_watcher_event_401_returns_unauthorized() {
+        let mut status = make_403_status();
+        status.code = 401;
+        status.reason = "Unauthorized".to_string();
+        let err = watcher::Error::WatchFailed(kube::Error::Api(status));
+        let event = map_watcher_event(Err(err), KubeResource::Pod);
+        assert!(matches!(event, KubeResourceEvent::Unauthorized(_)));
+        let err = watcher::Error::InitialListFailed(kube::Error::Api(make_404_status()));
+        let event = map_watcher_event(Err(err), KubeResource::Pod);
+        assert!(matches!(event, KubeResourceEvent::Error(_)));
+    }
+
     #[test]
     fn map_watcher_event_403_returns_forbidden() {
         let err = watcher::Error::InitialListFailed(kube::Error::Api(make_403_status()));
-        let event = map_watcher_event::<Pod>(Err(err));
+        let event = map_watcher_event(Err(err), KubeResource::Pod);
         assert!(
             matches!(event, KubeResourceEvent::WatcherForbidden(msg) if msg.contains("forbidden"))
         );
@@ -362,20 +826,100 @@ mod tests {
     #[test]
     fn map_watcher_event_404_returns_error() {
         let err = watcher::Error::InitialListFailed(kube::Error::Api(make_404_status()));
-        let event = map_watcher_event::<Pod>(Err(err));
+        let event = map_watcher_event(Err(err), KubeResource::Pod);
         assert!(matches!(event, KubeResourceEvent::Error(_)));
     }
 
     #[test]
     fn map_watcher_event_init_done_returns_initial_list_done() {
-        let event = map_watcher_event::<Pod>(Ok(watcher::Event::InitDone));
+        let event = map_watcher_event(Ok(watcher::Event::InitDone), KubeResource::Pod);
         assert!(matches!(event, KubeResourceEvent::InitialListDone));
     }
 
     #[test]
-    fn map_watcher_event_apply_returns_refresh() {
+    fn map_watcher_event_apply_and_delete_carry_the_object() {
         let pod = Pod::default();
-        let event = map_watcher_event::<Pod>(Ok(watcher::Event::Apply(pod)));
+        let event = map_watcher_event(Ok(watcher::Event::Apply(pod.clone())), KubeResource::Pod);
+        assert!(matches!(
+            event,
+            KubeResourceEvent::Applied(KubeResource::Pod(_))
+        ));
+        let event = map_watcher_event(Ok(watcher::Event::Delete(pod.clone())), KubeResource::Pod);
+        assert!(matches!(
+            event,
+            KubeResourceEvent::Deleted(KubeResource::Pod(_))
+        ));
+        let event = map_watcher_event(Ok(watcher::Event::InitApply(pod)), KubeResource::Pod);
         assert!(matches!(event, KubeResourceEvent::Refresh));
     }
+
+    #[tokio::test]
+    async fn watcher_events_update_watch_health() {
+        let mut app = crate::app::App::new_test();
+        let mut watcher: BoxStream<'static, KubeResourceEvent> =
+            Box::pin(futures::stream::pending());
+        handle_watcher_event(&mut app, KubeResourceEvent::InitialListDone, &mut watcher);
+        assert_eq!(app.watch_health, WatchHealth::Live);
+        handle_watcher_event(
+            &mut app,
+            KubeResourceEvent::Error("Watcher error: timeout".to_string()),
+            &mut watcher,
+        );
+        handle_watcher_event(
+            &mut app,
+            KubeResourceEvent::Error("Watcher error: connection reset".to_string()),
+            &mut watcher,
+        );
+        assert!(matches!(
+            &app.watch_health,
+            WatchHealth::Reconnecting { attempts: 2, last_error, .. } if last_error.contains("reset")
+        ));
+        // Retries don't flash the footer; the outage is in the history once.
+        assert!(app.last_error.is_none());
+        assert_eq!(app.notifications.iter().filter(|n| n.error).count(), 1);
+        handle_watcher_event(&mut app, KubeResourceEvent::Refresh, &mut watcher);
+        assert_eq!(app.watch_health, WatchHealth::Live);
+        handle_watcher_event(
+            &mut app,
+            KubeResourceEvent::WatcherForbidden(String::new()),
+            &mut watcher,
+        );
+        assert_eq!(app.watch_health, WatchHealth::Forbidden);
+    }
+
+    #[tokio::test]
+    async fn bulk_result_opens_report() {
+        let mut app = crate::app::App::new_test();
+        handle_channel_event(
+            &mut app,
+            KubeResourceEvent::BulkResult(
+                "Delete pod(s)".to_string(),
+                vec![
+                    ("a".to_string(), Ok(())),
+                    ("b".to_string(), Err("forbidden".to_string())),
+                ],
+            ),
+        );
+        assert_eq!(app.mode, AppMode::ValuePager);
+        assert_eq!(app.pager_title, "Delete pod(s): 1 succeeded, 1 failed");
+        assert!(app.pager_text.starts_with("FAILED  b: forbidden"));
+        assert!(app.last_error.is_some());
+    }
+
+    #[tokio::test]
+    async fn log_lines_redraw_only_in_the_log_view() {
+        let mut app = crate::app::App::new_test();
+        let line = KubeResourceEvent::Log("hello".to_string());
+        assert!(!redraws(&app, &line));
+        assert!(!redraws(
+            &app,
+            &KubeResourceEvent::ShellOutput(b"$ ".to_vec())
+        ));
+        assert!(redraws(
+            &app,
+            &KubeResourceEvent::Success("done".to_string())
+        ));
+        app.push_mode(AppMode::LogView);
+        assert!(redraws(&app, &line));
+    }
 }
@@ -1,9 +1,126 @@
-use crate::app::{App, LOG_CHROME_LINES};
-use crate::models::{AppMode, KubeResource, KubeResourceEvent, PendingAction, ResourceType};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::app::{App, MarkAction};
+use crate::models::{
+    AppMode, ConfigValue, EditorRequest, KubeResource, KubeResourceEvent, PendingAction,
+    ResourceType, SecretForm, SecretFormField,
+};
+use base64::prelude::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
 use std::collections::HashSet;
 
+/// Whether `key` in the current mode would change the cluster or open a shell.
+fn is_mutating_key(app: &App, key: &KeyEvent) -> bool {
+    let tab = app.active_tab;
+    let workload = matches!(tab, ResourceType::Pod | ResourceType::Deployment);
+    match (app.mode, key.code) {
+        (AppMode::List, KeyCode::Char('D') | KeyCode::Delete) => workload,
+        (AppMode::List, KeyCode::Char('e')) => workload,
+        (AppMode::List, KeyCode::Char('s')) => tab == ResourceType::Pod,
+        (AppMode::List, KeyCode::Char('S') | KeyCode::Char('r')) => tab == ResourceType::Deployment,
+        (AppMode::List, KeyCode::Char('a')) => {
+            tab == ResourceType::Secret && !key.modifiers.contains(KeyModifiers::CONTROL)
+        }
+        (AppMode::SecretDecode, KeyCode::Char('e')) => true,
+        (AppMode::NamespaceSelect, KeyCode::Char('a' | 'D') | KeyCode::Delete) => {
+            !app.namespace_typing
+        }
+        _ => false,
+    }
+}
+
 pub fn handle_input(app: &mut App, key: KeyEvent) {
+    if app.read_only && is_mutating_key(app, &key) {
+        app.set_error(format!(
+            "Context '{}' is read-only",
+            app.context_label(&app.current_context)
+        ));
+        return;
+    }
+    if let Some(action) = app.pending_mark.take() {
+        handle_mark_key(app, action, key);
+        return;
+    }
+    if matches!(
+        app.mode,
+        AppMode::List | AppMode::LogView | AppMode::DescribeView
+    ) {
+        handle_counted_input(app, key);
+    } else {
+        dispatch_input(app, key);
+    }
+}
+
+/// The letter after `m` or `'`; anything else cancels.
+fn handle_mark_key(app: &mut App, action: MarkAction, key: KeyEvent) {
+    let KeyCode::Char(c) = key.code else {
+        return;
+    };
+    if !c.is_ascii_alphabetic() {
+        return;
+    }
+    match action {
+        MarkAction::Set => app.set_mark(c),
+        MarkAction::Jump => app.jump_to_mark(c),
+    }
+}
+
+/// Longest run a count prefix can repeat a motion for.
+const MAX_COUNT: usize = 9999;
+
+/// Whether digit `c` starts or extends a count; in the list tab digits jump instead.
+fn counts(app: &App, c: char) -> bool {
+    let digit = c as usize - '0' as usize;
+    match (app.pending_count, app.mode) {
+        (Some(_), _) => true,
+        (None, AppMode::List) => digit == 0 || digit > ResourceType::ALL.len(),
+        (None, _) => digit != 0,
+    }
+}
+
+/// Collects a numeric prefix and repeats the next motion key that many times.
+fn handle_counted_input(app: &mut App, key: KeyEvent) {
+    if let KeyCode::Char(c @ '0'..='9') = key.code
+        && !key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        && counts(app, c)
+    {
+        let digit = c as usize - '0' as usize;
+        app.pending_count = Some((app.pending_count.unwrap_or(0) * 10 + digit).min(MAX_COUNT));
+        return;
+    }
+    let key = match key.code {
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE)
+        }
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE)
+        }
+        _ => key,
+    };
+    let count = app.pending_count.take().unwrap_or(1).max(1);
+    let motion = matches!(
+        key.code,
+        KeyCode::Char('j' | 'k')
+            | KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::PageUp
+            | KeyCode::PageDown
+    );
+    if !motion {
+        dispatch_input(app, key);
+        return;
+    }
+    let mode = app.mode;
+    for _ in 0..count {
+        dispatch_input(app, key);
+        if app.mode != mode {
+            break;
+        }
+    }
+}
+
+fn dispatch_input(app: &mut App, key: KeyEvent) {
     match app.mode {
         AppMode::FilterInput => handle_filter_input(app, key),
         AppMode::SecretDecode => handle_secret_modal_input(app, key),
@@ -15,24 +132,156 @@ pub fn handle_input(app: &mut App, key: KeyEvent) {
         AppMode::Confirm => handle_confirm_input(app, key),
         AppMode::ShellView => handle_shell_input(app, key),
         AppMode::DescribeView => handle_describe_input(app, key),
+        AppMode::DescribeSearchInput => handle_describe_search_input(app, key),
         AppMode::StatusFilter => handle_status_filter_input(app, key),
+        AppMode::SecretCreate => handle_secret_create_input(app, key),
+        AppMode::SecretEditValue => handle_secret_edit_input(app, key),
+        AppMode::SecretExport => handle_secret_export_input(app, key),
+        AppMode::SecretSaveRaw => handle_secret_save_raw_input(app, key),
+        AppMode::SplitInput => handle_split_input(app, key),
+        AppMode::NamespaceCreate => handle_namespace_create_input(app, key),
+        AppMode::AccessReview => handle_access_review_input(app, key),
+        AppMode::NamespaceSummary => handle_namespace_summary_input(app, key),
+        AppMode::MetricsView => handle_metrics_input(app, key),
+        AppMode::CommandInput => handle_command_input(app, key),
+        AppMode::ActionMenu => handle_action_menu_input(app, key),
+        AppMode::PluginMenu => handle_plugin_menu_input(app, key),
+        AppMode::LinkMenu => handle_link_menu_input(app, key),
+        AppMode::EndpointMenu => handle_endpoint_menu_input(app, key),
+        AppMode::ManifestExport => handle_manifest_export_input(app, key),
+        AppMode::SecretDiffInput => handle_secret_diff_input(app, key),
+        AppMode::SecretDiff => handle_secret_diff_view_input(app, key),
+        AppMode::ConfigMapView => handle_configmap_view_input(app, key),
+        AppMode::SecretUsage => handle_secret_usage_input(app, key),
+        AppMode::ValuePager => handle_pager_input(app, key),
+        AppMode::ValuePagerSearch => handle_pager_search_input(app, key),
+        AppMode::EditDiff => handle_edit_diff_input(app, key),
+        AppMode::ApplyDiff => handle_apply_diff_input(app, key),
         AppMode::List => handle_global_input(app, key),
     }
 }
 
+/// Scrolls, picks tabs and rows, and closes modals with the mouse.
+pub fn handle_mouse(app: &mut App, mouse: MouseEvent) {
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    match (mouse.kind, app.mode) {
+        (_, AppMode::ShellView) => {}
+        (MouseEventKind::ScrollDown, AppMode::List) => {
+            let len = app.filtered_items.len();
+            if len > 0 {
+                let i = app
+                    .table_state
+                    .selected()
+                    .map_or(0, |i| (i + 1).min(len - 1));
+                app.table_state.select(Some(i));
+            }
+        }
+        (MouseEventKind::ScrollUp, AppMode::List) if !app.filtered_items.is_empty() => {
+            let i = app.table_state.selected().unwrap_or(0);
+            app.table_state.select(Some(i.saturating_sub(1)));
+        }
+        (
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp,
+            AppMode::LogView
+            | AppMode::DescribeView
+            | AppMode::ValuePager
+            | AppMode::EditDiff
+            | AppMode::ApplyDiff
+            | AppMode::SecretDecode
+            | AppMode::SecretDiff
+            | AppMode::SecretUsage
+            | AppMode::ConfigMapView
+            | AppMode::ContextSelect
+            | AppMode::NamespaceSelect
+            | AppMode::StatusFilter
+            | AppMode::ActionMenu
+            | AppMode::PluginMenu
+            | AppMode::LinkMenu
+            | AppMode::EndpointMenu,
+        ) => {
+            let code = if mouse.kind == MouseEventKind::ScrollDown {
+                KeyCode::Down
+            } else {
+                KeyCode::Up
+            };
+            handle_input(app, key(code));
+        }
+        (MouseEventKind::Down(MouseButton::Left), mode) => {
+            let pos = Position::new(mouse.column, mouse.row);
+            if let Some(modal) = app.hit_areas.modal {
+                if !modal.contains(pos) {
+                    handle_input(app, key(KeyCode::Esc));
+                }
+            } else if mode == AppMode::List {
+                let tabs = app.hit_areas.tabs;
+                if tabs.contains(pos) {
+                    if let Some(tab) = crate::ui::tab_at(pos.x - tabs.x) {
+                        app.select_tab(tab);
+                    }
+                } else if let Some(row) = table_row_at(app, pos) {
+                    app.table_state.select(Some(row));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The list row under `pos`.
+fn table_row_at(app: &App, pos: Position) -> Option<usize> {
+    let area = app.hit_areas.table;
+    let first = area.y + 3;
+    if !area.contains(pos) || pos.y < first || pos.y + 1 >= area.bottom() {
+        return None;
+    }
+    let row = app.table_state.offset() + (pos.y - first) as usize;
+    (row < app.filtered_items.len()).then_some(row)
+}
+
 fn handle_popup_input(app: &mut App, key: KeyEvent) {
-    let len = app.available_contexts.len();
+    let len = app.filtered_contexts.len();
     match key.code {
+        KeyCode::Esc if app.context_typing => {
+            app.context_input.clear();
+            app.context_typing = false;
+            app.update_context_filter();
+            let idx = app
+                .filtered_contexts
+                .iter()
+                .position(|ctx| *ctx == app.current_context);
+            app.popup_state.select(idx.or(Some(0)));
+        }
         KeyCode::Esc => {
-            app.mode = AppMode::List;
+            app.pop_mode();
         }
         KeyCode::Enter => {
             if let Some(i) = app.popup_state.selected()
-                && let Some(ctx) = app.available_contexts.get(i)
+                && let Some(ctx) = app.filtered_contexts.get(i)
             {
                 app.pending_context = Some(ctx.clone());
             }
-            app.mode = AppMode::List;
+            app.context_input.clear();
+            app.context_typing = false;
+            app.pop_mode();
+        }
+        KeyCode::Char('/') if !app.context_typing => {
+            app.context_typing = true;
+            app.context_input.clear();
+        }
+        KeyCode::Char('L') if !app.context_typing => {
+            if let Some(i) = app.popup_state.selected()
+                && let Some(ctx) = app.filtered_contexts.get(i)
+            {
+                app.tsh_login(ctx.clone());
+            }
+        }
+        KeyCode::Backspace if app.context_typing => {
+            app.context_input.pop();
+            app.update_context_filter();
+        }
+        KeyCode::Char(c) if app.context_typing => {
+            app.context_input.push(c);
+            app.update_context_filter();
         }
         KeyCode::Up | KeyCode::Char('k') => {
             let i = app
@@ -42,7 +291,7 @@ fn handle_popup_input(app: &mut App, key: KeyEvent) {
                 .unwrap_or(0);
             app.popup_state.select(Some(i));
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        KeyCode::Down | KeyCode::Char('j') if len > 0 => {
             let i = app
                 .popup_state
                 .selected()
@@ -63,20 +312,62 @@ fn is_valid_k8s_name(s: &str) -> bool {
         && s.ends_with(|c: char| c.is_ascii_alphanumeric())
 }
 
+fn is_valid_dns_subdomain(s: &str) -> bool {
+    !s.is_empty()
+        && s.len() <= 253
+        && s.split('.').all(|label| {
+            !label.is_empty()
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+                && label.starts_with(|c: char| c.is_ascii_alphanumeric())
+                && label.ends_with(|c: char| c.is_ascii_alphanumeric())
+        })
+}
+
 fn select_namespace(app: &mut App, ns: String) {
     if !ns.is_empty() {
-        app.current_namespace = ns.clone();
-        let ctx = app.current_context.clone();
-        app.app_state.add_namespace(&ctx, &ns);
-        if !app.available_namespaces.contains(&ns) {
-            app.available_namespaces.push(ns);
-            app.available_namespaces.sort();
-        }
-        app.app_state.save();
+        app.switch_namespace(ns);
     }
     app.namespace_input.clear();
     app.namespace_typing = false;
-    app.mode = AppMode::List;
+    app.pop_mode();
+}
+
+/// Namespaces the popup never offers to delete.
+const PROTECTED_NAMESPACES: &[&str] = &["default", "kube-system", "kube-public", "kube-node-lease"];
+
+fn handle_namespace_create_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.namespace_create_input.clear();
+            app.mode = AppMode::NamespaceSelect;
+        }
+        KeyCode::Enter => {
+            let name = app.namespace_create_input.trim().to_string();
+            if !is_valid_k8s_name(&name) {
+                app.set_error(
+                    "Invalid namespace name (RFC 1123: lowercase, digits, hyphens, max 63 chars)"
+                        .to_string(),
+                );
+                return;
+            }
+            if app.available_namespaces.contains(&name) {
+                app.set_error(format!("Namespace '{name}' already exists"));
+                return;
+            }
+            app.namespace_create_input.clear();
+            app.mode = AppMode::NamespaceSelect;
+            app.write(PendingAction::CreateNamespace { name });
+        }
+        KeyCode::Backspace => {
+            app.namespace_create_input.pop();
+        }
+        KeyCode::Char(c) => {
+            app.namespace_create_input.push(c);
+        }
+        _ => {}
+    }
 }
 
 fn handle_namespace_input(app: &mut App, key: KeyEvent) {
@@ -85,8 +376,7 @@ fn handle_namespace_input(app: &mut App, key: KeyEvent) {
             KeyCode::Esc => {
                 app.namespace_input.clear();
                 app.namespace_typing = false;
-                app.filtered_namespaces
-                    .clone_from(&app.available_namespaces);
+                app.update_namespace_filter();
                 let idx = app
                     .filtered_namespaces
                     .iter()
@@ -124,6 +414,7 @@ fn handle_namespace_input(app: &mut App, key: KeyEvent) {
                     app.popup_state.select(Some(i));
                 }
             }
+            KeyCode::Tab => app.cycle_filter_mode(),
             KeyCode::Backspace => {
                 app.namespace_input.pop();
                 app.update_namespace_filter();
@@ -140,12 +431,35 @@ fn handle_namespace_input(app: &mut App, key: KeyEvent) {
             KeyCode::Esc => {
                 app.namespace_input.clear();
                 app.namespace_typing = false;
-                app.mode = AppMode::List;
+                app.pop_mode();
             }
             KeyCode::Char('/') => {
                 app.namespace_typing = true;
                 app.namespace_input.clear();
             }
+            KeyCode::Char('f') => app.toggle_favorite_namespace(),
+            KeyCode::Char('a') => {
+                app.namespace_create_input.clear();
+                app.mode = AppMode::NamespaceCreate;
+            }
+            KeyCode::Char('D') | KeyCode::Delete => {
+                if let Some(ns) = app
+                    .popup_state
+                    .selected()
+                    .and_then(|i| app.filtered_namespaces.get(i).cloned())
+                {
+                    if PROTECTED_NAMESPACES.contains(&ns.as_str()) {
+                        app.set_error(format!("Refusing to delete system namespace '{ns}'"));
+                    } else if ns == app.current_namespace {
+                        app.set_error(format!(
+                            "'{ns}' is the current namespace; switch away before deleting it"
+                        ));
+                    } else {
+                        app.pending_action = Some(PendingAction::DeleteNamespace { name: ns });
+                        app.mode = AppMode::Confirm;
+                    }
+                }
+            }
             KeyCode::Enter => {
                 if let Some(ns) = app
                     .popup_state
@@ -163,15 +477,13 @@ fn handle_namespace_input(app: &mut App, key: KeyEvent) {
                     .unwrap_or(0);
                 app.popup_state.select(Some(i));
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if len > 0 {
-                    let i = app
-                        .popup_state
-                        .selected()
-                        .map(|i| (i + 1).min(len.saturating_sub(1)))
-                        .unwrap_or(0);
-                    app.popup_state.select(Some(i));
-                }
+            KeyCode::Down | KeyCode::Char('j') if len > 0 => {
+                let i = app
+                    .popup_state
+                    .selected()
+                    .map(|i| (i + 1).min(len.saturating_sub(1)))
+                    .unwrap_or(0);
+                app.popup_state.select(Some(i));
             }
             _ => {}
         }
@@ -179,21 +491,16 @@ fn handle_namespace_input(app: &mut App, key: KeyEvent) {
 }
 
 fn log_max_scroll(app: &App) -> usize {
-    let visible = crossterm::terminal::size()
-        .map(|(_, h)| (h as usize).saturating_sub(LOG_CHROME_LINES))
-        .unwrap_or(20);
-    app.log_buffer.len().saturating_sub(visible)
+    app.log_buffer.len().saturating_sub(app.viewport.log_rows)
 }
 
 fn handle_log_input(app: &mut App, key: KeyEvent) {
-    let page_size = crossterm::terminal::size()
-        .map(|(_, h)| (h as usize).saturating_sub(LOG_CHROME_LINES))
-        .unwrap_or(20);
+    let page_size = app.viewport.log_rows;
 
     match key.code {
         KeyCode::Char('q') => {
             app.abort_log_stream();
-            app.mode = AppMode::List;
+            app.pop_mode();
         }
         KeyCode::Esc => {
             if !app.log_search_query.is_empty() {
@@ -202,7 +509,7 @@ fn handle_log_input(app: &mut App, key: KeyEvent) {
                 app.log_search_pending = false;
             } else {
                 app.abort_log_stream();
-                app.mode = AppMode::List;
+                app.pop_mode();
             }
         }
         KeyCode::Char('/') => {
@@ -215,6 +522,17 @@ fn handle_log_input(app: &mut App, key: KeyEvent) {
         KeyCode::Char('N') => {
             app.log_search_prev();
         }
+        KeyCode::Char('R') => {
+            app.reattach_logs();
+        }
+        KeyCode::Char('|') => {
+            let text = app
+                .log_buffer
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+            app.pending_editor = Some(EditorRequest::Pager(text.join("\n")));
+        }
         KeyCode::Char('j') | KeyCode::Down => {
             let max = log_max_scroll(app);
             if let Some(offset) = &mut app.log_scroll_offset {
@@ -298,22 +616,30 @@ fn handle_global_input(app: &mut App, key: KeyEvent) {
         KeyCode::Char('q') => app.should_quit = true,
         KeyCode::Tab => app.next_tab(),
         KeyCode::BackTab => app.prev_tab(),
+        KeyCode::Char(c @ '1'..='9') => {
+            if let Some(&tab) = ResourceType::ALL.get(c as usize - '1' as usize) {
+                app.select_tab(tab);
+            }
+        }
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             app.should_quit = true;
         }
         KeyCode::Char('c') => {
+            app.context_input.clear();
+            app.context_typing = false;
+            app.update_context_filter();
             let current_idx = app
-                .available_contexts
+                .filtered_contexts
                 .iter()
                 .position(|ctx| *ctx == app.current_context);
             app.popup_state.select(current_idx.or(Some(0)));
-            app.mode = AppMode::ContextSelect;
+            app.push_mode(AppMode::ContextSelect);
+            app.refresh_teleport_status();
         }
         KeyCode::Char('n') => {
             app.namespace_input.clear();
             app.namespace_typing = false;
-            app.filtered_namespaces
-                .clone_from(&app.available_namespaces);
+            app.update_namespace_filter();
             let current_idx = app
                 .filtered_namespaces
                 .iter()
@@ -324,17 +650,57 @@ fn handle_global_input(app: &mut App, key: KeyEvent) {
                 } else {
                     Some(0)
                 }));
-            app.mode = AppMode::NamespaceSelect;
+            app.push_mode(AppMode::NamespaceSelect);
         }
         KeyCode::Char('/') => {
-            app.mode = AppMode::FilterInput;
+            app.filter_history_pos = None;
+            app.push_mode(AppMode::FilterInput);
+        }
+        KeyCode::Char(':') => {
+            app.command_input.clear();
+            app.command_history_pos = None;
+            app.push_mode(AppMode::CommandInput);
+        }
+        KeyCode::Char('?') => {
+            app.open_pager("Keybindings".to_string(), crate::keymap::help_text());
+        }
+        KeyCode::Char('A') => app.review_access(),
+        KeyCode::Char('E') => app.open_last_error(),
+        KeyCode::Char('R') => app.request_refresh(),
+        KeyCode::Char('m') => app.pending_mark = Some(MarkAction::Set),
+        KeyCode::Char('\'') => app.pending_mark = Some(MarkAction::Jump),
+        KeyCode::Char('N') => {
+            let title = format!("Notifications [{}]", app.notifications.len());
+            app.open_pager(title, app.notification_log());
+        }
+        KeyCode::Char('p') => app.toggle_detail_pane(),
+        KeyCode::Char('w')
+            if matches!(app.active_tab, ResourceType::Pod | ResourceType::Deployment) =>
+        {
+            app.wide = !app.wide;
+            app.column_offset = 0;
+        }
+        KeyCode::Left if app.wide => {
+            app.column_offset = app.column_offset.saturating_sub(1);
+        }
+        // The view clamps the offset to the columns it actually has.
+        KeyCode::Right if app.wide => app.column_offset += 1,
+        KeyCode::Char('<') => app.resize_detail_pane(false),
+        KeyCode::Char('>') => app.resize_detail_pane(true),
+        KeyCode::Char('i') => app.load_namespace_summary(),
+        KeyCode::Char('[') => app.cycle_namespace(false),
+        KeyCode::Char(']') => app.cycle_namespace(true),
+        KeyCode::Char('-') => {
+            let ctx = app.current_context.clone();
+            match app.app_state.previous_namespace(&ctx).map(str::to_owned) {
+                Some(prev) if prev != app.current_namespace => app.switch_namespace(prev),
+                _ => app.set_error("No previous namespace for this context".to_string()),
+            }
         }
         KeyCode::Char('j') | KeyCode::Down => next_row(app),
         KeyCode::Char('k') | KeyCode::Up => prev_row(app),
-        KeyCode::Char('g') => {
-            if !app.filtered_items.is_empty() {
-                app.table_state.select(Some(0));
-            }
+        KeyCode::Char('g') if !app.filtered_items.is_empty() => {
+            app.table_state.select(Some(0));
         }
         KeyCode::Char('G') => {
             let len = app.filtered_items.len();
@@ -345,24 +711,20 @@ fn handle_global_input(app: &mut App, key: KeyEvent) {
         KeyCode::PageDown => {
             let len = app.filtered_items.len();
             if len > 0 {
-                let page = crossterm::terminal::size()
-                    .map(|(_, h)| (h as usize).saturating_sub(8))
-                    .unwrap_or(20);
+                let page = app.viewport.table_rows;
                 let i = app.table_state.selected().unwrap_or(0);
                 app.table_state.select(Some((i + page).min(len - 1)));
             }
         }
-        KeyCode::PageUp => {
-            if !app.filtered_items.is_empty() {
-                let page = crossterm::terminal::size()
-                    .map(|(_, h)| (h as usize).saturating_sub(8))
-                    .unwrap_or(20);
-                let i = app.table_state.selected().unwrap_or(0);
-                app.table_state.select(Some(i.saturating_sub(page)));
-            }
+        KeyCode::PageUp if !app.filtered_items.is_empty() => {
+            let page = app.viewport.table_rows;
+            let i = app.table_state.selected().unwrap_or(0);
+            app.table_state.select(Some(i.saturating_sub(page)));
         }
 
-        KeyCode::Char(' ') if app.active_tab != ResourceType::Secret => {
+        KeyCode::Char(' ')
+            if matches!(app.active_tab, ResourceType::Pod | ResourceType::Deployment) =>
+        {
             if let Some(i) = app.table_state.selected()
                 && !app.selected_indices.remove(&i)
             {
@@ -385,7 +747,7 @@ fn handle_global_input(app: &mut App, key: KeyEvent) {
                 } else {
                     Some(0)
                 });
-            app.mode = AppMode::StatusFilter;
+            app.push_mode(AppMode::StatusFilter);
         }
 
         KeyCode::Char('l') if app.active_tab == ResourceType::Pod => {
@@ -432,7 +794,7 @@ fn handle_global_input(app: &mut App, key: KeyEvent) {
                     _ => "resource(s)",
                 };
                 app.pending_action = Some(PendingAction::DeleteResource { count, kind, names });
-                app.mode = AppMode::Confirm;
+                app.push_mode(AppMode::Confirm);
             } else {
                 app.set_error("No resource selected".to_string());
             }
@@ -441,7 +803,7 @@ fn handle_global_input(app: &mut App, key: KeyEvent) {
         KeyCode::Char('S') if app.active_tab == ResourceType::Deployment => {
             if app.get_selected_resource().is_some() {
                 app.scale_input.clear();
-                app.mode = AppMode::ScaleInput;
+                app.push_mode(AppMode::ScaleInput);
             } else {
                 app.set_error("No deployment selected".to_string());
             }
@@ -450,7 +812,7 @@ fn handle_global_input(app: &mut App, key: KeyEvent) {
             if let Some(res) = app.get_selected_resource() {
                 let name = res.name().to_string();
                 app.pending_action = Some(PendingAction::RestartDeployment { name });
-                app.mode = AppMode::Confirm;
+                app.push_mode(AppMode::Confirm);
             } else {
                 app.set_error("No deployment selected".to_string());
             }
@@ -466,34 +828,13 @@ fn handle_global_input(app: &mut App, key: KeyEvent) {
                     ResourceType::Deployment => "deployment",
                     _ => return,
                 };
-                let name = res.name().to_owned();
-                let ns = app.current_namespace.clone();
-                let ctx = app.current_context.clone();
-                let tx = app.event_tx.clone();
-                tokio::spawn(async move {
-                    match tokio::process::Command::new("kubectl")
-                        .args(["describe", kind, &name, "-n", &ns, "--context", &ctx])
-                        .output()
-                        .await
-                    {
-                        Ok(output) if output.status.success() => {
-                            let text = String::from_utf8_lossy(&output.stdout);
-                            let lines: Vec<String> = text.lines().map(|l| l.to_string()).collect();
-                            let _ = tx.send(KubeResourceEvent::DescribeReady(lines));
-                        }
-                        Ok(output) => {
-                            let stderr = String::from_utf8_lossy(&output.stderr);
-                            let _ = tx.send(KubeResourceEvent::Error(format!(
-                                "Describe failed: {}",
-                                stderr.trim()
-                            )));
-                        }
-                        Err(e) => {
-                            let _ =
-                                tx.send(KubeResourceEvent::Error(format!("Describe failed: {e}")));
-                        }
-                    }
-                });
+                let target = crate::app::DescribeTarget {
+                    kind,
+                    name: res.name().to_owned(),
+                    namespace: app.current_namespace.clone(),
+                    context: app.current_context.clone(),
+                };
+                app.describe(target, false);
             } else {
                 app.set_error("No resource selected".to_string());
             }
@@ -511,21 +852,129 @@ fn handle_global_input(app: &mut App, key: KeyEvent) {
                 };
                 let name = res.name().to_owned();
                 let ns = app.current_namespace.clone();
-                app.start_kubectl_edit(kind, &name, &ns);
+                app.edit_resource(kind, &name, &ns);
             } else {
                 app.set_error("No resource selected".to_string());
             }
         }
 
+        KeyCode::Char('=') if app.active_tab == ResourceType::Secret => {
+            if app.get_selected_resource().is_some() {
+                app.secret_diff_input.clear();
+                app.push_mode(AppMode::SecretDiffInput);
+            } else {
+                app.set_error("No secret selected".to_string());
+            }
+        }
+        KeyCode::Char('|') => {
+            if app.split.take().is_some() {
+                app.set_success("Split view closed".to_string());
+            } else {
+                app.split_input.clear();
+                app.push_mode(AppMode::SplitInput);
+            }
+        }
+        KeyCode::Char('u') if app.active_tab == ResourceType::Secret => {
+            app.find_selected_secret_usage();
+        }
+        KeyCode::Char('a') if app.active_tab == ResourceType::Secret => {
+            app.secret_form = SecretForm::default();
+            app.push_mode(AppMode::SecretCreate);
+        }
+
         KeyCode::Enter | KeyCode::Char('x') if app.active_tab == ResourceType::Secret => {
             app.decode_selected_secret();
             if app.selected_secret_decoded.is_some() {
                 app.secret_scroll = 0;
                 app.secret_revealed = false;
-                app.mode = AppMode::SecretDecode;
+                app.secret_show_base64 = false;
+                app.push_mode(AppMode::SecretDecode);
+            }
+        }
+        KeyCode::Enter | KeyCode::Char('x') if app.active_tab == ResourceType::ConfigMap => {
+            app.open_selected_configmap();
+            if app.configmap_entries.is_some() {
+                app.configmap_scroll = 0;
+                app.configmap_value_scroll = 0;
+                app.push_mode(AppMode::ConfigMapView);
             }
         }
 
+        KeyCode::Char('.') | KeyCode::Enter => {
+            if app.get_selected_resource().is_some() {
+                app.action_menu_state.select(Some(0));
+                app.push_mode(AppMode::ActionMenu);
+            } else {
+                app.set_error("No resource selected".to_string());
+            }
+        }
+        KeyCode::Char('M')
+            if matches!(app.active_tab, ResourceType::Pod | ResourceType::Deployment) =>
+        {
+            app.open_metrics();
+        }
+        KeyCode::Char('O')
+            if matches!(app.active_tab, ResourceType::Pod | ResourceType::Deployment) =>
+        {
+            app.find_endpoints();
+        }
+        KeyCode::Char('W')
+            if matches!(app.active_tab, ResourceType::Pod | ResourceType::Deployment) =>
+        {
+            app.toggle_watch();
+        }
+        KeyCode::Char('X') => {
+            if app.get_selected_resource().is_some() {
+                app.export_path_input = format!("./{}", app.current_namespace);
+                app.push_mode(AppMode::ManifestExport);
+            } else {
+                app.set_error("No resource selected".to_string());
+            }
+        }
+        KeyCode::Char('o') => {
+            let links = app.config.links(&app.current_context, app.active_tab).len();
+            if links == 0 {
+                app.set_error(format!(
+                    "No links for {} in this context; add them under 'links' in config.yaml",
+                    app.active_tab.plural()
+                ));
+            } else if app.get_selected_resource().is_none() {
+                app.set_error("No resource selected".to_string());
+            } else if links == 1 {
+                app.open_link(0);
+            } else {
+                app.link_menu_state.select(Some(0));
+                app.push_mode(AppMode::LinkMenu);
+            }
+        }
+        KeyCode::Char('P') => {
+            if app.config.plugins(app.active_tab, app.read_only).is_empty() {
+                app.set_error(format!(
+                    "No plugins for {}; add them under 'plugins' in config.yaml",
+                    app.active_tab.plural()
+                ));
+            } else if app.get_selected_resource().is_some() {
+                app.plugin_menu_state.select(Some(0));
+                app.push_mode(AppMode::PluginMenu);
+            } else {
+                app.set_error("No resource selected".to_string());
+            }
+        }
+        KeyCode::Char('V')
+            if matches!(app.active_tab, ResourceType::Pod | ResourceType::Deployment) =>
+        {
+            app.toggle_visual();
+        }
+        KeyCode::Char('!')
+            if matches!(app.active_tab, ResourceType::Pod | ResourceType::Deployment) =>
+        {
+            app.visual_anchor = None;
+            app.selected_indices = (0..app.filtered_items.len())
+                .filter(|i| !app.selected_indices.contains(i))
+                .collect();
+        }
+
+        KeyCode::Esc if app.visual_anchor.is_some() => app.visual_anchor = None,
         KeyCode::Esc => {
             app.filter_query.clear();
             app.status_filter.clear();
@@ -533,23 +982,55 @@ fn handle_global_input(app: &mut App, key: KeyEvent) {
         }
         _ => {}
     }
+    app.extend_visual();
 }
 
 fn handle_filter_input(app: &mut App, key: KeyEvent) {
+    let tab = app.active_tab.plural();
+    let history_len = app.app_state.filter_history(tab).len();
     match key.code {
         KeyCode::Esc => {
-            app.mode = AppMode::List;
+            app.flush_filter(true);
+            app.pop_mode();
         }
         KeyCode::Enter => {
-            app.mode = AppMode::List;
+            app.flush_filter(true);
+            if !app.filter_query.is_empty() {
+                let query = app.filter_query.clone();
+                app.app_state.record_filter(tab, &query);
+                app.app_state.save();
+            }
+            app.pop_mode();
+        }
+        KeyCode::Tab => app.cycle_filter_mode(),
+        KeyCode::Up if history_len > 0 => {
+            let pos = app
+                .filter_history_pos
+                .map_or(history_len - 1, |p| p.saturating_sub(1));
+            app.filter_history_pos = Some(pos);
+            app.filter_query = app.app_state.filter_history(tab)[pos].clone();
+            app.update_filter();
         }
+        KeyCode::Down => match app.filter_history_pos {
+            Some(p) if p + 1 < history_len => {
+                app.filter_history_pos = Some(p + 1);
+                app.filter_query = app.app_state.filter_history(tab)[p + 1].clone();
+                app.update_filter();
+            }
+            Some(_) => {
+                app.filter_history_pos = None;
+                app.filter_query.clear();
+                app.update_filter();
+            }
+            None => {}
+        },
         KeyCode::Backspace => {
             app.filter_query.pop();
-            app.update_filter();
+            app.filter_typed();
         }
         KeyCode::Char(c) => {
             app.filter_query.push(c);
-            app.update_filter();
+            app.filter_typed();
         }
         _ => {}
     }
@@ -558,7 +1039,7 @@ fn handle_filter_input(app: &mut App, key: KeyEvent) {
 fn handle_secret_modal_input(app: &mut App, key: KeyEvent) {
     match key.code {
         KeyCode::Esc | KeyCode::Char('q') => {
-            app.mode = AppMode::List;
+            app.pop_mode();
             app.selected_secret_decoded = None;
         }
         KeyCode::Char('j') | KeyCode::Down => {
@@ -574,1233 +1055,3969 @@ fn handle_secret_modal_input(app: &mut App, key: KeyEvent) {
         KeyCode::Char('r') => {
             app.secret_revealed = !app.secret_revealed;
         }
-        KeyCode::Char('c') => {
+        // The pager and hex dump show the value in full; only once revealed.
+        KeyCode::Enter | KeyCode::Char('v' | 'h') if !app.secret_revealed => {
+            app.set_error("Values are hidden; press r to reveal them first".to_string());
+        }
+        KeyCode::Enter | KeyCode::Char('v') => {
             if let Some(decoded) = &app.selected_secret_decoded
                 && let Some((key, value)) = decoded.get(app.secret_scroll)
             {
-                match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(value.clone())) {
-                    Ok(()) => {
-                        if let Some(handle) = app.clipboard_clear_task.take() {
-                            handle.abort();
-                        }
-                        app.set_success(format!("Copied '{key}' to clipboard (clears in 15s)"));
-                        let handle = tokio::spawn(async {
-                            tokio::time::sleep(std::time::Duration::from_secs(15)).await;
-                            if let Ok(mut cb) = arboard::Clipboard::new() {
-                                let _ = cb.set_text(String::new());
-                            }
-                        });
-                        app.clipboard_clear_task = Some(handle.abort_handle());
+                let title = format!("{}/{key}", app.decoded_secret_name);
+                match value {
+                    ConfigValue::Binary(_) => open_hex_view(app, title),
+                    ConfigValue::Text(text) => {
+                        let text = text.clone();
+                        app.open_pager(title, text);
                     }
-                    Err(e) => app.set_error(format!("Clipboard error: {e}")),
                 }
             }
         }
-        _ => {}
-    }
-}
-
-fn describe_max_scroll(app: &App) -> usize {
-    let visible = crossterm::terminal::size()
-        .map(|(_, h)| ((h as usize) * 90 / 100).saturating_sub(2))
-        .unwrap_or(20);
-    app.describe_content.len().saturating_sub(visible)
-}
-
-fn handle_describe_input(app: &mut App, key: KeyEvent) {
-    let page_size = crossterm::terminal::size()
-        .map(|(_, h)| ((h as usize) * 90 / 100).saturating_sub(2))
-        .unwrap_or(20);
-
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('q') => {
-            app.describe_content.clear();
-            app.mode = AppMode::List;
+        KeyCode::Char('h') => {
+            if let Some(decoded) = &app.selected_secret_decoded
+                && let Some((key, _)) = decoded.get(app.secret_scroll)
+            {
+                let title = format!("{}/{key}", app.decoded_secret_name);
+                open_hex_view(app, title);
+            }
         }
-        KeyCode::Char('j') | KeyCode::Down => {
-            let max = describe_max_scroll(app);
-            if app.describe_scroll < max {
-                app.describe_scroll += 1;
+        KeyCode::Char('s') => {
+            if let Some(decoded) = &app.selected_secret_decoded
+                && let Some((key, _)) = decoded.get(app.secret_scroll)
+            {
+                app.export_path_input = format!("./{key}");
+                app.mode = AppMode::SecretSaveRaw;
             }
         }
-        KeyCode::Char('k') | KeyCode::Up => {
-            app.describe_scroll = app.describe_scroll.saturating_sub(1);
+        KeyCode::Char('w') if app.selected_secret_decoded.is_some() => {
+            app.export_path_input = format!("./{}.env", app.decoded_secret_name);
+            app.mode = AppMode::SecretExport;
         }
-        KeyCode::PageDown => {
-            let max = describe_max_scroll(app);
-            app.describe_scroll = (app.describe_scroll + page_size).min(max);
+        KeyCode::Char('e') => {
+            if let Some(owner) = &app.secret_owner {
+                app.pending_action = Some(PendingAction::EditManagedSecret {
+                    owner: format!("{} '{}'", owner.kind, owner.name),
+                });
+                app.mode = AppMode::Confirm;
+            } else {
+                start_secret_value_edit(app);
+            }
         }
-        KeyCode::PageUp => {
-            app.describe_scroll = app.describe_scroll.saturating_sub(page_size);
+        KeyCode::Char('b') => {
+            app.secret_show_base64 = !app.secret_show_base64;
         }
-        KeyCode::Char('G') => {
-            app.describe_scroll = describe_max_scroll(app);
+        KeyCode::Char('c') => {
+            if let Some(decoded) = &app.selected_secret_decoded
+                && let Some((key, value)) = decoded.get(app.secret_scroll)
+            {
+                let (key, value) = match value {
+                    ConfigValue::Text(text) if !app.secret_show_base64 => {
+                        (key.clone(), text.clone())
+                    }
+                    _ => (
+                        format!("{key} (base64)"),
+                        BASE64_STANDARD.encode(value.bytes()),
+                    ),
+                };
+                app.copy_to_clipboard(&format!("'{key}'"), value);
+            }
         }
-        KeyCode::Char('g') => {
-            app.describe_scroll = 0;
+        KeyCode::Char('C') => {
+            if let Some(decoded) = &app.selected_secret_decoded {
+                let text = text_values(decoded);
+                let skipped = decoded.len() - text.len();
+                if text.is_empty() {
+                    app.set_error("No text values to copy".to_string());
+                    return;
+                }
+                let label = if skipped > 0 {
+                    format!("{} exports ({skipped} binary skipped)", text.len())
+                } else {
+                    format!("{} exports", text.len())
+                };
+                app.copy_to_clipboard(&label, crate::utils::format_shell_exports(&text));
+            }
         }
         _ => {}
     }
 }
 
-fn handle_status_filter_input(app: &mut App, key: KeyEvent) {
-    let len = app.status_filter_items.len();
+fn handle_split_input(app: &mut App, key: KeyEvent) {
     match key.code {
         KeyCode::Esc => {
-            app.mode = AppMode::List;
+            app.pop_mode();
         }
         KeyCode::Enter => {
-            let selected = if app.status_filter_selected.is_empty() {
-                app.status_filter_state
-                    .selected()
-                    .into_iter()
-                    .collect::<HashSet<_>>()
-            } else {
-                app.status_filter_selected.clone()
-            };
-            if selected.len() == app.status_filter_items.len() {
-                app.status_filter.clear();
-            } else {
-                app.status_filter = selected
-                    .iter()
-                    .filter_map(|&i| {
-                        app.status_filter_items
-                            .get(i)
-                            .map(|(phase, _)| phase.clone())
-                    })
-                    .collect();
+            app.pop_mode();
+            let target = app.split_input.clone();
+            app.request_split(&target);
+        }
+        KeyCode::Backspace => {
+            app.split_input.pop();
+        }
+        KeyCode::Char(c) => {
+            app.split_input.push(c);
+        }
+        _ => {}
+    }
+}
+
+fn handle_secret_usage_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.secret_usage.clear();
+            app.pop_mode();
+        }
+        KeyCode::Char('j') | KeyCode::Down
+            if app.secret_usage_scroll < app.secret_usage.len().saturating_sub(1) =>
+        {
+            app.secret_usage_scroll += 1;
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.secret_usage_scroll = app.secret_usage_scroll.saturating_sub(1);
+        }
+        _ => {}
+    }
+}
+
+fn handle_command_input(app: &mut App, key: KeyEvent) {
+    let history_len = app.app_state.command_history.len();
+    match key.code {
+        KeyCode::Esc => {
+            app.command_input.clear();
+            app.pop_mode();
+        }
+        KeyCode::Enter => {
+            let input = app.command_input.trim().to_string();
+            app.command_input.clear();
+            app.pop_mode();
+            if input.is_empty() {
+                return;
+            }
+            match crate::command::parse_command(&input) {
+                Ok(command) => {
+                    app.app_state.record_command(&input);
+                    app.app_state.save();
+                    run_command(app, command);
+                }
+                Err(e) => app.set_error(e),
             }
-            app.update_filter();
-            app.mode = AppMode::List;
         }
-        KeyCode::Char(' ') => {
-            if let Some(i) = app.status_filter_state.selected()
-                && !app.status_filter_selected.remove(&i)
-            {
-                app.status_filter_selected.insert(i);
+        KeyCode::Tab => {
+            if let Some(completed) = crate::command::complete(
+                &app.command_input,
+                &app.available_namespaces,
+                &app.available_contexts,
+            ) {
+                app.command_input = completed;
             }
         }
-        KeyCode::Char('a') => {
-            if app.status_filter_selected.len() == len {
-                app.status_filter_selected.clear();
+        KeyCode::Up if history_len > 0 => {
+            let pos = app
+                .command_history_pos
+                .map_or(history_len - 1, |p| p.saturating_sub(1));
+            app.command_history_pos = Some(pos);
+            app.command_input = app.app_state.command_history[pos].clone();
+        }
+        KeyCode::Down => match app.command_history_pos {
+            Some(p) if p + 1 < history_len => {
+                app.command_history_pos = Some(p + 1);
+                app.command_input = app.app_state.command_history[p + 1].clone();
+            }
+            Some(_) => {
+                app.command_history_pos = None;
+                app.command_input.clear();
+            }
+            None => {}
+        },
+        KeyCode::Backspace if app.command_input.is_empty() => {
+            app.pop_mode();
+        }
+        KeyCode::Backspace => {
+            app.command_input.pop();
+        }
+        KeyCode::Char(c) => {
+            app.command_input.push(c);
+        }
+        _ => {}
+    }
+}
+
+fn run_command(app: &mut App, command: crate::command::Command) {
+    use crate::command::Command;
+    match command {
+        Command::Quit => app.should_quit = true,
+        Command::Help => {
+            app.open_pager("Keybindings".to_string(), crate::keymap::help_text());
+        }
+        Command::Tab(tab) => app.select_tab(tab),
+        Command::Audit => app.open_audit_log(),
+        Command::Apply(path) => app.start_manifest_apply(path),
+        Command::Export(path) => app.export_table(path),
+        Command::Login(ctx) => {
+            let ctx = ctx.unwrap_or_else(|| app.current_context.clone());
+            if app.available_contexts.contains(&ctx) {
+                app.tsh_login(ctx);
             } else {
-                app.status_filter_selected = (0..len).collect();
+                app.set_error(format!("Unknown context '{ctx}'"));
             }
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            let i = app
-                .status_filter_state
-                .selected()
-                .map(|i| i.saturating_sub(1))
-                .unwrap_or(0);
-            app.status_filter_state.select(Some(i));
+        Command::Namespace(Some(ns)) => {
+            if is_valid_k8s_name(&ns) {
+                app.switch_namespace(ns);
+            } else {
+                app.set_error(format!("Invalid namespace name '{ns}'"));
+            }
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if len > 0 {
-                let i = app
-                    .status_filter_state
-                    .selected()
-                    .map(|i| (i + 1).min(len.saturating_sub(1)))
-                    .unwrap_or(0);
-                app.status_filter_state.select(Some(i));
+        Command::Namespace(None) => {
+            handle_global_input(app, KeyEvent::from(KeyCode::Char('n')));
+        }
+        Command::Context(Some(ctx)) => {
+            if app.available_contexts.contains(&ctx) {
+                if ctx != app.current_context {
+                    app.pending_context = Some(ctx);
+                }
+            } else {
+                app.set_error(format!("Unknown context '{ctx}'"));
             }
         }
+        Command::Context(None) => {
+            handle_global_input(app, KeyEvent::from(KeyCode::Char('c')));
+        }
+    }
+}
+
+fn handle_namespace_summary_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.namespace_summary = None;
+            app.pop_mode();
+        }
+        KeyCode::Char('r') => app.load_namespace_summary(),
         _ => {}
     }
 }
 
-fn handle_shell_input(app: &mut App, key: KeyEvent) {
-    use std::io::Write;
+fn handle_metrics_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.metrics = None;
+            app.pop_mode();
+        }
+        KeyCode::Char('r') => app.refresh_metrics(),
+        _ => {}
+    }
+}
 
-    if key.code == KeyCode::Char('q') && key.modifiers.contains(KeyModifiers::CONTROL) {
-        app.shell_session = None;
-        app.mode = AppMode::List;
-        return;
+fn handle_access_review_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.access_checks.clear();
+            app.pop_mode();
+        }
+        KeyCode::Char('r') => app.review_access(),
+        _ => {}
     }
+}
 
-    let bytes = key_to_pty_bytes(key);
-    if !bytes.is_empty()
-        && let Some(session) = &mut app.shell_session
-    {
-        let _ = session.writer.write_all(&bytes);
+fn handle_configmap_view_input(app: &mut App, key: KeyEvent) {
+    let len = app.configmap_entries.as_ref().map_or(0, |e| e.len());
+    let value_lines = app
+        .configmap_entries
+        .as_ref()
+        .and_then(|e| e.get(app.configmap_scroll))
+        .map_or(0, |(_, v)| match v {
+            ConfigValue::Text(text) => text.lines().count(),
+            ConfigValue::Binary(_) => 1,
+        });
+    let max_value_scroll = value_lines.saturating_sub(1).min(u16::MAX as usize) as u16;
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.pop_mode();
+            app.configmap_entries = None;
+        }
+        KeyCode::Char('j') | KeyCode::Down if app.configmap_scroll < len.saturating_sub(1) => {
+            app.configmap_scroll += 1;
+            app.configmap_value_scroll = 0;
+        }
+        KeyCode::Char('k') | KeyCode::Up if app.configmap_scroll > 0 => {
+            app.configmap_scroll -= 1;
+            app.configmap_value_scroll = 0;
+        }
+        KeyCode::Enter | KeyCode::Char('v') => {
+            if let Some(entries) = &app.configmap_entries
+                && let Some((key, ConfigValue::Text(text))) = entries.get(app.configmap_scroll)
+            {
+                let title = format!("{}/{key}", app.configmap_name);
+                let text = text.clone();
+                app.open_pager(title, text);
+            }
+        }
+        KeyCode::Char('J') => {
+            app.configmap_value_scroll = (app.configmap_value_scroll + 1).min(max_value_scroll);
+        }
+        KeyCode::Char('K') => {
+            app.configmap_value_scroll = app.configmap_value_scroll.saturating_sub(1);
+        }
+        KeyCode::PageDown => {
+            app.configmap_value_scroll = app
+                .configmap_value_scroll
+                .saturating_add(10)
+                .min(max_value_scroll);
+        }
+        KeyCode::PageUp => {
+            app.configmap_value_scroll = app.configmap_value_scroll.saturating_sub(10);
+        }
+        KeyCode::Char('c') => {
+            if let Some(entries) = &app.configmap_entries
+                && let Some((key, value)) = entries.get(app.configmap_scroll)
+            {
+                let (label, text) = match value {
+                    ConfigValue::Text(text) => (format!("'{key}'"), text.clone()),
+                    ConfigValue::Binary(bytes) => {
+                        (format!("'{key}' (base64)"), BASE64_STANDARD.encode(bytes))
+                    }
+                };
+                app.copy_to_clipboard(&label, text);
+            }
+        }
+        _ => {}
     }
 }
 
-fn key_to_pty_bytes(key: KeyEvent) -> Vec<u8> {
-    let has_alt = key.modifiers.contains(KeyModifiers::ALT);
+fn handle_pager_input(app: &mut App, key: KeyEvent) {
+    let (width, height) = app.viewport.pager;
+    let rows = crate::utils::wrap_rows(&app.pager_text, width).len();
+    let max = rows.saturating_sub(height);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.pager_text.clear();
+            app.pop_mode();
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.pager_scroll = (app.pager_scroll + 1).min(max);
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.pager_scroll = app.pager_scroll.saturating_sub(1);
+        }
+        KeyCode::PageDown | KeyCode::Char(' ') => {
+            app.pager_scroll = (app.pager_scroll + height).min(max);
+        }
+        KeyCode::PageUp => {
+            app.pager_scroll = app.pager_scroll.saturating_sub(height);
+        }
+        KeyCode::Char('g') => app.pager_scroll = 0,
+        KeyCode::Char('G') => app.pager_scroll = max,
+        KeyCode::Char('/') => {
+            app.pager_search_input.clear();
+            app.mode = AppMode::ValuePagerSearch;
+        }
+        KeyCode::Char('n') => app.pager_search(width, true, false),
+        KeyCode::Char('N') => app.pager_search(width, false, false),
+        KeyCode::Char('c') => {
+            let label = format!("'{}'", app.pager_title);
+            let text = app.pager_text.clone();
+            app.copy_to_clipboard(&label, text);
+        }
+        KeyCode::Char('|') => {
+            app.pending_editor = Some(EditorRequest::Pager(app.pager_text.clone()));
+        }
+        _ => {}
+    }
+}
 
-    if key.modifiers.contains(KeyModifiers::CONTROL)
-        && let KeyCode::Char(c) = key.code
-    {
-        let code = (c as u8).wrapping_sub(b'a').wrapping_add(1);
-        if has_alt {
-            return vec![0x1b, code];
+fn handle_edit_diff_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('y') => {
+            if let Some(edit) = &app.resource_edit {
+                let action = PendingAction::ApplyEdit {
+                    kind: edit.kind,
+                    name: edit.name.clone(),
+                    namespace: edit.namespace.clone(),
+                };
+                app.write(action);
+            }
         }
-        return vec![code];
+        KeyCode::Char('e') => {
+            app.pager_text.clear();
+            app.pop_mode();
+            if let Some(edit) = app.resource_edit.take() {
+                app.pending_editor = Some(EditorRequest::Resource(edit));
+            }
+        }
+        KeyCode::Esc | KeyCode::Char('q') => {
+            if let Some(edit) = app.resource_edit.take() {
+                app.set_success(format!("Edit of '{}' discarded", edit.name));
+            }
+            app.pager_text.clear();
+            app.pop_mode();
+        }
+        KeyCode::Char('/' | 'n' | 'N' | 'c') => {}
+        _ => handle_pager_input(app, key),
     }
+}
 
-    if has_alt && let KeyCode::Char(c) = key.code {
-        let mut bytes = vec![0x1b];
-        let mut buf = [0u8; 4];
-        c.encode_utf8(&mut buf);
-        bytes.extend_from_slice(&buf[..c.len_utf8()]);
-        return bytes;
+fn handle_apply_diff_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('y') => {
+            let mut namespaces: Vec<String> = app
+                .manifest_plan
+                .iter()
+                .filter_map(|item| item.namespace.clone())
+                .collect();
+            namespaces.dedup();
+            app.write(PendingAction::ApplyManifest {
+                count: app.manifest_plan.len(),
+                namespaces,
+            });
+        }
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.manifest_plan.clear();
+            app.pager_text.clear();
+            app.pop_mode();
+            app.set_success("Apply discarded".to_string());
+        }
+        KeyCode::Char('/' | 'n' | 'N' | 'c') => {}
+        _ => handle_pager_input(app, key),
     }
+}
 
+fn handle_pager_search_input(app: &mut App, key: KeyEvent) {
     match key.code {
+        KeyCode::Enter => {
+            app.pager_query = app.pager_search_input.to_ascii_lowercase();
+            app.mode = AppMode::ValuePager;
+            let (width, _) = app.viewport.pager;
+            app.pager_search(width, true, true);
+        }
+        KeyCode::Esc => {
+            app.pager_search_input.clear();
+            app.mode = AppMode::ValuePager;
+        }
+        KeyCode::Backspace => {
+            app.pager_search_input.pop();
+        }
         KeyCode::Char(c) => {
-            let mut buf = [0u8; 4];
-            c.encode_utf8(&mut buf);
-            buf[..c.len_utf8()].to_vec()
+            app.pager_search_input.push(c);
         }
-        KeyCode::Enter => vec![b'\r'],
-        KeyCode::Backspace => vec![0x7f],
-        KeyCode::Tab => vec![b'\t'],
-        KeyCode::Esc => vec![0x1b],
-        KeyCode::Up => b"\x1b[A".to_vec(),
-        KeyCode::Down => b"\x1b[B".to_vec(),
-        KeyCode::Right => b"\x1b[C".to_vec(),
-        KeyCode::Left => b"\x1b[D".to_vec(),
-        KeyCode::Home => b"\x1b[H".to_vec(),
-        KeyCode::End => b"\x1b[F".to_vec(),
-        KeyCode::Delete => b"\x1b[3~".to_vec(),
-        KeyCode::PageUp => b"\x1b[5~".to_vec(),
-        KeyCode::PageDown => b"\x1b[6~".to_vec(),
-        _ => vec![],
+        _ => {}
     }
 }
 
-fn handle_scale_input(app: &mut App, key: KeyEvent) {
+fn handle_secret_create_input(app: &mut App, key: KeyEvent) {
+    let form = &mut app.secret_form;
     match key.code {
         KeyCode::Esc => {
-            app.mode = AppMode::List;
+            app.pop_mode();
         }
-        KeyCode::Enter => {
-            if app.scale_input.is_empty() {
-                app.set_error("Enter a replica count".to_string());
-                return;
+        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            submit_secret_form(app);
+        }
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            form.entries.pop();
+        }
+        KeyCode::Tab | KeyCode::Down => form.field = form.field.next(),
+        KeyCode::BackTab | KeyCode::Up => form.field = form.field.prev(),
+        KeyCode::Enter => match form.field {
+            SecretFormField::Name | SecretFormField::Type => form.field = form.field.next(),
+            SecretFormField::Key => {
+                if !form.key.is_empty() {
+                    form.field = SecretFormField::Value;
+                }
             }
-            if let Ok(replicas) = app.scale_input.parse::<u32>() {
-                if replicas > 1000 {
-                    app.set_error("Replica count must be <= 1000".to_string());
-                } else if let Some(res) = app.get_selected_resource() {
-                    let name = res.name().to_owned();
-                    app.pending_action = Some(PendingAction::ScaleDeployment { name, replicas });
-                    app.mode = AppMode::Confirm;
+            SecretFormField::Value => {
+                let key = form.key.trim().to_string();
+                if !crate::utils::is_valid_data_key(&key) {
+                    app.set_error(format!("Invalid key '{key}'"));
                     return;
                 }
+                let value = std::mem::take(&mut form.value);
+                form.upsert(key, value);
+                form.key.clear();
+                form.field = SecretFormField::Key;
+            }
+            SecretFormField::EnvFile => {
+                let path = crate::utils::expand_home(form.env_file.trim());
+                let parsed = std::fs::read_to_string(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|text| crate::utils::parse_env_file(&text));
+                match parsed {
+                    Ok(entries) => {
+                        let count = entries.len();
+                        for (k, v) in entries {
+                            form.upsert(k, v);
+                        }
+                        form.env_file.clear();
+                        app.set_success(format!("Loaded {count} key(s) from {}", path.display()));
+                    }
+                    Err(e) => app.set_error(format!("Failed to load {}: {e}", path.display())),
+                }
+            }
+        },
+        KeyCode::Backspace => {
+            form.input_mut().pop();
+        }
+        KeyCode::Char(c) => {
+            form.input_mut().push(c);
+        }
+        _ => {}
+    }
+}
+
+fn submit_secret_form(app: &mut App) {
+    let form = &mut app.secret_form;
+    if !form.key.is_empty() {
+        let key = form.key.trim().to_string();
+        if !crate::utils::is_valid_data_key(&key) {
+            app.set_error(format!("Invalid key '{key}'"));
+            return;
+        }
+        let value = std::mem::take(&mut form.value);
+        form.upsert(key, value);
+        form.key.clear();
+    }
+    let name = form.name.trim().to_string();
+    if !is_valid_dns_subdomain(&name) {
+        app.set_error("Invalid secret name (lowercase, digits, '-', '.')".to_string());
+        return;
+    }
+    let type_ = match form.type_.trim() {
+        "" => "Opaque".to_string(),
+        t => t.to_string(),
+    };
+    let entries = form.entries.clone();
+    app.pop_mode();
+    app.write(PendingAction::CreateSecret {
+        name,
+        type_,
+        entries,
+    });
+}
+
+fn handle_secret_edit_input(app: &mut App, key: KeyEvent) {
+    let Some((name, original)) = app
+        .selected_secret_decoded
+        .as_ref()
+        .and_then(|d| d.get(app.secret_scroll))
+        .cloned()
+    else {
+        app.mode = AppMode::SecretDecode;
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => {
+            app.secret_edit_input.clear();
+            app.mode = AppMode::SecretDecode;
+        }
+        KeyCode::Enter => {
+            let value = std::mem::take(&mut app.secret_edit_input);
+            app.mode = AppMode::SecretDecode;
+            if original.text() == Some(value.as_str()) {
+                app.set_success(format!("'{name}' unchanged"));
             } else {
-                app.set_error("Invalid number".to_string());
+                app.write(PendingAction::PatchSecretKey {
+                    secret: app.decoded_secret_name.clone(),
+                    key: name,
+                    value,
+                });
             }
-            app.mode = AppMode::List;
+        }
+        KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.pending_editor = Some(EditorRequest::SecretValue {
+                secret: app.decoded_secret_name.clone(),
+                key: name,
+                value: std::mem::take(&mut app.secret_edit_input),
+            });
+            app.mode = AppMode::SecretDecode;
         }
         KeyCode::Backspace => {
-            app.scale_input.pop();
+            app.secret_edit_input.pop();
         }
-        KeyCode::Char(c) if c.is_ascii_digit() => {
-            app.scale_input.push(c);
+        KeyCode::Char(c) => {
+            app.secret_edit_input.push(c);
         }
         _ => {}
     }
 }
 
-fn handle_confirm_input(app: &mut App, key: KeyEvent) {
-    match key.code {
-        KeyCode::Char('y') | KeyCode::Char('Y') => {
-            if let Some(action) = app.pending_action.take() {
-                match action {
-                    PendingAction::DeleteResource { .. } => {
-                        let indices: Vec<usize> = if app.selected_indices.is_empty() {
-                            app.table_state.selected().into_iter().collect()
-                        } else {
-                            let mut v: Vec<usize> = app.selected_indices.iter().copied().collect();
-                            v.sort_unstable();
-                            v
-                        };
-                        for idx in indices {
-                            if let Some(item) = app.filtered_items.get(idx).cloned() {
-                                let client = app.client.clone();
-                                let ns = app.current_namespace.clone();
-                                let tx = app.event_tx.clone();
-                                match item {
-                                    KubeResource::Pod(p) => {
-                                        let name = p.metadata.name.clone().unwrap_or_default();
-                                        tokio::spawn(async move {
-                                            let result =
-                                                crate::k8s::actions::delete_pod(client, &ns, &name)
-                                                    .await;
-                                            let _ = tx.send(match result {
-                                                Ok(()) => KubeResourceEvent::Success(format!(
-                                                    "Pod '{name}' deleted"
-                                                )),
-                                                Err(e) => KubeResourceEvent::Error(format!(
-                                                    "Delete '{name}' failed: {e}"
-                                                )),
-                                            });
-                                        });
-                                    }
-                                    KubeResource::Deployment(d) => {
-                                        let name = d.metadata.name.clone().unwrap_or_default();
-                                        tokio::spawn(async move {
-                                            let result = crate::k8s::actions::delete_deployment(
-                                                client, &ns, &name,
-                                            )
-                                            .await;
-                                            let _ = tx.send(match result {
-                                                Ok(()) => KubeResourceEvent::Success(format!(
-                                                    "Deployment '{name}' deleted"
-                                                )),
-                                                Err(e) => KubeResourceEvent::Error(format!(
-                                                    "Delete '{name}' failed: {e}"
-                                                )),
-                                            });
-                                        });
-                                    }
-                                    KubeResource::Secret(_) => {}
-                                }
-                            }
-                        }
-                    }
-                    PendingAction::RestartDeployment { name } => {
-                        let client = app.client.clone();
-                        let ns = app.current_namespace.clone();
-                        let tx = app.event_tx.clone();
-                        tokio::spawn(async move {
-                            let result =
-                                crate::k8s::actions::rollout_restart(client, &ns, &name).await;
-                            let _ = tx.send(match result {
-                                Ok(()) => {
-                                    KubeResourceEvent::Success(format!("Rollout restart: '{name}'"))
-                                }
-                                Err(e) => KubeResourceEvent::Error(format!(
-                                    "Restart '{name}' failed: {e}"
-                                )),
-                            });
-                        });
-                    }
-                    PendingAction::ScaleDeployment { name, replicas } => {
-                        let client = app.client.clone();
-                        let ns = app.current_namespace.clone();
-                        let tx = app.event_tx.clone();
-                        tokio::spawn(async move {
-                            let result =
-                                crate::k8s::actions::scale_deployment(client, &ns, &name, replicas)
-                                    .await;
-                            let _ = tx.send(match result {
-                                Ok(()) => KubeResourceEvent::Success(format!(
-                                    "'{name}' scaled to {replicas} replicas"
-                                )),
-                                Err(e) => {
-                                    KubeResourceEvent::Error(format!("Scale '{name}' failed: {e}"))
-                                }
-                            });
-                        });
-                    }
+fn start_secret_value_edit(app: &mut App) {
+    if let Some(decoded) = &app.selected_secret_decoded
+        && let Some((key, value)) = decoded.get(app.secret_scroll)
+    {
+        match value {
+            ConfigValue::Binary(_) => {
+                app.set_error(format!(
+                    "'{key}' holds binary data and can't be edited here"
+                ));
+            }
+            ConfigValue::Text(text) if text.contains('\n') => {
+                app.pending_editor = Some(EditorRequest::SecretValue {
+                    secret: app.decoded_secret_name.clone(),
+                    key: key.clone(),
+                    value: text.clone(),
+                });
+            }
+            ConfigValue::Text(text) => {
+                app.secret_edit_input.clone_from(text);
+                app.mode = AppMode::SecretEditValue;
+            }
+        }
+    }
+}
+
+/// Key and value pairs of the secret's text values.
+fn text_values(decoded: &[(String, ConfigValue)]) -> Vec<(String, String)> {
+    decoded
+        .iter()
+        .filter_map(|(k, v)| Some((k.clone(), v.text()?.to_string())))
+        .collect()
+}
+
+fn open_hex_view(app: &mut App, title: String) {
+    let Some((_, value)) = app
+        .selected_secret_decoded
+        .as_ref()
+        .and_then(|d| d.get(app.secret_scroll))
+    else {
+        return;
+    };
+    let title = format!("{title} (hex, {} bytes)", value.bytes().len());
+    let dump = crate::utils::hex_dump(value.bytes());
+    app.open_pager(title, dump);
+}
+
+fn handle_secret_save_raw_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.mode = AppMode::SecretDecode;
+        }
+        KeyCode::Enter => {
+            let path = crate::utils::expand_home(app.export_path_input.trim());
+            let written = app
+                .selected_secret_decoded
+                .as_ref()
+                .and_then(|d| d.get(app.secret_scroll))
+                .map(|(_, v)| {
+                    crate::utils::write_private_file(&path, v.bytes()).map(|()| v.bytes().len())
+                });
+            match written {
+                Some(Ok(len)) => {
+                    app.set_success(format!("Wrote {len} bytes to {}", path.display()))
                 }
-                app.selected_indices.clear();
+                Some(Err(e)) => app.set_error(format!("Save failed: {e}")),
+                None => {}
             }
-            app.mode = AppMode::List;
+            app.mode = AppMode::SecretDecode;
         }
-        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-            app.selected_indices.clear();
-            app.pending_action = None;
-            app.mode = AppMode::List;
+        KeyCode::Backspace => {
+            app.export_path_input.pop();
+        }
+        KeyCode::Char(c) => {
+            app.export_path_input.push(c);
+        }
+        _ => {}
+    }
+}
+
+fn handle_secret_export_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.mode = AppMode::SecretDecode;
+        }
+        KeyCode::Enter => {
+            let path = crate::utils::expand_home(app.export_path_input.trim());
+            let decoded = app.selected_secret_decoded.as_deref().unwrap_or_default();
+            let entries = text_values(decoded);
+            let binary = decoded.len() - entries.len();
+            let text = crate::utils::format_env_file(&entries);
+            match crate::utils::write_private_file(&path, text.as_bytes()) {
+                Ok(()) if binary == 0 => {
+                    app.set_success(format!(
+                        "Wrote {} key(s) to {}",
+                        entries.len(),
+                        path.display()
+                    ));
+                }
+                Ok(()) => {
+                    app.set_success(format!(
+                        "Wrote {} key(s) to {} (skipped {} binary)",
+                        entries.len(),
+                        path.display(),
+                        binary
+                    ));
+                }
+                Err(e) => app.set_error(format!("Export failed: {e}")),
+            }
+            app.mode = AppMode::SecretDecode;
+        }
+        KeyCode::Backspace => {
+            app.export_path_input.pop();
+        }
+        KeyCode::Char(c) => {
+            app.export_path_input.push(c);
+        }
+        _ => {}
+    }
+}
+
+fn handle_secret_diff_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.pop_mode();
+        }
+        KeyCode::Enter => {
+            let target = app.secret_diff_input.trim().to_string();
+            app.pop_mode();
+            app.diff_selected_secret(&target);
+        }
+        KeyCode::Backspace => {
+            app.secret_diff_input.pop();
+        }
+        KeyCode::Char(c) => {
+            app.secret_diff_input.push(c);
+        }
+        _ => {}
+    }
+}
+
+fn handle_secret_diff_view_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.secret_diff.clear();
+            app.pop_mode();
+        }
+        KeyCode::Char('j') | KeyCode::Down
+            if app.secret_scroll < app.secret_diff.len().saturating_sub(1) =>
+        {
+            app.secret_scroll += 1;
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.secret_scroll = app.secret_scroll.saturating_sub(1);
+        }
+        KeyCode::Char('r') => {
+            app.secret_revealed = !app.secret_revealed;
+        }
+        _ => {}
+    }
+}
+
+fn describe_max_scroll(app: &App) -> usize {
+    app.describe_content
+        .len()
+        .saturating_sub(app.viewport.describe_rows)
+}
+
+fn handle_describe_input(app: &mut App, key: KeyEvent) {
+    let page_size = app.viewport.describe_rows;
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.describe_content.clear();
+            app.pop_mode();
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            let max = describe_max_scroll(app);
+            if app.describe_scroll < max {
+                app.describe_scroll += 1;
+            }
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.describe_scroll = app.describe_scroll.saturating_sub(1);
+        }
+        KeyCode::PageDown => {
+            let max = describe_max_scroll(app);
+            app.describe_scroll = (app.describe_scroll + page_size).min(max);
+        }
+        KeyCode::PageUp => {
+            app.describe_scroll = app.describe_scroll.saturating_sub(page_size);
+        }
+        KeyCode::Char('G') => {
+            app.describe_scroll = describe_max_scroll(app);
+        }
+        KeyCode::Char('g') => {
+            app.describe_scroll = 0;
+        }
+        KeyCode::Char('/') => {
+            app.describe_search_input.clear();
+            app.mode = AppMode::DescribeSearchInput;
+        }
+        KeyCode::Char('n') => app.describe_search(true, false),
+        KeyCode::Char('N') => app.describe_search(false, false),
+        KeyCode::Char('a') => {
+            app.describe_auto_refresh = !app.describe_auto_refresh;
+            if app.describe_auto_refresh {
+                app.refresh_describe();
+            }
+        }
+        KeyCode::Char('|') => {
+            app.pending_editor = Some(EditorRequest::Pager(app.describe_content.join("\n")));
+        }
+        _ => {}
+    }
+}
+
+fn handle_describe_search_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Enter => {
+            app.describe_query = app.describe_search_input.to_ascii_lowercase();
+            app.mode = AppMode::DescribeView;
+            app.describe_search(true, true);
+        }
+        KeyCode::Esc => {
+            app.describe_search_input.clear();
+            app.mode = AppMode::DescribeView;
+        }
+        KeyCode::Backspace => {
+            app.describe_search_input.pop();
+        }
+        KeyCode::Char(c) => {
+            app.describe_search_input.push(c);
+        }
+        _ => {}
+    }
+}
+
+/// Runs the chosen action by replaying its list key.
+fn handle_action_menu_input(app: &mut App, key: KeyEvent) {
+    let actions = crate::keymap::resource_actions(app.active_tab);
+    let plugins = app.menu_kubectl_plugins().to_vec();
+    let selected = app.action_menu_state.selected();
+    // Built-in actions first, then the kubectl plugins.
+    let plugin = selected
+        .and_then(|i| i.checked_sub(actions.len()))
+        .and_then(|i| plugins.get(i));
+    let chosen = match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.pop_mode();
+            return;
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            let last = (actions.len() + plugins.len()).saturating_sub(1);
+            let i = selected.map_or(0, |i| i + 1);
+            app.action_menu_state.select(Some(i.min(last)));
+            return;
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            let i = selected.unwrap_or(0);
+            app.action_menu_state.select(Some(i.saturating_sub(1)));
+            return;
+        }
+        KeyCode::Enter => match plugin {
+            Some(plugin) => {
+                app.pop_mode();
+                app.run_kubectl_plugin(plugin);
+                return;
+            }
+            None => selected.and_then(|i| actions.get(i)).map(|(c, _)| *c),
+        },
+        KeyCode::Char('c') => {
+            let action = selected.and_then(|i| actions.get(i));
+            let command = match (plugin, action) {
+                (Some(plugin), _) => app.kubectl_plugin_line(plugin),
+                (None, Some((action, _))) => app.action_kubectl(*action),
+                (None, None) => return,
+            };
+            match command {
+                Some(command) => app.copy_text("kubectl command", command),
+                None => app.set_error(format!(
+                    "No kubectl equivalent for {}",
+                    action.map_or("this entry", |(_, label)| label)
+                )),
+            }
+            return;
+        }
+        KeyCode::Char(c) => actions.iter().find(|(k, _)| *k == c).map(|(k, _)| *k),
+        _ => None,
+    };
+    if let Some(c) = chosen {
+        app.pop_mode();
+        handle_input(app, KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+}
+
+fn handle_manifest_export_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.pop_mode(),
+        KeyCode::Enter => {
+            app.pop_mode();
+            app.export_manifests();
+        }
+        KeyCode::Tab => app.export_clean = !app.export_clean,
+        KeyCode::Backspace => {
+            app.export_path_input.pop();
+        }
+        KeyCode::Char(c) => {
+            app.export_path_input.push(c);
+        }
+        _ => {}
+    }
+}
+
+fn handle_link_menu_input(app: &mut App, key: KeyEvent) {
+    let len = app.config.links(&app.current_context, app.active_tab).len();
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.pop_mode(),
+        KeyCode::Char('j') | KeyCode::Down => {
+            let i = app.link_menu_state.selected().map_or(0, |i| i + 1);
+            app.link_menu_state
+                .select(Some(i.min(len.saturating_sub(1))));
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            let i = app.link_menu_state.selected().unwrap_or(0);
+            app.link_menu_state.select(Some(i.saturating_sub(1)));
+        }
+        KeyCode::Enter => {
+            app.pop_mode();
+            if let Some(i) = app.link_menu_state.selected() {
+                app.open_link(i);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_endpoint_menu_input(app: &mut App, key: KeyEvent) {
+    let selected = app.endpoint_menu_state.selected();
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.pop_mode(),
+        KeyCode::Char('j') | KeyCode::Down => {
+            let i = selected.map_or(0, |i| i + 1);
+            let last = app.endpoints.len().saturating_sub(1);
+            app.endpoint_menu_state.select(Some(i.min(last)));
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            let i = selected.unwrap_or(0);
+            app.endpoint_menu_state.select(Some(i.saturating_sub(1)));
+        }
+        KeyCode::Enter => {
+            if let Some(e) = selected.and_then(|i| app.endpoints.get(i)).cloned() {
+                app.pop_mode();
+                app.open_url(e.url.clone(), e.url);
+            }
+        }
+        KeyCode::Char('c') => {
+            if let Some(e) = selected.and_then(|i| app.endpoints.get(i)).cloned() {
+                app.copy_text("URL", e.url);
+            }
         }
         _ => {}
     }
-}
+}
+
+fn handle_plugin_menu_input(app: &mut App, key: KeyEvent) {
+    let plugins = app.config.plugins(app.active_tab, app.read_only);
+    let chosen = match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.pop_mode();
+            return;
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            let i = app.plugin_menu_state.selected().map_or(0, |i| i + 1);
+            let last = plugins.len().saturating_sub(1);
+            app.plugin_menu_state.select(Some(i.min(last)));
+            return;
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            let i = app.plugin_menu_state.selected().unwrap_or(0);
+            app.plugin_menu_state.select(Some(i.saturating_sub(1)));
+            return;
+        }
+        KeyCode::Enter => app
+            .plugin_menu_state
+            .selected()
+            .and_then(|i| plugins.get(i)),
+        KeyCode::Char(c) => plugins.iter().find(|(_, p)| p.key == Some(c)),
+        _ => None,
+    };
+    let Some(&(index, plugin)) = chosen else {
+        return;
+    };
+    let confirm = plugin.confirm.then(|| plugin.name.clone());
+    app.pop_mode();
+    match confirm {
+        Some(label) => {
+            let target = app
+                .get_selected_resource()
+                .map(|r| r.name().to_owned())
+                .unwrap_or_default();
+            app.pending_action = Some(PendingAction::RunPlugin {
+                index,
+                label,
+                target,
+            });
+            app.push_mode(AppMode::Confirm);
+        }
+        None => app.run_plugin(index),
+    }
+}
+
+fn handle_status_filter_input(app: &mut App, key: KeyEvent) {
+    let len = app.status_filter_items.len();
+    match key.code {
+        KeyCode::Esc => {
+            app.pop_mode();
+        }
+        KeyCode::Enter => {
+            let selected = if app.status_filter_selected.is_empty() {
+                app.status_filter_state
+                    .selected()
+                    .into_iter()
+                    .collect::<HashSet<_>>()
+            } else {
+                app.status_filter_selected.clone()
+            };
+            if selected.len() == app.status_filter_items.len() {
+                app.status_filter.clear();
+            } else {
+                app.status_filter = selected
+                    .iter()
+                    .filter_map(|&i| {
+                        app.status_filter_items
+                            .get(i)
+                            .map(|(phase, _)| phase.clone())
+                    })
+                    .collect();
+            }
+            app.update_filter();
+            app.pop_mode();
+        }
+        KeyCode::Char(' ') => {
+            if let Some(i) = app.status_filter_state.selected()
+                && !app.status_filter_selected.remove(&i)
+            {
+                app.status_filter_selected.insert(i);
+            }
+        }
+        KeyCode::Char('a') => {
+            if app.status_filter_selected.len() == len {
+                app.status_filter_selected.clear();
+            } else {
+                app.status_filter_selected = (0..len).collect();
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            let i = app
+                .status_filter_state
+                .selected()
+                .map(|i| i.saturating_sub(1))
+                .unwrap_or(0);
+            app.status_filter_state.select(Some(i));
+        }
+        KeyCode::Down | KeyCode::Char('j') if len > 0 => {
+            let i = app
+                .status_filter_state
+                .selected()
+                .map(|i| (i + 1).min(len.saturating_sub(1)))
+                .unwrap_or(0);
+            app.status_filter_state.select(Some(i));
+        }
+        _ => {}
+    }
+}
+
+fn handle_shell_input(app: &mut App, key: KeyEvent) {
+    use std::io::Write;
+
+    if key.code == KeyCode::Char('q') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        app.shell_session = None;
+        app.pop_mode();
+        return;
+    }
+
+    let bytes = key_to_pty_bytes(key);
+    if !bytes.is_empty()
+        && let Some(session) = &mut app.shell_session
+    {
+        let _ = session.writer.write_all(&bytes);
+    }
+}
+
+fn key_to_pty_bytes(key: KeyEvent) -> Vec<u8> {
+    let has_alt = key.modifiers.contains(KeyModifiers::ALT);
+
+    if key.modifiers.contains(KeyModifiers::CONTROL)
+        && let KeyCode::Char(c) = key.code
+    {
+        let code = (c as u8).wrapping_sub(b'a').wrapping_add(1);
+        if has_alt {
+            return vec![0x1b, code];
+        }
+        return vec![code];
+    }
+
+    if has_alt && let KeyCode::Char(c) = key.code {
+        let mut bytes = vec![0x1b];
+        let mut buf = [0u8; 4];
+        c.encode_utf8(&mut buf);
+        bytes.extend_from_slice(&buf[..c.len_utf8()]);
+        return bytes;
+    }
+
+    match key.code {
+        KeyCode::Char(c) => {
+            let mut buf = [0u8; 4];
+            c.encode_utf8(&mut buf);
+            buf[..c.len_utf8()].to_vec()
+        }
+        KeyCode::Enter => vec![b'\r'],
+        KeyCode::Backspace => vec![0x7f],
+        KeyCode::Tab => vec![b'\t'],
+        KeyCode::Esc => vec![0x1b],
+        KeyCode::Up => b"\x1b[A".to_vec(),
+        KeyCode::Down => b"\x1b[B".to_vec(),
+        KeyCode::Right => b"\x1b[C".to_vec(),
+        KeyCode::Left => b"\x1b[D".to_vec(),
+        KeyCode::Home => b"\x1b[H".to_vec(),
+        KeyCode::End => b"\x1b[F".to_vec(),
+        KeyCode::Delete => b"\x1b[3~".to_vec(),
+        KeyCode::PageUp => b"\x1b[5~".to_vec(),
+        KeyCode::PageDown => b"\x1b[6~".to_vec(),
+        _ => vec![],
+    }
+}
+
+fn handle_scale_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.pop_mode();
+        }
+        KeyCode::Enter => {
+            if app.scale_input.is_empty() {
+                app.set_error("Enter a replica count".to_string());
+                return;
+            }
+            if let Ok(replicas) = app.scale_input.parse::<u32>() {
+                if replicas > 1000 {
+                    app.set_error("Replica count must be <= 1000".to_string());
+                } else if let Some(res) = app.get_selected_resource() {
+                    let name = res.name().to_owned();
+                    app.pending_action = Some(PendingAction::ScaleDeployment { name, replicas });
+                    app.mode = AppMode::Confirm;
+                    return;
+                }
+            } else {
+                app.set_error("Invalid number".to_string());
+            }
+            app.pop_mode();
+        }
+        KeyCode::Backspace => {
+            app.scale_input.pop();
+        }
+        KeyCode::Char(c) if c.is_ascii_digit() => {
+            app.scale_input.push(c);
+        }
+        _ => {}
+    }
+}
+
+fn handle_confirm_input(app: &mut App, key: KeyEvent) {
+    let copy = match app.typed_confirmation() {
+        Some(_) => key.code == KeyCode::Char('y') && key.modifiers.contains(KeyModifiers::CONTROL),
+        None => key.code == KeyCode::Char('c'),
+    };
+    if copy {
+        let command = app
+            .pending_action
+            .as_ref()
+            .and_then(|a| a.kubectl(&app.current_context, &app.current_namespace));
+        if let Some(command) = command {
+            app.copy_text("kubectl command", command);
+        }
+        return;
+    }
+    // Typed confirmations turn a matching Enter into `y`; every other key
+    // edits the input, so a stray `y` cannot confirm.
+    let key = match app.typed_confirmation() {
+        Some(expected) => match key.code {
+            KeyCode::Esc => key,
+            KeyCode::Enter if app.confirm_input == expected => {
+                KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE)
+            }
+            KeyCode::Backspace => {
+                app.confirm_input.pop();
+                return;
+            }
+            KeyCode::Char(c) => {
+                app.confirm_input.push(c);
+                return;
+            }
+            _ => return,
+        },
+        None => key,
+    };
+    handle_confirm_key(app, key);
+    if app.mode != AppMode::Confirm {
+        app.confirm_input.clear();
+    }
+}
+
+/// Deletes pods or deployments, reporting several together once all finish.
+fn delete_resources(app: &mut App, mut items: Vec<KubeResource>, kind: &'static str) {
+    items.retain(|i| matches!(i, KubeResource::Pod(_) | KubeResource::Deployment(_)));
+    let object = |app: &App, item: &KubeResource| match item {
+        KubeResource::Deployment(_) => app.object_key("deployment", item.name()),
+        _ => app.object_key("pod", item.name()),
+    };
+    let before = items.len();
+    items.retain(|i| app.tasks.in_flight("delete", &object(app, i)).is_none());
+    if items.len() < before {
+        app.set_error(format!(
+            "Skipped {} already being deleted",
+            before - items.len()
+        ));
+    }
+    if items.is_empty() {
+        return;
+    }
+    let objects = items.iter().map(|i| object(app, i)).collect();
+    let audits: Vec<_> = items
+        .iter()
+        .map(|item| {
+            let kind = match item {
+                KubeResource::Deployment(_) => "deployment",
+                _ => "pod",
+            };
+            app.audit("delete", format!("{kind}/{}", item.name()))
+        })
+        .collect();
+    let client = app.client.clone();
+    let ns = app.current_namespace.clone();
+    let tx = app.event_tx.clone();
+    let label = match items.as_slice() {
+        [item] => format!("delete {}", item.name()),
+        _ => format!("delete {} {kind}", items.len()),
+    };
+    app.mutate(label, "delete", objects, async move {
+        let deletes = items.iter().zip(audits).map(|(item, audit)| {
+            let client = client.clone();
+            let ns = &ns;
+            async move {
+                let name = item.name().to_string();
+                let result = if let KubeResource::Deployment(_) = item {
+                    crate::k8s::actions::delete_deployment(client, ns, &name).await
+                } else {
+                    crate::k8s::actions::delete_pod(client, ns, &name).await
+                };
+                audit.record(&result);
+                (name, result.map_err(|e| e.to_string()))
+            }
+        });
+        let mut results = futures::future::join_all(deletes).await;
+        let event = match results.len() {
+            1 => match results.remove(0) {
+                (name, Ok(())) => KubeResourceEvent::Success(match items[0] {
+                    KubeResource::Deployment(_) => format!("Deployment '{name}' deleted"),
+                    _ => format!("Pod '{name}' deleted"),
+                }),
+                (name, Err(e)) => KubeResourceEvent::Error(format!("Delete '{name}' failed: {e}")),
+            },
+            _ => KubeResourceEvent::BulkResult(format!("Delete {kind}"), results),
+        };
+        let _ = tx.send(event);
+    });
+}
+
+fn handle_confirm_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Char('Y')
+            if matches!(
+                app.pending_action,
+                Some(PendingAction::EditManagedSecret { .. })
+            ) =>
+        {
+            app.pending_action = None;
+            app.mode = AppMode::SecretDecode;
+            start_secret_value_edit(app);
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc
+            if matches!(
+                app.pending_action,
+                Some(PendingAction::EditManagedSecret { .. })
+            ) =>
+        {
+            app.pending_action = None;
+            app.mode = AppMode::SecretDecode;
+        }
+        KeyCode::Char('y') | KeyCode::Char('Y')
+            if matches!(
+                app.pending_action,
+                Some(PendingAction::DeleteNamespace { .. })
+            ) =>
+        {
+            if let Some(PendingAction::DeleteNamespace { name }) = app.pending_action.take() {
+                let client = app.client.clone();
+                let tx = app.event_tx.clone();
+                let object = app.object_key("namespace", &name);
+                let audit = app.audit("delete", format!("namespace/{name}"));
+                app.mutate(
+                    format!("delete namespace {name}"),
+                    "delete",
+                    vec![object],
+                    async move {
+                        let result = crate::k8s::actions::delete_namespace(client, &name).await;
+                        audit.record(&result);
+                        let _ = tx.send(match result {
+                            Ok(()) => KubeResourceEvent::NamespaceDeleted(name),
+                            Err(e) => KubeResourceEvent::Error(format!(
+                                "Delete namespace '{name}' failed: {e}"
+                            )),
+                        });
+                    },
+                );
+            }
+            app.mode = AppMode::NamespaceSelect;
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc
+            if matches!(
+                app.pending_action,
+                Some(PendingAction::DeleteNamespace { .. })
+            ) =>
+        {
+            app.pending_action = None;
+            app.mode = AppMode::NamespaceSelect;
+        }
+        KeyCode::Char('y') | KeyCode::Char('Y') => {
+            if let Some(action) = app.pending_action.take() {
+                match action {
+                    PendingAction::DeleteResource { kind, .. } => {
+                        let indices: Vec<usize> = if app.selected_indices.is_empty() {
+                            app.table_state.selected().into_iter().collect()
+                        } else {
+                            let mut v: Vec<usize> = app.selected_indices.iter().copied().collect();
+                            v.sort_unstable();
+                            v
+                        };
+                        let items = indices
+                            .into_iter()
+                            .filter_map(|i| app.filtered_items.get(i).cloned())
+                            .collect();
+                        delete_resources(app, items, kind);
+                    }
+                    PendingAction::RestartDeployment { name } => {
+                        let client = app.client.clone();
+                        let ns = app.current_namespace.clone();
+                        let tx = app.event_tx.clone();
+                        let object = app.object_key("deployment", &name);
+                        let audit = app.audit("restart", format!("deployment/{name}"));
+                        app.mutate(
+                            format!("restart {name}"),
+                            "restart",
+                            vec![object],
+                            async move {
+                                let result =
+                                    crate::k8s::actions::rollout_restart(client, &ns, &name).await;
+                                audit.record(&result);
+                                let _ = tx.send(match result {
+                                    Ok(()) => KubeResourceEvent::Success(format!(
+                                        "Rollout restart: '{name}'"
+                                    )),
+                                    Err(e) => KubeResourceEvent::Error(format!(
+                                        "Restart '{name}' failed: {e}"
+                                    )),
+                                });
+                            },
+                        );
+                    }
+                    PendingAction::ScaleDeployment { name, replicas } => {
+                        let client = app.client.clone();
+                        let ns = app.current_namespace.clone();
+                        let tx = app.event_tx.clone();
+                        let object = app.object_key("deployment", &name);
+                        let audit = app.audit("scale", format!("deployment/{name} to {replicas}"));
+                        // A later scale of the same deployment waits for
+                        // this one rather than being refused.
+                        app.mutate(
+                            format!("scale {name} to {replicas}"),
+                            format!("scale {replicas}"),
+                            vec![object],
+                            async move {
+                                let result = crate::k8s::actions::scale_deployment(
+                                    client, &ns, &name, replicas,
+                                )
+                                .await;
+                                audit.record(&result);
+                                let _ = tx.send(match result {
+                                    Ok(()) => KubeResourceEvent::Success(format!(
+                                        "'{name}' scaled to {replicas} replicas"
+                                    )),
+                                    Err(e) => KubeResourceEvent::Error(format!(
+                                        "Scale '{name}' failed: {e}"
+                                    )),
+                                });
+                            },
+                        );
+                    }
+                    PendingAction::RunPlugin { index, .. } => {
+                        app.pop_mode();
+                        app.run_plugin(index);
+                        return;
+                    }
+                    action @ (PendingAction::CreateSecret { .. }
+                    | PendingAction::PatchSecretKey { .. }
+                    | PendingAction::CreateNamespace { .. }
+                    | PendingAction::ApplyEdit { .. }
+                    | PendingAction::ApplyManifest { .. }) => {
+                        app.pop_mode();
+                        app.run_write(action);
+                        return;
+                    }
+                    // Handled by the dedicated arms above.
+                    PendingAction::EditManagedSecret { .. }
+                    | PendingAction::DeleteNamespace { .. } => {}
+                }
+                app.selected_indices.clear();
+                app.visual_anchor = None;
+            }
+            app.pop_mode();
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+            app.selected_indices.clear();
+            app.visual_anchor = None;
+            app.pending_action = None;
+            app.pop_mode();
+        }
+        _ => {}
+    }
+}
+
+fn next_row(app: &mut App) {
+    let len = app.filtered_items.len();
+    if len == 0 {
+        return;
+    }
+    let i = match app.table_state.selected() {
+        Some(i) => (i + 1) % len,
+        None => 0,
+    };
+    app.table_state.select(Some(i));
+}
+
+fn prev_row(app: &mut App) {
+    let len = app.filtered_items.len();
+    if len == 0 {
+        return;
+    }
+    let i = match app.table_state.selected() {
+        Some(i) => {
+            if i == 0 {
+                len - 1
+            } else {
+                i - 1
+            }
+        }
+        None => len - 1,
+    };
+    app.table_state.select(Some(i));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::App;
+    use crate::models::{
+        AppMode, EditorRequest, KubeResource, PendingAction, ResourceType, SecretFormField,
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
+    use k8s_openapi::api::core::v1::Pod;
+    use std::sync::Arc;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    fn text(value: &str) -> ConfigValue {
+        ConfigValue::Text(value.to_string())
+    }
+
+    fn key_with_mod(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    fn make_pod(name: &str) -> KubeResource {
+        let mut pod = Pod::default();
+        pod.metadata.name = Some(name.to_string());
+        KubeResource::Pod(Arc::new(pod))
+    }
+
+    #[tokio::test]
+    async fn nav_j_moves_down() {
+        let mut app = App::new_test();
+        app.filtered_items = vec![make_pod("a"), make_pod("b"), make_pod("c")];
+
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.table_state.selected(), Some(0));
+
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.table_state.selected(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn nav_k_moves_up() {
+        let mut app = App::new_test();
+        app.filtered_items = vec![make_pod("a"), make_pod("b"), make_pod("c")];
+        app.table_state.select(Some(2));
+
+        handle_input(&mut app, key(KeyCode::Char('k')));
+        assert_eq!(app.table_state.selected(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn nav_wraps_forward() {
+        let mut app = App::new_test();
+        app.filtered_items = vec![make_pod("a"), make_pod("b")];
+        app.table_state.select(Some(1));
+
+        handle_input(&mut app, key(KeyCode::Down));
+        assert_eq!(app.table_state.selected(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn nav_wraps_backward() {
+        let mut app = App::new_test();
+        app.filtered_items = vec![make_pod("a"), make_pod("b")];
+        app.table_state.select(Some(0));
+
+        handle_input(&mut app, key(KeyCode::Up));
+        assert_eq!(app.table_state.selected(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn nav_empty_list_does_nothing() {
+        let mut app = App::new_test();
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.table_state.selected(), None);
+    }
+
+    #[tokio::test]
+    async fn tab_switches_forward() {
+        let mut app = App::new_test();
+        assert_eq!(app.active_tab, ResourceType::Pod);
+
+        handle_input(&mut app, key(KeyCode::Tab));
+        assert_eq!(app.active_tab, ResourceType::Deployment);
+
+        handle_input(&mut app, key(KeyCode::Tab));
+        assert_eq!(app.active_tab, ResourceType::Secret);
+    }
+
+    #[tokio::test]
+    async fn backtab_switches_backward() {
+        let mut app = App::new_test();
+        handle_input(&mut app, key(KeyCode::BackTab));
+        assert_eq!(app.active_tab, ResourceType::ConfigMap);
+    }
+
+    #[tokio::test]
+    async fn q_quits() {
+        let mut app = App::new_test();
+        handle_input(&mut app, key(KeyCode::Char('q')));
+        assert!(app.should_quit);
+    }
+
+    #[tokio::test]
+    async fn slash_enters_filter_mode() {
+        let mut app = App::new_test();
+        handle_input(&mut app, key(KeyCode::Char('/')));
+        assert_eq!(app.mode, AppMode::FilterInput);
+    }
+
+    #[tokio::test]
+    async fn ctrl_c_quits() {
+        let mut app = App::new_test();
+        handle_input(
+            &mut app,
+            key_with_mod(KeyCode::Char('c'), KeyModifiers::CONTROL),
+        );
+        assert!(app.should_quit);
+    }
+
+    #[tokio::test]
+    async fn c_opens_context_select() {
+        let mut app = App::new_test();
+        handle_input(&mut app, key(KeyCode::Char('c')));
+        assert_eq!(app.mode, AppMode::ContextSelect);
+        assert_eq!(app.popup_state.selected(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn n_opens_namespace_select() {
+        let mut app = App::new_test();
+        handle_input(&mut app, key(KeyCode::Char('n')));
+        assert_eq!(app.mode, AppMode::NamespaceSelect);
+        assert_eq!(app.popup_state.selected(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn filter_input_adds_chars() {
+        let mut app = App::new_test();
+        app.mode = AppMode::FilterInput;
+        app.items = vec![make_pod("nginx"), make_pod("redis")];
+
+        handle_input(&mut app, key(KeyCode::Char('n')));
+        assert_eq!(app.filter_query, "n");
+
+        handle_input(&mut app, key(KeyCode::Char('g')));
+        assert_eq!(app.filter_query, "ng");
+    }
+
+    #[tokio::test]
+    async fn filter_backspace_removes_char() {
+        let mut app = App::new_test();
+        app.mode = AppMode::FilterInput;
+        app.filter_query = "abc".to_string();
+        app.items = vec![make_pod("abc")];
+
+        handle_input(&mut app, key(KeyCode::Backspace));
+        assert_eq!(app.filter_query, "ab");
+    }
+
+    #[tokio::test]
+    async fn filter_esc_returns_to_list() {
+        let mut app = App::new_test();
+        app.mode = AppMode::FilterInput;
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::List);
+    }
+
+    #[tokio::test]
+    async fn filter_enter_returns_to_list() {
+        let mut app = App::new_test();
+        app.mode = AppMode::FilterInput;
+
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::List);
+    }
+
+    #[tokio::test]
+    async fn popup_j_k_navigation() {
+        let mut app = App::new_test();
+        app.mode = AppMode::ContextSelect;
+        app.available_contexts = vec!["a".into(), "b".into(), "c".into()];
+        app.filtered_contexts = app.available_contexts.clone();
+        app.popup_state.select(Some(0));
+
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.popup_state.selected(), Some(1));
+
+        handle_input(&mut app, key(KeyCode::Char('k')));
+        assert_eq!(app.popup_state.selected(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn popup_enter_selects_namespace() {
+        let mut app = App::new_test();
+        app.mode = AppMode::NamespaceSelect;
+        app.available_namespaces = vec!["default".into(), "staging".into()];
+        app.filtered_namespaces = vec!["default".into(), "staging".into()];
+        app.popup_state.select(Some(1));
+
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.current_namespace, "staging");
+        assert_eq!(app.mode, AppMode::List);
+    }
+
+    #[tokio::test]
+    async fn namespace_input_accepts_custom_text() {
+        let mut app = App::new_test();
+        app.mode = AppMode::NamespaceSelect;
+        app.available_namespaces = vec![];
+        app.filtered_namespaces = vec![];
+        app.popup_state.select(None);
+
+        handle_input(&mut app, key(KeyCode::Char('/')));
+        assert!(app.namespace_typing);
+
+        handle_input(&mut app, key(KeyCode::Char('m')));
+        handle_input(&mut app, key(KeyCode::Char('y')));
+        handle_input(&mut app, key(KeyCode::Char('-')));
+        handle_input(&mut app, key(KeyCode::Char('n')));
+        handle_input(&mut app, key(KeyCode::Char('s')));
+        assert_eq!(app.namespace_input, "my-ns");
+
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.current_namespace, "my-ns");
+        assert_eq!(app.mode, AppMode::List);
+    }
+
+    #[tokio::test]
+    async fn namespace_input_filters_list() {
+        let mut app = App::new_test();
+        app.mode = AppMode::NamespaceSelect;
+        app.available_namespaces = vec!["default".into(), "kube-system".into(), "dev".into()];
+        app.filtered_namespaces = vec!["default".into(), "kube-system".into(), "dev".into()];
+
+        handle_input(&mut app, key(KeyCode::Char('/')));
+        handle_input(&mut app, key(KeyCode::Char('d')));
+        handle_input(&mut app, key(KeyCode::Char('e')));
+        assert_eq!(app.filtered_namespaces.len(), 2);
+        assert_eq!(app.filtered_namespaces[0], "default");
+        assert_eq!(app.filtered_namespaces[1], "dev");
+    }
+
+    #[tokio::test]
+    async fn namespace_jk_scrolls_in_default_mode() {
+        let mut app = App::new_test();
+        app.mode = AppMode::NamespaceSelect;
+        app.available_namespaces = vec!["a".into(), "b".into(), "c".into()];
+        app.filtered_namespaces = vec!["a".into(), "b".into(), "c".into()];
+        app.popup_state.select(Some(0));
+
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.popup_state.selected(), Some(1));
+
+        handle_input(&mut app, key(KeyCode::Char('k')));
+        assert_eq!(app.popup_state.selected(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn namespace_esc_typing_returns_to_scroll() {
+        let mut app = App::new_test();
+        app.mode = AppMode::NamespaceSelect;
+        app.available_namespaces = vec!["default".into()];
+        app.filtered_namespaces = vec!["default".into()];
+        app.popup_state.select(Some(0));
+
+        handle_input(&mut app, key(KeyCode::Char('/')));
+        assert!(app.namespace_typing);
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert!(!app.namespace_typing);
+        assert_eq!(app.mode, AppMode::NamespaceSelect);
+    }
+
+    #[tokio::test]
+    async fn popup_enter_selects_context() {
+        let mut app = App::new_test();
+        app.mode = AppMode::ContextSelect;
+        app.available_contexts = vec!["dev".into(), "prod".into()];
+        app.filtered_contexts = app.available_contexts.clone();
+        app.popup_state.select(Some(1));
+
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.pending_context, Some("prod".to_string()));
+        assert_eq!(app.mode, AppMode::List);
+    }
+
+    #[tokio::test]
+    async fn popup_esc_cancels() {
+        let mut app = App::new_test();
+        app.mode = AppMode::ContextSelect;
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::List);
+        assert!(app.pending_context.is_none());
+    }
+
+    #[tokio::test]
+    async fn secret_enter_opens_decode() {
+        let mut app = App::new_test();
+        app.active_tab = ResourceType::Secret;
+        let mut secret = k8s_openapi::api::core::v1::Secret::default();
+        secret.metadata.name = Some("s1".to_string());
+        secret.data = Some(std::collections::BTreeMap::new());
+        app.filtered_items = vec![KubeResource::Secret(Arc::new(secret))];
+        app.table_state.select(Some(0));
+
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::SecretDecode);
+        assert!(app.selected_secret_decoded.is_some());
+    }
+
+    #[tokio::test]
+    async fn secret_modal_esc_closes() {
+        let mut app = App::new_test();
+        app.mode = AppMode::SecretDecode;
+        app.selected_secret_decoded = Some(vec![("k".into(), text("v"))]);
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::List);
+        assert!(app.selected_secret_decoded.is_none());
+    }
+
+    #[tokio::test]
+    async fn secret_modal_scroll() {
+        let mut app = App::new_test();
+        app.mode = AppMode::SecretDecode;
+        app.selected_secret_decoded = Some(vec![
+            ("a".into(), text("1")),
+            ("b".into(), text("2")),
+            ("c".into(), text("3")),
+        ]);
+        app.secret_scroll = 0;
+
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.secret_scroll, 1);
+
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.secret_scroll, 2);
+
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.secret_scroll, 2);
+
+        handle_input(&mut app, key(KeyCode::Char('k')));
+        assert_eq!(app.secret_scroll, 1);
+    }
+
+    #[tokio::test]
+    async fn log_esc_exits_to_list() {
+        let mut app = App::new_test();
+        app.mode = AppMode::LogView;
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::List);
+    }
+
+    #[tokio::test]
+    async fn log_q_exits_to_list() {
+        let mut app = App::new_test();
+        app.mode = AppMode::LogView;
+
+        handle_input(&mut app, key(KeyCode::Char('q')));
+        assert_eq!(app.mode, AppMode::List);
+    }
+
+    #[tokio::test]
+    async fn log_scroll_up_at_top_triggers_load_more() {
+        let mut app = App::new_test();
+        app.mode = AppMode::LogView;
+        app.log_pod_name = "test-pod".into();
+        app.log_namespace = "default".into();
+        for i in 0..50 {
+            app.log_buffer.push_back(format!("line {i}"));
+        }
+        app.log_scroll_offset = Some(0);
+
+        handle_input(&mut app, key(KeyCode::Char('k')));
+        assert!(app.log_loading_history);
+        assert_eq!(app.log_tail_lines, 200);
+    }
+
+    #[tokio::test]
+    async fn log_load_more_skips_when_already_loading() {
+        let mut app = App::new_test();
+        app.mode = AppMode::LogView;
+        app.log_pod_name = "test-pod".into();
+        app.log_namespace = "default".into();
+        app.log_loading_history = true;
+        app.log_tail_lines = 200;
+        app.log_scroll_offset = Some(0);
+
+        handle_input(&mut app, key(KeyCode::Char('k')));
+        assert_eq!(app.log_tail_lines, 200);
+    }
+
+    #[tokio::test]
+    async fn log_pageup_at_top_triggers_load_more() {
+        let mut app = App::new_test();
+        app.mode = AppMode::LogView;
+        app.log_pod_name = "test-pod".into();
+        app.log_namespace = "default".into();
+        for i in 0..50 {
+            app.log_buffer.push_back(format!("line {i}"));
+        }
+        app.log_scroll_offset = Some(0);
+
+        handle_input(&mut app, key(KeyCode::PageUp));
+        assert!(app.log_loading_history);
+        assert_eq!(app.log_tail_lines, 200);
+    }
+
+    #[tokio::test]
+    async fn scale_accepts_digits() {
+        let mut app = App::new_test();
+        app.mode = AppMode::ScaleInput;
+
+        handle_input(&mut app, key(KeyCode::Char('3')));
+        assert_eq!(app.scale_input, "3");
+
+        handle_input(&mut app, key(KeyCode::Char('5')));
+        assert_eq!(app.scale_input, "35");
+    }
+
+    #[tokio::test]
+    async fn scale_rejects_letters() {
+        let mut app = App::new_test();
+        app.mode = AppMode::ScaleInput;
+
+        handle_input(&mut app, key(KeyCode::Char('a')));
+        assert_eq!(app.scale_input, "");
+    }
+
+    #[tokio::test]
+    async fn scale_backspace() {
+        let mut app = App::new_test();
+        app.mode = AppMode::ScaleInput;
+        app.scale_input = "12".to_string();
+
+        handle_input(&mut app, key(KeyCode::Backspace));
+        assert_eq!(app.scale_input, "1");
+    }
+
+    #[tokio::test]
+    async fn scale_esc_cancels() {
+        let mut app = App::new_test();
+        app.mode = AppMode::ScaleInput;
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::List);
+    }
+
+    #[tokio::test]
+    async fn confirm_n_cancels() {
+        let mut app = App::new_test();
+        app.mode = AppMode::Confirm;
+        app.pending_action = Some(PendingAction::DeleteResource {
+            count: 1,
+            kind: "pod(s)",
+            names: vec!["test".into()],
+        });
+
+        handle_input(&mut app, key(KeyCode::Char('n')));
+        assert_eq!(app.mode, AppMode::List);
+        assert!(app.pending_action.is_none());
+    }
+
+    #[tokio::test]
+    async fn confirm_esc_cancels() {
+        let mut app = App::new_test();
+        app.mode = AppMode::Confirm;
+        app.pending_action = Some(PendingAction::DeleteResource {
+            count: 1,
+            kind: "pod(s)",
+            names: vec!["test".into()],
+        });
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::List);
+        assert!(app.pending_action.is_none());
+    }
+
+    #[tokio::test]
+    async fn delete_key_opens_confirm_for_pod() {
+        let mut app = App::new_test();
+        app.active_tab = ResourceType::Pod;
+        app.filtered_items = vec![make_pod("nginx")];
+        app.table_state.select(Some(0));
+
+        handle_input(&mut app, key(KeyCode::Delete));
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert!(app.pending_action.is_some());
+    }
+
+    #[tokio::test]
+    async fn s_starts_shell_for_pod() {
+        let mut app = App::new_test();
+        app.active_tab = ResourceType::Pod;
+        app.filtered_items = vec![make_pod("nginx")];
+        app.table_state.select(Some(0));
+
+        handle_input(&mut app, key(KeyCode::Char('s')));
+        assert!(app.mode == AppMode::ShellView || app.last_error.is_some());
+    }
+
+    #[tokio::test]
+    async fn shift_s_opens_scale_for_deployment() {
+        let mut app = App::new_test();
+        app.active_tab = ResourceType::Deployment;
+        let mut dep = k8s_openapi::api::apps::v1::Deployment::default();
+        dep.metadata.name = Some("web".to_string());
+        app.filtered_items = vec![KubeResource::Deployment(Arc::new(dep))];
+        app.table_state.select(Some(0));
+
+        handle_input(&mut app, key(KeyCode::Char('S')));
+        assert_eq!(app.mode, AppMode::ScaleInput);
+    }
+
+    #[tokio::test]
+    async fn namespace_rejects_invalid_name() {
+        let mut app = App::new_test();
+        app.mode = AppMode::NamespaceSelect;
+        app.available_namespaces = vec![];
+        app.filtered_namespaces = vec![];
+        app.popup_state.select(None);
+
+        handle_input(&mut app, key(KeyCode::Char('/')));
+        handle_input(&mut app, key(KeyCode::Char('M')));
+        handle_input(&mut app, key(KeyCode::Char('y')));
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert!(app.last_error.is_some());
+    }
+
+    #[tokio::test]
+    async fn namespace_rejects_trailing_hyphen() {
+        let mut app = App::new_test();
+        app.mode = AppMode::NamespaceSelect;
+        app.available_namespaces = vec![];
+        app.filtered_namespaces = vec![];
+        app.popup_state.select(None);
+
+        handle_input(&mut app, key(KeyCode::Char('/')));
+        for c in "my-ns-".chars() {
+            handle_input(&mut app, key(KeyCode::Char(c)));
+        }
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert!(app.last_error.is_some());
+    }
+
+    #[tokio::test]
+    async fn scale_rejects_over_1000() {
+        let mut app = App::new_test();
+        app.mode = AppMode::ScaleInput;
+        app.active_tab = ResourceType::Deployment;
+        let mut dep = k8s_openapi::api::apps::v1::Deployment::default();
+        dep.metadata.name = Some("web".to_string());
+        app.filtered_items = vec![KubeResource::Deployment(Arc::new(dep))];
+        app.table_state.select(Some(0));
+        app.scale_input = "9999".to_string();
+
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert!(app.last_error.is_some());
+        assert!(app.last_error.as_ref().unwrap().contains("1000"));
+    }
+
+    #[tokio::test]
+    async fn esc_in_list_clears_filter() {
+        let mut app = App::new_test();
+        app.items = vec![make_pod("a"), make_pod("b")];
+        app.filter_query = "a".to_string();
+        app.status_filter.insert("Running".to_string());
+        app.update_filter();
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.filter_query, "");
+        assert!(app.status_filter.is_empty());
+        assert_eq!(app.filtered_items.len(), 2);
+    }
+
+    #[test]
+    fn pty_alt_char_sends_esc_prefix() {
+        let ev = key_with_mod(KeyCode::Char(':'), KeyModifiers::ALT);
+        assert_eq!(key_to_pty_bytes(ev), vec![0x1b, b':']);
+    }
+
+    #[test]
+    fn pty_alt_letter_sends_esc_prefix() {
+        let ev = key_with_mod(KeyCode::Char('d'), KeyModifiers::ALT);
+        assert_eq!(key_to_pty_bytes(ev), vec![0x1b, b'd']);
+    }
+
+    #[test]
+    fn pty_ctrl_alt_sends_esc_plus_control_code() {
+        let ev = key_with_mod(
+            KeyCode::Char('c'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        );
+        assert_eq!(key_to_pty_bytes(ev), vec![0x1b, 0x03]);
+    }
+
+    #[test]
+    fn pty_plain_char_no_esc_prefix() {
+        let ev = key(KeyCode::Char(':'));
+        assert_eq!(key_to_pty_bytes(ev), vec![b':']);
+    }
+
+    fn make_pod_with_status(name: &str, phase: &str) -> KubeResource {
+        use k8s_openapi::api::core::v1::PodStatus;
+        let mut pod = Pod::default();
+        pod.metadata.name = Some(name.to_string());
+        pod.status = Some(PodStatus {
+            phase: Some(phase.to_string()),
+            ..Default::default()
+        });
+        KubeResource::Pod(Arc::new(pod))
+    }
+
+    #[tokio::test]
+    async fn f_opens_status_filter() {
+        let mut app = App::new_test();
+        app.items = vec![
+            make_pod_with_status("a", "Running"),
+            make_pod_with_status("b", "Pending"),
+        ];
+        handle_input(&mut app, key(KeyCode::Char('f')));
+        assert_eq!(app.mode, AppMode::StatusFilter);
+        assert_eq!(app.status_filter_items.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn f_ignored_on_deployment_tab() {
+        let mut app = App::new_test();
+        app.active_tab = ResourceType::Deployment;
+        handle_input(&mut app, key(KeyCode::Char('f')));
+        assert_eq!(app.mode, AppMode::List);
+    }
+
+    #[tokio::test]
+    async fn status_filter_space_toggles() {
+        let mut app = App::new_test();
+        app.items = vec![
+            make_pod_with_status("a", "Running"),
+            make_pod_with_status("b", "Pending"),
+        ];
+        app.build_status_filter_items();
+        app.status_filter_state.select(Some(0));
+        app.mode = AppMode::StatusFilter;
+
+        handle_input(&mut app, key(KeyCode::Char(' ')));
+        assert!(app.status_filter_selected.contains(&0));
+
+        handle_input(&mut app, key(KeyCode::Char(' ')));
+        assert!(!app.status_filter_selected.contains(&0));
+    }
+
+    #[tokio::test]
+    async fn status_filter_enter_applies() {
+        let mut app = App::new_test();
+        app.items = vec![
+            make_pod_with_status("a", "Running"),
+            make_pod_with_status("b", "Pending"),
+            make_pod_with_status("c", "Running"),
+        ];
+        app.update_filter();
+        assert_eq!(app.filtered_items.len(), 3);
+
+        app.build_status_filter_items();
+        app.status_filter_state.select(Some(0));
+        app.mode = AppMode::StatusFilter;
+
+        let running_idx = app
+            .status_filter_items
+            .iter()
+            .position(|(p, _)| p == "Pending")
+            .unwrap();
+        app.status_filter_selected.insert(running_idx);
+
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::List);
+        assert_eq!(app.filtered_items.len(), 1);
+        assert_eq!(app.filtered_items[0].name(), "b");
+    }
+
+    #[tokio::test]
+    async fn status_filter_esc_cancels() {
+        let mut app = App::new_test();
+        app.items = vec![
+            make_pod_with_status("a", "Running"),
+            make_pod_with_status("b", "Pending"),
+        ];
+        app.update_filter();
+        app.build_status_filter_items();
+        app.status_filter_state.select(Some(0));
+        app.status_filter_selected.insert(0);
+        app.mode = AppMode::StatusFilter;
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::List);
+        assert!(app.status_filter.is_empty());
+        assert_eq!(app.filtered_items.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn status_filter_a_toggles_all() {
+        let mut app = App::new_test();
+        app.items = vec![
+            make_pod_with_status("a", "Running"),
+            make_pod_with_status("b", "Pending"),
+        ];
+        app.build_status_filter_items();
+        app.status_filter_state.select(Some(0));
+        app.mode = AppMode::StatusFilter;
+
+        handle_input(&mut app, key(KeyCode::Char('a')));
+        assert_eq!(app.status_filter_selected.len(), 2);
+
+        handle_input(&mut app, key(KeyCode::Char('a')));
+        assert!(app.status_filter_selected.is_empty());
+    }
+
+    #[tokio::test]
+    async fn status_filter_enter_selects_cursor_when_none_toggled() {
+        let mut app = App::new_test();
+        app.items = vec![
+            make_pod_with_status("a", "Running"),
+            make_pod_with_status("b", "Pending"),
+            make_pod_with_status("c", "Running"),
+        ];
+        app.update_filter();
+        app.build_status_filter_items();
+
+        let pending_idx = app
+            .status_filter_items
+            .iter()
+            .position(|(p, _)| p == "Pending")
+            .unwrap();
+        app.status_filter_state.select(Some(pending_idx));
+        app.mode = AppMode::StatusFilter;
+
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::List);
+        assert_eq!(app.filtered_items.len(), 1);
+        assert_eq!(app.filtered_items[0].name(), "b");
+    }
 
-fn next_row(app: &mut App) {
-    let len = app.filtered_items.len();
-    if len == 0 {
-        return;
+    #[tokio::test]
+    async fn log_slash_enters_search_input() {
+        let mut app = App::new_test();
+        app.mode = AppMode::LogView;
+
+        handle_input(&mut app, key(KeyCode::Char('/')));
+        assert_eq!(app.mode, AppMode::LogSearchInput);
     }
-    let i = match app.table_state.selected() {
-        Some(i) => (i + 1) % len,
-        None => 0,
-    };
-    app.table_state.select(Some(i));
-}
 
-fn prev_row(app: &mut App) {
-    let len = app.filtered_items.len();
-    if len == 0 {
-        return;
+    #[tokio::test]
+    async fn log_search_input_accumulates_chars() {
+        let mut app = App::new_test();
+        app.mode = AppMode::LogSearchInput;
+
+        handle_input(&mut app, key(KeyCode::Char('e')));
+        handle_input(&mut app, key(KeyCode::Char('r')));
+        handle_input(&mut app, key(KeyCode::Char('r')));
+        assert_eq!(app.log_search_input, "err");
     }
-    let i = match app.table_state.selected() {
-        Some(i) => {
-            if i == 0 {
-                len - 1
-            } else {
-                i - 1
-            }
-        }
-        None => len - 1,
-    };
-    app.table_state.select(Some(i));
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::app::App;
-    use crate::models::{AppMode, KubeResource, PendingAction, ResourceType};
-    use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
-    use k8s_openapi::api::core::v1::Pod;
-    use std::sync::Arc;
+    #[tokio::test]
+    async fn log_search_enter_confirms() {
+        let mut app = App::new_test();
+        app.mode = AppMode::LogSearchInput;
+        app.log_search_input = "test".to_string();
 
-    fn key(code: KeyCode) -> KeyEvent {
-        KeyEvent {
-            code,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::LogView);
+        assert_eq!(app.log_search_query, "test");
     }
 
-    fn key_with_mod(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
-        KeyEvent {
-            code,
-            modifiers,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }
+    #[tokio::test]
+    async fn log_search_esc_cancels() {
+        let mut app = App::new_test();
+        app.mode = AppMode::LogSearchInput;
+        app.log_search_input = "test".to_string();
+        app.log_search_query = "old".to_string();
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::LogView);
+        assert_eq!(app.log_search_input, "");
+        assert_eq!(app.log_search_query, "old");
     }
 
-    fn make_pod(name: &str) -> KubeResource {
-        let mut pod = Pod::default();
-        pod.metadata.name = Some(name.to_string());
-        KubeResource::Pod(Arc::new(pod))
+    #[tokio::test]
+    async fn log_esc_clears_search_first() {
+        let mut app = App::new_test();
+        app.mode = AppMode::LogView;
+        app.log_search_query = "test".to_string();
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::LogView);
+        assert_eq!(app.log_search_query, "");
     }
 
     #[tokio::test]
-    async fn nav_j_moves_down() {
+    async fn log_esc_exits_when_no_search() {
         let mut app = App::new_test();
-        app.filtered_items = vec![make_pod("a"), make_pod("b"), make_pod("c")];
+        app.mode = AppMode::LogView;
+        app.log_search_query.clear();
 
-        handle_input(&mut app, key(KeyCode::Char('j')));
-        assert_eq!(app.table_state.selected(), Some(0));
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::List);
+    }
 
-        handle_input(&mut app, key(KeyCode::Char('j')));
-        assert_eq!(app.table_state.selected(), Some(1));
+    #[tokio::test]
+    async fn log_n_jumps_to_next_match() {
+        let mut app = App::new_test();
+        app.mode = AppMode::LogView;
+        for i in 0..50 {
+            app.log_buffer.push_back(format!("line {i}"));
+        }
+        app.log_buffer.push_back("error found here".to_string());
+        for i in 51..200 {
+            app.log_buffer.push_back(format!("line {i}"));
+        }
+        app.log_scroll_offset = Some(100);
+        app.log_search_query = "error".to_string();
+
+        handle_input(&mut app, key(KeyCode::Char('n')));
+        assert_eq!(app.log_search_match_line, Some(50));
     }
 
     #[tokio::test]
-    async fn nav_k_moves_up() {
+    async fn log_n_finds_above_scroll() {
         let mut app = App::new_test();
-        app.filtered_items = vec![make_pod("a"), make_pod("b"), make_pod("c")];
-        app.table_state.select(Some(2));
+        app.mode = AppMode::LogView;
+        app.log_buffer.push_back("error first".to_string());
+        for i in 1..50 {
+            app.log_buffer.push_back(format!("line {i}"));
+        }
+        app.log_scroll_offset = Some(10);
+        app.log_search_query = "error".to_string();
 
-        handle_input(&mut app, key(KeyCode::Char('k')));
-        assert_eq!(app.table_state.selected(), Some(1));
+        handle_input(&mut app, key(KeyCode::Char('n')));
+        assert_eq!(app.log_search_match_line, Some(0));
     }
 
     #[tokio::test]
-    async fn nav_wraps_forward() {
+    async fn log_shift_n_jumps_to_prev() {
         let mut app = App::new_test();
-        app.filtered_items = vec![make_pod("a"), make_pod("b")];
-        app.table_state.select(Some(1));
+        app.mode = AppMode::LogView;
+        for i in 0..80 {
+            app.log_buffer.push_back(format!("line {i}"));
+        }
+        app.log_buffer.push_back("error found here".to_string());
+        for i in 81..200 {
+            app.log_buffer.push_back(format!("line {i}"));
+        }
+        app.log_scroll_offset = Some(50);
+        app.log_search_query = "error".to_string();
 
-        handle_input(&mut app, key(KeyCode::Down));
-        assert_eq!(app.table_state.selected(), Some(0));
+        handle_input(&mut app, key(KeyCode::Char('N')));
+        assert_eq!(app.log_search_match_line, Some(80));
     }
 
     #[tokio::test]
-    async fn nav_wraps_backward() {
+    async fn a_opens_secret_create_on_secret_tab() {
         let mut app = App::new_test();
-        app.filtered_items = vec![make_pod("a"), make_pod("b")];
-        app.table_state.select(Some(0));
+        app.active_tab = ResourceType::Secret;
+        app.secret_form.name = "stale".into();
 
-        handle_input(&mut app, key(KeyCode::Up));
-        assert_eq!(app.table_state.selected(), Some(1));
+        handle_input(&mut app, key(KeyCode::Char('a')));
+        assert_eq!(app.mode, AppMode::SecretCreate);
+        assert!(app.secret_form.name.is_empty());
+        assert_eq!(app.secret_form.type_, "Opaque");
     }
 
     #[tokio::test]
-    async fn nav_empty_list_does_nothing() {
+    async fn secret_create_adds_key_value_pairs() {
         let mut app = App::new_test();
-        handle_input(&mut app, key(KeyCode::Char('j')));
-        assert_eq!(app.table_state.selected(), None);
+        app.mode = AppMode::SecretCreate;
+        app.secret_form.field = SecretFormField::Key;
+
+        for c in "user".chars() {
+            handle_input(&mut app, key(KeyCode::Char(c)));
+        }
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.secret_form.field, SecretFormField::Value);
+        for c in "admin".chars() {
+            handle_input(&mut app, key(KeyCode::Char(c)));
+        }
+        handle_input(&mut app, key(KeyCode::Enter));
+
+        assert_eq!(
+            app.secret_form.entries,
+            vec![("user".to_string(), "admin".to_string())]
+        );
+        assert_eq!(app.secret_form.field, SecretFormField::Key);
+        assert!(app.secret_form.key.is_empty());
     }
 
     #[tokio::test]
-    async fn tab_switches_forward() {
+    async fn secret_create_rejects_invalid_name() {
         let mut app = App::new_test();
-        assert_eq!(app.active_tab, ResourceType::Pod);
+        app.mode = AppMode::SecretCreate;
+        app.secret_form.name = "Bad_Name".into();
 
-        handle_input(&mut app, key(KeyCode::Tab));
-        assert_eq!(app.active_tab, ResourceType::Deployment);
+        handle_input(
+            &mut app,
+            key_with_mod(KeyCode::Char('s'), KeyModifiers::CONTROL),
+        );
+        assert_eq!(app.mode, AppMode::SecretCreate);
+        assert!(app.last_error.is_some());
+    }
 
-        handle_input(&mut app, key(KeyCode::Tab));
-        assert_eq!(app.active_tab, ResourceType::Secret);
+    #[tokio::test]
+    async fn secret_create_loads_env_file() {
+        let mut app = App::new_test();
+        let path = std::env::temp_dir().join(format!("kr-test-{}.env", std::process::id()));
+        std::fs::write(&path, "A=1\nB=2\n").unwrap();
+        app.mode = AppMode::SecretCreate;
+        app.secret_form.field = SecretFormField::EnvFile;
+        app.secret_form.env_file = path.display().to_string();
+
+        handle_input(&mut app, key(KeyCode::Enter));
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(app.secret_form.entries.len(), 2);
+        assert!(app.secret_form.env_file.is_empty());
     }
 
     #[tokio::test]
-    async fn backtab_switches_backward() {
+    async fn secret_modal_e_opens_inline_edit() {
         let mut app = App::new_test();
-        handle_input(&mut app, key(KeyCode::BackTab));
-        assert_eq!(app.active_tab, ResourceType::Secret);
+        app.mode = AppMode::SecretDecode;
+        app.decoded_secret_name = "creds".into();
+        app.selected_secret_decoded = Some(vec![("user".into(), text("admin"))]);
+
+        handle_input(&mut app, key(KeyCode::Char('e')));
+        assert_eq!(app.mode, AppMode::SecretEditValue);
+        assert_eq!(app.secret_edit_input, "admin");
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::SecretDecode);
     }
 
     #[tokio::test]
-    async fn q_quits() {
+    async fn secret_modal_e_multiline_requests_editor() {
         let mut app = App::new_test();
-        handle_input(&mut app, key(KeyCode::Char('q')));
-        assert!(app.should_quit);
+        app.mode = AppMode::SecretDecode;
+        app.decoded_secret_name = "creds".into();
+        app.selected_secret_decoded = Some(vec![("cfg".into(), text("a\nb"))]);
+
+        handle_input(&mut app, key(KeyCode::Char('e')));
+        assert_eq!(app.mode, AppMode::SecretDecode);
+        assert_eq!(
+            app.pending_editor,
+            Some(EditorRequest::SecretValue {
+                secret: "creds".into(),
+                key: "cfg".into(),
+                value: "a\nb".into(),
+            })
+        );
     }
 
     #[tokio::test]
-    async fn slash_enters_filter_mode() {
+    async fn secret_edit_unchanged_value_skips_patch() {
         let mut app = App::new_test();
-        handle_input(&mut app, key(KeyCode::Char('/')));
-        assert_eq!(app.mode, AppMode::FilterInput);
+        app.mode = AppMode::SecretEditValue;
+        app.selected_secret_decoded = Some(vec![("user".into(), text("admin"))]);
+        app.secret_edit_input = "admin".into();
+
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::SecretDecode);
+        assert!(app.last_success.as_ref().unwrap().contains("unchanged"));
     }
 
     #[tokio::test]
-    async fn ctrl_c_quits() {
+    async fn secret_export_writes_env_file() {
         let mut app = App::new_test();
-        handle_input(
-            &mut app,
-            key_with_mod(KeyCode::Char('c'), KeyModifiers::CONTROL),
+        app.mode = AppMode::SecretDecode;
+        app.decoded_secret_name = "creds".into();
+        app.selected_secret_decoded = Some(vec![
+            ("USER".into(), text("admin")),
+            ("BLOB".into(), ConfigValue::Binary(vec![0xff])),
+        ]);
+
+        handle_input(&mut app, key(KeyCode::Char('w')));
+        assert_eq!(app.mode, AppMode::SecretExport);
+        assert_eq!(app.export_path_input, "./creds.env");
+
+        let path = std::env::temp_dir().join(format!("kr-export-{}.env", std::process::id()));
+        app.export_path_input = path.display().to_string();
+        handle_input(&mut app, key(KeyCode::Enter));
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(written, "USER=admin\n");
+        assert_eq!(app.mode, AppMode::SecretDecode);
+        assert!(
+            app.last_success
+                .as_ref()
+                .unwrap()
+                .contains("skipped 1 binary")
         );
-        assert!(app.should_quit);
     }
 
     #[tokio::test]
-    async fn c_opens_context_select() {
+    async fn equals_opens_secret_diff_prompt() {
         let mut app = App::new_test();
-        handle_input(&mut app, key(KeyCode::Char('c')));
-        assert_eq!(app.mode, AppMode::ContextSelect);
-        assert_eq!(app.popup_state.selected(), Some(0));
+        app.active_tab = ResourceType::Secret;
+        let mut secret = k8s_openapi::api::core::v1::Secret::default();
+        secret.metadata.name = Some("s1".to_string());
+        app.filtered_items = vec![KubeResource::Secret(Arc::new(secret))];
+        app.table_state.select(Some(0));
+
+        handle_input(&mut app, key(KeyCode::Char('=')));
+        assert_eq!(app.mode, AppMode::SecretDiffInput);
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::List);
+    }
+
+    #[tokio::test]
+    async fn secret_diff_view_scrolls_and_closes() {
+        use crate::models::{DiffStatus, SecretDiffRow};
+        let mut app = App::new_test();
+        app.mode = AppMode::SecretDiff;
+        app.secret_diff = ["a", "b"]
+            .iter()
+            .map(|k| SecretDiffRow {
+                key: k.to_string(),
+                status: DiffStatus::Same,
+                left: Some("v".into()),
+                right: Some("v".into()),
+            })
+            .collect();
+
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.secret_scroll, 1);
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::List);
+        assert!(app.secret_diff.is_empty());
     }
 
     #[tokio::test]
-    async fn n_opens_namespace_select() {
+    async fn secret_modal_b_toggles_base64() {
         let mut app = App::new_test();
-        handle_input(&mut app, key(KeyCode::Char('n')));
-        assert_eq!(app.mode, AppMode::NamespaceSelect);
-        assert_eq!(app.popup_state.selected(), Some(0));
+        app.mode = AppMode::SecretDecode;
+        app.selected_secret_decoded = Some(vec![("k".into(), text("v"))]);
+
+        handle_input(&mut app, key(KeyCode::Char('b')));
+        assert!(app.secret_show_base64);
+        handle_input(&mut app, key(KeyCode::Char('b')));
+        assert!(!app.secret_show_base64);
     }
 
     #[tokio::test]
-    async fn filter_input_adds_chars() {
+    async fn secret_modal_copy_all_skips_binary_only_secret() {
         let mut app = App::new_test();
-        app.mode = AppMode::FilterInput;
-        app.items = vec![make_pod("nginx"), make_pod("redis")];
-
-        handle_input(&mut app, key(KeyCode::Char('n')));
-        assert_eq!(app.filter_query, "n");
+        app.mode = AppMode::SecretDecode;
+        app.selected_secret_decoded = Some(vec![("blob".into(), ConfigValue::Binary(vec![0xff]))]);
 
-        handle_input(&mut app, key(KeyCode::Char('g')));
-        assert_eq!(app.filter_query, "ng");
+        handle_input(&mut app, key(KeyCode::Char('C')));
+        assert_eq!(app.last_error.as_deref(), Some("No text values to copy"));
     }
 
     #[tokio::test]
-    async fn filter_backspace_removes_char() {
+    async fn secret_text_that_reads_binary_is_still_text() {
         let mut app = App::new_test();
-        app.mode = AppMode::FilterInput;
-        app.filter_query = "abc".to_string();
-        app.items = vec![make_pod("abc")];
+        app.mode = AppMode::SecretDecode;
+        app.decoded_secret_name = "notes".into();
+        app.selected_secret_decoded = Some(vec![("note".into(), text("<binary>"))]);
+        app.secret_revealed = true;
 
-        handle_input(&mut app, key(KeyCode::Backspace));
-        assert_eq!(app.filter_query, "ab");
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::ValuePager);
+        assert_eq!(app.pager_title, "notes/note");
+        assert_eq!(app.pager_text, "<binary>");
     }
 
     #[tokio::test]
-    async fn filter_esc_returns_to_list() {
+    async fn enter_on_configmap_opens_view_with_binary_data() {
+        use k8s_openapi::ByteString;
+        use k8s_openapi::api::core::v1::ConfigMap;
+        use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
         let mut app = App::new_test();
-        app.mode = AppMode::FilterInput;
+        app.active_tab = ResourceType::ConfigMap;
+        let cm = ConfigMap {
+            metadata: ObjectMeta {
+                name: Some("settings".into()),
+                ..Default::default()
+            },
+            data: Some([("app.yaml".to_string(), "a: 1\nb: 2\nc: 3".to_string())].into()),
+            binary_data: Some([("logo.png".to_string(), ByteString(vec![0xff, 0xd8]))].into()),
+            ..Default::default()
+        };
+        app.filtered_items = vec![KubeResource::ConfigMap(Arc::new(cm))];
+        app.table_state.select(Some(0));
 
-        handle_input(&mut app, key(KeyCode::Esc));
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::ConfigMapView);
+        assert_eq!(app.configmap_name, "settings");
+        assert_eq!(
+            app.configmap_entries,
+            Some(vec![
+                (
+                    "app.yaml".to_string(),
+                    ConfigValue::Text("a: 1\nb: 2\nc: 3".into())
+                ),
+                (
+                    "logo.png".to_string(),
+                    ConfigValue::Binary(vec![0xff, 0xd8])
+                ),
+            ])
+        );
+
+        handle_input(&mut app, key(KeyCode::Char('J')));
+        handle_input(&mut app, key(KeyCode::Char('J')));
+        handle_input(&mut app, key(KeyCode::Char('J')));
+        assert_eq!(app.configmap_value_scroll, 2);
+
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.configmap_scroll, 1);
+        assert_eq!(app.configmap_value_scroll, 0);
+
+        handle_input(&mut app, key(KeyCode::Char('q')));
         assert_eq!(app.mode, AppMode::List);
+        assert!(app.configmap_entries.is_none());
     }
 
     #[tokio::test]
-    async fn filter_enter_returns_to_list() {
+    async fn secret_modal_enter_opens_pager_and_q_returns() {
         let mut app = App::new_test();
-        app.mode = AppMode::FilterInput;
+        app.mode = AppMode::SecretDecode;
+        app.decoded_secret_name = "kubeconfig".into();
+        app.selected_secret_decoded = Some(vec![
+            ("blob".into(), ConfigValue::Binary(vec![0xff])),
+            ("config".into(), text("apiVersion: v1\nclusters: []")),
+        ]);
 
+        app.secret_scroll = 1;
         handle_input(&mut app, key(KeyCode::Enter));
-        assert_eq!(app.mode, AppMode::List);
+        assert_eq!(app.mode, AppMode::SecretDecode);
+        assert!(
+            app.last_error
+                .as_ref()
+                .is_some_and(|e| e.contains("press r"))
+        );
+        handle_input(&mut app, key(KeyCode::Char('r')));
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::ValuePager);
+        assert_eq!(app.pager_title, "kubeconfig/config");
+
+        handle_input(&mut app, key(KeyCode::Char('/')));
+        assert_eq!(app.mode, AppMode::ValuePagerSearch);
+        for c in "CLUSTERS".chars() {
+            handle_input(&mut app, key(KeyCode::Char(c)));
+        }
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::ValuePager);
+        assert_eq!(app.pager_query, "clusters");
+        assert_eq!(app.pager_scroll, 1);
+
+        handle_input(&mut app, key(KeyCode::Char('q')));
+        assert_eq!(app.mode, AppMode::SecretDecode);
+        assert!(app.selected_secret_decoded.is_some());
     }
 
     #[tokio::test]
-    async fn popup_j_k_navigation() {
+    async fn secret_usage_view_scrolls_and_closes() {
+        use crate::models::SecretConsumer;
         let mut app = App::new_test();
-        app.mode = AppMode::ContextSelect;
-        app.available_contexts = vec!["a".into(), "b".into(), "c".into()];
-        app.popup_state.select(Some(0));
+        app.mode = AppMode::SecretUsage;
+        app.secret_usage = vec![
+            SecretConsumer {
+                kind: "Deployment",
+                name: "web".into(),
+                refs: vec!["volume creds".into()],
+            },
+            SecretConsumer {
+                kind: "Pod",
+                name: "web-abc".into(),
+                refs: vec!["volume creds".into()],
+            },
+        ];
 
         handle_input(&mut app, key(KeyCode::Char('j')));
-        assert_eq!(app.popup_state.selected(), Some(1));
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.secret_usage_scroll, 1);
 
-        handle_input(&mut app, key(KeyCode::Char('k')));
-        assert_eq!(app.popup_state.selected(), Some(0));
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::List);
+        assert!(app.secret_usage.is_empty());
     }
 
     #[tokio::test]
-    async fn popup_enter_selects_namespace() {
+    async fn secret_modal_binary_value_opens_hex_view_and_saves_raw() {
         let mut app = App::new_test();
-        app.mode = AppMode::NamespaceSelect;
-        app.available_namespaces = vec!["default".into(), "staging".into()];
-        app.filtered_namespaces = vec!["default".into(), "staging".into()];
-        app.popup_state.select(Some(1));
+        app.mode = AppMode::SecretDecode;
+        app.decoded_secret_name = "keystore".into();
+        app.selected_secret_decoded = Some(vec![(
+            "store.p12".into(),
+            ConfigValue::Binary(vec![0x30, 0x82, 0x00, 0xff]),
+        )]);
+        handle_input(&mut app, key(KeyCode::Char('h')));
+        assert_eq!(app.mode, AppMode::SecretDecode);
+        app.secret_revealed = true;
 
         handle_input(&mut app, key(KeyCode::Enter));
-        assert_eq!(app.current_namespace, "staging");
-        assert_eq!(app.mode, AppMode::List);
+        assert_eq!(app.mode, AppMode::ValuePager);
+        assert_eq!(app.pager_title, "keystore/store.p12 (hex, 4 bytes)");
+        assert!(app.pager_text.starts_with("00000000  30 82 00 ff"));
+        handle_input(&mut app, key(KeyCode::Char('q')));
+
+        let path = std::env::temp_dir().join(format!("kr-raw-{}", std::process::id()));
+        handle_input(&mut app, key(KeyCode::Char('s')));
+        assert_eq!(app.mode, AppMode::SecretSaveRaw);
+        assert_eq!(app.export_path_input, "./store.p12");
+        app.export_path_input = path.display().to_string();
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::SecretDecode);
+        let written = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(written, vec![0x30, 0x82, 0x00, 0xff]);
     }
 
     #[tokio::test]
-    async fn namespace_input_accepts_custom_text() {
+    async fn editing_managed_secret_asks_for_confirmation() {
+        use crate::models::SecretOwner;
         let mut app = App::new_test();
-        app.mode = AppMode::NamespaceSelect;
-        app.available_namespaces = vec![];
-        app.filtered_namespaces = vec![];
-        app.popup_state.select(None);
+        app.mode = AppMode::SecretDecode;
+        app.selected_secret_decoded = Some(vec![("pw".into(), text("old"))]);
+        app.secret_owner = Some(SecretOwner {
+            kind: "ExternalSecret".into(),
+            name: "db".into(),
+            api_version: "external-secrets.io/v1beta1".into(),
+        });
 
-        handle_input(&mut app, key(KeyCode::Char('/')));
-        assert!(app.namespace_typing);
+        handle_input(&mut app, key(KeyCode::Char('e')));
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert!(
+            app.pending_action
+                .as_ref()
+                .unwrap()
+                .message()
+                .contains("ExternalSecret 'db'")
+        );
 
-        handle_input(&mut app, key(KeyCode::Char('m')));
-        handle_input(&mut app, key(KeyCode::Char('y')));
-        handle_input(&mut app, key(KeyCode::Char('-')));
         handle_input(&mut app, key(KeyCode::Char('n')));
-        handle_input(&mut app, key(KeyCode::Char('s')));
-        assert_eq!(app.namespace_input, "my-ns");
+        assert_eq!(app.mode, AppMode::SecretDecode);
+        assert!(app.pending_action.is_none());
 
-        handle_input(&mut app, key(KeyCode::Enter));
-        assert_eq!(app.current_namespace, "my-ns");
-        assert_eq!(app.mode, AppMode::List);
+        handle_input(&mut app, key(KeyCode::Char('e')));
+        handle_input(&mut app, key(KeyCode::Char('y')));
+        assert_eq!(app.mode, AppMode::SecretEditValue);
+        assert_eq!(app.secret_edit_input, "old");
     }
 
     #[tokio::test]
-    async fn namespace_input_filters_list() {
+    async fn pipe_toggles_split_view() {
         let mut app = App::new_test();
-        app.mode = AppMode::NamespaceSelect;
-        app.available_namespaces = vec!["default".into(), "kube-system".into(), "dev".into()];
-        app.filtered_namespaces = vec!["default".into(), "kube-system".into(), "dev".into()];
+        handle_input(&mut app, key(KeyCode::Char('|')));
+        assert_eq!(app.mode, AppMode::SplitInput);
+        handle_input(&mut app, key(KeyCode::Char('x')));
+        assert_eq!(app.split_input, "x");
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::List);
+        assert!(app.split.is_none());
 
-        handle_input(&mut app, key(KeyCode::Char('/')));
-        handle_input(&mut app, key(KeyCode::Char('d')));
-        handle_input(&mut app, key(KeyCode::Char('e')));
-        assert_eq!(app.filtered_namespaces.len(), 2);
-        assert_eq!(app.filtered_namespaces[0], "default");
-        assert_eq!(app.filtered_namespaces[1], "dev");
+        let client = app.client.clone();
+        app.open_split(client, "other".into(), "staging".into());
+        assert!(app.split.is_some());
+        handle_input(&mut app, key(KeyCode::Char('|')));
+        assert!(app.split.is_none());
+        assert_eq!(app.mode, AppMode::List);
     }
 
     #[tokio::test]
-    async fn namespace_jk_scrolls_in_default_mode() {
+    async fn namespace_f_pins_favorite_to_top() {
         let mut app = App::new_test();
-        app.mode = AppMode::NamespaceSelect;
         app.available_namespaces = vec!["a".into(), "b".into(), "c".into()];
-        app.filtered_namespaces = vec!["a".into(), "b".into(), "c".into()];
-        app.popup_state.select(Some(0));
-
-        handle_input(&mut app, key(KeyCode::Char('j')));
-        assert_eq!(app.popup_state.selected(), Some(1));
+        handle_input(&mut app, key(KeyCode::Char('n')));
+        assert_eq!(app.mode, AppMode::NamespaceSelect);
 
-        handle_input(&mut app, key(KeyCode::Char('k')));
+        app.popup_state.select(Some(2));
+        handle_input(&mut app, key(KeyCode::Char('f')));
+        assert_eq!(app.app_state.get_favorites("test-context"), vec!["c"]);
+        assert_eq!(app.filtered_namespaces, vec!["c", "a", "b"]);
         assert_eq!(app.popup_state.selected(), Some(0));
+
+        handle_input(&mut app, key(KeyCode::Char('/')));
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.filtered_namespaces, vec!["c", "a", "b"]);
+
+        handle_input(&mut app, key(KeyCode::Char('f')));
+        assert!(app.app_state.get_favorites("test-context").is_empty());
+        assert_eq!(app.filtered_namespaces, vec!["a", "b", "c"]);
+        assert_eq!(app.popup_state.selected(), Some(2));
     }
 
     #[tokio::test]
-    async fn namespace_esc_typing_returns_to_scroll() {
+    async fn namespace_popup_create_validates_name() {
         let mut app = App::new_test();
         app.mode = AppMode::NamespaceSelect;
-        app.available_namespaces = vec!["default".into()];
-        app.filtered_namespaces = vec!["default".into()];
         app.popup_state.select(Some(0));
 
-        handle_input(&mut app, key(KeyCode::Char('/')));
-        assert!(app.namespace_typing);
+        handle_input(&mut app, key(KeyCode::Char('a')));
+        assert_eq!(app.mode, AppMode::NamespaceCreate);
+        for c in "Bad_Name".chars() {
+            handle_input(&mut app, key(KeyCode::Char(c)));
+        }
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::NamespaceCreate);
+        assert!(
+            app.last_error
+                .as_ref()
+                .unwrap()
+                .contains("Invalid namespace")
+        );
+
+        app.namespace_create_input = "kube-system".into();
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert!(app.last_error.as_ref().unwrap().contains("already exists"));
 
         handle_input(&mut app, key(KeyCode::Esc));
-        assert!(!app.namespace_typing);
         assert_eq!(app.mode, AppMode::NamespaceSelect);
+        assert!(app.namespace_create_input.is_empty());
     }
 
     #[tokio::test]
-    async fn popup_enter_selects_context() {
+    async fn namespace_popup_delete_is_guarded() {
         let mut app = App::new_test();
-        app.mode = AppMode::ContextSelect;
-        app.available_contexts = vec!["dev".into(), "prod".into()];
-        app.popup_state.select(Some(1));
+        app.mode = AppMode::NamespaceSelect;
+        app.available_namespaces = vec!["default".into(), "kube-system".into(), "scratch".into()];
+        app.filtered_namespaces = app.available_namespaces.clone();
 
-        handle_input(&mut app, key(KeyCode::Enter));
-        assert_eq!(app.pending_context, Some("prod".to_string()));
-        assert_eq!(app.mode, AppMode::List);
-    }
+        app.popup_state.select(Some(1));
+        handle_input(&mut app, key(KeyCode::Char('D')));
+        assert_eq!(app.mode, AppMode::NamespaceSelect);
+        assert!(
+            app.last_error
+                .as_ref()
+                .unwrap()
+                .contains("system namespace")
+        );
 
-    #[tokio::test]
-    async fn popup_esc_cancels() {
-        let mut app = App::new_test();
-        app.mode = AppMode::ContextSelect;
+        app.popup_state.select(Some(2));
+        handle_input(&mut app, key(KeyCode::Char('D')));
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert!(matches!(
+            app.pending_action,
+            Some(PendingAction::DeleteNamespace { ref name }) if name == "scratch"
+        ));
 
         handle_input(&mut app, key(KeyCode::Esc));
-        assert_eq!(app.mode, AppMode::List);
-        assert!(app.pending_context.is_none());
+        assert_eq!(app.mode, AppMode::NamespaceSelect);
+        assert!(app.pending_action.is_none());
     }
 
     #[tokio::test]
-    async fn secret_enter_opens_decode() {
+    async fn context_popup_search_filters() {
         let mut app = App::new_test();
-        app.active_tab = ResourceType::Secret;
-        let mut secret = k8s_openapi::api::core::v1::Secret::default();
-        secret.metadata.name = Some("s1".to_string());
-        secret.data = Some(std::collections::BTreeMap::new());
-        app.filtered_items = vec![KubeResource::Secret(Arc::new(secret))];
-        app.table_state.select(Some(0));
+        app.available_contexts = vec!["prod-eu".into(), "dev".into(), "prod-us".into()];
+        handle_input(&mut app, key(KeyCode::Char('c')));
+        assert_eq!(app.filtered_contexts.len(), 3);
+
+        handle_input(&mut app, key(KeyCode::Char('/')));
+        assert!(app.context_typing);
+        for c in "PROD".chars() {
+            handle_input(&mut app, key(KeyCode::Char(c)));
+        }
+        assert_eq!(app.filtered_contexts, vec!["prod-eu", "prod-us"]);
 
+        // j/k are part of the query while typing; arrows navigate.
+        handle_input(&mut app, key(KeyCode::Down));
+        assert_eq!(app.popup_state.selected(), Some(1));
         handle_input(&mut app, key(KeyCode::Enter));
-        assert_eq!(app.mode, AppMode::SecretDecode);
-        assert!(app.selected_secret_decoded.is_some());
+        assert_eq!(app.pending_context, Some("prod-us".to_string()));
+        assert_eq!(app.mode, AppMode::List);
+        assert!(!app.context_typing);
     }
 
     #[tokio::test]
-    async fn secret_modal_esc_closes() {
+    async fn context_popup_esc_leaves_search_first() {
         let mut app = App::new_test();
-        app.mode = AppMode::SecretDecode;
-        app.selected_secret_decoded = Some(vec![("k".into(), "v".into())]);
+        handle_input(&mut app, key(KeyCode::Char('c')));
+        handle_input(&mut app, key(KeyCode::Char('/')));
+        handle_input(&mut app, key(KeyCode::Char('2')));
+        assert_eq!(app.filtered_contexts, vec!["ctx2"]);
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert!(!app.context_typing);
+        assert_eq!(app.mode, AppMode::ContextSelect);
+        assert_eq!(app.filtered_contexts.len(), 2);
 
         handle_input(&mut app, key(KeyCode::Esc));
         assert_eq!(app.mode, AppMode::List);
-        assert!(app.selected_secret_decoded.is_none());
     }
 
     #[tokio::test]
-    async fn secret_modal_scroll() {
+    async fn dash_flips_to_previous_namespace() {
         let mut app = App::new_test();
-        app.mode = AppMode::SecretDecode;
-        app.selected_secret_decoded = Some(vec![
-            ("a".into(), "1".into()),
-            ("b".into(), "2".into()),
-            ("c".into(), "3".into()),
-        ]);
-        app.secret_scroll = 0;
-
-        handle_input(&mut app, key(KeyCode::Char('j')));
-        assert_eq!(app.secret_scroll, 1);
-
-        handle_input(&mut app, key(KeyCode::Char('j')));
-        assert_eq!(app.secret_scroll, 2);
+        handle_input(&mut app, key(KeyCode::Char('-')));
+        assert_eq!(app.current_namespace, "default");
+        assert!(app.last_error.is_some());
 
-        handle_input(&mut app, key(KeyCode::Char('j')));
-        assert_eq!(app.secret_scroll, 2);
+        app.mode = AppMode::NamespaceSelect;
+        app.filtered_namespaces = vec!["default".into(), "kube-system".into()];
+        app.popup_state.select(Some(1));
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.current_namespace, "kube-system");
 
-        handle_input(&mut app, key(KeyCode::Char('k')));
-        assert_eq!(app.secret_scroll, 1);
+        handle_input(&mut app, key(KeyCode::Char('-')));
+        assert_eq!(app.current_namespace, "default");
+        handle_input(&mut app, key(KeyCode::Char('-')));
+        assert_eq!(app.current_namespace, "kube-system");
     }
 
     #[tokio::test]
-    async fn log_esc_exits_to_list() {
+    async fn brackets_cycle_known_namespaces() {
         let mut app = App::new_test();
-        app.mode = AppMode::LogView;
-
-        handle_input(&mut app, key(KeyCode::Esc));
-        assert_eq!(app.mode, AppMode::List);
+        app.available_namespaces = vec!["a".into(), "default".into(), "z".into()];
+
+        handle_input(&mut app, key(KeyCode::Char(']')));
+        assert_eq!(app.current_namespace, "z");
+        handle_input(&mut app, key(KeyCode::Char(']')));
+        assert_eq!(app.current_namespace, "a");
+        handle_input(&mut app, key(KeyCode::Char('[')));
+        assert_eq!(app.current_namespace, "z");
+        handle_input(&mut app, key(KeyCode::Char('-')));
+        assert_eq!(app.current_namespace, "a");
     }
 
     #[tokio::test]
-    async fn log_q_exits_to_list() {
+    async fn access_review_panel_closes() {
         let mut app = App::new_test();
-        app.mode = AppMode::LogView;
-
+        app.access_checks = crate::models::ACCESS_CHECKS.to_vec();
+        app.mode = AppMode::AccessReview;
         handle_input(&mut app, key(KeyCode::Char('q')));
         assert_eq!(app.mode, AppMode::List);
+        assert!(app.access_checks.is_empty());
     }
 
     #[tokio::test]
-    async fn log_scroll_up_at_top_triggers_load_more() {
+    async fn read_only_context_blocks_mutations() {
         let mut app = App::new_test();
-        app.mode = AppMode::LogView;
-        app.log_pod_name = "test-pod".into();
-        app.log_namespace = "default".into();
-        for i in 0..50 {
-            app.log_buffer.push_back(format!("line {i}"));
-        }
-        app.log_scroll_offset = Some(0);
+        app.read_only = true;
+        app.active_tab = ResourceType::Pod;
+        app.items = vec![make_pod("web-1")];
+        app.filtered_items = app.items.clone();
+        app.table_state.select(Some(0));
 
-        handle_input(&mut app, key(KeyCode::Char('k')));
-        assert!(app.log_loading_history);
-        assert_eq!(app.log_tail_lines, 200);
+        handle_input(&mut app, key(KeyCode::Char('D')));
+        assert_eq!(app.mode, AppMode::List);
+        assert!(app.last_error.as_ref().unwrap().contains("read-only"));
+
+        handle_input(&mut app, key(KeyCode::Char('s')));
+        assert_eq!(app.mode, AppMode::List);
+
+        handle_input(&mut app, key(KeyCode::Char('l')));
+        assert_eq!(app.mode, AppMode::LogView);
     }
 
     #[tokio::test]
-    async fn log_load_more_skips_when_already_loading() {
+    async fn question_mark_opens_help_in_pager() {
         let mut app = App::new_test();
-        app.mode = AppMode::LogView;
-        app.log_pod_name = "test-pod".into();
-        app.log_namespace = "default".into();
-        app.log_loading_history = true;
-        app.log_tail_lines = 200;
-        app.log_scroll_offset = Some(0);
+        handle_input(&mut app, key(KeyCode::Char('?')));
+        assert_eq!(app.mode, AppMode::ValuePager);
+        assert_eq!(app.pager_title, "Keybindings");
+        assert!(app.pager_text.contains("Secrets"));
 
-        handle_input(&mut app, key(KeyCode::Char('k')));
-        assert_eq!(app.log_tail_lines, 200);
+        handle_input(&mut app, key(KeyCode::Char('q')));
+        assert_eq!(app.mode, AppMode::List);
     }
 
     #[tokio::test]
-    async fn log_pageup_at_top_triggers_load_more() {
+    async fn command_palette_runs_and_recalls_commands() {
         let mut app = App::new_test();
-        app.mode = AppMode::LogView;
-        app.log_pod_name = "test-pod".into();
-        app.log_namespace = "default".into();
-        for i in 0..50 {
-            app.log_buffer.push_back(format!("line {i}"));
+        handle_input(&mut app, key(KeyCode::Char(':')));
+        assert_eq!(app.mode, AppMode::CommandInput);
+        for c in "de".chars() {
+            handle_input(&mut app, key(KeyCode::Char(c)));
         }
-        app.log_scroll_offset = Some(0);
+        handle_input(&mut app, key(KeyCode::Tab));
+        assert_eq!(app.command_input, "deploy");
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::List);
+        assert_eq!(app.active_tab, ResourceType::Deployment);
 
-        handle_input(&mut app, key(KeyCode::PageUp));
-        assert!(app.log_loading_history);
-        assert_eq!(app.log_tail_lines, 200);
-    }
+        handle_input(&mut app, key(KeyCode::Char(':')));
+        for c in "ns kube-s".chars() {
+            handle_input(&mut app, key(KeyCode::Char(c)));
+        }
+        handle_input(&mut app, key(KeyCode::Tab));
+        assert_eq!(app.command_input, "ns kube-system");
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.current_namespace, "kube-system");
 
-    #[tokio::test]
-    async fn scale_accepts_digits() {
-        let mut app = App::new_test();
-        app.mode = AppMode::ScaleInput;
+        handle_input(&mut app, key(KeyCode::Char(':')));
+        handle_input(&mut app, key(KeyCode::Up));
+        assert_eq!(app.command_input, "ns kube-system");
+        handle_input(&mut app, key(KeyCode::Up));
+        assert_eq!(app.command_input, "deploy");
+        handle_input(&mut app, key(KeyCode::Down));
+        assert_eq!(app.command_input, "ns kube-system");
+        handle_input(&mut app, key(KeyCode::Esc));
 
-        handle_input(&mut app, key(KeyCode::Char('3')));
-        assert_eq!(app.scale_input, "3");
+        handle_input(&mut app, key(KeyCode::Char(':')));
+        for c in "ctx nope".chars() {
+            handle_input(&mut app, key(KeyCode::Char(c)));
+        }
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert!(app.last_error.as_ref().unwrap().contains("Unknown context"));
+        assert!(app.pending_context.is_none());
+    }
 
-        handle_input(&mut app, key(KeyCode::Char('5')));
-        assert_eq!(app.scale_input, "35");
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
     }
 
     #[tokio::test]
-    async fn scale_rejects_letters() {
+    async fn mouse_wheel_moves_selection_without_wrapping() {
         let mut app = App::new_test();
-        app.mode = AppMode::ScaleInput;
-
-        handle_input(&mut app, key(KeyCode::Char('a')));
-        assert_eq!(app.scale_input, "");
+        app.filtered_items = vec![make_pod("a"), make_pod("b")];
+        app.table_state.select(Some(0));
+        handle_mouse(&mut app, mouse(MouseEventKind::ScrollUp, 5, 5));
+        assert_eq!(app.table_state.selected(), Some(0));
+        handle_mouse(&mut app, mouse(MouseEventKind::ScrollDown, 5, 5));
+        handle_mouse(&mut app, mouse(MouseEventKind::ScrollDown, 5, 5));
+        assert_eq!(app.table_state.selected(), Some(1));
     }
 
     #[tokio::test]
-    async fn scale_backspace() {
+    async fn mouse_click_selects_tab_and_row() {
         let mut app = App::new_test();
-        app.mode = AppMode::ScaleInput;
-        app.scale_input = "12".to_string();
+        app.hit_areas.tabs = ratatui::layout::Rect::new(0, 0, 80, 1);
+        app.hit_areas.table = ratatui::layout::Rect::new(0, 3, 80, 10);
+        handle_mouse(
+            &mut app,
+            mouse(MouseEventKind::Down(MouseButton::Left), 9, 0),
+        );
+        assert_eq!(app.active_tab, ResourceType::Deployment);
 
-        handle_input(&mut app, key(KeyCode::Backspace));
-        assert_eq!(app.scale_input, "1");
+        app.filtered_items = vec![make_pod("a"), make_pod("b"), make_pod("c")];
+        handle_mouse(
+            &mut app,
+            mouse(MouseEventKind::Down(MouseButton::Left), 5, 7),
+        );
+        assert_eq!(app.table_state.selected(), Some(1));
+        // Header row and rows past the end are ignored.
+        handle_mouse(
+            &mut app,
+            mouse(MouseEventKind::Down(MouseButton::Left), 5, 4),
+        );
+        handle_mouse(
+            &mut app,
+            mouse(MouseEventKind::Down(MouseButton::Left), 5, 10),
+        );
+        assert_eq!(app.table_state.selected(), Some(1));
     }
 
     #[tokio::test]
-    async fn scale_esc_cancels() {
+    async fn mouse_click_outside_modal_closes_it() {
         let mut app = App::new_test();
-        app.mode = AppMode::ScaleInput;
+        app.mode = AppMode::DescribeView;
+        app.hit_areas.modal = Some(ratatui::layout::Rect::new(10, 5, 40, 10));
+        handle_mouse(
+            &mut app,
+            mouse(MouseEventKind::Down(MouseButton::Left), 20, 8),
+        );
+        assert_eq!(app.mode, AppMode::DescribeView);
+        handle_mouse(
+            &mut app,
+            mouse(MouseEventKind::Down(MouseButton::Left), 2, 2),
+        );
+        assert_eq!(app.mode, AppMode::List);
+    }
 
+    #[tokio::test]
+    async fn esc_unwinds_the_view_stack() {
+        let mut app = App::new_test();
+        app.push_mode(AppMode::SecretDecode);
+        app.open_pager("value".to_string(), "text".to_string());
+        assert_eq!(app.mode, AppMode::ValuePager);
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::SecretDecode);
         handle_input(&mut app, key(KeyCode::Esc));
         assert_eq!(app.mode, AppMode::List);
+        assert!(app.mode_stack.is_empty());
     }
 
     #[tokio::test]
-    async fn confirm_n_cancels() {
+    async fn wide_mode_scrolls_columns_only_when_enabled() {
         let mut app = App::new_test();
-        app.mode = AppMode::Confirm;
-        app.pending_action = Some(PendingAction::DeleteResource {
-            count: 1,
-            kind: "pod(s)",
-            names: vec!["test".into()],
-        });
-
-        handle_input(&mut app, key(KeyCode::Char('n')));
-        assert_eq!(app.mode, AppMode::List);
-        assert!(app.pending_action.is_none());
+        handle_input(&mut app, key(KeyCode::Right));
+        assert_eq!(app.column_offset, 0);
+        handle_input(&mut app, key(KeyCode::Char('w')));
+        assert!(app.wide);
+        handle_input(&mut app, key(KeyCode::Right));
+        handle_input(&mut app, key(KeyCode::Right));
+        handle_input(&mut app, key(KeyCode::Left));
+        assert_eq!(app.column_offset, 1);
+        handle_input(&mut app, key(KeyCode::Char('w')));
+        assert!(!app.wide);
+        assert_eq!(app.column_offset, 0);
     }
 
     #[tokio::test]
-    async fn confirm_esc_cancels() {
+    async fn bulk_delete_requires_typed_count() {
         let mut app = App::new_test();
+        app.config.type_to_confirm_above = Some(1);
         app.mode = AppMode::Confirm;
         app.pending_action = Some(PendingAction::DeleteResource {
-            count: 1,
+            count: 2,
             kind: "pod(s)",
-            names: vec!["test".into()],
+            names: vec!["a".into(), "b".into()],
         });
 
+        handle_input(&mut app, key(KeyCode::Char('y')));
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert_eq!(app.confirm_input, "y");
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::Confirm);
+
+        handle_input(&mut app, key(KeyCode::Backspace));
+        handle_input(&mut app, key(KeyCode::Char('2')));
         handle_input(&mut app, key(KeyCode::Esc));
         assert_eq!(app.mode, AppMode::List);
         assert!(app.pending_action.is_none());
+        assert!(app.confirm_input.is_empty());
     }
 
     #[tokio::test]
-    async fn delete_key_opens_confirm_for_pod() {
+    async fn protected_namespace_requires_typing_the_context() {
         let mut app = App::new_test();
-        app.active_tab = ResourceType::Pod;
-        app.filtered_items = vec![make_pod("nginx")];
-        app.table_state.select(Some(0));
+        app.config.contexts.insert(
+            "test-context".into(),
+            crate::config::ContextConfig {
+                protected_namespaces: vec!["default".into()],
+                ..Default::default()
+            },
+        );
+        app.current_namespace = "default".into();
+        app.mode = AppMode::Confirm;
+        app.pending_action = Some(PendingAction::ScaleDeployment {
+            name: "api".into(),
+            replicas: 0,
+        });
+        assert!(app.protected());
 
-        handle_input(&mut app, key(KeyCode::Delete));
+        handle_input(&mut app, key(KeyCode::Char('y')));
         assert_eq!(app.mode, AppMode::Confirm);
-        assert!(app.pending_action.is_some());
+        for c in "test-context".chars() {
+            handle_input(&mut app, key(KeyCode::Char(c)));
+        }
+        assert_eq!(app.confirm_input, "ytest-context");
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::Confirm);
+
+        app.current_namespace = "staging".into();
+        assert!(!app.protected());
+        assert_eq!(app.typed_confirmation(), None);
     }
 
-    #[tokio::test]
-    async fn s_starts_shell_for_pod() {
-        let mut app = App::new_test();
-        app.active_tab = ResourceType::Pod;
-        app.filtered_items = vec![make_pod("nginx")];
-        app.table_state.select(Some(0));
+    /// Marks the test context protected.
+    fn protect(app: &mut App) {
+        app.config.contexts.insert(
+            "test-context".into(),
+            crate::config::ContextConfig {
+                protected: true,
+                ..Default::default()
+            },
+        );
+    }
 
-        handle_input(&mut app, key(KeyCode::Char('s')));
-        assert!(app.mode == AppMode::ShellView || app.last_error.is_some());
+    /// Checks a plain `y` leaves the write pending, then types the context name.
+    fn confirm_protected_write(app: &mut App) {
+        assert_eq!(app.mode, AppMode::Confirm);
+        handle_input(app, key(KeyCode::Char('y')));
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert!(app.pending_action.is_some());
+        app.confirm_input.clear();
+        for c in "test-context".chars() {
+            handle_input(app, key(KeyCode::Char(c)));
+        }
+        handle_input(app, key(KeyCode::Enter));
+        assert!(app.pending_action.is_none());
     }
 
     #[tokio::test]
-    async fn shift_s_opens_scale_for_deployment() {
+    async fn protected_context_guards_creates_and_key_patches() {
         let mut app = App::new_test();
-        app.active_tab = ResourceType::Deployment;
-        let mut dep = k8s_openapi::api::apps::v1::Deployment::default();
-        dep.metadata.name = Some("web".to_string());
-        app.filtered_items = vec![KubeResource::Deployment(Arc::new(dep))];
-        app.table_state.select(Some(0));
+        protect(&mut app);
 
-        handle_input(&mut app, key(KeyCode::Char('S')));
-        assert_eq!(app.mode, AppMode::ScaleInput);
+        app.push_mode(AppMode::SecretCreate);
+        app.secret_form.name = "api".into();
+        app.secret_form.entries = vec![("token".into(), "t".into())];
+        handle_input(
+            &mut app,
+            key_with_mod(KeyCode::Char('s'), KeyModifiers::CONTROL),
+        );
+        let object = app.object_key("secret", "api");
+        assert!(app.tasks.in_flight("create", &object).is_none());
+        confirm_protected_write(&mut app);
+        assert_eq!(app.mode, AppMode::List);
+        assert!(app.tasks.in_flight("create", &object).is_some());
+
+        app.mode = AppMode::SecretEditValue;
+        app.decoded_secret_name = "api".into();
+        app.selected_secret_decoded = Some(vec![("token".into(), text("t"))]);
+        app.secret_edit_input = "u".into();
+        handle_input(&mut app, key(KeyCode::Enter));
+        let object = app.object_key("secret", "api");
+        assert!(app.tasks.in_flight("patch token", &object).is_none());
+        confirm_protected_write(&mut app);
+        assert_eq!(app.mode, AppMode::SecretDecode);
+        assert!(app.tasks.in_flight("patch token", &object).is_some());
+
+        app.mode = AppMode::NamespaceCreate;
+        app.namespace_create_input = "scratch".into();
+        handle_input(&mut app, key(KeyCode::Enter));
+        let object = app.object_key("namespace", "scratch");
+        assert!(app.tasks.in_flight("create", &object).is_none());
+        confirm_protected_write(&mut app);
+        assert_eq!(app.mode, AppMode::NamespaceSelect);
+        assert!(app.tasks.in_flight("create", &object).is_some());
     }
 
     #[tokio::test]
-    async fn namespace_rejects_invalid_name() {
+    async fn manifest_apply_into_a_protected_namespace_needs_the_context() {
         let mut app = App::new_test();
-        app.mode = AppMode::NamespaceSelect;
-        app.available_namespaces = vec![];
-        app.filtered_namespaces = vec![];
-        app.popup_state.select(None);
+        app.config.contexts.insert(
+            "test-context".into(),
+            crate::config::ContextConfig {
+                protected_namespaces: vec!["kube-system".into()],
+                ..Default::default()
+            },
+        );
+        let objects = crate::k8s::actions::parse_manifest(
+            "apiVersion: v1\nkind: ConfigMap\nmetadata: {name: a, namespace: kube-system}\n",
+        )
+        .unwrap();
+        let plan = |namespace: &str| crate::models::ManifestObject {
+            label: "configmap/a".into(),
+            object: objects[0].clone(),
+            resource: None,
+            namespace: Some(namespace.into()),
+            plan: Err("forbidden".into()),
+            created: false,
+        };
+        assert!(!app.protected());
+
+        app.show_manifest_plan(vec![plan("kube-system")]);
+        handle_input(&mut app, key(KeyCode::Char('y')));
+        assert!(!app.manifest_plan.is_empty());
+        confirm_protected_write(&mut app);
+        assert_eq!(app.mode, AppMode::List);
+        assert!(app.manifest_plan.is_empty());
 
-        handle_input(&mut app, key(KeyCode::Char('/')));
-        handle_input(&mut app, key(KeyCode::Char('M')));
+        app.show_manifest_plan(vec![plan("staging")]);
         handle_input(&mut app, key(KeyCode::Char('y')));
-        handle_input(&mut app, key(KeyCode::Enter));
-        assert!(app.last_error.is_some());
+        assert_eq!(app.mode, AppMode::List);
+        assert!(app.manifest_plan.is_empty());
     }
 
     #[tokio::test]
-    async fn namespace_rejects_trailing_hyphen() {
+    async fn edit_apply_in_a_protected_context_needs_the_context() {
         let mut app = App::new_test();
-        app.mode = AppMode::NamespaceSelect;
-        app.available_namespaces = vec![];
-        app.filtered_namespaces = vec![];
-        app.popup_state.select(None);
+        protect(&mut app);
+        app.resource_edit = Some(crate::models::ResourceEdit {
+            kind: "deployment",
+            name: "web".into(),
+            namespace: "default".into(),
+            original: String::new(),
+            edited: String::new(),
+            error: None,
+        });
+        app.push_mode(AppMode::EditDiff);
 
-        handle_input(&mut app, key(KeyCode::Char('/')));
-        for c in "my-ns-".chars() {
-            handle_input(&mut app, key(KeyCode::Char(c)));
-        }
-        handle_input(&mut app, key(KeyCode::Enter));
-        assert!(app.last_error.is_some());
+        handle_input(&mut app, key(KeyCode::Char('y')));
+        assert!(app.resource_edit.is_some());
+        confirm_protected_write(&mut app);
+        assert_eq!(app.mode, AppMode::List);
+        assert!(app.resource_edit.is_none());
+        let object = app.object_key("deployment", "web");
+        assert!(app.tasks.in_flight("apply", &object).is_some());
     }
 
     #[tokio::test]
-    async fn scale_rejects_over_1000() {
+    async fn count_prefix_repeats_motion() {
         let mut app = App::new_test();
-        app.mode = AppMode::ScaleInput;
-        app.active_tab = ResourceType::Deployment;
-        let mut dep = k8s_openapi::api::apps::v1::Deployment::default();
-        dep.metadata.name = Some("web".to_string());
-        app.filtered_items = vec![KubeResource::Deployment(Arc::new(dep))];
+        app.filtered_items = (0..20).map(|i| make_pod(&format!("p{i}"))).collect();
         app.table_state.select(Some(0));
-        app.scale_input = "9999".to_string();
 
-        handle_input(&mut app, key(KeyCode::Enter));
-        assert!(app.last_error.is_some());
-        assert!(app.last_error.as_ref().unwrap().contains("1000"));
+        handle_input(&mut app, key(KeyCode::Char('0')));
+        handle_input(&mut app, key(KeyCode::Char('1')));
+        handle_input(&mut app, key(KeyCode::Char('2')));
+        assert_eq!(app.pending_count, Some(12));
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.table_state.selected(), Some(12));
+        assert_eq!(app.pending_count, None);
+
+        handle_input(&mut app, key(KeyCode::Char('5')));
+        handle_input(&mut app, key(KeyCode::Char('k')));
+        assert_eq!(app.table_state.selected(), Some(7));
+
+        // A non-motion key drops the count.
+        handle_input(&mut app, key(KeyCode::Char('6')));
+        handle_input(&mut app, key(KeyCode::Char('p')));
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.table_state.selected(), Some(8));
     }
 
     #[tokio::test]
-    async fn esc_in_list_clears_filter() {
+    async fn number_keys_jump_to_tabs() {
         let mut app = App::new_test();
-        app.items = vec![make_pod("a"), make_pod("b")];
-        app.filter_query = "a".to_string();
-        app.status_filter.insert("Running".to_string());
-        app.update_filter();
-
+        handle_input(&mut app, key(KeyCode::Char('3')));
+        assert_eq!(app.active_tab, ResourceType::Secret);
+        assert_eq!(app.pending_count, None);
+        handle_input(&mut app, key(KeyCode::Char('9')));
+        assert_eq!(app.pending_count, Some(9));
         handle_input(&mut app, key(KeyCode::Esc));
-        assert_eq!(app.filter_query, "");
-        assert!(app.status_filter.is_empty());
-        assert_eq!(app.filtered_items.len(), 2);
-    }
-
-    #[test]
-    fn pty_alt_char_sends_esc_prefix() {
-        let ev = key_with_mod(KeyCode::Char(':'), KeyModifiers::ALT);
-        assert_eq!(key_to_pty_bytes(ev), vec![0x1b, b':']);
+        handle_input(&mut app, key(KeyCode::Char('1')));
+        assert_eq!(app.active_tab, ResourceType::Pod);
     }
 
-    #[test]
-    fn pty_alt_letter_sends_esc_prefix() {
-        let ev = key_with_mod(KeyCode::Char('d'), KeyModifiers::ALT);
-        assert_eq!(key_to_pty_bytes(ev), vec![0x1b, b'd']);
-    }
+    #[tokio::test]
+    async fn count_prefix_scrolls_describe() {
+        let mut app = App::new_test();
+        app.mode = AppMode::DescribeView;
+        app.describe_content = (0..500).map(|i| format!("line {i}")).collect();
 
-    #[test]
-    fn pty_ctrl_alt_sends_esc_plus_control_code() {
-        let ev = key_with_mod(
-            KeyCode::Char('c'),
-            KeyModifiers::CONTROL | KeyModifiers::ALT,
-        );
-        assert_eq!(key_to_pty_bytes(ev), vec![0x1b, 0x03]);
+        handle_input(&mut app, key(KeyCode::Char('4')));
+        handle_input(&mut app, key(KeyCode::Char('0')));
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.describe_scroll, 40);
     }
 
-    #[test]
-    fn pty_plain_char_no_esc_prefix() {
-        let ev = key(KeyCode::Char(':'));
-        assert_eq!(key_to_pty_bytes(ev), vec![b':']);
-    }
+    #[tokio::test]
+    async fn filter_up_down_recalls_history_for_the_tab() {
+        let mut app = App::new_test();
+        app.app_state.record_filter("pods", "api");
+        app.app_state.record_filter("pods", "web");
+        app.app_state.record_filter("secrets", "tls");
 
-    fn make_pod_with_status(name: &str, phase: &str) -> KubeResource {
-        use k8s_openapi::api::core::v1::PodStatus;
-        let mut pod = Pod::default();
-        pod.metadata.name = Some(name.to_string());
-        pod.status = Some(PodStatus {
-            phase: Some(phase.to_string()),
-            ..Default::default()
-        });
-        KubeResource::Pod(Arc::new(pod))
+        handle_input(&mut app, key(KeyCode::Char('/')));
+        handle_input(&mut app, key(KeyCode::Up));
+        assert_eq!(app.filter_query, "web");
+        handle_input(&mut app, key(KeyCode::Up));
+        handle_input(&mut app, key(KeyCode::Up));
+        assert_eq!(app.filter_query, "api");
+        handle_input(&mut app, key(KeyCode::Down));
+        assert_eq!(app.filter_query, "web");
+        handle_input(&mut app, key(KeyCode::Down));
+        assert!(app.filter_query.is_empty());
     }
 
     #[tokio::test]
-    async fn f_opens_status_filter() {
+    async fn visual_mode_selects_a_range() {
         let mut app = App::new_test();
-        app.items = vec![
-            make_pod_with_status("a", "Running"),
-            make_pod_with_status("b", "Pending"),
-        ];
-        handle_input(&mut app, key(KeyCode::Char('f')));
-        assert_eq!(app.mode, AppMode::StatusFilter);
-        assert_eq!(app.status_filter_items.len(), 2);
+        app.filtered_items = (0..10).map(|i| make_pod(&format!("p{i}"))).collect();
+        app.table_state.select(Some(0));
+        handle_input(&mut app, key(KeyCode::Char(' ')));
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        handle_input(&mut app, key(KeyCode::Char('j')));
+
+        handle_input(&mut app, key(KeyCode::Char('V')));
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.selected_indices, HashSet::from([0, 3, 4, 5]));
+        handle_input(&mut app, key(KeyCode::Char('k')));
+        assert_eq!(app.selected_indices, HashSet::from([0, 3, 4]));
+
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.visual_anchor, None);
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.selected_indices, HashSet::from([0, 3, 4]));
     }
 
     #[tokio::test]
-    async fn f_ignored_on_deployment_tab() {
+    async fn bang_inverts_selection() {
         let mut app = App::new_test();
-        app.active_tab = ResourceType::Deployment;
-        handle_input(&mut app, key(KeyCode::Char('f')));
-        assert_eq!(app.mode, AppMode::List);
+        app.filtered_items = (0..4).map(|i| make_pod(&format!("p{i}"))).collect();
+        app.selected_indices = HashSet::from([1, 2]);
+
+        handle_input(&mut app, key(KeyCode::Char('!')));
+        assert_eq!(app.selected_indices, HashSet::from([0, 3]));
     }
 
     #[tokio::test]
-    async fn status_filter_space_toggles() {
+    async fn action_menu_runs_the_chosen_key() {
         let mut app = App::new_test();
-        app.items = vec![
-            make_pod_with_status("a", "Running"),
-            make_pod_with_status("b", "Pending"),
-        ];
-        app.build_status_filter_items();
-        app.status_filter_state.select(Some(0));
-        app.mode = AppMode::StatusFilter;
+        app.active_tab = ResourceType::Pod;
+        app.filtered_items = vec![make_pod("nginx")];
+        app.table_state.select(Some(0));
 
-        handle_input(&mut app, key(KeyCode::Char(' ')));
-        assert!(app.status_filter_selected.contains(&0));
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::ActionMenu);
+        handle_input(&mut app, key(KeyCode::Char('k')));
+        assert_eq!(app.action_menu_state.selected(), Some(0));
+        for _ in 0..10 {
+            handle_input(&mut app, key(KeyCode::Char('j')));
+        }
+        // The last pod action is Delete.
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert!(matches!(
+            app.pending_action,
+            Some(PendingAction::DeleteResource { count: 1, .. })
+        ));
 
-        handle_input(&mut app, key(KeyCode::Char(' ')));
-        assert!(!app.status_filter_selected.contains(&0));
+        handle_input(&mut app, key(KeyCode::Esc));
+        handle_input(&mut app, key(KeyCode::Char('.')));
+        handle_input(&mut app, key(KeyCode::Char('x')));
+        assert_eq!(app.mode, AppMode::ActionMenu);
+        handle_input(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.mode, AppMode::List);
     }
 
     #[tokio::test]
-    async fn status_filter_enter_applies() {
+    async fn plugin_menu_lists_the_tab_s_plugins() {
         let mut app = App::new_test();
-        app.items = vec![
-            make_pod_with_status("a", "Running"),
-            make_pod_with_status("b", "Pending"),
-            make_pod_with_status("c", "Running"),
-        ];
-        app.update_filter();
-        assert_eq!(app.filtered_items.len(), 3);
-
-        app.build_status_filter_items();
-        app.status_filter_state.select(Some(0));
-        app.mode = AppMode::StatusFilter;
+        app.active_tab = ResourceType::Pod;
+        app.filtered_items = vec![make_pod("nginx")];
+        app.table_state.select(Some(0));
 
-        let running_idx = app
-            .status_filter_items
-            .iter()
-            .position(|(p, _)| p == "Pending")
-            .unwrap();
-        app.status_filter_selected.insert(running_idx);
+        handle_input(&mut app, key(KeyCode::Char('P')));
+        assert_eq!(app.mode, AppMode::List);
+        assert!(app.last_error.as_ref().unwrap().starts_with("No plugins"));
+
+        app.config.plugins = crate::config::Config::parse(
+            "plugins:\n  - {name: Rollouts, command: x, scopes: [deploy]}\n  - {name: Evict, key: v, command: x, confirm: true, dangerous: true}\n",
+        )
+        .unwrap()
+        .plugins;
+        handle_input(&mut app, key(KeyCode::Char('P')));
+        assert_eq!(app.mode, AppMode::PluginMenu);
+        handle_input(&mut app, key(KeyCode::Char('v')));
+        assert_eq!(app.mode, AppMode::Confirm);
+        assert!(matches!(
+            app.pending_action,
+            Some(PendingAction::RunPlugin { index: 1, ref target, .. }) if target == "nginx"
+        ));
+        handle_input(&mut app, key(KeyCode::Esc));
 
-        handle_input(&mut app, key(KeyCode::Enter));
+        // Dangerous plugins are hidden in read-only contexts.
+        app.read_only = true;
+        handle_input(&mut app, key(KeyCode::Char('P')));
         assert_eq!(app.mode, AppMode::List);
-        assert_eq!(app.filtered_items.len(), 1);
-        assert_eq!(app.filtered_items[0].name(), "b");
     }
 
     #[tokio::test]
-    async fn status_filter_esc_cancels() {
+    async fn o_offers_a_menu_when_the_context_has_several_links() {
         let mut app = App::new_test();
-        app.items = vec![
-            make_pod_with_status("a", "Running"),
-            make_pod_with_status("b", "Pending"),
-        ];
-        app.update_filter();
-        app.build_status_filter_items();
-        app.status_filter_state.select(Some(0));
-        app.status_filter_selected.insert(0);
-        app.mode = AppMode::StatusFilter;
+        app.active_tab = ResourceType::Pod;
+        app.filtered_items = vec![make_pod("nginx")];
+        app.table_state.select(Some(0));
 
+        handle_input(&mut app, key(KeyCode::Char('o')));
+        assert!(app.last_error.as_ref().unwrap().starts_with("No links"));
+
+        let config = crate::config::Config::parse(&format!(
+            "contexts:\n  {}:\n    links:\n      - {{name: A, url: 'https://a/$NAME'}}\n      - {{name: B, url: 'https://b/$NAME'}}\n",
+            app.current_context
+        ))
+        .unwrap();
+        app.config.contexts = config.contexts;
+        handle_input(&mut app, key(KeyCode::Char('o')));
+        assert_eq!(app.mode, AppMode::LinkMenu);
+        handle_input(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.link_menu_state.selected(), Some(1));
         handle_input(&mut app, key(KeyCode::Esc));
         assert_eq!(app.mode, AppMode::List);
-        assert!(app.status_filter.is_empty());
-        assert_eq!(app.filtered_items.len(), 2);
     }
 
     #[tokio::test]
-    async fn status_filter_a_toggles_all() {
+    async fn x_exports_the_selected_rows_as_manifests() {
         let mut app = App::new_test();
-        app.items = vec![
-            make_pod_with_status("a", "Running"),
-            make_pod_with_status("b", "Pending"),
-        ];
-        app.build_status_filter_items();
-        app.status_filter_state.select(Some(0));
-        app.mode = AppMode::StatusFilter;
+        app.active_tab = ResourceType::Pod;
+        app.filtered_items = vec![make_pod("a"), make_pod("b"), make_pod("c")];
+        app.table_state.select(Some(0));
+        app.selected_indices = HashSet::from([1, 2]);
+        let dir = std::env::temp_dir().join(format!("kr-manifests-{}", std::process::id()));
 
-        handle_input(&mut app, key(KeyCode::Char('a')));
-        assert_eq!(app.status_filter_selected.len(), 2);
+        handle_input(&mut app, key(KeyCode::Char('X')));
+        assert_eq!(app.mode, AppMode::ManifestExport);
+        app.export_path_input = dir.display().to_string();
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::List);
 
-        handle_input(&mut app, key(KeyCode::Char('a')));
-        assert!(app.status_filter_selected.is_empty());
+        let mut files: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        let manifest = std::fs::read_to_string(dir.join("pod-b.yaml")).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(files, ["pod-b.yaml", "pod-c.yaml"]);
+        assert!(manifest.contains("kind: Pod") && manifest.contains("name: b"));
     }
 
     #[tokio::test]
-    async fn status_filter_enter_selects_cursor_when_none_toggled() {
+    async fn metrics_need_a_prometheus_for_the_context() {
         let mut app = App::new_test();
-        app.items = vec![
-            make_pod_with_status("a", "Running"),
-            make_pod_with_status("b", "Pending"),
-            make_pod_with_status("c", "Running"),
-        ];
-        app.update_filter();
-        app.build_status_filter_items();
-
-        let pending_idx = app
-            .status_filter_items
-            .iter()
-            .position(|(p, _)| p == "Pending")
-            .unwrap();
-        app.status_filter_state.select(Some(pending_idx));
-        app.mode = AppMode::StatusFilter;
+        app.active_tab = ResourceType::Pod;
+        app.filtered_items = vec![make_pod("api")];
+        app.table_state.select(Some(0));
 
-        handle_input(&mut app, key(KeyCode::Enter));
+        handle_input(&mut app, key(KeyCode::Char('M')));
         assert_eq!(app.mode, AppMode::List);
-        assert_eq!(app.filtered_items.len(), 1);
-        assert_eq!(app.filtered_items[0].name(), "b");
+        assert!(app.last_error.as_deref().unwrap().contains("No Prometheus"));
     }
 
     #[tokio::test]
-    async fn log_slash_enters_search_input() {
+    async fn shift_w_toggles_a_watch_on_the_selected_row() {
         let mut app = App::new_test();
-        app.mode = AppMode::LogView;
+        app.active_tab = ResourceType::Pod;
+        app.filtered_items = vec![make_pod("api")];
+        app.table_state.select(Some(0));
 
-        handle_input(&mut app, key(KeyCode::Char('/')));
-        assert_eq!(app.mode, AppMode::LogSearchInput);
+        handle_input(&mut app, key(KeyCode::Char('W')));
+        assert!(
+            app.last_success
+                .as_deref()
+                .unwrap()
+                .starts_with("Watching pod/api")
+        );
+        assert!(app.tasks.labels().iter().any(|l| l.ends_with("/pod/api")));
+        handle_input(&mut app, key(KeyCode::Char('W')));
+        assert_eq!(
+            app.last_success.as_deref(),
+            Some("Stopped watching pod/api")
+        );
     }
 
     #[tokio::test]
-    async fn log_search_input_accumulates_chars() {
+    async fn action_menu_entries_map_to_kubectl_commands() {
         let mut app = App::new_test();
-        app.mode = AppMode::LogSearchInput;
+        app.current_context = "prod".to_string();
+        app.current_namespace = "payments".to_string();
+        app.active_tab = ResourceType::Pod;
+        app.filtered_items = vec![make_pod("api")];
+        app.table_state.select(Some(0));
 
-        handle_input(&mut app, key(KeyCode::Char('e')));
-        handle_input(&mut app, key(KeyCode::Char('r')));
-        handle_input(&mut app, key(KeyCode::Char('r')));
-        assert_eq!(app.log_search_input, "err");
+        assert_eq!(
+            app.action_kubectl('l').as_deref(),
+            Some("kubectl --context prod -n payments logs -f api")
+        );
+        assert_eq!(
+            app.action_kubectl('s').as_deref(),
+            Some("kubectl --context prod -n payments exec -it api -- sh")
+        );
+        assert_eq!(
+            app.action_kubectl('D').as_deref(),
+            Some("kubectl --context prod -n payments delete pod api")
+        );
+        assert_eq!(app.action_kubectl('u'), None);
     }
 
     #[tokio::test]
-    async fn log_search_enter_confirms() {
+    async fn export_command_writes_the_filtered_table() {
         let mut app = App::new_test();
-        app.mode = AppMode::LogSearchInput;
-        app.log_search_input = "test".to_string();
+        app.active_tab = ResourceType::Pod;
+        app.items = vec![make_pod("api"), make_pod("web")];
+        app.filtered_items = vec![make_pod("web")];
+        let path = std::env::temp_dir().join(format!("kr-export-{}.tsv", std::process::id()));
 
+        app.mode = AppMode::CommandInput;
+        app.command_input = format!("export {}", path.display());
         handle_input(&mut app, key(KeyCode::Enter));
-        assert_eq!(app.mode, AppMode::LogView);
-        assert_eq!(app.log_search_query, "test");
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "Name\tReady\tStatus\tRestarts\tAge");
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].starts_with("web\t0/0\t"));
     }
 
     #[tokio::test]
-    async fn log_search_esc_cancels() {
+    async fn shift_r_requests_a_relist_keeping_the_cursor() {
         let mut app = App::new_test();
-        app.mode = AppMode::LogSearchInput;
-        app.log_search_input = "test".to_string();
-        app.log_search_query = "old".to_string();
+        app.active_tab = ResourceType::Pod;
+        app.filtered_items = vec![make_pod("a"), make_pod("b")];
+        app.table_state.select(Some(1));
+        app.selected_indices.insert(0);
 
-        handle_input(&mut app, key(KeyCode::Esc));
-        assert_eq!(app.mode, AppMode::LogView);
-        assert_eq!(app.log_search_input, "");
-        assert_eq!(app.log_search_query, "old");
+        handle_input(&mut app, key(KeyCode::Char('R')));
+        assert!(app.refresh_requested);
+        assert_eq!(app.restore_selection.as_deref(), Some("b"));
+        assert!(app.selected_indices.is_empty());
     }
 
     #[tokio::test]
-    async fn log_esc_clears_search_first() {
+    async fn marks_jump_back_across_tabs_and_namespaces() {
         let mut app = App::new_test();
-        app.mode = AppMode::LogView;
-        app.log_search_query = "test".to_string();
+        app.active_tab = ResourceType::Pod;
+        app.current_namespace = "payments".to_string();
+        app.items = vec![make_pod("a"), make_pod("api")];
+        app.update_filter();
+        app.table_state.select(Some(1));
 
-        handle_input(&mut app, key(KeyCode::Esc));
-        assert_eq!(app.mode, AppMode::LogView);
-        assert_eq!(app.log_search_query, "");
+        handle_input(&mut app, key(KeyCode::Char('m')));
+        handle_input(&mut app, key(KeyCode::Char('a')));
+        assert_eq!(app.marks[&'a'].name, "api");
+        assert_eq!(app.pending_mark, None);
+
+        app.select_tab(ResourceType::Secret);
+        app.switch_namespace("other".to_string());
+        handle_input(&mut app, key(KeyCode::Char('\'')));
+        handle_input(&mut app, key(KeyCode::Char('a')));
+        assert_eq!(app.active_tab, ResourceType::Pod);
+        assert_eq!(app.current_namespace, "payments");
+        assert_eq!(app.restore_selection.as_deref(), Some("api"));
+
+        handle_input(&mut app, key(KeyCode::Char('\'')));
+        handle_input(&mut app, key(KeyCode::Char('z')));
+        assert!(app.last_error.as_deref().unwrap().contains("not set"));
     }
 
     #[tokio::test]
-    async fn log_esc_exits_when_no_search() {
+    async fn describe_search_jumps_to_matches_and_wraps() {
         let mut app = App::new_test();
-        app.mode = AppMode::LogView;
-        app.log_search_query.clear();
+        app.show_describe(
+            vec![
+                "Name: api".into(),
+                "Status: Running".into(),
+                "Conditions:".into(),
+                "  Ready True".into(),
+                "  ContainersReady True".into(),
+            ],
+            false,
+        );
+        assert_eq!(app.mode, AppMode::DescribeView);
+        handle_input(&mut app, key(KeyCode::Char('/')));
+        assert_eq!(app.mode, AppMode::DescribeSearchInput);
+        for c in "READY".chars() {
+            handle_input(&mut app, key(KeyCode::Char(c)));
+        }
+        handle_input(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::DescribeView);
+        assert_eq!(app.describe_query, "ready");
+        assert_eq!(app.describe_scroll, 3);
+        handle_input(&mut app, key(KeyCode::Char('n')));
+        assert_eq!(app.describe_scroll, 4);
+        handle_input(&mut app, key(KeyCode::Char('n')));
+        assert_eq!(app.describe_scroll, 3);
+        handle_input(&mut app, key(KeyCode::Char('N')));
+        assert_eq!(app.describe_scroll, 4);
 
+        // A refresh keeps the position; once closed, late results are dropped.
+        app.show_describe(vec!["Name: api".into(); 10], true);
+        assert_eq!(app.describe_scroll, 4);
+        assert_eq!(app.describe_content.len(), 10);
         handle_input(&mut app, key(KeyCode::Esc));
+        app.show_describe(vec!["stale".into()], true);
         assert_eq!(app.mode, AppMode::List);
+        assert!(app.describe_content.is_empty());
     }
 
     #[tokio::test]
-    async fn log_n_jumps_to_next_match() {
+    async fn describe_auto_refresh_toggles_while_open() {
+        let mut app = App::new_test();
+        app.show_describe(vec!["Name: api".into()], false);
+        assert!(!app.describe_refresh_due());
+        handle_input(&mut app, key(KeyCode::Char('a')));
+        assert!(app.describe_auto_refresh);
+        assert!(app.describe_refresh_due());
+        app.pop_mode();
+        assert!(!app.describe_refresh_due());
+    }
+
+    #[tokio::test]
+    async fn pipe_hands_the_view_to_the_external_pager() {
         let mut app = App::new_test();
+        app.show_describe(vec!["Name: api".into(), "Status: Running".into()], false);
+        handle_input(&mut app, key(KeyCode::Char('|')));
+        assert_eq!(
+            app.pending_editor.take(),
+            Some(EditorRequest::Pager("Name: api\nStatus: Running".into()))
+        );
+        assert_eq!(app.mode, AppMode::DescribeView);
+
         app.mode = AppMode::LogView;
-        for i in 0..50 {
-            app.log_buffer.push_back(format!("line {i}"));
-        }
-        app.log_buffer.push_back("error found here".to_string());
-        for i in 51..200 {
-            app.log_buffer.push_back(format!("line {i}"));
-        }
-        app.log_scroll_offset = Some(100);
-        app.log_search_query = "error".to_string();
+        app.log_buffer
+            .extend(["one".to_string(), "two".to_string()]);
+        handle_input(&mut app, key(KeyCode::Char('|')));
+        assert_eq!(
+            app.pending_editor.take(),
+            Some(EditorRequest::Pager("one\ntwo".into()))
+        );
+    }
 
-        handle_input(&mut app, key(KeyCode::Char('n')));
-        assert_eq!(app.log_search_match_line, Some(50));
+    #[tokio::test]
+    async fn tsh_login_needs_a_teleport_context() {
+        let mut app = App::new_test();
+        app.available_contexts = vec!["kind".into()];
+        app.filtered_contexts = app.available_contexts.clone();
+        app.mode = AppMode::ContextSelect;
+        app.popup_state.select(Some(0));
+        handle_input(&mut app, key(KeyCode::Char('L')));
+        assert_eq!(app.tsh_login, None);
+        assert!(app.last_error.as_ref().is_some_and(|e| e.contains("tsh")));
+
+        app.mode = AppMode::List;
+        run_command(
+            &mut app,
+            crate::command::Command::Login(Some("prod".into())),
+        );
+        assert_eq!(app.tsh_login, None);
+        assert_eq!(app.last_error.as_deref(), Some("Unknown context 'prod'"));
     }
 
     #[tokio::test]
-    async fn log_n_finds_above_scroll() {
+    async fn log_reattach_follows_the_replacement_pod() {
+        use crate::models::LogStreamEnd;
+
         let mut app = App::new_test();
         app.mode = AppMode::LogView;
-        app.log_buffer.push_back("error first".to_string());
-        for i in 1..50 {
-            app.log_buffer.push_back(format!("line {i}"));
-        }
-        app.log_scroll_offset = Some(10);
-        app.log_search_query = "error".to_string();
-
-        handle_input(&mut app, key(KeyCode::Char('n')));
-        assert_eq!(app.log_search_match_line, Some(0));
+        app.log_pod_name = "web-1".to_string();
+        app.log_namespace = "default".to_string();
+        app.log_task = Some(tokio::spawn(async {}).abort_handle());
+
+        handle_input(&mut app, key(KeyCode::Char('R')));
+        assert_eq!(app.log_pod_name, "web-1");
+
+        let end = LogStreamEnd::PodTerminated {
+            replacement: Some("web-2".to_string()),
+        };
+        app.apply_log_end("other", end.clone());
+        assert!(app.log_end.is_none());
+        app.apply_log_end("web-1", end);
+
+        handle_input(&mut app, key(KeyCode::Char('R')));
+        assert_eq!(app.log_pod_name, "web-2");
+        assert_eq!(app.log_namespace, "default");
+        assert_eq!(app.mode, AppMode::LogView);
+        assert!(app.log_end.is_none());
     }
 
     #[tokio::test]
-    async fn log_shift_n_jumps_to_prev() {
+    async fn paging_uses_the_drawn_view_sizes() {
         let mut app = App::new_test();
         app.mode = AppMode::LogView;
-        for i in 0..80 {
-            app.log_buffer.push_back(format!("line {i}"));
+        app.log_buffer = (0..100).map(|i| i.to_string()).collect();
+        app.viewport.log_rows = 30;
+        app.log_scroll_offset = Some(0);
+
+        handle_input(&mut app, key(KeyCode::PageDown));
+        assert_eq!(app.log_scroll_offset, Some(30));
+        handle_input(&mut app, key(KeyCode::PageDown));
+        handle_input(&mut app, key(KeyCode::PageDown));
+        assert_eq!(app.log_scroll_offset, Some(70));
+
+        app.mode = AppMode::DescribeView;
+        app.describe_content = (0..50).map(|i| i.to_string()).collect();
+        app.viewport.describe_rows = 45;
+        handle_input(&mut app, key(KeyCode::Char('G')));
+        assert_eq!(app.describe_scroll, 5);
+    }
+
+    fn make_secret(name: &str) -> KubeResource {
+        let mut secret = k8s_openapi::api::core::v1::Secret::default();
+        secret.metadata.name = Some(name.to_string());
+        KubeResource::Secret(Arc::new(secret))
+    }
+
+    fn make_configmap(name: &str) -> KubeResource {
+        let mut cm = k8s_openapi::api::core::v1::ConfigMap::default();
+        cm.metadata.name = Some(name.to_string());
+        KubeResource::ConfigMap(Arc::new(cm))
+    }
+
+    /// The key presses a keymap entry such as `5j` or `Shift+Tab` stands for.
+    fn keymap_keys(entry: &str) -> Vec<KeyEvent> {
+        let mut keys = Vec::new();
+        for part in entry.split(' ') {
+            let (modifiers, name) = match part.split_once('+') {
+                Some(("Ctrl", name)) => (KeyModifiers::CONTROL, name.to_lowercase()),
+                Some(("Alt", name)) => (KeyModifiers::ALT, name.to_string()),
+                Some(("Shift", "Tab")) => (KeyModifiers::NONE, "BackTab".to_string()),
+                _ => (KeyModifiers::NONE, part.to_string()),
+            };
+            let code = match name.as_str() {
+                "Tab" => KeyCode::Tab,
+                "BackTab" => KeyCode::BackTab,
+                "Enter" => KeyCode::Enter,
+                "Esc" => KeyCode::Esc,
+                "Space" => KeyCode::Char(' '),
+                "PgUp" => KeyCode::PageUp,
+                "PgDn" => KeyCode::PageDown,
+                "Delete" => KeyCode::Delete,
+                "←" => KeyCode::Left,
+                "→" => KeyCode::Right,
+                chars => {
+                    keys.extend(
+                        chars
+                            .chars()
+                            .map(|c| key_with_mod(KeyCode::Char(c), modifiers)),
+                    );
+                    continue;
+                }
+            };
+            keys.push(key_with_mod(code, modifiers));
         }
-        app.log_buffer.push_back("error found here".to_string());
-        for i in 81..200 {
-            app.log_buffer.push_back(format!("line {i}"));
+        keys
+    }
+
+    /// What a handled key changes, for telling it from one dispatch ignores.
+    fn observable(app: &App) -> Vec<String> {
+        let mut selected: Vec<_> = app.selected_indices.iter().collect();
+        selected.sort();
+        vec![
+            format!("{:?} {:?} {:?}", app.mode, app.mode_stack, app.active_tab),
+            format!("{} {:?}", app.should_quit, app.pending_editor),
+            format!("{:?} {:?}", app.pending_action, app.pending_mark),
+            format!("{:?} {:?}", app.pending_count, app.marks.keys()),
+            format!("{:?} {:?}", app.table_state.selected(), selected),
+            format!("{:?} {}", app.visual_anchor, app.filter_query),
+            format!(
+                "{} {} {}",
+                app.wide, app.column_offset, app.refresh_requested
+            ),
+            format!("{:?} {:?}", app.last_error, app.last_success),
+            format!("{:?}", app.app_state),
+            format!("{:?} {:?}", app.popup_state.selected(), app.tasks.labels()),
+            format!("{} {}", app.secret_revealed, app.secret_show_base64),
+            format!("{} {}", app.configmap_scroll, app.configmap_value_scroll),
+            format!("{} {}", app.pager_scroll, app.pager_text.len()),
+            format!(
+                "{:?} {:?}",
+                app.log_scroll_offset, app.log_search_match_line
+            ),
+            format!("{} {}", app.describe_scroll, app.describe_auto_refresh),
+            format!("{} {}", app.log_search_query, app.describe_query),
+        ]
+    }
+
+    /// The states each keymap section's keys are tried in.
+    fn keymap_setups(section: &str) -> Vec<App> {
+        let with = |f: &dyn Fn(&mut App)| {
+            let mut app = App::new_test();
+            f(&mut app);
+            app
+        };
+        let list = |tab: ResourceType, items: Vec<KubeResource>, read_only: bool| {
+            with(&|app: &mut App| {
+                app.active_tab = tab;
+                app.items = items.clone();
+                app.filtered_items = items.clone();
+                if read_only {
+                    // Mutating keys are refused rather than run.
+                    app.read_only = true;
+                    app.table_state.select(Some(0));
+                }
+            })
+        };
+        let lines = |n: usize| (0..n).map(|i| format!("line {i}")).collect::<Vec<_>>();
+        let pods = || {
+            (0..20)
+                .map(|i| make_pod(&format!("p{i}")))
+                .collect::<Vec<_>>()
+        };
+        let pager = |mode: AppMode| {
+            with(&|app: &mut App| {
+                app.push_mode(mode);
+                app.pager_text = lines(100).join("\n");
+                app.pager_scroll = 5;
+                app.pager_query = "line".into();
+                app.viewport.pager = (80, 10);
+            })
+        };
+        match section {
+            "Navigation" => vec![with(&|app: &mut App| {
+                app.active_tab = ResourceType::Deployment;
+                app.items = pods();
+                app.filtered_items = pods();
+                app.table_state.select(Some(5));
+                app.filter_query = "p".into();
+            })],
+            "Cluster" => vec![
+                list(ResourceType::Pod, pods(), true),
+                with(&|app: &mut App| {
+                    app.push_mode(AppMode::ContextSelect);
+                    app.available_contexts = vec!["test-context".into()];
+                    app.filtered_contexts = app.available_contexts.clone();
+                    app.popup_state.select(Some(0));
+                }),
+                with(&|app: &mut App| {
+                    app.push_mode(AppMode::NamespaceSelect);
+                    app.available_namespaces = vec!["default".into(), "scratch".into()];
+                    app.filtered_namespaces = app.available_namespaces.clone();
+                    app.popup_state.select(Some(1));
+                }),
+            ],
+            "Pods" => vec![
+                list(ResourceType::Pod, pods(), false),
+                list(ResourceType::Pod, pods(), true),
+                with(&|app: &mut App| {
+                    app.filtered_items = pods();
+                    app.table_state.select(Some(0));
+                    app.push_mode(AppMode::ActionMenu);
+                    app.action_menu_state.select(Some(0));
+                }),
+                with(&|app: &mut App| {
+                    app.push_mode(AppMode::Confirm);
+                    app.pending_action =
+                        Some(PendingAction::RestartDeployment { name: "api".into() });
+                }),
+            ],
+            "Deployments" => vec![list(ResourceType::Deployment, pods(), true)],
+            "Secrets" => vec![
+                list(ResourceType::Secret, vec![make_secret("s1")], false),
+                with(&|app: &mut App| {
+                    app.active_tab = ResourceType::Secret;
+                    app.filtered_items = vec![make_secret("s1")];
+                    app.table_state.select(Some(0));
+                }),
+                with(&|app: &mut App| {
+                    app.push_mode(AppMode::SecretDecode);
+                    app.decoded_secret_name = "s1".into();
+                    app.selected_secret_decoded = Some(vec![("k".into(), text("v"))]);
+                    app.secret_revealed = true;
+                }),
+            ],
+            "ConfigMaps" => vec![
+                with(&|app: &mut App| {
+                    app.active_tab = ResourceType::ConfigMap;
+                    app.filtered_items = vec![make_configmap("c1")];
+                    app.table_state.select(Some(0));
+                }),
+                with(&|app: &mut App| {
+                    app.push_mode(AppMode::ConfigMapView);
+                    app.configmap_name = "c1".into();
+                    let value = text(&lines(50).join("\n"));
+                    let entries = ["a", "b", "c"].map(|k| (k.to_string(), value.clone()));
+                    app.configmap_entries = Some(entries.to_vec());
+                    app.configmap_scroll = 1;
+                    app.configmap_value_scroll = 1;
+                }),
+            ],
+            "Value Pager" => vec![pager(AppMode::ValuePager)],
+            "Edit Diff" => vec![with(&|app: &mut App| {
+                app.resource_edit = Some(crate::models::ResourceEdit {
+                    kind: "deployment",
+                    name: "web".into(),
+                    namespace: "default".into(),
+                    original: String::new(),
+                    edited: String::new(),
+                    error: None,
+                });
+                app.push_mode(AppMode::EditDiff);
+                app.pager_text = lines(100).join("\n");
+                app.pager_scroll = 5;
+                app.viewport.pager = (80, 10);
+            })],
+            "Log View" => vec![with(&|app: &mut App| {
+                app.push_mode(AppMode::LogView);
+                app.log_buffer = lines(100).into();
+                app.log_scroll_offset = Some(5);
+                app.log_search_query = "line".into();
+                app.log_end = Some(crate::models::LogStreamEnd::PodTerminated {
+                    replacement: Some("p2".into()),
+                });
+            })],
+            "Describe" => vec![with(&|app: &mut App| {
+                app.push_mode(AppMode::DescribeView);
+                app.describe_content = lines(100);
+                app.describe_scroll = 5;
+                app.describe_query = "line".into();
+            })],
+            "Shell" => vec![with(&|app: &mut App| app.push_mode(AppMode::ShellView))],
+            _ => Vec::new(),
         }
-        app.log_scroll_offset = Some(50);
-        app.log_search_query = "error".to_string();
+    }
 
-        handle_input(&mut app, key(KeyCode::Char('N')));
-        assert_eq!(app.log_search_match_line, Some(80));
+    #[tokio::test]
+    async fn every_keymap_binding_is_handled() {
+        for section in crate::keymap::SECTIONS {
+            for (keys, _) in section.bindings {
+                let entries = keys.split('`').skip(1).step_by(2);
+                for entry in entries.filter(|e| !e.ends_with('…')) {
+                    let handled = keymap_setups(section.title).into_iter().any(|mut app| {
+                        let before = observable(&app);
+                        for key in keymap_keys(entry) {
+                            handle_input(&mut app, key);
+                        }
+                        observable(&app) != before
+                    });
+                    assert!(handled, "{}: `{entry}` does nothing", section.title);
+                }
+            }
+        }
     }
 }
@@ -1,37 +1,54 @@
 use anyhow::Result;
 use futures::{AsyncBufReadExt, StreamExt};
-use k8s_openapi::api::{apps::v1::Deployment, core::v1::Pod};
+use k8s_openapi::ByteString;
+use k8s_openapi::api::{
+    apps::v1::Deployment,
+    core::v1::{ConfigMap, Namespace, Pod, ResourceQuota, Secret, Service},
+    networking::v1::Ingress,
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
 use kube::Client;
-use kube::api::{Api, LogParams};
+use kube::api::{
+    Api, ApiResource, DynamicObject, GroupVersionKind, ListParams, LogParams, Patch, PatchParams,
+    PostParams,
+};
 use tokio::sync::mpsc::UnboundedSender;
 
-use crate::models::KubeResourceEvent;
+use crate::models::{
+    AccessCheck, Endpoint, KubeResource, KubeResourceEvent, LogStreamEnd, ManifestObject,
+    NamespaceSummary, ResourceEdit, ResourceType, SecretConsumer, SecretOwner,
+};
+use std::sync::Arc;
 
+/// Sends each log line as a `Log` event, and `LogEnded` when a followed stream ends.
 pub fn stream_pod_logs(
     client: Client,
     namespace: &str,
     pod_name: &str,
     tx: UnboundedSender<KubeResourceEvent>,
-    tail_lines: i64,
+    lp: LogParams,
 ) -> tokio::task::AbortHandle {
     let namespace = namespace.to_owned();
     let pod_name = pod_name.to_owned();
     let handle = tokio::spawn(async move {
         let pods: Api<Pod> = Api::namespaced(client, &namespace);
-        let lp = LogParams {
-            follow: true,
-            tail_lines: Some(tail_lines),
-            ..Default::default()
+        // Remembered up front: once the pod is gone, so is its owner.
+        let pod = match lp.follow {
+            true => pods.get_opt(&pod_name).await.ok().flatten(),
+            false => None,
         };
-
         match pods.log_stream(&pod_name, &lp).await {
             Ok(stream) => {
                 let mut lines = stream.lines();
                 while let Some(Ok(line)) = lines.next().await {
                     if tx.send(KubeResourceEvent::Log(line)).is_err() {
-                        break;
+                        return;
                     }
                 }
+                if lp.follow {
+                    let end = log_stream_end(&pods, &pod_name, pod.as_ref()).await;
+                    let _ = tx.send(KubeResourceEvent::LogEnded(pod_name, end));
+                }
             }
             Err(e) => {
                 if tx
@@ -46,6 +63,60 @@ pub fn stream_pod_logs(
     handle.abort_handle()
 }
 
+/// Tells a container exit from the pod being deleted and replaced.
+async fn log_stream_end(pods: &Api<Pod>, name: &str, pod: Option<&Pod>) -> LogStreamEnd {
+    let uid = pod.and_then(|p| p.metadata.uid.clone());
+    // A StatefulSet replacement reuses the name, so the uid tells them apart.
+    let same_pod = |p: &Pod| uid.is_none() || p.metadata.uid == uid;
+    match pods.get_opt(name).await {
+        Ok(Some(current))
+            if same_pod(&current) && current.metadata.deletion_timestamp.is_none() =>
+        {
+            return LogStreamEnd::Ended;
+        }
+        Err(_) => return LogStreamEnd::Ended,
+        _ => {}
+    }
+    let Some(owner) = pod.and_then(controller_uid) else {
+        return LogStreamEnd::PodTerminated { replacement: None };
+    };
+    for attempt in 0..5 {
+        if attempt > 0 {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+        let Ok(list) = pods.list(&ListParams::default()).await else {
+            break;
+        };
+        let replacement = list
+            .items
+            .into_iter()
+            .filter(|p| {
+                p.metadata.uid != uid
+                    && p.metadata.deletion_timestamp.is_none()
+                    && controller_uid(p).as_deref() == Some(owner.as_str())
+            })
+            .max_by(|a, b| {
+                a.metadata
+                    .creation_timestamp
+                    .cmp(&b.metadata.creation_timestamp)
+            })
+            .and_then(|p| p.metadata.name);
+        if replacement.is_some() {
+            return LogStreamEnd::PodTerminated { replacement };
+        }
+    }
+    LogStreamEnd::PodTerminated { replacement: None }
+}
+
+fn controller_uid(pod: &Pod) -> Option<String> {
+    pod.metadata
+        .owner_references
+        .as_ref()?
+        .iter()
+        .find(|r| r.controller == Some(true))
+        .map(|r| r.uid.clone())
+}
+
 pub async fn delete_pod(client: Client, namespace: &str, name: &str) -> Result<()> {
     let pods: Api<Pod> = Api::namespaced(client, namespace);
     pods.delete(name, &Default::default()).await?;
@@ -58,6 +129,394 @@ pub async fn delete_deployment(client: Client, namespace: &str, name: &str) -> R
     Ok(())
 }
 
+/// Lists a tab's resources once, sorted by name, for the CLI.
+pub async fn list_resources(
+    client: Client,
+    namespace: &str,
+    tab: ResourceType,
+) -> Result<Vec<KubeResource>> {
+    let lp = ListParams::default();
+    let mut items: Vec<KubeResource> = match tab {
+        ResourceType::Pod => Api::<Pod>::namespaced(client, namespace)
+            .list(&lp)
+            .await?
+            .items
+            .into_iter()
+            .map(|p| KubeResource::Pod(Arc::new(p)))
+            .collect(),
+        ResourceType::Deployment => Api::<Deployment>::namespaced(client, namespace)
+            .list(&lp)
+            .await?
+            .items
+            .into_iter()
+            .map(|d| KubeResource::Deployment(Arc::new(d)))
+            .collect(),
+        ResourceType::Secret => Api::<Secret>::namespaced(client, namespace)
+            .list(&lp)
+            .await?
+            .items
+            .into_iter()
+            .map(|s| KubeResource::Secret(Arc::new(s)))
+            .collect(),
+        ResourceType::ConfigMap => Api::<ConfigMap>::namespaced(client, namespace)
+            .list(&lp)
+            .await?
+            .items
+            .into_iter()
+            .map(|c| KubeResource::ConfigMap(Arc::new(c)))
+            .collect(),
+    };
+    items.sort_by(|a, b| a.name().cmp(b.name()));
+    Ok(items)
+}
+
+/// Lists what the namespace summary needs; unreadable quotas are left out.
+pub async fn fetch_namespace_summary(client: Client, namespace: &str) -> Result<NamespaceSummary> {
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let deployments: Api<Deployment> = Api::namespaced(client.clone(), namespace);
+    let quotas: Api<ResourceQuota> = Api::namespaced(client, namespace);
+    let lp = ListParams::default();
+    let (pods, deployments) = tokio::try_join!(pods.list(&lp), deployments.list(&lp))?;
+    let quotas = quotas.list(&lp).await.map(|l| l.items).unwrap_or_default();
+    Ok(crate::utils::summarize_namespace(
+        &pods.items,
+        &deployments.items,
+        &quotas,
+    ))
+}
+
+/// Runs a SelfSubjectAccessReview for each check in `namespace`, concurrently.
+pub async fn review_access(
+    client: Client,
+    namespace: &str,
+    checks: &[AccessCheck],
+) -> Vec<AccessCheck> {
+    use k8s_openapi::api::authorization::v1::{
+        ResourceAttributes, SelfSubjectAccessReview, SelfSubjectAccessReviewSpec,
+    };
+    let api: Api<SelfSubjectAccessReview> = Api::all(client);
+    let reviews = checks.iter().map(|check| {
+        let api = api.clone();
+        let review = SelfSubjectAccessReview {
+            spec: SelfSubjectAccessReviewSpec {
+                resource_attributes: Some(ResourceAttributes {
+                    namespace: Some(namespace.to_string()),
+                    verb: Some(check.verb.to_string()),
+                    group: Some(check.group.to_string()),
+                    resource: Some(check.resource.to_string()),
+                    subresource: (!check.subresource.is_empty())
+                        .then(|| check.subresource.to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        async move {
+            let allowed = api
+                .create(&PostParams::default(), &review)
+                .await
+                .map(|r| r.status.is_some_and(|s| s.allowed))
+                .map_err(|e| e.to_string());
+            AccessCheck {
+                allowed,
+                ..check.clone()
+            }
+        }
+    });
+    futures::future::join_all(reviews).await
+}
+
+/// Fetches the API server's git version, failing if it doesn't answer in time.
+pub async fn fetch_server_version(client: Client) -> Result<String> {
+    let info = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        client.apiserver_version(),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("API server did not respond within 5s"))??;
+    Ok(info.git_version)
+}
+
+pub async fn create_namespace(client: Client, name: &str) -> Result<()> {
+    let namespaces: Api<Namespace> = Api::all(client);
+    let namespace = Namespace {
+        metadata: ObjectMeta {
+            name: Some(name.to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    namespaces
+        .create(&PostParams::default(), &namespace)
+        .await?;
+    Ok(())
+}
+
+pub async fn delete_namespace(client: Client, name: &str) -> Result<()> {
+    let namespaces: Api<Namespace> = Api::all(client);
+    namespaces.delete(name, &Default::default()).await?;
+    Ok(())
+}
+
+fn edit_api(client: Client, edit: &ResourceEdit) -> Api<DynamicObject> {
+    let ar = match edit.kind {
+        "deployment" => ApiResource::erase::<Deployment>(&()),
+        _ => ApiResource::erase::<Pod>(&()),
+    };
+    Api::namespaced_with(client, &edit.namespace, &ar)
+}
+
+/// The object as YAML for editing, without `managedFields`.
+pub async fn fetch_for_edit(client: Client, edit: &ResourceEdit) -> Result<String> {
+    let mut obj = edit_api(client, edit).get(&edit.name).await?;
+    obj.metadata.managed_fields = None;
+    Ok(serde_yaml::to_string(&obj)?)
+}
+
+/// Parses the user's YAML and checks it still names the edited object.
+pub fn validate_edit(edit: &ResourceEdit) -> Result<DynamicObject, String> {
+    let obj: DynamicObject = serde_yaml::from_str(&edit.edited).map_err(|e| e.to_string())?;
+    let original: DynamicObject =
+        serde_yaml::from_str(&edit.original).map_err(|e| e.to_string())?;
+    let same_type = obj.types == original.types;
+    if !same_type || obj.metadata.name != original.metadata.name {
+        return Err("apiVersion, kind and metadata.name cannot be changed".to_string());
+    }
+    if obj.metadata.namespace.is_some() && obj.metadata.namespace != original.metadata.namespace {
+        return Err("metadata.namespace cannot be changed".to_string());
+    }
+    Ok(obj)
+}
+
+/// The user's `edited` YAML moved onto `current`'s `resourceVersion`.
+pub fn rebase_edit(edited: &str, current: &str) -> String {
+    let version = |yaml: &str| {
+        serde_yaml::from_str::<DynamicObject>(yaml)
+            .ok()
+            .and_then(|obj| obj.metadata.resource_version)
+    };
+    let Ok(mut obj) = serde_yaml::from_str::<serde_yaml::Value>(edited) else {
+        return edited.to_string();
+    };
+    let Some(metadata) = obj.get_mut("metadata").and_then(|m| m.as_mapping_mut()) else {
+        return edited.to_string();
+    };
+    match version(current) {
+        Some(v) => metadata.insert("resourceVersion".into(), v.into()),
+        None => metadata.remove("resourceVersion"),
+    };
+    serde_yaml::to_string(&obj).unwrap_or_else(|_| edited.to_string())
+}
+
+/// Why applying an edit failed.
+pub enum ApplyError {
+    /// The object changed on the server since it was fetched (409).
+    Conflict,
+    /// The server rejected the object itself (400, 422).
+    Invalid(String),
+    Other(String),
+}
+
+/// The JSON merge patch that turns `from` into `to`, if anything changed.
+fn merge_patch(from: &serde_json::Value, to: &serde_json::Value) -> Option<serde_json::Value> {
+    use serde_json::Value;
+    let (Value::Object(from), Value::Object(to)) = (from, to) else {
+        return (from != to).then(|| to.clone());
+    };
+    let mut patch = serde_json::Map::new();
+    for key in from.keys().filter(|k| !to.contains_key(*k)) {
+        patch.insert(key.clone(), Value::Null);
+    }
+    for (key, value) in to {
+        let changed = match from.get(key) {
+            Some(old) => merge_patch(old, value),
+            None => Some(value.clone()),
+        };
+        if let Some(changed) = changed {
+            patch.insert(key.clone(), changed);
+        }
+    }
+    (!patch.is_empty()).then_some(Value::Object(patch))
+}
+
+/// What the user changed between `original` and `edited`, as a merge patch.
+pub fn edit_patch(edit: &ResourceEdit) -> Result<serde_json::Value, String> {
+    let parse = |yaml: &str| -> Result<serde_json::Value, String> {
+        let mut value: serde_json::Value = serde_yaml::from_str(yaml).map_err(|e| e.to_string())?;
+        if let Some(obj) = value.as_object_mut() {
+            obj.remove("status");
+            if let Some(metadata) = obj.get_mut("metadata").and_then(|m| m.as_object_mut()) {
+                metadata.remove("managedFields");
+            }
+        }
+        Ok(value)
+    };
+    let original = parse(&edit.original)?;
+    let edited = parse(&edit.edited)?;
+    let mut patch = merge_patch(&original, &edited).unwrap_or_else(|| serde_json::json!({}));
+    let version = edited
+        .pointer("/metadata/resourceVersion")
+        .or_else(|| original.pointer("/metadata/resourceVersion"))
+        .cloned();
+    if let (Some(version), Some(patch)) = (version, patch.as_object_mut()) {
+        let metadata = patch
+            .entry("metadata")
+            .or_insert_with(|| serde_json::json!({}));
+        if let Some(metadata) = metadata.as_object_mut() {
+            metadata.insert("resourceVersion".to_string(), version);
+        }
+    }
+    Ok(patch)
+}
+
+/// Sends the editor's changes as a merge patch, leaving fields other managers own alone.
+pub async fn apply_edit(client: Client, edit: &ResourceEdit) -> Result<(), ApplyError> {
+    validate_edit(edit).map_err(ApplyError::Invalid)?;
+    let patch = edit_patch(edit).map_err(ApplyError::Invalid)?;
+    let params = PatchParams {
+        field_manager: Some("kr".to_string()),
+        ..Default::default()
+    };
+    match edit_api(client, edit)
+        .patch(&edit.name, &params, &Patch::Merge(&patch))
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(kube::Error::Api(status)) if status.code == 409 => Err(ApplyError::Conflict),
+        Err(kube::Error::Api(status)) if matches!(status.code, 400 | 422) => {
+            Err(ApplyError::Invalid(status.message.clone()))
+        }
+        Err(e) => Err(ApplyError::Other(e.to_string())),
+    }
+}
+
+/// The objects of a multi-document YAML manifest.
+pub fn parse_manifest(text: &str) -> Result<Vec<DynamicObject>, String> {
+    use serde::Deserialize;
+    let mut objects = Vec::new();
+    for (i, doc) in serde_yaml::Deserializer::from_str(text).enumerate() {
+        let at = |e: &dyn std::fmt::Display| format!("document {}: {e}", i + 1);
+        let value = serde_yaml::Value::deserialize(doc).map_err(|e| at(&e))?;
+        if value.is_null() {
+            continue;
+        }
+        let obj: DynamicObject = serde_yaml::from_value(value).map_err(|e| at(&e))?;
+        if obj.types.is_none() {
+            return Err(at(&"apiVersion and kind are required"));
+        }
+        if obj.metadata.name.is_none() {
+            return Err(at(&"metadata.name is required"));
+        }
+        objects.push(obj);
+    }
+    if objects.is_empty() {
+        return Err("no objects in the manifest".to_string());
+    }
+    Ok(objects)
+}
+
+/// The object as YAML for a diff, without the fields every write changes.
+fn diffable_yaml(obj: &DynamicObject) -> String {
+    let mut obj = obj.clone();
+    obj.metadata.managed_fields = None;
+    obj.metadata.resource_version = None;
+    serde_yaml::to_string(&obj).unwrap_or_default()
+}
+
+/// Resolves, places and dry-runs each object of a manifest.
+pub async fn plan_manifest(
+    client: Client,
+    objects: Vec<DynamicObject>,
+    namespace: &str,
+) -> Vec<ManifestObject> {
+    let mut planned = Vec::with_capacity(objects.len());
+    for mut object in objects {
+        let types = object.types.clone().unwrap_or_default();
+        let name = object.metadata.name.clone().unwrap_or_default();
+        let mut item = ManifestObject {
+            label: format!("{}/{name}", types.kind.to_lowercase()),
+            resource: None,
+            object: object.clone(),
+            namespace: None,
+            plan: Err(String::new()),
+            created: false,
+        };
+        let discovered = match GroupVersionKind::try_from(&types) {
+            Ok(gvk) => kube::discovery::pinned_kind(&client, &gvk)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        let (resource, caps) = match discovered {
+            Ok(found) => found,
+            Err(e) => {
+                item.plan = Err(e);
+                planned.push(item);
+                continue;
+            }
+        };
+        if caps.scope == kube::discovery::Scope::Namespaced {
+            let ns = object
+                .metadata
+                .namespace
+                .get_or_insert_with(|| namespace.to_string());
+            item.namespace = Some(ns.clone());
+        } else {
+            object.metadata.namespace = None;
+        }
+        let api = manifest_api(client.clone(), &resource, item.namespace.as_deref());
+        let params = PatchParams::apply("kr").force().dry_run();
+        item.plan = match api.get_opt(&name).await {
+            Ok(live) => match api.patch(&name, &params, &Patch::Apply(&object)).await {
+                Ok(result) => {
+                    item.created = live.is_none();
+                    let before = live.as_ref().map(diffable_yaml).unwrap_or_default();
+                    Ok(crate::utils::diff_lines(
+                        &before,
+                        &diffable_yaml(&result),
+                        3,
+                    ))
+                }
+                Err(e) => Err(e.to_string()),
+            },
+            Err(e) => Err(e.to_string()),
+        };
+        item.resource = Some(resource);
+        item.object = object;
+        planned.push(item);
+    }
+    planned
+}
+
+fn manifest_api(
+    client: Client,
+    resource: &ApiResource,
+    namespace: Option<&str>,
+) -> Api<DynamicObject> {
+    match namespace {
+        Some(ns) => Api::namespaced_with(client, ns, resource),
+        None => Api::all_with(client, resource),
+    }
+}
+
+/// Applies one planned object with server-side apply.
+pub async fn apply_manifest_object(client: Client, item: &ManifestObject) -> Result<()> {
+    let name = item.object.metadata.name.as_deref().unwrap_or_default();
+    let resource = item
+        .resource
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("unknown kind"))?;
+    manifest_api(client, resource, item.namespace.as_deref())
+        .patch(
+            name,
+            &PatchParams::apply("kr").force(),
+            &Patch::Apply(&item.object),
+        )
+        .await?;
+    Ok(())
+}
+
 pub async fn scale_deployment(
     client: Client,
     namespace: &str,
@@ -78,6 +537,128 @@ pub async fn scale_deployment(
     Ok(())
 }
 
+pub async fn create_secret(
+    client: Client,
+    namespace: &str,
+    name: &str,
+    type_: &str,
+    entries: &[(String, String)],
+) -> Result<()> {
+    let secrets: Api<Secret> = Api::namespaced(client, namespace);
+    let data = entries
+        .iter()
+        .map(|(k, v)| (k.clone(), ByteString(v.as_bytes().to_vec())))
+        .collect();
+    let secret = Secret {
+        metadata: ObjectMeta {
+            name: Some(name.to_string()),
+            namespace: Some(namespace.to_string()),
+            ..Default::default()
+        },
+        type_: Some(type_.to_string()),
+        data: Some(data),
+        ..Default::default()
+    };
+    secrets.create(&PostParams::default(), &secret).await?;
+    Ok(())
+}
+
+pub async fn patch_secret_key(
+    client: Client,
+    namespace: &str,
+    name: &str,
+    key: &str,
+    value: Vec<u8>,
+) -> Result<()> {
+    let secrets: Api<Secret> = Api::namespaced(client, namespace);
+    let patch = serde_json::json!({
+        "data": { key: ByteString(value) }
+    });
+    secrets
+        .patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await?;
+    Ok(())
+}
+
+/// Lists pods and deployments in `namespace` that reference the secret `name`.
+pub async fn find_secret_consumers(
+    client: Client,
+    namespace: &str,
+    name: &str,
+) -> Result<Vec<SecretConsumer>> {
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let deployments: Api<Deployment> = Api::namespaced(client, namespace);
+    let lp = ListParams::default();
+    let (pods, deployments) = tokio::try_join!(pods.list(&lp), deployments.list(&lp))?;
+
+    let mut consumers = Vec::new();
+    for d in &deployments.items {
+        if let Some(spec) = d.spec.as_ref().and_then(|s| s.template.spec.as_ref()) {
+            let refs = crate::utils::secret_refs_in_pod_spec(spec, name);
+            if !refs.is_empty() {
+                consumers.push(SecretConsumer {
+                    kind: "Deployment",
+                    name: d.metadata.name.clone().unwrap_or_default(),
+                    refs,
+                });
+            }
+        }
+    }
+    for p in &pods.items {
+        if let Some(spec) = &p.spec {
+            let refs = crate::utils::secret_refs_in_pod_spec(spec, name);
+            if !refs.is_empty() {
+                consumers.push(SecretConsumer {
+                    kind: "Pod",
+                    name: p.metadata.name.clone().unwrap_or_default(),
+                    refs,
+                });
+            }
+        }
+    }
+    Ok(consumers)
+}
+
+/// Finds the URLs that reach pods labelled `labels` from outside.
+pub async fn fetch_endpoints(
+    client: Client,
+    namespace: &str,
+    labels: &std::collections::BTreeMap<String, String>,
+) -> Result<Vec<Endpoint>> {
+    let lp = ListParams::default();
+    let services = Api::<Service>::namespaced(client.clone(), namespace)
+        .list(&lp)
+        .await?;
+    // Without access to ingresses the services' addresses still help.
+    let ingresses = Api::<Ingress>::namespaced(client, namespace)
+        .list(&lp)
+        .await
+        .map(|l| l.items)
+        .unwrap_or_default();
+    Ok(crate::utils::workload_endpoints(
+        labels,
+        &services.items,
+        &ingresses,
+    ))
+}
+
+/// Summarises the sync condition of the secret's ExternalSecret/SealedSecret.
+pub async fn fetch_owner_status(
+    client: Client,
+    namespace: &str,
+    owner: &SecretOwner,
+) -> Result<String> {
+    let (group, version) = owner
+        .api_version
+        .split_once('/')
+        .unwrap_or(("", owner.api_version.as_str()));
+    let gvk = GroupVersionKind::gvk(group, version, &owner.kind);
+    let ar = ApiResource::from_gvk(&gvk);
+    let api: Api<DynamicObject> = Api::namespaced_with(client, namespace, &ar);
+    let obj = api.get(&owner.name).await?;
+    Ok(crate::utils::owner_sync_status(&obj.data))
+}
+
 pub async fn rollout_restart(client: Client, namespace: &str, name: &str) -> Result<()> {
     let deployments: Api<Deployment> = Api::namespaced(client, namespace);
     let now = jiff::Timestamp::now().to_string();
@@ -135,3 +716,83 @@ pub fn fetch_log_history(
     });
     handle.abort_handle()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    /// Applies a merge patch the way the API server does.
+    fn apply(target: &mut Value, patch: &Value) {
+        let Value::Object(patch) = patch else {
+            *target = patch.clone();
+            return;
+        };
+        if !target.is_object() {
+            *target = json!({});
+        }
+        let target = target.as_object_mut().unwrap();
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                apply(target.entry(key.as_str()).or_insert(Value::Null), value);
+            }
+        }
+    }
+
+    #[test]
+    fn edit_patch_removes_deleted_fields() {
+        let original = "apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: web
+  resourceVersion: \"42\"
+  labels:
+    app: web
+    tier: frontend
+  managedFields:
+  - manager: kube-controller-manager
+spec:
+  replicas: 3
+  paused: false
+status:
+  readyReplicas: 3
+";
+        let edit = ResourceEdit {
+            kind: "deployment",
+            name: "web".into(),
+            namespace: "default".into(),
+            original: original.into(),
+            edited: original
+                .replace("    tier: frontend\n", "")
+                .replace("  paused: false\n", "")
+                .replace("readyReplicas: 3", "readyReplicas: 0"),
+            error: None,
+        };
+        let patch = edit_patch(&edit).unwrap();
+        assert_eq!(
+            patch,
+            json!({
+                "metadata": {"labels": {"tier": null}, "resourceVersion": "42"},
+                "spec": {"paused": null},
+            })
+        );
+
+        let mut live: Value = serde_yaml::from_str(original).unwrap();
+        apply(&mut live, &patch);
+        assert_eq!(live["metadata"]["labels"], json!({"app": "web"}));
+        assert_eq!(live["spec"], json!({"replicas": 3}));
+        assert_eq!(live["status"]["readyReplicas"], 3);
+
+        // Saved unchanged, it still carries the version to conflict on.
+        let unchanged = ResourceEdit {
+            edited: original.into(),
+            ..edit
+        };
+        assert_eq!(
+            edit_patch(&unchanged).unwrap(),
+            json!({"metadata": {"resourceVersion": "42"}})
+        );
+    }
+}
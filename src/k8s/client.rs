@@ -1,6 +1,31 @@
 use anyhow::Result;
-use kube::Client;
+use kube::config::KubeConfigOptions;
+use kube::{Client, Config};
 
 pub async fn default_client() -> Result<Client> {
+    // An explicit --kubeconfig must be used or fail, never fall back.
+    if crate::k8s::config::kubeconfig_override().is_some() {
+        let kubeconfig = crate::k8s::config::load_kubeconfig()?;
+        let config =
+            Config::from_custom_kubeconfig(kubeconfig, &KubeConfigOptions::default()).await?;
+        return Ok(Client::try_from(config)?);
+    }
+    // Prefer our own kubeconfig merge so a KUBECONFIG listing missing files
+    // still works; fall back to kube's inference (e.g. in-cluster config).
+    if let Ok(kubeconfig) = crate::k8s::config::load_kubeconfig()
+        && let Ok(config) =
+            Config::from_custom_kubeconfig(kubeconfig, &KubeConfigOptions::default()).await
+    {
+        return Ok(Client::try_from(config)?);
+    }
     Ok(Client::try_default().await?)
 }
+
+/// Whether the API server rejected the client's credentials.
+pub fn is_unauthorized(err: &kube::Error) -> bool {
+    match err {
+        kube::Error::Api(status) => status.code == 401,
+        kube::Error::Auth(_) => true,
+        _ => false,
+    }
+}
@@ -1,19 +1,138 @@
-use anyhow::Result;
-use kube::config::{KubeConfigOptions, Kubeconfig};
+use anyhow::{Result, bail};
+use kube::config::{ExecConfig, ExecInteractiveMode, KubeConfigOptions, Kubeconfig};
 use kube::{Client, Config};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::k8s::teleport::TeleportLogin;
+use crate::models::KubeResourceEvent;
+
+const KUBECONFIG_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+static KUBECONFIG_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Uses `path` instead of `KUBECONFIG` / `~/.kube/config` from now on.
+pub fn set_kubeconfig_override(path: PathBuf) {
+    let _ = KUBECONFIG_OVERRIDE.set(path);
+}
+
+/// The `--kubeconfig` path, if one was given.
+pub fn kubeconfig_override() -> Option<&'static Path> {
+    KUBECONFIG_OVERRIDE.get().map(PathBuf::as_path)
+}
+
+/// Environment for kubectl and tsh so they read the same kubeconfig as kr.
+pub fn kubectl_env() -> Option<(&'static str, &'static Path)> {
+    kubeconfig_override().map(|path| ("KUBECONFIG", path))
+}
+
+/// Loads the kubeconfig, merging every file in `KUBECONFIG` like kubectl.
+pub fn load_kubeconfig() -> Result<Kubeconfig> {
+    if let Some(path) = kubeconfig_override() {
+        return Ok(Kubeconfig::read_from(path)?);
+    }
+    match std::env::var_os("KUBECONFIG") {
+        Some(value) => {
+            let paths: Vec<PathBuf> = std::env::split_paths(&value)
+                .filter(|p| !p.as_os_str().is_empty())
+                .collect();
+            if paths.is_empty() {
+                return Ok(Kubeconfig::read()?);
+            }
+            merge_kubeconfig_files(&paths)
+        }
+        None => Ok(Kubeconfig::read()?),
+    }
+}
+
+fn merge_kubeconfig_files(paths: &[PathBuf]) -> Result<Kubeconfig> {
+    let mut merged: Option<Kubeconfig> = None;
+    for path in paths.iter().filter(|p| p.exists()) {
+        let config = Kubeconfig::read_from(path)?;
+        merged = Some(match merged {
+            // kubectl ignores apiVersion/kind, so clear them rather than let
+            // a mismatch between files abort the merge.
+            Some(acc) => acc.merge(Kubeconfig {
+                api_version: None,
+                kind: None,
+                ..config
+            })?,
+            None => config,
+        });
+    }
+    match merged {
+        Some(config) => Ok(config),
+        None => bail!("none of the KUBECONFIG files exist"),
+    }
+}
+
+/// Files that make up the kubeconfig.
+fn kubeconfig_paths() -> Vec<PathBuf> {
+    if let Some(path) = kubeconfig_override() {
+        return vec![path.to_path_buf()];
+    }
+    let from_env: Vec<PathBuf> = std::env::var_os("KUBECONFIG")
+        .map(|value| {
+            std::env::split_paths(&value)
+                .filter(|p| !p.as_os_str().is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    if !from_env.is_empty() {
+        return from_env;
+    }
+    dirs::home_dir()
+        .map(|home| vec![home.join(".kube").join("config")])
+        .unwrap_or_default()
+}
+
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+/// Sends `ContextsChanged` whenever the kubeconfig files change.
+pub fn watch_kubeconfig(tx: UnboundedSender<KubeResourceEvent>) {
+    tokio::spawn(async move {
+        let paths = kubeconfig_paths();
+        let mut last = modified_times(&paths);
+        let mut interval = tokio::time::interval(KUBECONFIG_POLL_INTERVAL);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let current = modified_times(&paths);
+            if current == last {
+                continue;
+            }
+            last = current;
+            // Editors may leave the file half-written; retry on the next change.
+            if let Ok(contexts) = list_contexts()
+                && tx
+                    .send(KubeResourceEvent::ContextsChanged(contexts))
+                    .is_err()
+            {
+                break;
+            }
+        }
+    });
+}
 
 pub fn list_contexts() -> Result<Vec<String>> {
-    let config = Kubeconfig::read()?;
+    let config = load_kubeconfig()?;
     Ok(config.contexts.into_iter().map(|c| c.name).collect())
 }
 
 pub fn get_current_context() -> Result<String> {
-    let config = Kubeconfig::read()?;
+    let config = load_kubeconfig()?;
     Ok(config.current_context.unwrap_or_default())
 }
 
 pub fn get_context_namespace() -> Result<String> {
-    let config = Kubeconfig::read()?;
+    let config = load_kubeconfig()?;
     let ctx_name = config.current_context.as_deref().unwrap_or_default();
     let ns = config
         .contexts
@@ -26,7 +145,7 @@ pub fn get_context_namespace() -> Result<String> {
 }
 
 pub fn get_namespace_for_context(context: &str) -> String {
-    Kubeconfig::read()
+    load_kubeconfig()
         .ok()
         .and_then(|config| {
             config
@@ -39,12 +158,178 @@ pub fn get_namespace_for_context(context: &str) -> String {
         .unwrap_or_else(|| "default".to_string())
 }
 
+/// The kubeconfig user `context` authenticates as.
+pub fn context_user(context: &str) -> Option<String> {
+    load_kubeconfig()
+        .ok()?
+        .contexts
+        .into_iter()
+        .find(|c| c.name == context)?
+        .context?
+        .user
+}
+
+/// Whether connecting to `context` runs an exec plugin that may prompt.
+pub fn needs_interactive_auth(context: &str) -> bool {
+    load_kubeconfig().is_ok_and(|config| interactive_auth(&config, context))
+}
+
+fn interactive_auth(config: &Kubeconfig, context: &str) -> bool {
+    exec_config(config, context)
+        .is_some_and(|exec| exec.interactive_mode != Some(ExecInteractiveMode::Never))
+}
+
+/// The exec credential plugin of `context`'s user, if it has one.
+fn exec_config<'a>(config: &'a Kubeconfig, context: &str) -> Option<&'a ExecConfig> {
+    let user = config
+        .contexts
+        .iter()
+        .find(|c| c.name == context)?
+        .context
+        .as_ref()?
+        .user
+        .as_deref()?;
+    config
+        .auth_infos
+        .iter()
+        .find(|a| a.name == user)?
+        .auth_info
+        .as_ref()?
+        .exec
+        .as_ref()
+}
+
+/// How to renew `context`'s session when it authenticates with `tsh`.
+pub fn teleport_login(context: &str) -> Option<TeleportLogin> {
+    let config = load_kubeconfig().ok()?;
+    TeleportLogin::from_exec(exec_config(&config, context)?)
+}
+
+/// Every context that authenticates with `tsh`, by name.
+pub fn teleport_logins() -> Vec<(String, TeleportLogin)> {
+    let Ok(config) = load_kubeconfig() else {
+        return Vec::new();
+    };
+    config
+        .contexts
+        .iter()
+        .filter_map(|c| {
+            let login = TeleportLogin::from_exec(exec_config(&config, &c.name)?)?;
+            Some((c.name.clone(), login))
+        })
+        .collect()
+}
+
 pub async fn create_client_with_context(context: &str) -> Result<Client> {
     let options = KubeConfigOptions {
         context: Some(context.to_string()),
         ..Default::default()
     };
-    let config = Config::from_kubeconfig(&options).await?;
+    let config = Config::from_custom_kubeconfig(load_kubeconfig()?, &options).await?;
     let client = Client::try_from(config)?;
     Ok(client)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_config(name: &str, body: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("kr-{name}-{}.yaml", std::process::id()));
+        std::fs::write(&path, body).unwrap();
+        path
+    }
+
+    #[test]
+    fn merges_files_first_wins_and_skips_missing() {
+        let a = write_config(
+            "kubeconfig-a",
+            "apiVersion: v1\nkind: Config\ncurrent-context: dev\ncontexts:\n- name: dev\n  context:\n    cluster: dev\n    namespace: team-a\n",
+        );
+        let b = write_config(
+            "kubeconfig-b",
+            "current-context: prod\ncontexts:\n- name: prod\n  context:\n    cluster: prod\n- name: dev\n  context:\n    cluster: other\n    namespace: ignored\n",
+        );
+        let missing = std::env::temp_dir().join("kr-kubeconfig-does-not-exist.yaml");
+
+        let merged = merge_kubeconfig_files(&[a.clone(), missing, b.clone()]).unwrap();
+        let _ = std::fs::remove_file(&a);
+        let _ = std::fs::remove_file(&b);
+
+        assert_eq!(merged.current_context.as_deref(), Some("dev"));
+        let names: Vec<&str> = merged.contexts.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["dev", "prod"]);
+        let dev_ns = merged.contexts[0]
+            .context
+            .as_ref()
+            .and_then(|c| c.namespace.as_deref());
+        assert_eq!(dev_ns, Some("team-a"));
+    }
+
+    #[test]
+    fn merge_fails_when_no_file_exists() {
+        let missing = std::env::temp_dir().join("kr-kubeconfig-missing-too.yaml");
+        assert!(merge_kubeconfig_files(&[missing]).is_err());
+    }
+
+    #[test]
+    fn only_prompting_exec_plugins_need_the_terminal() {
+        let config: Kubeconfig = serde_yaml::from_str(
+            "contexts:
+- name: sso
+  context: {cluster: c, user: sso}
+- name: ci
+  context: {cluster: c, user: ci}
+- name: token
+  context: {cluster: c, user: token}
+users:
+- name: sso
+  user:
+    exec: {apiVersion: client.authentication.k8s.io/v1, command: login}
+- name: ci
+  user:
+    exec: {apiVersion: client.authentication.k8s.io/v1, command: login, interactiveMode: Never}
+- name: token
+  user: {token: abc}
+",
+        )
+        .unwrap();
+        assert!(interactive_auth(&config, "sso"));
+        assert!(!interactive_auth(&config, "ci"));
+        assert!(!interactive_auth(&config, "token"));
+        assert!(!interactive_auth(&config, "missing"));
+    }
+
+    #[test]
+    fn teleport_contexts_come_from_tsh_exec_plugins() {
+        let config: Kubeconfig = serde_yaml::from_str(
+            "contexts:
+- name: teleport.example.com-prod
+  context: {cluster: teleport.example.com, user: teleport.example.com-prod}
+- name: sso
+  context: {cluster: c, user: sso}
+users:
+- name: teleport.example.com-prod
+  user:
+    exec:
+      apiVersion: client.authentication.k8s.io/v1beta1
+      command: tsh
+      args: [kube, credentials, --kube-cluster=prod, --teleport-cluster=teleport.example.com, --proxy=teleport.example.com:443]
+- name: sso
+  user:
+    exec: {apiVersion: client.authentication.k8s.io/v1, command: login}
+",
+        )
+        .unwrap();
+        let login = exec_config(&config, "teleport.example.com-prod")
+            .and_then(TeleportLogin::from_exec)
+            .unwrap();
+        assert_eq!(login.kube_cluster, "prod");
+        assert_eq!(login.proxy.as_deref(), Some("teleport.example.com:443"));
+        assert!(
+            exec_config(&config, "sso")
+                .and_then(TeleportLogin::from_exec)
+                .is_none()
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub mod actions;
 pub mod client;
 pub mod config;
+pub mod teleport;
 pub mod watcher;
@@ -0,0 +1,215 @@
+//! Contexts whose credentials come from Teleport's `tsh kube credentials`
+//! exec plugin: their login status and the `tsh kube login` that renews it.
+
+use jiff::Timestamp;
+use kube::config::ExecConfig;
+use serde::Deserialize;
+
+/// What `tsh kube login` needs to renew a context's session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TeleportLogin {
+    pub kube_cluster: String,
+    /// Proxy address, `host:port`.
+    pub proxy: Option<String>,
+    /// Teleport cluster, when it isn't the proxy's root cluster.
+    pub cluster: Option<String>,
+}
+
+impl TeleportLogin {
+    /// `None` unless `exec` runs `tsh kube credentials`.
+    pub fn from_exec(exec: &ExecConfig) -> Option<Self> {
+        let command = std::path::Path::new(exec.command.as_deref()?).file_stem()?;
+        if command != "tsh" {
+            return None;
+        }
+        let args = exec.args.as_deref().unwrap_or_default();
+        if !args
+            .windows(2)
+            .any(|w| w[0] == "kube" && w[1] == "credentials")
+        {
+            return None;
+        }
+        let flag = |name: &str| {
+            args.iter().enumerate().find_map(|(i, arg)| {
+                match arg.strip_prefix(name)?.strip_prefix('=') {
+                    Some(value) => Some(value.to_string()),
+                    None if arg == name => args.get(i + 1).cloned(),
+                    None => None,
+                }
+            })
+        };
+        Some(Self {
+            kube_cluster: flag("--kube-cluster")?,
+            proxy: flag("--proxy"),
+            cluster: flag("--teleport-cluster"),
+        })
+    }
+
+    /// Arguments for `tsh`.
+    pub fn login_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "kube".to_string(),
+            "login".to_string(),
+            self.kube_cluster.clone(),
+        ];
+        if let Some(proxy) = &self.proxy {
+            args.push(format!("--proxy={proxy}"));
+        }
+        if let Some(cluster) = &self.cluster {
+            args.push(format!("--cluster={cluster}"));
+        }
+        args
+    }
+
+    /// The session of the profile for this context's proxy.
+    pub fn status(&self, status: &TshStatus, now: Timestamp) -> LoginStatus {
+        let profile = match &self.proxy {
+            Some(proxy) => status
+                .profiles
+                .iter()
+                .chain(&status.active)
+                .find(|p| host(&p.profile_url) == host(proxy)),
+            None => status.active.as_ref(),
+        };
+        match profile.and_then(|p| p.valid_until) {
+            Some(until) if until > now => LoginStatus::ValidUntil(until),
+            Some(_) => LoginStatus::Expired,
+            None => LoginStatus::LoggedOut,
+        }
+    }
+}
+
+/// The address without scheme, port or path.
+fn host(address: &str) -> &str {
+    let address = address.split_once("://").map_or(address, |(_, rest)| rest);
+    address.split(['/', ':']).next().unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginStatus {
+    ValidUntil(Timestamp),
+    Expired,
+    LoggedOut,
+}
+
+impl LoginStatus {
+    /// Shown next to the context in the context popup.
+    pub fn label(self, now: Timestamp) -> String {
+        match self {
+            LoginStatus::ValidUntil(until) => {
+                let left = until.as_second() - now.as_second();
+                format!("tsh: {} left", crate::utils::format_secs(left))
+            }
+            LoginStatus::Expired => "tsh: expired".to_string(),
+            LoginStatus::LoggedOut => "tsh: logged out".to_string(),
+        }
+    }
+}
+
+/// `tsh status --format=json`, reduced to what the status needs.
+#[derive(Debug, Default, Deserialize)]
+pub struct TshStatus {
+    pub active: Option<TshProfile>,
+    #[serde(default)]
+    pub profiles: Vec<TshProfile>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TshProfile {
+    /// `https://proxy:port`.
+    pub profile_url: String,
+    pub valid_until: Option<Timestamp>,
+}
+
+/// Runs `tsh status`; only a missing `tsh` is an error.
+pub async fn status() -> Result<TshStatus, String> {
+    let output = tokio::process::Command::new("tsh")
+        .args(["status", "--format=json"])
+        .envs(crate::k8s::config::kubectl_env())
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| format!("tsh: {e}"))?;
+    Ok(serde_json::from_slice(&output.stdout).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exec(command: &str, args: &[&str]) -> ExecConfig {
+        serde_json::from_value(serde_json::json!({
+            "apiVersion": "client.authentication.k8s.io/v1beta1",
+            "command": command,
+            "args": args,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn reads_the_login_from_tsh_kube_credentials() {
+        let login = TeleportLogin::from_exec(&exec(
+            "/usr/local/bin/tsh",
+            &[
+                "kube",
+                "credentials",
+                "--kube-cluster=prod-eu",
+                "--teleport-cluster",
+                "leaf",
+                "--proxy=teleport.example.com:443",
+            ],
+        ))
+        .unwrap();
+        assert_eq!(
+            login.login_args(),
+            [
+                "kube",
+                "login",
+                "prod-eu",
+                "--proxy=teleport.example.com:443",
+                "--cluster=leaf"
+            ]
+        );
+        assert_eq!(
+            TeleportLogin::from_exec(&exec("aws", &["eks", "get-token"])),
+            None
+        );
+        assert_eq!(
+            TeleportLogin::from_exec(&exec("tsh", &["kube", "credentials"])),
+            None
+        );
+    }
+
+    #[test]
+    fn status_follows_the_proxy_profile() {
+        let status: TshStatus = serde_json::from_str(
+            r#"{
+                "active": {"profile_url": "https://teleport.example.com:443", "valid_until": "2026-10-15T20:00:00Z"},
+                "profiles": [{"profile_url": "https://old.example.com:3080", "valid_until": "2026-10-14T20:00:00Z"}]
+            }"#,
+        )
+        .unwrap();
+        let now: Timestamp = "2026-10-15T13:00:00Z".parse().unwrap();
+        let login = |proxy: Option<&str>| TeleportLogin {
+            kube_cluster: "prod".into(),
+            proxy: proxy.map(str::to_string),
+            cluster: None,
+        };
+        let valid = login(Some("teleport.example.com:443")).status(&status, now);
+        assert_eq!(valid.label(now), "tsh: 7h left");
+        assert_eq!(
+            login(Some("old.example.com")).status(&status, now),
+            LoginStatus::Expired
+        );
+        assert_eq!(
+            login(Some("other.example.com")).status(&status, now),
+            LoginStatus::LoggedOut
+        );
+        assert_eq!(login(None).status(&status, now), valid);
+        assert_eq!(
+            login(None).status(&TshStatus::default(), now),
+            LoginStatus::LoggedOut
+        );
+    }
+}
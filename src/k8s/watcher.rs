@@ -1,15 +1,26 @@
-use futures::Stream;
+use futures::{Stream, StreamExt};
+use k8s_openapi::api::{
+    apps::v1::Deployment,
+    core::v1::{ConfigMap, Pod, Secret},
+};
 use kube::{
     Client,
     api::{Api, Resource},
-    runtime::{reflector, reflector::Store, watcher},
+    runtime::{WatchStreamExt, reflector, reflector::Store, watcher},
 };
 use serde::de::DeserializeOwned;
+use std::collections::HashMap;
 use std::fmt::Debug;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::AbortHandle;
+
+use crate::models::{KubeResource, KubeResourceEvent, ResourceType};
 
+/// Watches `K` in `namespace`, filtered by `field_selector` on the server.
 pub fn reflect_resources<K>(
     client: Client,
     namespace: &str,
+    field_selector: Option<&str>,
 ) -> (
     Store<K>,
     impl Stream<Item = Result<watcher::Event<K>, watcher::Error>> + use<K>,
@@ -25,7 +36,263 @@ where
 {
     let api = Api::<K>::namespaced(client, namespace);
     let (reader, writer) = reflector::store();
-    let watcher_config = watcher::Config::default().any_semantic().page_size(5000);
-    let stream = reflector(writer, watcher(api, watcher_config));
+    let stream = reflector(
+        writer,
+        watcher(api, watcher_config(field_selector)).default_backoff(),
+    );
     (reader, stream)
 }
+
+/// Settings shared by every watch kr runs.
+fn watcher_config(field_selector: Option<&str>) -> watcher::Config {
+    let mut config = watcher::Config::default().any_semantic().page_size(5000);
+    if let Some(fields) = field_selector {
+        config = config.fields(fields);
+    }
+    config
+}
+
+/// Store backing the second pane of the split view.
+pub enum PaneStore {
+    Pod(Store<Pod>),
+    Deployment(Store<Deployment>),
+    Secret(Store<Secret>),
+    ConfigMap(Store<ConfigMap>),
+}
+
+impl PaneStore {
+    pub fn items(&self) -> Vec<KubeResource> {
+        let mut items: Vec<KubeResource> = match self {
+            PaneStore::Pod(s) => s.state().into_iter().map(KubeResource::Pod).collect(),
+            PaneStore::Deployment(s) => s
+                .state()
+                .into_iter()
+                .map(KubeResource::Deployment)
+                .collect(),
+            PaneStore::Secret(s) => s.state().into_iter().map(KubeResource::Secret).collect(),
+            PaneStore::ConfigMap(s) => s.state().into_iter().map(KubeResource::ConfigMap).collect(),
+        };
+        items.sort_by(|a, b| a.name().cmp(b.name()));
+        items
+    }
+}
+
+/// Runs a watcher for the split pane on its own task.
+pub fn spawn_pane_watcher(
+    client: Client,
+    namespace: &str,
+    tab: ResourceType,
+    field_selector: Option<&str>,
+    tx: UnboundedSender<KubeResourceEvent>,
+) -> (PaneStore, AbortHandle) {
+    match tab {
+        ResourceType::Pod => {
+            let (store, handle) = drive_pane::<Pod>(client, namespace, field_selector, tx);
+            (PaneStore::Pod(store), handle)
+        }
+        ResourceType::Deployment => {
+            let (store, handle) = drive_pane::<Deployment>(client, namespace, field_selector, tx);
+            (PaneStore::Deployment(store), handle)
+        }
+        ResourceType::Secret => {
+            let (store, handle) = drive_pane::<Secret>(client, namespace, field_selector, tx);
+            (PaneStore::Secret(store), handle)
+        }
+        ResourceType::ConfigMap => {
+            let (store, handle) = drive_pane::<ConfigMap>(client, namespace, field_selector, tx);
+            (PaneStore::ConfigMap(store), handle)
+        }
+    }
+}
+
+fn drive_pane<K>(
+    client: Client,
+    namespace: &str,
+    field_selector: Option<&str>,
+    tx: UnboundedSender<KubeResourceEvent>,
+) -> (Store<K>, AbortHandle)
+where
+    K: Resource<Scope = k8s_openapi::NamespaceResourceScope>
+        + Clone
+        + DeserializeOwned
+        + Debug
+        + Send
+        + Sync
+        + 'static,
+    K::DynamicType: Default + Eq + std::hash::Hash + Clone,
+{
+    let (store, stream) = reflect_resources::<K>(client, namespace, field_selector);
+    let handle = tokio::spawn(async move {
+        let mut stream = std::pin::pin!(stream);
+        while let Some(event) = stream.next().await {
+            let msg = match event {
+                Ok(watcher::Event::Init | watcher::Event::InitApply(_)) => continue,
+                Ok(_) => KubeResourceEvent::SplitRefresh,
+                Err(e) => KubeResourceEvent::SplitError(format!("Split watcher: {e}")),
+            };
+            if tx.send(msg).is_err() {
+                break;
+            }
+        }
+    });
+    (store, handle.abort_handle())
+}
+
+/// Reports each crash loop or failed rollout in `namespace` as `WebhookFired`.
+pub fn spawn_webhook_watcher(
+    client: Client,
+    namespace: &str,
+    tx: UnboundedSender<KubeResourceEvent>,
+) -> AbortHandle {
+    let pods = webhook_stream(client.clone(), namespace, KubeResource::Pod);
+    let deployments = webhook_stream(client, namespace, KubeResource::Deployment);
+    let handle = tokio::spawn(async move {
+        let mut stream = futures::stream::select(pods, deployments);
+        let mut previous: HashMap<(ResourceType, String), KubeResource> = HashMap::new();
+        while let Some((item, deleted)) = stream.next().await {
+            let key = (item.resource_type(), item.name().to_string());
+            if deleted {
+                previous.remove(&key);
+                continue;
+            }
+            let fired = previous
+                .get(&key)
+                .and_then(|before| crate::notify::webhook_event(before, &item));
+            if let Some((event, what)) = fired {
+                let target = format!("{}/{}", key.0.singular(), key.1);
+                if tx
+                    .send(KubeResourceEvent::WebhookFired(event, target, what))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            previous.insert(key, item);
+        }
+    });
+    handle.abort_handle()
+}
+
+/// Objects of `K` as they change, with whether each was deleted.
+fn webhook_stream<K>(
+    client: Client,
+    namespace: &str,
+    wrap: fn(std::sync::Arc<K>) -> KubeResource,
+) -> impl Stream<Item = (KubeResource, bool)> + Send + use<K>
+where
+    K: Resource<Scope = k8s_openapi::NamespaceResourceScope>
+        + Clone
+        + DeserializeOwned
+        + Debug
+        + Send
+        + 'static,
+    K::DynamicType: Default,
+{
+    let api = Api::<K>::namespaced(client, namespace);
+    let stream = watcher(api, watcher_config(None)).default_backoff();
+    Box::pin(stream.filter_map(move |event| {
+        std::future::ready(match event {
+            Ok(watcher::Event::Apply(o) | watcher::Event::InitApply(o)) => {
+                Some((wrap(o.into()), false))
+            }
+            Ok(watcher::Event::Delete(o)) => Some((wrap(o.into()), true)),
+            Ok(_) => None,
+            Err(e) => {
+                tracing::debug!("Webhook watch failed, retrying: {e}");
+                None
+            }
+        })
+    }))
+}
+
+/// Follows the object `name` until `condition` describes it as met.
+pub async fn wait_for<K>(
+    client: Client,
+    namespace: &str,
+    name: &str,
+    condition: impl Fn(&K) -> Option<String>,
+) -> Result<String, String>
+where
+    K: Resource<Scope = k8s_openapi::NamespaceResourceScope>
+        + Clone
+        + DeserializeOwned
+        + Debug
+        + Send
+        + 'static,
+    K::DynamicType: Default,
+{
+    let api = Api::<K>::namespaced(client, namespace);
+    let config = watcher_config(Some(&format!("metadata.name={name}")));
+    let mut stream = std::pin::pin!(watcher(api, config).default_backoff());
+    let mut seen = false;
+    while let Some(event) = stream.next().await {
+        match event {
+            Ok(event) => {
+                if let Some(outcome) = wait_step(event, &mut seen, &condition) {
+                    return outcome;
+                }
+            }
+            Err(e) => tracing::debug!("Watch of {name} failed, retrying: {e}"),
+        }
+    }
+    Err("watch ended".to_string())
+}
+
+/// Advances `wait_for` by one event.
+fn wait_step<K>(
+    event: watcher::Event<K>,
+    seen: &mut bool,
+    condition: impl Fn(&K) -> Option<String>,
+) -> Option<Result<String, String>> {
+    match event {
+        watcher::Event::Init => *seen = false,
+        watcher::Event::Apply(o) | watcher::Event::InitApply(o) => {
+            *seen = true;
+            return condition(&o).map(Ok);
+        }
+        watcher::Event::Delete(_) => return Some(Ok("was deleted".to_string())),
+        // The object is gone if a relist finds nothing.
+        watcher::Event::InitDone if !*seen => return Some(Err("not found".to_string())),
+        watcher::Event::InitDone => {}
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watches_request_field_selectors() {
+        let config = watcher_config(Some("status.phase!=Succeeded"));
+        assert_eq!(
+            config.field_selector.as_deref(),
+            Some("status.phase!=Succeeded")
+        );
+        assert!(watcher_config(None).field_selector.is_none());
+    }
+
+    #[test]
+    fn waits_fail_when_a_relist_misses_the_object() {
+        let never = |_: &Pod| None;
+        let mut seen = false;
+        assert_eq!(
+            wait_step(watcher::Event::Apply(Pod::default()), &mut seen, never),
+            None
+        );
+        assert_eq!(wait_step(watcher::Event::Init, &mut seen, never), None);
+        assert_eq!(
+            wait_step(watcher::Event::InitDone, &mut seen, never),
+            Some(Err("not found".to_string()))
+        );
+
+        let mut seen = false;
+        for event in [
+            watcher::Event::Init,
+            watcher::Event::InitApply(Pod::default()),
+        ] {
+            assert_eq!(wait_step(event, &mut seen, never), None);
+        }
+        assert_eq!(wait_step(watcher::Event::InitDone, &mut seen, never), None);
+    }
+}
@@ -0,0 +1,395 @@
+//! Single definition of kr's keybindings, for the `?` overlay and the README.
+
+use crate::models::ResourceType;
+
+pub struct Section {
+    pub title: &'static str,
+    /// `(keys, action)` rows; keys use Markdown backticks.
+    pub bindings: &'static [(&'static str, &'static str)],
+}
+
+/// Actions offered by the `.` / `Enter` menu for a row of `tab`.
+pub fn resource_actions(tab: ResourceType) -> &'static [(char, &'static str)] {
+    match tab {
+        ResourceType::Pod => &[
+            ('l', "Logs"),
+            ('s', "Shell"),
+            ('d', "Describe"),
+            ('e', "Edit YAML"),
+            ('D', "Delete"),
+        ],
+        ResourceType::Deployment => &[
+            ('d', "Describe"),
+            ('e', "Edit YAML"),
+            ('S', "Scale"),
+            ('r', "Rollout restart"),
+            ('D', "Delete"),
+        ],
+        ResourceType::Secret => &[
+            ('x', "Decode"),
+            ('=', "Diff against another namespace"),
+            ('u', "Find pods and deployments using it"),
+        ],
+        ResourceType::ConfigMap => &[('x', "View")],
+    }
+}
+
+pub const SECTIONS: &[Section] = &[
+    Section {
+        title: "Navigation",
+        bindings: &[
+            (
+                "`Tab` / `Shift+Tab`",
+                "Switch between Pods / Deployments / Secrets / ConfigMaps",
+            ),
+            ("`1` – `4`", "Jump to a tab by position"),
+            ("`j` / `k`", "Move up / down"),
+            ("`g` / `G`", "Jump to top / bottom"),
+            ("`PgUp` / `PgDn`, `Ctrl+U` / `Ctrl+D`", "Page scroll"),
+            (
+                "`5j`, `010k`, `03 Ctrl+D`",
+                "Count prefix: repeat a motion (list, logs and describe); in the list a count starts with `0` or `5`–`9`, as `1`–`4` jump to tabs",
+            ),
+            (
+                "`/`",
+                "Filter by name (`Tab` cycles substring / fuzzy, ranked by score / regex such as `^api-`; also in the namespace popup; `Up` / `Down` recall earlier filters for the tab)",
+            ),
+            (
+                "`p`",
+                "Toggle the detail pane for the selected row (hidden while a split pane is open)",
+            ),
+            ("`<` / `>`", "Narrow / widen the detail pane"),
+            (
+                "`w`",
+                "Toggle wide columns on Pods / Deployments (`←` / `→` scroll them; name stays pinned)",
+            ),
+            (
+                "`m` + letter",
+                "Mark the selected resource; `'` + letter jumps back to it, switching tab and namespace",
+            ),
+            (
+                "`R`",
+                "Restart the watch and relist the current tab (the status bar shows when it last updated)",
+            ),
+            ("`Esc`", "Clear filter / close modal / back"),
+            ("`?`", "Show all keybindings"),
+            (
+                "`E`",
+                "Show the last error in full, with the API status code and reason when there is one",
+            ),
+            (
+                "`N`",
+                "Notification history: every error and success message with its time, newest first",
+            ),
+            (
+                "`:`",
+                "Command palette: `ns <name>`, `ctx <name>`, `pods`, `deploy`, `secrets`, `cm`, `audit`, `apply [file]`, `export [file]`, `login [ctx]`, `help`, `q` (Tab completes, Up/Down recalls history)",
+            ),
+            ("`q`", "Quit"),
+        ],
+    },
+    Section {
+        title: "Cluster",
+        bindings: &[
+            (
+                "`c`",
+                "Switch context (cluster); the list is ordered by most recent use, `/` filters it",
+            ),
+            (
+                "`L` (context popup)",
+                "Renew the highlighted context's Teleport session with `tsh kube login`; Teleport contexts show the time left",
+            ),
+            (
+                "`n`",
+                "Switch namespace (`f` in the popup pins/unpins a favorite, listed first)",
+            ),
+            (
+                "`i`",
+                "Namespace summary: pods by status, deployments, CPU/memory requests and quota usage",
+            ),
+            (
+                "`A`",
+                "\"Can I?\" panel: check list/delete/exec/scale/etc. permissions in the current namespace",
+            ),
+            (
+                "`M`",
+                "Metrics panel for the selected pod or deployment from the context's Prometheus (`r` refreshes)",
+            ),
+            (
+                "`X`",
+                "Export the selected rows as YAML files into a directory (`Tab` keeps status and server fields)",
+            ),
+            (
+                "`o`",
+                "Open the selected row in the browser via the context's `links` (a menu when there are several)",
+            ),
+            (
+                "`O`",
+                "Endpoints of the selected pod or deployment: ingress hosts (https when covered by TLS) and external service addresses, as clickable links; `Enter` opens, `c` copies",
+            ),
+            (
+                "`P`",
+                "Plugin menu: the commands configured under `plugins` for the selected row",
+            ),
+            (
+                "`[` / `]`",
+                "Previous / next known namespace of this context",
+            ),
+            ("`-`", "Flip back to the previous namespace of this context"),
+            (
+                "`a` / `D` (namespace popup)",
+                "Create a namespace / delete the highlighted one (with confirmation; system and current namespaces are protected)",
+            ),
+            (
+                "`|`",
+                "Toggle a read-only split pane showing the same tab in another namespace (`ns` or `context/ns`)",
+            ),
+        ],
+    },
+    Section {
+        title: "Pods",
+        bindings: &[
+            ("`l`", "Stream logs"),
+            ("`s`", "Open shell"),
+            ("`d`", "Describe"),
+            (
+                "`e`",
+                "Edit YAML in `$EDITOR`, then review the diff before applying",
+            ),
+            ("`f`", "Filter by pod's status"),
+            (
+                "`W`",
+                "Watch: desktop notification once the pod is Ready, crash-looping or done (`W` again cancels)",
+            ),
+            ("`D` / `Delete`", "Delete (with confirmation)"),
+            (
+                "`.` / `Enter`",
+                "Action menu for the selected row (`Enter` decodes / views on Secrets / ConfigMaps)",
+            ),
+            (
+                "`c` (action menu / confirm dialog)",
+                "Copy the equivalent `kubectl` command, e.g. for an incident channel (`Ctrl+Y` where the dialog asks to type)",
+            ),
+            (
+                "`Enter` on `kubectl …` (action menu)",
+                "Run a kubectl/krew plugin found on `PATH` with the row's name, namespace and context in the embedded terminal",
+            ),
+            ("`Space`", "Toggle select"),
+            ("`!`", "Invert the selection within the filtered list"),
+            (
+                "`V`",
+                "Visual select: `j` / `k` extend the selection over a range; `V` or `Esc` ends it",
+            ),
+            ("`Ctrl+A`", "Select / deselect all"),
+        ],
+    },
+    Section {
+        title: "Deployments",
+        bindings: &[
+            ("`S`", "Scale replicas"),
+            ("`r`", "Rollout restart"),
+            (
+                "`W`",
+                "Watch: desktop notification once the rollout completes or stalls (`W` again cancels)",
+            ),
+            ("`d`", "Describe"),
+            (
+                "`e`",
+                "Edit YAML in `$EDITOR`, then review the diff before applying",
+            ),
+            ("`D` / `Delete`", "Delete (with confirmation)"),
+        ],
+    },
+    Section {
+        title: "Secrets",
+        bindings: &[
+            ("`Enter` / `x`", "Decode and view"),
+            ("`r`", "Reveal / hide values"),
+            (
+                "`Enter` / `v`",
+                "Open selected value in the full-screen pager (binary values as a hex dump) once revealed with `r`",
+            ),
+            (
+                "`h`",
+                "Hex/ASCII dump of the selected value once revealed with `r`",
+            ),
+            (
+                "`s`",
+                "Save the selected value's raw bytes to a file (mode 0600)",
+            ),
+            ("`c`", "Copy selected value to clipboard"),
+            ("`C`", "Copy all keys as `export KEY='value'` lines"),
+            (
+                "`e`",
+                "Edit selected value (multi-line values open `$EDITOR`)",
+            ),
+            (
+                "`w`",
+                "Export all keys to a `.env` file (written with mode 0600)",
+            ),
+            (
+                "`b`",
+                "Toggle an extra column with the raw base64 value (`c` then copies it)",
+            ),
+            (
+                "`=`",
+                "Diff against the same secret in another namespace (`ns` or `context/ns`)",
+            ),
+            (
+                "`u`",
+                "List pods and deployments that use the secret (env, envFrom, volumes, imagePullSecrets)",
+            ),
+            (
+                "`a`",
+                "Create a new secret (key/value pairs or a local `.env` file)",
+            ),
+        ],
+    },
+    Section {
+        title: "ConfigMaps",
+        bindings: &[
+            ("`Enter` / `x`", "View keys (`data` and `binaryData`)"),
+            ("`j` / `k`", "Select key"),
+            ("`J` / `K`, `PgUp` / `PgDn`", "Scroll the selected value"),
+            (
+                "`Enter` / `v`",
+                "Open selected value in the full-screen pager",
+            ),
+            (
+                "`c`",
+                "Copy selected value to clipboard (binary values as base64)",
+            ),
+        ],
+    },
+    Section {
+        title: "Value Pager",
+        bindings: &[
+            ("`j` / `k`", "Scroll"),
+            ("`PgUp` / `PgDn` / `Space`", "Page up / down"),
+            ("`g` / `G`", "Top / bottom"),
+            ("`/`", "Search (case-insensitive)"),
+            ("`n` / `N`", "Next / previous match"),
+            ("`c`", "Copy the whole value"),
+            ("`|`", "Open in `$PAGER` (`less` by default)"),
+            ("`q` / `Esc`", "Back to the modal"),
+        ],
+    },
+    Section {
+        title: "Edit Diff",
+        bindings: &[
+            ("`y`", "Apply the changed fields"),
+            ("`e`", "Back to the editor"),
+            ("`j` / `k`, `PgUp` / `PgDn`", "Scroll"),
+            ("`q` / `Esc`", "Discard the edit"),
+        ],
+    },
+    Section {
+        title: "Log View",
+        bindings: &[
+            ("`j` / `k`", "Scroll"),
+            ("`g`", "Jump to top"),
+            ("`G`", "Resume auto-follow"),
+            ("`/`", "Search"),
+            ("`n` / `N`", "Next / previous search match"),
+            ("`R`", "Follow the pod that replaced a terminated one"),
+            (
+                "`|`",
+                "Open the loaded lines in `$PAGER` (`less` by default)",
+            ),
+            ("`q` / `Esc`", "Exit"),
+        ],
+    },
+    Section {
+        title: "Describe",
+        bindings: &[
+            ("`j` / `k`, `PgUp` / `PgDn`", "Scroll"),
+            ("`g` / `G`", "Top / bottom"),
+            ("`/`", "Search (case-insensitive), matches highlighted"),
+            ("`n` / `N`", "Next / previous match"),
+            ("`a`", "Toggle auto-refresh (re-runs describe every 5s)"),
+            ("`|`", "Open in `$PAGER` (`less` by default)"),
+            ("`q` / `Esc`", "Close"),
+        ],
+    },
+    Section {
+        title: "Shell",
+        bindings: &[
+            ("`Ctrl+Q`", "Close shell session"),
+            ("All other keys", "Forwarded to the shell"),
+        ],
+    },
+    Section {
+        title: "Mouse",
+        bindings: &[
+            ("Click tab / row", "Switch tab / select row"),
+            ("Click outside popup", "Close popup"),
+            ("Wheel", "Scroll lists, logs, describe and pager"),
+        ],
+    },
+];
+
+/// Plain-text help for the pager, one aligned table per section.
+pub fn help_text() -> String {
+    let width = SECTIONS
+        .iter()
+        .flat_map(|s| s.bindings)
+        .map(|(keys, _)| keys.replace('`', "").chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+    for (i, section) in SECTIONS.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(section.title);
+        out.push('\n');
+        for (keys, action) in section.bindings {
+            let keys = keys.replace('`', "");
+            out.push_str(&format!("  {keys:<width$}  {}\n", action.replace('`', "")));
+        }
+    }
+    out
+}
+
+/// The README's keybinding tables, as generated from `SECTIONS`.
+pub fn markdown() -> String {
+    let mut out = String::new();
+    for section in SECTIONS {
+        out.push_str(&format!(
+            "### {}\n\n| Key | Action |\n|-----|--------|\n",
+            section.title
+        ));
+        for (keys, action) in section.bindings {
+            out.push_str(&format!("| {} | {} |\n", keys.replace('|', "\\|"), action));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readme_matches_keymap() {
+        let readme = include_str!("../README.md");
+        let start = readme.find("## Keybindings\n\n").unwrap() + "## Keybindings\n\n".len();
+        let end = readme.find("## Requirements").unwrap();
+        assert_eq!(
+            &readme[start..end],
+            markdown(),
+            "README keybindings drifted from src/keymap.rs"
+        );
+    }
+
+    #[test]
+    fn help_text_lists_every_section() {
+        let help = help_text();
+        for section in SECTIONS {
+            assert!(help.contains(section.title));
+        }
+        assert!(help.contains("  ?"));
+        assert!(!help.contains('`'));
+    }
+}
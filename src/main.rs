@@ -1,36 +1,123 @@
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{Terminal, backend::CrosstermBackend};
-use std::io;
+use std::io::{self, IsTerminal};
 
 struct TerminalGuard;
 
 impl Drop for TerminalGuard {
     fn drop(&mut self) {
         let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        let _ = execute!(io::stdout(), DisableMouseCapture, LeaveAlternateScreen);
         let _ = execute!(io::stdout(), crossterm::cursor::Show);
     }
 }
 
 mod app;
+mod audit;
+pub mod cert;
+mod cli;
+pub mod command;
+pub mod config;
 mod event_loop;
 mod input;
 mod k8s;
+pub mod keymap;
 pub mod models;
+mod notify;
+mod prometheus;
 pub mod state;
 mod ui;
 pub mod utils;
+mod web;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     #[arg(short, long)]
     command: Option<String>,
+    /// Kubeconfig file to use instead of KUBECONFIG / ~/.kube/config.
+    #[arg(long, global = true)]
+    kubeconfig: Option<std::path::PathBuf>,
+    /// Tab to open on: pods, deployments, secrets or configmaps.
+    #[arg(short, long, value_parser = parse_tab)]
+    tab: Option<models::ResourceType>,
+    /// Start with this name filter applied, as if typed after `/`.
+    #[arg(short, long)]
+    filter: Option<String>,
+    /// Start showing only pods in this status; repeat for several.
+    #[arg(short, long)]
+    status: Vec<String>,
+    /// Print the table once as plain text and exit.
+    #[arg(long)]
+    snapshot: bool,
+    #[command(subcommand)]
+    subcommand: Option<Cmd>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Cmd {
+    /// Print a tab's resources once, as the TUI table shows them.
+    Get {
+        /// pods, deployments, secrets or configmaps.
+        #[arg(value_parser = parse_tab)]
+        tab: models::ResourceType,
+        /// Namespace; the kubeconfig's current one by default.
+        #[arg(short, long)]
+        namespace: Option<String>,
+        #[arg(short, long, value_enum, default_value_t)]
+        output: cli::OutputFormat,
+    },
+    /// Print a pod's log to stdout.
+    Logs {
+        pod: String,
+        /// Namespace; the kubeconfig's current one by default.
+        #[arg(short, long)]
+        namespace: Option<String>,
+        /// Container, for pods with more than one.
+        #[arg(short, long)]
+        container: Option<String>,
+        /// Keep streaming new lines.
+        #[arg(short, long)]
+        follow: bool,
+        /// Only lines newer than this, e.g. 30s, 10m or 2h.
+        #[arg(long)]
+        since: Option<String>,
+    },
+    /// Run a command in a pod on this terminal.
+    Exec {
+        pod: String,
+        /// Namespace; the kubeconfig's current one by default.
+        #[arg(short, long)]
+        namespace: Option<String>,
+        /// Container, for pods with more than one.
+        #[arg(short, long)]
+        container: Option<String>,
+        /// Command and arguments, after `--`.
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
+    /// Forward local ports to a pod or service until Ctrl+C.
+    PortForward {
+        /// pod/<name>, svc/<name>, or a pod name.
+        target: String,
+        /// local:remote, or one port for both.
+        #[arg(required = true)]
+        ports: Vec<String>,
+        /// Namespace; the kubeconfig's current one by default.
+        #[arg(short, long)]
+        namespace: Option<String>,
+    },
+}
+
+fn parse_tab(name: &str) -> Result<models::ResourceType, String> {
+    models::ResourceType::from_name(name)
+        .ok_or_else(|| format!("unknown tab '{name}' (pods, deployments, secrets, configmaps)"))
 }
 
 fn init_tracing(to_file: bool) {
@@ -63,6 +150,9 @@ fn init_tracing(to_file: bool) {
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
+    if let Some(path) = args.kubeconfig.clone() {
+        k8s::config::set_kubeconfig_override(path);
+    }
 
     if let Some(cmd) = args.command {
         init_tracing(false);
@@ -76,6 +166,7 @@ async fn main() -> Result<()> {
         };
         let status = std::process::Command::new("kubectl")
             .args(&args_vec)
+            .envs(k8s::config::kubectl_env())
             .status();
 
         match status {
@@ -91,15 +182,55 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
-    init_tracing(true);
+    if let Some(subcommand) = args.subcommand {
+        init_tracing(false);
+        return match subcommand {
+            Cmd::Get {
+                tab,
+                namespace,
+                output,
+            } => cli::get(tab, namespace, output).await,
+            Cmd::Logs {
+                pod,
+                namespace,
+                container,
+                follow,
+                since,
+            } => cli::logs(pod, namespace, container, follow, since).await,
+            Cmd::Exec {
+                pod,
+                namespace,
+                container,
+                command,
+            } => {
+                let code = cli::exec(pod, namespace, container, command)?;
+                std::process::exit(code);
+            }
+            Cmd::PortForward {
+                target,
+                ports,
+                namespace,
+            } => cli::port_forward(target, ports, namespace).await,
+        };
+    }
+
+    let startup = app::Startup {
+        tab: args.tab,
+        filter: args.filter,
+        status: args.status,
+    };
+    if args.snapshot || !io::stdout().is_terminal() {
+        init_tracing(false);
+        return cli::snapshot(startup).await;
+    }
 
-    eprintln!("Connecting to cluster...");
-    let client = k8s::client::default_client().await?;
+    init_tracing(true);
+    let config = app::App::load_config();
 
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
         let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        let _ = execute!(io::stdout(), DisableMouseCapture, LeaveAlternateScreen);
         let _ = execute!(io::stdout(), crossterm::cursor::Show);
         original_hook(panic_info);
     }));
@@ -108,11 +239,15 @@ async fn main() -> Result<()> {
     let _guard = TerminalGuard;
 
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let (app, event_rx) = app::App::new(client).await?;
+    let Some(client) = event_loop::connect(&mut terminal, config.0.tick()).await? else {
+        return Ok(());
+    };
+    let (mut app, event_rx) = app::App::new(client, config).await?;
+    app.startup = startup;
     event_loop::run(&mut terminal, app, event_rx).await?;
 
     Ok(())
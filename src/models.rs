@@ -1,7 +1,8 @@
 use k8s_openapi::api::{
     apps::v1::Deployment,
-    core::v1::{Pod, Secret},
+    core::v1::{ConfigMap, Pod, Secret},
 };
+use std::collections::HashMap;
 use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,15 +17,175 @@ pub enum AppMode {
     Confirm,
     ShellView,
     DescribeView,
+    DescribeSearchInput,
     StatusFilter,
     LogSearchInput,
+    SecretCreate,
+    SecretEditValue,
+    SecretExport,
+    SecretDiffInput,
+    SecretDiff,
+    ConfigMapView,
+    ValuePager,
+    ValuePagerSearch,
+    EditDiff,
+    SecretUsage,
+    SecretSaveRaw,
+    SplitInput,
+    NamespaceCreate,
+    AccessReview,
+    NamespaceSummary,
+    CommandInput,
+    ActionMenu,
+    PluginMenu,
+    LinkMenu,
+    EndpointMenu,
+    ManifestExport,
+    ApplyDiff,
+    MetricsView,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl AppMode {
+    /// Name of the view in the header breadcrumb; `None` for prompts and popups.
+    pub fn view_label(self) -> Option<&'static str> {
+        match self {
+            AppMode::LogView | AppMode::LogSearchInput => Some("Logs"),
+            AppMode::SecretDecode | AppMode::SecretEditValue | AppMode::SecretSaveRaw => {
+                Some("Data")
+            }
+            AppMode::ConfigMapView => Some("Data"),
+            AppMode::ShellView => Some("Shell"),
+            AppMode::DescribeView | AppMode::DescribeSearchInput => Some("Describe"),
+            AppMode::SecretUsage => Some("Usage"),
+            AppMode::SecretDiff | AppMode::EditDiff | AppMode::ApplyDiff => Some("Diff"),
+            AppMode::ValuePager | AppMode::ValuePagerSearch => Some("Pager"),
+            AppMode::AccessReview => Some("Access"),
+            AppMode::NamespaceSummary => Some("Summary"),
+            AppMode::MetricsView => Some("Metrics"),
+            _ => None,
+        }
+    }
+
+    /// Whether the view shows the selected resource, so the breadcrumb names it.
+    pub fn is_resource_view(self) -> bool {
+        matches!(
+            self.view_label(),
+            Some("Logs" | "Data" | "Shell" | "Describe" | "Usage" | "Diff" | "Metrics")
+        )
+    }
+}
+
+/// Why a followed log stream stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogStreamEnd {
+    /// The pod is still there: its container exited or the connection dropped.
+    Ended,
+    /// The pod was deleted; `replacement` is a newer pod from the same controller.
+    PodTerminated { replacement: Option<String> },
+}
+
+/// State of the main list's watch, shown in the status bar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchHealth {
+    /// Waiting for the initial list.
+    Syncing,
+    Live,
+    /// The watch stream failed and is being retried with backoff.
+    Reconnecting {
+        /// Consecutive failed attempts.
+        attempts: u32,
+        since: std::time::Instant,
+        last_error: String,
+    },
+    Forbidden,
+}
+
+/// How the `/` filter and the namespace popup match typed text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum FilterMode {
+    #[default]
+    Substring,
+    /// fzf-style subsequence match, ranked by score.
+    Fuzzy,
+    /// Case-insensitive regular expression, e.g. `^api-(foo|bar)`.
+    Regex,
+}
+
+impl FilterMode {
+    pub fn next(self) -> Self {
+        match self {
+            FilterMode::Substring => FilterMode::Fuzzy,
+            FilterMode::Fuzzy => FilterMode::Regex,
+            FilterMode::Regex => FilterMode::Substring,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FilterMode::Substring => "substring",
+            FilterMode::Fuzzy => "fuzzy",
+            FilterMode::Regex => "regex",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ResourceType {
     Pod,
     Deployment,
     Secret,
+    ConfigMap,
+}
+
+impl ResourceType {
+    /// Tabs in display order; `1`, `2`, ... jump to them by position.
+    pub const ALL: [ResourceType; 4] = [
+        ResourceType::Pod,
+        ResourceType::Deployment,
+        ResourceType::Secret,
+        ResourceType::ConfigMap,
+    ];
+
+    /// Tab bar title.
+    pub fn title(self) -> &'static str {
+        match self {
+            ResourceType::Pod => "Pods",
+            ResourceType::Deployment => "Deployments",
+            ResourceType::Secret => "Secrets",
+            ResourceType::ConfigMap => "ConfigMaps",
+        }
+    }
+
+    /// Lowercase plural, as in `kubectl get pods`.
+    pub fn plural(self) -> &'static str {
+        match self {
+            ResourceType::Pod => "pods",
+            ResourceType::Deployment => "deployments",
+            ResourceType::Secret => "secrets",
+            ResourceType::ConfigMap => "configmaps",
+        }
+    }
+
+    /// Lowercase singular, as in `kubectl describe pod`.
+    pub fn singular(self) -> &'static str {
+        match self {
+            ResourceType::Pod => "pod",
+            ResourceType::Deployment => "deployment",
+            ResourceType::Secret => "secret",
+            ResourceType::ConfigMap => "configmap",
+        }
+    }
+
+    /// Parses a tab name or kubectl-style alias (`pods`, `deploy`, `cm`, ...).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "po" | "pod" | "pods" => Some(ResourceType::Pod),
+            "deploy" | "deployment" | "deployments" | "dp" => Some(ResourceType::Deployment),
+            "secret" | "secrets" | "sec" => Some(ResourceType::Secret),
+            "cm" | "configmap" | "configmaps" => Some(ResourceType::ConfigMap),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -32,6 +193,7 @@ pub enum KubeResource {
     Pod(Arc<Pod>),
     Deployment(Arc<Deployment>),
     Secret(Arc<Secret>),
+    ConfigMap(Arc<ConfigMap>),
 }
 
 impl KubeResource {
@@ -40,23 +202,387 @@ impl KubeResource {
             KubeResource::Pod(p) => &p.metadata,
             KubeResource::Deployment(d) => &d.metadata,
             KubeResource::Secret(s) => &s.metadata,
+            KubeResource::ConfigMap(c) => &c.metadata,
         };
         meta.name.as_deref().unwrap_or_default()
     }
+
+    pub fn resource_type(&self) -> ResourceType {
+        match self {
+            KubeResource::Pod(_) => ResourceType::Pod,
+            KubeResource::Deployment(_) => ResourceType::Deployment,
+            KubeResource::Secret(_) => ResourceType::Secret,
+            KubeResource::ConfigMap(_) => ResourceType::ConfigMap,
+        }
+    }
+
+    /// The object as JSON, for evaluating custom column paths.
+    pub fn to_json(&self) -> serde_json::Value {
+        let value = match self {
+            KubeResource::Pod(p) => serde_json::to_value(&**p),
+            KubeResource::Deployment(d) => serde_json::to_value(&**d),
+            KubeResource::Secret(s) => serde_json::to_value(&**s),
+            KubeResource::ConfigMap(c) => serde_json::to_value(&**c),
+        };
+        value.unwrap_or_default()
+    }
 }
 
 pub enum KubeResourceEvent {
     Refresh,
+    /// An object of the watched tab was added or modified after the initial list.
+    Applied(KubeResource),
+    /// An object of the watched tab was deleted after the initial list.
+    Deleted(KubeResource),
     InitialListDone,
     Error(String),
     Success(String),
     WatcherForbidden(String),
+    /// The API server rejected the current client's credentials (401).
+    Unauthorized(String),
     Log(String),
     LogHistory(u64, Vec<String>),
+    /// A followed log stream for the named pod ended.
+    LogEnded(String, LogStreamEnd),
     ShellOutput(Vec<u8>),
     ShellExited,
-    DescribeReady(Vec<String>),
+    ClearOsc52Clipboard,
+    /// A resource fetched for editing; `true` after a conflict, back to the diff.
+    EditReady(ResourceEdit, bool),
+    /// Graphs for the metrics panel of the named pod or deployment.
+    MetricsReady(String, Vec<MetricSeries>),
+    /// URLs found for the named workload by `O`.
+    EndpointsReady(String, Vec<Endpoint>),
+    /// A manifest's objects after their server-side dry run.
+    ManifestPlanned(Vec<ManifestObject>),
+    /// Title and captured output of a plugin, for the pager.
+    PluginOutput(String, String),
+    /// Output of `kubectl describe`; `true` when it refreshes the open view.
+    DescribeReady(Vec<String>, bool),
     NamespacesLoaded(Vec<String>),
+    NamespaceCreated(String),
+    ContextsChanged(Vec<String>),
+    ClientWarmed(String, kube::Client),
+    /// Login status of the contexts that authenticate with `tsh`.
+    TeleportStatus(HashMap<String, crate::k8s::teleport::LoginStatus>),
+    AccessReviewReady(String, Vec<AccessCheck>),
+    NamespaceSummaryReady(String, NamespaceSummary),
+    /// Server version probe for a context: git version or error text.
+    ClusterProbe(String, Result<String, String>),
+    NamespaceDeleted(String),
+    /// Outcome of an action over several resources, with each name's error if any.
+    BulkResult(String, Vec<(String, Result<(), String>)>),
+    SecretDiffReady(String, Vec<SecretDiffRow>),
+    SecretUsageReady(String, Vec<SecretConsumer>),
+    SecretOwnerStatus(String, String),
+    SplitReady(kube::Client, String, String),
+    SplitRefresh,
+    SplitError(String),
+    /// A watched object entered a webhook condition: target and description.
+    WebhookFired(crate::config::WebhookEvent, String, String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SecretFormField {
+    #[default]
+    Name,
+    Type,
+    Key,
+    Value,
+    EnvFile,
+}
+
+impl SecretFormField {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Name => Self::Type,
+            Self::Type => Self::Key,
+            Self::Key => Self::Value,
+            Self::Value => Self::EnvFile,
+            Self::EnvFile => Self::Name,
+        }
+    }
+
+    pub fn prev(self) -> Self {
+        match self {
+            Self::Name => Self::EnvFile,
+            Self::Type => Self::Name,
+            Self::Key => Self::Type,
+            Self::Value => Self::Key,
+            Self::EnvFile => Self::Value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretForm {
+    pub name: String,
+    pub type_: String,
+    pub key: String,
+    pub value: String,
+    pub env_file: String,
+    pub entries: Vec<(String, String)>,
+    pub field: SecretFormField,
+}
+
+impl Default for SecretForm {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            type_: "Opaque".to_string(),
+            key: String::new(),
+            value: String::new(),
+            env_file: String::new(),
+            entries: Vec::new(),
+            field: SecretFormField::Name,
+        }
+    }
+}
+
+impl SecretForm {
+    pub fn input_mut(&mut self) -> &mut String {
+        match self.field {
+            SecretFormField::Name => &mut self.name,
+            SecretFormField::Type => &mut self.type_,
+            SecretFormField::Key => &mut self.key,
+            SecretFormField::Value => &mut self.value,
+            SecretFormField::EnvFile => &mut self.env_file,
+        }
+    }
+
+    /// Adds or replaces an entry, keeping the last value for duplicate keys.
+    pub fn upsert(&mut self, key: String, value: String) {
+        if let Some(entry) = self.entries.iter_mut().find(|(k, _)| *k == key) {
+            entry.1 = value;
+        } else {
+            self.entries.push((key, value));
+        }
+    }
+}
+
+/// A ConfigMap or Secret value; bytes that aren't UTF-8 stay as bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigValue {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl ConfigValue {
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        match String::from_utf8(bytes) {
+            Ok(text) => ConfigValue::Text(text),
+            Err(e) => ConfigValue::Binary(e.into_bytes()),
+        }
+    }
+
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            ConfigValue::Text(text) => Some(text),
+            ConfigValue::Binary(_) => None,
+        }
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        match self {
+            ConfigValue::Text(text) => text.as_bytes(),
+            ConfigValue::Binary(bytes) => bytes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffStatus {
+    Same,
+    Changed,
+    Added,
+    Removed,
+}
+
+/// One key of a secret compared against its counterpart elsewhere.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretDiffRow {
+    pub key: String,
+    pub status: DiffStatus,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+/// The ExternalSecret/SealedSecret that generates a secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretOwner {
+    pub kind: String,
+    pub name: String,
+    pub api_version: String,
+}
+
+/// A pod or deployment that references a secret, with each reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretConsumer {
+    pub kind: &'static str,
+    pub name: String,
+    pub refs: Vec<String>,
+}
+
+/// A URL that reaches a workload from outside the cluster.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    /// Where the URL comes from, e.g. `ingress web`.
+    pub source: String,
+    pub url: String,
+}
+
+/// Health overview of a namespace for the summary popup.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NamespaceSummary {
+    /// Pod count per phase, sorted by phase.
+    pub pods_by_status: Vec<(String, usize)>,
+    pub deployments: usize,
+    /// Deployments with all desired replicas ready.
+    pub deployments_ready: usize,
+    /// Summed container requests of pods that are not finished.
+    pub cpu_requests_millis: u64,
+    pub memory_requests_bytes: u64,
+    pub quotas: Vec<QuotaUsage>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotaUsage {
+    pub quota: String,
+    pub resource: String,
+    pub used: String,
+    pub hard: String,
+    /// `used / hard` when both quantities parse.
+    pub ratio: Option<f64>,
+}
+
+/// A footer message kept after it fades, for the notification history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub at: jiff::Zoned,
+    pub error: bool,
+    pub message: String,
+    /// Times the same message arrived back to back.
+    pub repeats: usize,
+}
+
+/// One SelfSubjectAccessReview row of the "Can I?" panel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessCheck {
+    pub verb: &'static str,
+    pub group: &'static str,
+    pub resource: &'static str,
+    pub subresource: &'static str,
+    /// `Err` holds the API error if the review itself could not be made.
+    pub allowed: Result<bool, String>,
+}
+
+impl AccessCheck {
+    pub const fn new(
+        verb: &'static str,
+        group: &'static str,
+        resource: &'static str,
+        subresource: &'static str,
+    ) -> Self {
+        Self {
+            verb,
+            group,
+            resource,
+            subresource,
+            allowed: Ok(false),
+        }
+    }
+
+    /// `pods/exec`-style resource label.
+    pub fn target(&self) -> String {
+        if self.subresource.is_empty() {
+            self.resource.to_string()
+        } else {
+            format!("{}/{}", self.resource, self.subresource)
+        }
+    }
+}
+
+/// Operations kr performs, checked by the "Can I?" panel.
+pub const ACCESS_CHECKS: &[AccessCheck] = &[
+    AccessCheck::new("list", "", "pods", ""),
+    AccessCheck::new("get", "", "pods", "log"),
+    AccessCheck::new("create", "", "pods", "exec"),
+    AccessCheck::new("delete", "", "pods", ""),
+    AccessCheck::new("list", "apps", "deployments", ""),
+    AccessCheck::new("patch", "apps", "deployments", ""),
+    AccessCheck::new("patch", "apps", "deployments", "scale"),
+    AccessCheck::new("delete", "apps", "deployments", ""),
+    AccessCheck::new("list", "", "secrets", ""),
+    AccessCheck::new("create", "", "secrets", ""),
+    AccessCheck::new("patch", "", "secrets", ""),
+    AccessCheck::new("list", "", "configmaps", ""),
+    AccessCheck::new("create", "", "namespaces", ""),
+    AccessCheck::new("delete", "", "namespaces", ""),
+];
+
+/// Work that needs the terminal handed over to `$EDITOR` or `$PAGER`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditorRequest {
+    SecretValue {
+        secret: String,
+        key: String,
+        value: String,
+    },
+    /// A resource's YAML, patched with the changes once saved.
+    Resource(ResourceEdit),
+    /// Manifests to apply, pasted or written into an empty buffer.
+    Manifest,
+    /// Text to read in `$PAGER`; nothing comes back.
+    Pager(String),
+}
+
+/// One graph of the metrics panel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSeries {
+    pub title: String,
+    /// `(minutes ago, value)`, oldest first, or why the query failed.
+    pub points: Result<Vec<(f64, f64)>, String>,
+}
+
+/// One object of a manifest being applied, with its dry-run outcome.
+#[derive(Debug, Clone)]
+pub struct ManifestObject {
+    /// `kind/name`.
+    pub label: String,
+    pub object: kube::api::DynamicObject,
+    /// The kind's API; `None` when discovery didn't find it.
+    pub resource: Option<kube::api::ApiResource>,
+    /// Where the object goes; `None` for cluster-scoped kinds.
+    pub namespace: Option<String>,
+    /// Diff of the live object to the dry-run result, or why the dry run failed.
+    pub plan: Result<Vec<String>, String>,
+    /// The object doesn't exist yet.
+    pub created: bool,
+}
+
+impl ManifestObject {
+    /// `kind/name`, with `-n namespace` for namespaced objects.
+    pub fn title(&self) -> String {
+        match &self.namespace {
+            Some(ns) => format!("{} -n {ns}", self.label),
+            None => self.label.clone(),
+        }
+    }
+}
+
+/// A resource being edited with `e`: the fetched YAML and the user's version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceEdit {
+    /// `pod` or `deployment`.
+    pub kind: &'static str,
+    pub name: String,
+    pub namespace: String,
+    /// The object as on the server, which the diff preview compares against.
+    pub original: String,
+    /// The YAML as last saved in the editor.
+    pub edited: String,
+    /// Why the last attempt was rejected.
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -73,6 +599,42 @@ pub enum PendingAction {
         name: String,
         replicas: u32,
     },
+    EditManagedSecret {
+        owner: String,
+    },
+    DeleteNamespace {
+        name: String,
+    },
+    /// Plugin `index` of the config, asked for because it sets `confirm`.
+    RunPlugin {
+        index: usize,
+        label: String,
+        target: String,
+    },
+    CreateSecret {
+        name: String,
+        type_: String,
+        entries: Vec<(String, String)>,
+    },
+    PatchSecretKey {
+        secret: String,
+        key: String,
+        value: String,
+    },
+    CreateNamespace {
+        name: String,
+    },
+    /// The saved `ResourceEdit`.
+    ApplyEdit {
+        kind: &'static str,
+        name: String,
+        namespace: String,
+    },
+    /// The planned manifest; `namespaces` are those its objects land in.
+    ApplyManifest {
+        count: usize,
+        namespaces: Vec<String>,
+    },
 }
 
 impl PendingAction {
@@ -99,8 +661,77 @@ impl PendingAction {
                     format!("Scale '{}' to {} replicas?", name, replicas)
                 }
             }
+            Self::EditManagedSecret { owner } => {
+                format!(
+                    "Secret is managed by {}.\nManual edits will be overwritten\non the next sync. Edit anyway?",
+                    owner
+                )
+            }
+            Self::DeleteNamespace { name } => {
+                format!(
+                    "Delete namespace '{}'?\nEverything inside it will be\ndeleted with it.",
+                    name
+                )
+            }
+            Self::RunPlugin { label, target, .. } => {
+                format!("Run '{}' on '{}'?", label, target)
+            }
+            Self::CreateSecret { name, .. } => format!("Create secret '{}'?", name),
+            Self::PatchSecretKey { secret, key, .. } => {
+                format!("Update '{}' in secret '{}'?", key, secret)
+            }
+            Self::CreateNamespace { name } => format!("Create namespace '{}'?", name),
+            Self::ApplyEdit { kind, name, .. } => format!("Apply the edit to {} '{}'?", kind, name),
+            Self::ApplyManifest { count, .. } => format!("Apply {} object(s)?", count),
         }
     }
+
+    /// The `kubectl` command doing the same, when there is one.
+    pub fn kubectl(&self, context: &str, namespace: &str) -> Option<String> {
+        let (namespace, args) = match self {
+            Self::DeleteResource { kind, names, .. } => {
+                let kind = match *kind {
+                    "pod(s)" => "pod",
+                    "deployment(s)" => "deployment",
+                    _ => return None,
+                };
+                let mut args = vec!["delete".to_string(), kind.to_string()];
+                args.extend(names.iter().cloned());
+                (Some(namespace), args)
+            }
+            Self::RestartDeployment { name } => (
+                Some(namespace),
+                vec![
+                    "rollout".into(),
+                    "restart".into(),
+                    format!("deployment/{name}"),
+                ],
+            ),
+            Self::ScaleDeployment { name, replicas } => (
+                Some(namespace),
+                vec![
+                    "scale".into(),
+                    format!("deployment/{name}"),
+                    format!("--replicas={replicas}"),
+                ],
+            ),
+            Self::DeleteNamespace { name } => (
+                None,
+                vec!["delete".into(), "namespace".into(), name.clone()],
+            ),
+            Self::CreateNamespace { name } => (
+                None,
+                vec!["create".into(), "namespace".into(), name.clone()],
+            ),
+            Self::EditManagedSecret { .. }
+            | Self::RunPlugin { .. }
+            | Self::CreateSecret { .. }
+            | Self::PatchSecretKey { .. }
+            | Self::ApplyEdit { .. }
+            | Self::ApplyManifest { .. } => return None,
+        };
+        Some(crate::utils::kubectl_line(context, namespace, &args))
+    }
 }
 
 #[cfg(test)]
@@ -109,27 +740,33 @@ mod tests {
     use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
 
     fn pod_with_name(name: &str) -> KubeResource {
-        let mut pod = Pod::default();
-        pod.metadata = ObjectMeta {
-            name: Some(name.to_string()),
+        let pod = Pod {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
             ..Default::default()
         };
         KubeResource::Pod(Arc::new(pod))
     }
 
     fn deployment_with_name(name: &str) -> KubeResource {
-        let mut dep = Deployment::default();
-        dep.metadata = ObjectMeta {
-            name: Some(name.to_string()),
+        let dep = Deployment {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
             ..Default::default()
         };
         KubeResource::Deployment(Arc::new(dep))
     }
 
     fn secret_with_name(name: &str) -> KubeResource {
-        let mut secret = Secret::default();
-        secret.metadata = ObjectMeta {
-            name: Some(name.to_string()),
+        let secret = Secret {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
             ..Default::default()
         };
         KubeResource::Secret(Arc::new(secret))
@@ -168,4 +805,57 @@ mod tests {
         assert_eq!(ResourceType::Pod, ResourceType::Pod);
         assert_ne!(ResourceType::Pod, ResourceType::Secret);
     }
+
+    #[test]
+    fn access_check_target_includes_subresource() {
+        let exec = ACCESS_CHECKS
+            .iter()
+            .find(|c| c.verb == "create" && c.resource == "pods")
+            .unwrap();
+        assert_eq!(exec.target(), "pods/exec");
+        assert_eq!(AccessCheck::new("list", "", "pods", "").target(), "pods");
+    }
+
+    #[test]
+    fn resource_type_from_name_accepts_aliases() {
+        assert_eq!(
+            ResourceType::from_name("Deploy"),
+            Some(ResourceType::Deployment)
+        );
+        assert_eq!(ResourceType::from_name("cm"), Some(ResourceType::ConfigMap));
+        assert_eq!(ResourceType::from_name("nodes"), None);
+        for tab in ResourceType::ALL {
+            assert_eq!(ResourceType::from_name(tab.plural()), Some(tab));
+        }
+    }
+
+    #[test]
+    fn pending_actions_spell_out_their_kubectl_command() {
+        let delete = PendingAction::DeleteResource {
+            count: 2,
+            kind: "pod(s)",
+            names: vec!["api-1".into(), "api-2".into()],
+        };
+        assert_eq!(
+            delete.kubectl("prod", "pay ments").as_deref(),
+            Some("kubectl --context prod -n 'pay ments' delete pod api-1 api-2")
+        );
+        let scale = PendingAction::ScaleDeployment {
+            name: "api".into(),
+            replicas: 0,
+        };
+        assert_eq!(
+            scale.kubectl("prod", "payments").as_deref(),
+            Some("kubectl --context prod -n payments scale deployment/api --replicas=0")
+        );
+        let namespace = PendingAction::DeleteNamespace { name: "old".into() };
+        assert_eq!(
+            namespace.kubectl("prod", "payments").as_deref(),
+            Some("kubectl --context prod delete namespace old")
+        );
+        let owner = PendingAction::EditManagedSecret {
+            owner: "argo".into(),
+        };
+        assert_eq!(owner.kubectl("prod", "payments"), None);
+    }
 }
@@ -0,0 +1,269 @@
+//! Conditions a `W` watch waits for, and the desktop notification sent
+//! when one is met; the failures posted to a context's webhooks.
+
+use crate::config::WebhookEvent;
+use crate::models::KubeResource;
+use k8s_openapi::api::{apps::v1::Deployment, core::v1::Pod};
+
+/// The first container waiting in CrashLoopBackOff, with its restart count.
+fn crash_looping(pod: &Pod) -> Option<(&str, i32)> {
+    let status = pod.status.as_ref()?;
+    status
+        .container_statuses
+        .iter()
+        .chain(&status.init_container_statuses)
+        .flatten()
+        .find(|cs| {
+            cs.state
+                .as_ref()
+                .and_then(|s| s.waiting.as_ref())
+                .and_then(|w| w.reason.as_deref())
+                == Some("CrashLoopBackOff")
+        })
+        .map(|cs| (cs.name.as_str(), cs.restart_count))
+}
+
+fn rollout_stalled(deployment: &Deployment) -> bool {
+    let Some(status) = deployment.status.as_ref() else {
+        return false;
+    };
+    status.conditions.iter().flatten().any(|c| {
+        c.type_ == "Progressing" && c.reason.as_deref() == Some("ProgressDeadlineExceeded")
+    })
+}
+
+/// Met once the pod is Ready, crash-looping or finished.
+pub fn pod_condition(pod: &Pod) -> Option<String> {
+    if crash_looping(pod).is_some() {
+        return Some("is in CrashLoopBackOff".to_string());
+    }
+    let status = pod.status.as_ref()?;
+    match status.phase.as_deref() {
+        Some("Succeeded") => return Some("completed".to_string()),
+        Some("Failed") => return Some("failed".to_string()),
+        _ => {}
+    }
+    status
+        .conditions
+        .iter()
+        .flatten()
+        .any(|c| c.type_ == "Ready" && c.status == "True")
+        .then(|| "is Ready".to_string())
+}
+
+/// Met once the rollout is complete or has exceeded its progress deadline.
+pub fn rollout_condition(deployment: &Deployment) -> Option<String> {
+    let status = deployment.status.as_ref()?;
+    let generation = deployment.metadata.generation.unwrap_or(0);
+    if status.observed_generation.unwrap_or(0) < generation {
+        return None;
+    }
+    if rollout_stalled(deployment) {
+        return Some("rollout exceeded its progress deadline".to_string());
+    }
+    let desired = deployment
+        .spec
+        .as_ref()
+        .and_then(|s| s.replicas)
+        .unwrap_or(1);
+    let updated = status.updated_replicas.unwrap_or(0);
+    let complete = updated >= desired
+        && status.replicas.unwrap_or(0) <= updated
+        && status.available_replicas.unwrap_or(0) >= updated;
+    complete.then(|| format!("rolled out ({updated}/{desired} updated and available)"))
+}
+
+/// Shows `body` as a desktop notification.
+pub fn desktop(body: String) {
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = notify_rust::Notification::new()
+            .summary("kr")
+            .body(&body)
+            .show()
+        {
+            tracing::warn!("Desktop notification failed: {e}");
+        }
+    });
+}
+
+/// The webhook condition `current` has entered since `previous`, with a description.
+pub fn webhook_event(
+    previous: &KubeResource,
+    current: &KubeResource,
+) -> Option<(WebhookEvent, String)> {
+    match (previous, current) {
+        (KubeResource::Pod(previous), KubeResource::Pod(current)) => {
+            let (container, restarts) = crash_looping(current)?;
+            crash_looping(previous).is_none().then(|| {
+                (
+                    WebhookEvent::CrashLoop,
+                    format!("is in CrashLoopBackOff (container {container}, {restarts} restarts)"),
+                )
+            })
+        }
+        (KubeResource::Deployment(previous), KubeResource::Deployment(current)) => {
+            (rollout_stalled(current) && !rollout_stalled(previous)).then(|| {
+                (
+                    WebhookEvent::RolloutFailed,
+                    "rollout failed: progress deadline exceeded".to_string(),
+                )
+            })
+        }
+        _ => None,
+    }
+}
+
+/// A Slack-compatible message naming the cluster, namespace and resource.
+pub fn webhook_body(context: &str, namespace: &str, target: &str, what: &str) -> String {
+    let text = format!(
+        "*{target} {what}*\nCluster: {context}\nNamespace: {namespace}\nResource: {target}"
+    );
+    serde_json::json!({ "text": text }).to_string()
+}
+
+/// The host of a webhook URL, safe to show where the URL itself isn't.
+pub fn webhook_host(url: &str) -> String {
+    let uri = url.parse::<http::Uri>().ok();
+    uri.as_ref()
+        .and_then(|u| u.host())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// POSTs `body` to `url`; failures are logged with the host only.
+pub async fn webhook(url: String, body: String) {
+    let request = http::Request::post(url.as_str())
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(body.into())
+        .map_err(|e| e.to_string());
+    let result = match request {
+        Ok(request) => crate::web::send(request).await,
+        Err(e) => Err(e),
+    };
+    let host = webhook_host(&url);
+    match result {
+        Ok((status, _)) if status.is_success() => {}
+        Ok((status, body)) => tracing::warn!("Webhook to {host} answered {status}: {body}"),
+        Err(e) => tracing::warn!("Webhook to {host} failed: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pod(value: serde_json::Value) -> Pod {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn deployment(generation: i64, value: serde_json::Value) -> Deployment {
+        let mut d: Deployment = serde_json::from_value(value).unwrap();
+        d.metadata.generation = Some(generation);
+        d
+    }
+
+    #[test]
+    fn pods_are_met_when_ready_crash_looping_or_done() {
+        let pending = pod(serde_json::json!({"status": {"phase": "Pending"}}));
+        assert_eq!(pod_condition(&pending), None);
+        let ready = pod(serde_json::json!({"status": {
+            "phase": "Running",
+            "conditions": [{"type": "Ready", "status": "True"}],
+        }}));
+        assert_eq!(pod_condition(&ready).as_deref(), Some("is Ready"));
+        let crashing = pod(serde_json::json!({"status": {
+            "phase": "Running",
+            "containerStatuses": [{
+                "name": "app", "image": "app", "imageID": "", "ready": false, "restartCount": 4,
+                "state": {"waiting": {"reason": "CrashLoopBackOff"}},
+            }],
+        }}));
+        assert_eq!(
+            pod_condition(&crashing).as_deref(),
+            Some("is in CrashLoopBackOff")
+        );
+        let failed = pod(serde_json::json!({"status": {"phase": "Failed"}}));
+        assert_eq!(pod_condition(&failed).as_deref(), Some("failed"));
+    }
+
+    #[test]
+    fn rollouts_are_met_once_every_replica_is_updated() {
+        let status = |observed: i64, replicas: i32, updated: i32, available: i32| {
+            serde_json::json!({
+                "spec": {"replicas": 3, "selector": {}, "template": {}},
+                "status": {
+                    "observedGeneration": observed,
+                    "replicas": replicas,
+                    "updatedReplicas": updated,
+                    "availableReplicas": available,
+                },
+            })
+        };
+        assert_eq!(rollout_condition(&deployment(2, status(1, 3, 3, 3))), None);
+        assert_eq!(rollout_condition(&deployment(2, status(2, 4, 2, 3))), None);
+        assert_eq!(rollout_condition(&deployment(2, status(2, 4, 3, 3))), None);
+        assert_eq!(
+            rollout_condition(&deployment(2, status(2, 3, 3, 3))).as_deref(),
+            Some("rolled out (3/3 updated and available)")
+        );
+        let mut stalled = status(2, 4, 1, 3);
+        stalled["status"]["conditions"] = serde_json::json!([{
+            "type": "Progressing", "status": "False", "reason": "ProgressDeadlineExceeded",
+        }]);
+        assert_eq!(
+            rollout_condition(&deployment(2, stalled)).as_deref(),
+            Some("rollout exceeded its progress deadline")
+        );
+    }
+
+    #[test]
+    fn webhooks_fire_on_entering_a_failure() {
+        let running =
+            KubeResource::Pod(pod(serde_json::json!({"status": {"phase": "Running"}})).into());
+        let crashing = KubeResource::Pod(
+            pod(serde_json::json!({"status": {
+                "phase": "Running",
+                "containerStatuses": [{
+                    "name": "app", "image": "app", "imageID": "", "ready": false, "restartCount": 5,
+                    "state": {"waiting": {"reason": "CrashLoopBackOff"}},
+                }],
+            }}))
+            .into(),
+        );
+        assert_eq!(
+            webhook_event(&running, &crashing),
+            Some((
+                WebhookEvent::CrashLoop,
+                "is in CrashLoopBackOff (container app, 5 restarts)".to_string()
+            ))
+        );
+        assert_eq!(webhook_event(&crashing, &crashing), None);
+        assert_eq!(webhook_event(&crashing, &running), None);
+
+        let progressing =
+            serde_json::json!({"spec": {"selector": {}, "template": {}}, "status": {}});
+        let mut stalled = progressing.clone();
+        stalled["status"]["conditions"] = serde_json::json!([{
+            "type": "Progressing", "status": "False", "reason": "ProgressDeadlineExceeded",
+        }]);
+        let progressing = KubeResource::Deployment(deployment(1, progressing).into());
+        let stalled = KubeResource::Deployment(deployment(1, stalled).into());
+        assert_eq!(
+            webhook_event(&progressing, &stalled).map(|(event, _)| event),
+            Some(WebhookEvent::RolloutFailed)
+        );
+        assert_eq!(webhook_event(&stalled, &stalled), None);
+
+        let body: serde_json::Value = serde_json::from_str(&webhook_body(
+            "prod",
+            "payments",
+            "pod/api-1",
+            "is in CrashLoopBackOff",
+        ))
+        .unwrap();
+        assert_eq!(
+            body["text"],
+            "*pod/api-1 is in CrashLoopBackOff*\nCluster: prod\nNamespace: payments\nResource: pod/api-1"
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Range queries against a context's Prometheus for the `M` metrics panel.
+
+use crate::config::PrometheusConfig;
+use crate::models::MetricSeries;
+
+/// How far back the panel's graphs reach.
+pub const RANGE_SECS: i64 = 30 * 60;
+/// Seconds between the points of a graph.
+const STEP_SECS: i64 = 30;
+
+/// A regex for PromQL's `pod=~` matching the pod `name` or the deployment's pods.
+pub fn pods_regex(name: &str, deployment: bool) -> String {
+    let name = name.replace('.', "\\\\.");
+    match deployment {
+        true => format!("{name}-[a-z0-9]+-[a-z0-9]+"),
+        false => name,
+    }
+}
+
+/// Runs every configured query over the last `RANGE_SECS`.
+pub async fn fetch_series(
+    client: kube::Client,
+    config: PrometheusConfig,
+    vars: Vec<(&'static str, String)>,
+) -> Vec<MetricSeries> {
+    let vars: Vec<(&str, &str)> = vars.iter().map(|(k, v)| (*k, v.as_str())).collect();
+    let end = jiff::Timestamp::now().as_second();
+    let mut series = Vec::new();
+    for query in config.queries() {
+        let promql = crate::utils::expand_placeholders(&query.query, &vars);
+        let path = format!(
+            "api/v1/query_range?query={}&start={}&end={end}&step={STEP_SECS}",
+            crate::utils::percent_encode(&promql),
+            end - RANGE_SECS,
+        );
+        let points = match get(&client, &config, &path).await {
+            Ok(body) => parse_matrix(&body, end),
+            Err(e) => Err(e),
+        };
+        series.push(MetricSeries {
+            title: query.title,
+            points,
+        });
+    }
+    series
+}
+
+/// GETs `path` from Prometheus by URL or through the API server's service proxy.
+async fn get(
+    client: &kube::Client,
+    config: &PrometheusConfig,
+    path: &str,
+) -> Result<String, String> {
+    if let Some((namespace, service)) = config.service() {
+        let uri = format!("/api/v1/namespaces/{namespace}/services/{service}/proxy/{path}");
+        let request = http::Request::get(uri)
+            .body(Vec::new())
+            .map_err(|e| e.to_string())?;
+        return client
+            .request_text(request)
+            .await
+            .map_err(|e| e.to_string());
+    }
+    let base = config
+        .url
+        .as_deref()
+        .unwrap_or_default()
+        .trim_end_matches('/');
+    let request = http::Request::get(format!("{base}/{path}"))
+        .body(Default::default())
+        .map_err(|e| e.to_string())?;
+    let (status, body) = crate::web::send(request).await?;
+    match status.is_success() {
+        true => Ok(body),
+        // Prometheus explains bad queries in the JSON body.
+        false => Err(parse_error(&body).unwrap_or_else(|| status.to_string())),
+    }
+}
+
+fn parse_error(body: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    value["error"].as_str().map(str::to_string)
+}
+
+/// Sums the series of a `query_range` matrix point by point.
+fn parse_matrix(body: &str, end: i64) -> Result<Vec<(f64, f64)>, String> {
+    let value: serde_json::Value = serde_json::from_str(body).map_err(|e| e.to_string())?;
+    if value["status"] != "success" {
+        return Err(parse_error(body).unwrap_or_else(|| "query failed".to_string()));
+    }
+    let mut sums: std::collections::BTreeMap<i64, f64> = std::collections::BTreeMap::new();
+    for series in value["data"]["result"].as_array().into_iter().flatten() {
+        for point in series["values"].as_array().into_iter().flatten() {
+            let (Some(at), Some(v)) = (
+                point[0].as_f64(),
+                point[1].as_str().and_then(|v| v.parse::<f64>().ok()),
+            ) else {
+                continue;
+            };
+            if v.is_finite() {
+                *sums.entry(at as i64).or_default() += v;
+            }
+        }
+    }
+    Ok(sums
+        .into_iter()
+        .map(|(at, v)| ((at - end) as f64 / 60.0, v))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_series_into_minutes_before_end() {
+        let body = r#"{"status":"success","data":{"resultType":"matrix","result":[
+            {"metric":{"pod":"a"},"values":[[1000,"1"],[1060,"2"]]},
+            {"metric":{"pod":"b"},"values":[[1060,"0.5"],[1090,"NaN"]]}
+        ]}}"#;
+        assert_eq!(parse_matrix(body, 1120), Ok(vec![(-2.0, 1.0), (-1.0, 2.5)]));
+        let error = r#"{"status":"error","errorType":"bad_data","error":"parse error"}"#;
+        assert_eq!(parse_matrix(error, 0), Err("parse error".to_string()));
+    }
+
+    #[test]
+    fn pods_regex_matches_deployment_pods() {
+        assert_eq!(pods_regex("api.v2", false), "api\\\\.v2");
+        assert_eq!(pods_regex("api", true), "api-[a-z0-9]+-[a-z0-9]+");
+    }
+}
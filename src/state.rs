@@ -1,3 +1,4 @@
+use crate::models::FilterMode;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -6,8 +7,35 @@ use std::path::PathBuf;
 pub struct AppState {
     #[serde(default)]
     pub namespaces: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub favorites: HashMap<String, Vec<String>>,
+    /// Context names, most recently used first.
+    #[serde(default)]
+    pub recent_contexts: Vec<String>,
+    /// Namespace used before the current one, per context (for `-`).
+    #[serde(default)]
+    pub previous_namespaces: HashMap<String, String>,
+    /// `:` commands, oldest first.
+    #[serde(default)]
+    pub command_history: Vec<String>,
+    /// `/` filter queries per tab (`pods`, `secrets`, ...), oldest first.
+    #[serde(default)]
+    pub filter_history: HashMap<String, Vec<String>>,
+    /// Whether the detail pane is shown beside the resource table.
+    #[serde(default)]
+    pub detail_pane: bool,
+    /// Detail pane width as a percentage of the screen.
+    #[serde(default)]
+    pub detail_width: Option<u16>,
+    /// How the name filter and namespace popup match typed text.
+    #[serde(default)]
+    pub filter_mode: FilterMode,
 }
 
+const MAX_RECENT_CONTEXTS: usize = 50;
+const MAX_COMMAND_HISTORY: usize = 100;
+const MAX_FILTER_HISTORY: usize = 50;
+
 fn state_path() -> PathBuf {
     let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
     path.push("kr");
@@ -65,6 +93,95 @@ impl AppState {
         }
     }
 
+    /// Forgets a deleted namespace, including its favorite pin.
+    pub fn remove_namespace(&mut self, context: &str, namespace: &str) {
+        if let Some(entry) = self.namespaces.get_mut(context) {
+            entry.retain(|ns| ns != namespace);
+        }
+        if let Some(entry) = self.favorites.get_mut(context) {
+            entry.retain(|ns| ns != namespace);
+            if entry.is_empty() {
+                self.favorites.remove(context);
+            }
+        }
+    }
+
+    pub fn get_favorites(&self, context: &str) -> Vec<String> {
+        self.favorites.get(context).cloned().unwrap_or_default()
+    }
+
+    /// Adds or removes a favorite namespace; returns whether it is now pinned.
+    pub fn toggle_favorite(&mut self, context: &str, namespace: &str) -> bool {
+        let entry = self.favorites.entry(context.to_string()).or_default();
+        if let Some(pos) = entry.iter().position(|ns| ns == namespace) {
+            entry.remove(pos);
+            if entry.is_empty() {
+                self.favorites.remove(context);
+            }
+            false
+        } else {
+            entry.push(namespace.to_string());
+            entry.sort();
+            true
+        }
+    }
+
+    pub fn previous_namespace(&self, context: &str) -> Option<&str> {
+        self.previous_namespaces.get(context).map(String::as_str)
+    }
+
+    pub fn set_previous_namespace(&mut self, context: &str, namespace: &str) {
+        self.previous_namespaces
+            .insert(context.to_string(), namespace.to_string());
+    }
+
+    /// Appends a command, dropping an identical previous entry.
+    pub fn record_command(&mut self, command: &str) {
+        self.command_history.retain(|c| c != command);
+        self.command_history.push(command.to_string());
+        let excess = self
+            .command_history
+            .len()
+            .saturating_sub(MAX_COMMAND_HISTORY);
+        self.command_history.drain(..excess);
+    }
+
+    pub fn filter_history(&self, tab: &str) -> &[String] {
+        self.filter_history.get(tab).map_or(&[], Vec::as_slice)
+    }
+
+    /// Appends a filter query for `tab`, dropping an identical previous entry.
+    pub fn record_filter(&mut self, tab: &str, query: &str) {
+        let entry = self.filter_history.entry(tab.to_string()).or_default();
+        entry.retain(|q| q != query);
+        entry.push(query.to_string());
+        let excess = entry.len().saturating_sub(MAX_FILTER_HISTORY);
+        entry.drain(..excess);
+    }
+
+    pub fn record_context(&mut self, context: &str) {
+        self.recent_contexts.retain(|c| c != context);
+        self.recent_contexts.insert(0, context.to_string());
+        self.recent_contexts.truncate(MAX_RECENT_CONTEXTS);
+    }
+
+    /// Orders `contexts` with `current` first, then by recency.
+    pub fn order_contexts(&self, contexts: &[String], current: &str) -> Vec<String> {
+        let rank = |ctx: &String| {
+            if ctx == current {
+                0
+            } else {
+                self.recent_contexts
+                    .iter()
+                    .position(|c| c == ctx)
+                    .map_or(usize::MAX, |i| i + 1)
+            }
+        };
+        let mut ordered = contexts.to_vec();
+        ordered.sort_by_key(rank);
+        ordered
+    }
+
     pub fn merge_namespaces(&mut self, context: &str, discovered: &[String]) -> Vec<String> {
         let entry = self.namespaces.entry(context.to_string()).or_default();
         for ns in discovered {
@@ -103,4 +220,81 @@ mod tests {
         let state = AppState::default();
         assert!(state.get_namespaces("unknown").is_empty());
     }
+
+    #[test]
+    fn toggle_favorite_adds_and_removes() {
+        let mut state = AppState::default();
+        assert!(state.toggle_favorite("ctx1", "prod"));
+        assert!(state.toggle_favorite("ctx1", "api"));
+        assert_eq!(state.get_favorites("ctx1"), vec!["api", "prod"]);
+        assert!(state.get_favorites("ctx2").is_empty());
+
+        assert!(!state.toggle_favorite("ctx1", "api"));
+        assert!(!state.toggle_favorite("ctx1", "prod"));
+        assert!(!state.favorites.contains_key("ctx1"));
+    }
+
+    #[test]
+    fn state_without_favorites_still_loads() {
+        let state: AppState = serde_json::from_str(r#"{"namespaces":{"c":["a"]}}"#).unwrap();
+        assert_eq!(state.get_namespaces("c"), vec!["a"]);
+        assert!(state.get_favorites("c").is_empty());
+    }
+
+    #[test]
+    fn remove_namespace_drops_favorite() {
+        let mut state = AppState::default();
+        state.add_namespace("ctx1", "a");
+        state.add_namespace("ctx1", "b");
+        state.toggle_favorite("ctx1", "b");
+        state.remove_namespace("ctx1", "b");
+        assert_eq!(state.get_namespaces("ctx1"), vec!["a"]);
+        assert!(state.get_favorites("ctx1").is_empty());
+    }
+
+    #[test]
+    fn contexts_ordered_by_recency_with_current_first() {
+        let mut state = AppState::default();
+        state.record_context("b");
+        state.record_context("d");
+        state.record_context("c");
+        state.record_context("d");
+        assert_eq!(state.recent_contexts, vec!["d", "c", "b"]);
+
+        let all: Vec<String> = ["a", "b", "c", "d", "e"].map(String::from).to_vec();
+        assert_eq!(
+            state.order_contexts(&all, "b"),
+            vec!["b", "d", "c", "a", "e"]
+        );
+    }
+
+    #[test]
+    fn command_history_deduplicates_and_caps() {
+        let mut state = AppState::default();
+        state.record_command("ns a");
+        state.record_command("pods");
+        state.record_command("ns a");
+        assert_eq!(state.command_history, vec!["pods", "ns a"]);
+        for i in 0..150 {
+            state.record_command(&format!("ns n{i}"));
+        }
+        assert_eq!(state.command_history.len(), MAX_COMMAND_HISTORY);
+        assert_eq!(state.command_history.last().unwrap(), "ns n149");
+    }
+
+    #[test]
+    fn filter_history_is_per_tab() {
+        let mut state = AppState::default();
+        state.record_filter("pods", "api");
+        state.record_filter("pods", "web");
+        state.record_filter("secrets", "tls");
+        state.record_filter("pods", "api");
+        assert_eq!(state.filter_history("pods"), ["web", "api"]);
+        assert_eq!(state.filter_history("secrets"), ["tls"]);
+        assert!(state.filter_history("configmaps").is_empty());
+        for i in 0..60 {
+            state.record_filter("pods", &format!("q{i}"));
+        }
+        assert_eq!(state.filter_history("pods").len(), MAX_FILTER_HISTORY);
+    }
 }
@@ -1,4 +1,8 @@
+use crate::config::ColumnConfig;
+use crate::models::{KubeResource, ResourceType};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::widgets::{Cell, TableState};
+use std::ops::Range;
 
 pub fn centered_fixed_rect(width: u16, height: u16, r: Rect) -> Rect {
     let w = width.min(r.width);
@@ -28,6 +32,188 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+/// Rows a list table's body shows in `area`.
+pub fn table_body_height(area: Rect) -> usize {
+    crate::ui::symbols::block()
+        .title("title")
+        .inner(area)
+        .height
+        .saturating_sub(2) as usize
+}
+
+/// The rows of a `len`-row table that fit in `height`, keeping `selected` in view.
+pub fn visible_rows(
+    len: usize,
+    selected: Option<usize>,
+    offset: usize,
+    height: usize,
+) -> Range<usize> {
+    let height = height.max(1);
+    let mut start = offset;
+    if let Some(sel) = selected.filter(|s| *s < len) {
+        if sel < start {
+            start = sel;
+        } else if sel >= start + height {
+            start = sel + 1 - height;
+        }
+    }
+    start = start.min(len.saturating_sub(height));
+    start..(start + height).min(len)
+}
+
+/// Records the window's scroll in `state` and returns the state to render.
+pub fn window_state(state: &mut TableState, window: &Range<usize>) -> TableState {
+    *state.offset_mut() = window.start;
+    TableState::default().with_selected(state.selected().map(|s| s.saturating_sub(window.start)))
+}
+
+/// Drops `offset` columns after the first `pinned` ones.
+pub fn scroll_columns<T>(columns: Vec<T>, pinned: usize, offset: usize) -> Vec<T> {
+    columns
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| *i < pinned || *i >= pinned + offset)
+        .map(|(_, c)| c)
+        .collect()
+}
+
+/// Width for a pinned name column in wide mode.
+pub fn name_column_width<'a>(names: impl Iterator<Item = &'a str>) -> u16 {
+    names
+        .map(|n| n.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(16, 60) as u16
+}
+
+/// Narrowest the name column gets before lower-priority columns give way.
+pub const MIN_NAME_WIDTH: u16 = 20;
+
+/// Which columns of a table fit in `width`, and the width left for the name.
+pub fn fit_columns(widths: &[Constraint], drop_order: &[usize], width: u16) -> (Vec<bool>, u16) {
+    let room = |keep: &[bool]| {
+        let kept = keep.iter().filter(|k| **k).count() as u16;
+        let fixed: u16 = widths
+            .iter()
+            .zip(keep)
+            .filter(|(_, k)| **k)
+            .map(|(c, _)| match c {
+                Constraint::Length(n) => *n,
+                _ => 0,
+            })
+            .sum();
+        // Borders, the `> ` highlight symbol and one space between columns.
+        width.saturating_sub(2 + 2 + kept.saturating_sub(1) + fixed)
+    };
+    let mut keep = vec![true; widths.len()];
+    for &i in drop_order {
+        if room(&keep) >= MIN_NAME_WIDTH {
+            break;
+        }
+        keep[i] = false;
+    }
+    let name_width = room(&keep);
+    (keep, name_width)
+}
+
+/// The columns `fit_columns` kept.
+pub fn keep_columns<T>(columns: Vec<T>, keep: &[bool]) -> Vec<T> {
+    columns
+        .into_iter()
+        .zip(keep)
+        .filter(|(_, k)| **k)
+        .map(|(c, _)| c)
+        .collect()
+}
+
+/// `drop_order` for a table with `custom` user columns after its `fixed` ones.
+pub fn drop_order_with_custom(fixed: usize, custom: usize, drop_order: &[usize]) -> Vec<usize> {
+    (fixed..fixed + custom)
+        .rev()
+        .chain(drop_order.iter().copied())
+        .collect()
+}
+
+/// Cells of the user's custom columns for one row.
+pub fn custom_cells(item: &KubeResource, columns: &[ColumnConfig]) -> Vec<Cell<'static>> {
+    custom_text(item, columns)
+        .into_iter()
+        .map(Cell::from)
+        .collect()
+}
+
+fn custom_text(item: &KubeResource, columns: &[ColumnConfig]) -> Vec<String> {
+    if columns.is_empty() {
+        return Vec::new();
+    }
+    let value = item.to_json();
+    columns
+        .iter()
+        .map(|c| {
+            crate::utils::parse_json_path(&c.path)
+                .map(|steps| crate::utils::eval_json_path(&value, &steps))
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// The list table of `tab` as text, header row first, for `:export`.
+pub fn table_text(
+    tab: ResourceType,
+    items: &[KubeResource],
+    custom: &[ColumnConfig],
+    wide: bool,
+) -> Vec<Vec<String>> {
+    use crate::ui::views::{configmaps_view, deployments_view, pods_view, secrets_view};
+    let (headers, wide_headers): (&[&str], &[&str]) = match tab {
+        ResourceType::Pod => (&pods_view::HEADERS[1..], &pods_view::WIDE_HEADERS),
+        ResourceType::Deployment => (
+            &deployments_view::HEADERS[1..],
+            &deployments_view::WIDE_HEADERS,
+        ),
+        ResourceType::Secret => (&secrets_view::HEADERS, &[]),
+        ResourceType::ConfigMap => (&configmaps_view::HEADERS, &[]),
+    };
+    let wide_headers = if wide { wide_headers } else { &[] };
+    let header = headers
+        .iter()
+        .map(|h| h.to_string())
+        .chain(custom.iter().map(|c| c.header.clone()))
+        .chain(wide_headers.iter().map(|h| h.to_string()))
+        .collect();
+    let rows = items.iter().map(|item| {
+        let (mut row, extra): (Vec<String>, Vec<String>) = match item {
+            KubeResource::Pod(p) => (
+                pods_view::columns(p).into(),
+                pods_view::wide_columns(p).into(),
+            ),
+            KubeResource::Deployment(d) => (
+                deployments_view::columns(d).into(),
+                deployments_view::wide_columns(d).into(),
+            ),
+            KubeResource::Secret(s) => (secrets_view::columns(s).into(), Vec::new()),
+            KubeResource::ConfigMap(c) => (configmaps_view::columns(c).into(), Vec::new()),
+        };
+        row.extend(custom_text(item, custom));
+        if wide {
+            row.extend(extra);
+        }
+        row
+    });
+    std::iter::once(header).chain(rows).collect()
+}
+
+/// Cuts `name` to `width` characters, ending in `…` when shortened.
+pub fn truncate_name(name: &str, width: u16) -> String {
+    let width = width as usize;
+    if name.chars().count() <= width {
+        return name.to_string();
+    }
+    let mut cut: String = name.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,4 +247,58 @@ mod tests {
         assert!(r.width <= parent.width);
         assert!(r.height <= parent.height);
     }
+
+    #[test]
+    fn scroll_columns_keeps_pinned() {
+        let cols = vec!["m", "name", "a", "b", "c"];
+        assert_eq!(scroll_columns(cols.clone(), 2, 0), cols);
+        assert_eq!(scroll_columns(cols.clone(), 2, 2), vec!["m", "name", "c"]);
+        assert_eq!(scroll_columns(cols, 2, 9), vec!["m", "name"]);
+    }
+
+    #[test]
+    fn fit_columns_drops_low_priority_first() {
+        let widths = [
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(8),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Length(8),
+        ];
+        let (keep, name) = fit_columns(&widths, &[5, 4], 120);
+        assert!(keep.iter().all(|k| *k));
+        assert_eq!(name, 120 - 49);
+
+        let (keep, name) = fit_columns(&widths, &[5, 4], 60);
+        assert_eq!(keep, [true, true, true, true, true, false]);
+        assert_eq!(name, 20);
+
+        let (keep, _) = fit_columns(&widths, &[5, 4], 45);
+        assert_eq!(keep, [true, true, true, true, false, false]);
+    }
+
+    #[test]
+    fn truncate_name_adds_ellipsis() {
+        assert_eq!(truncate_name("api-server", 20), "api-server");
+        assert_eq!(truncate_name("api-server", 6), "api-s…");
+        assert_eq!(truncate_name("api", 0), "…");
+    }
+
+    #[test]
+    fn custom_columns_give_way_first() {
+        assert_eq!(drop_order_with_custom(6, 2, &[5, 4]), vec![7, 6, 5, 4]);
+        assert_eq!(drop_order_with_custom(3, 0, &[2, 1]), vec![2, 1]);
+    }
+
+    #[test]
+    fn visible_rows_follow_the_selection() {
+        assert_eq!(visible_rows(10_000, Some(0), 0, 20), 0..20);
+        assert_eq!(visible_rows(10_000, Some(25), 0, 20), 6..26);
+        assert_eq!(visible_rows(10_000, Some(3), 6, 20), 3..23);
+        assert_eq!(visible_rows(10_000, Some(10), 6, 20), 6..26);
+        // A shrunken list scrolls back to fill the screen.
+        assert_eq!(visible_rows(5, Some(4), 30, 20), 0..5);
+        assert_eq!(visible_rows(0, None, 3, 20), 0..0);
+    }
 }
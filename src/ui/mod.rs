@@ -1,21 +1,26 @@
 pub mod components;
+pub mod symbols;
 pub mod theme;
 pub mod views;
 
-use crate::app::App;
-use crate::models::{AppMode, ResourceType};
-use crate::ui::components::centered_fixed_rect;
+use crate::app::{App, HitAreas};
+use crate::models::{AppMode, ResourceType, WatchHealth};
+use crate::ui::components::{centered_fixed_rect, truncate_name};
+use crate::ui::symbols::{block, symbols};
 use crate::ui::theme::*;
 use crate::ui::views::*;
+use crate::utils::NameMatcher;
 use ratatui::{
     Frame,
+    buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
-    style::Style,
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Tabs},
 };
 
 pub fn draw(f: &mut Frame, app: &mut App) {
+    app.drawn_links.clear();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -25,27 +30,92 @@ pub fn draw(f: &mut Frame, app: &mut App) {
         ])
         .split(f.area());
 
+    app.hit_areas = HitAreas {
+        tabs: Rect {
+            height: 1,
+            ..chunks[0]
+        },
+        ..HitAreas::default()
+    };
     draw_header(f, app, chunks[0]);
-    draw_main(f, app, chunks[1]);
+    let main = match cluster_banner(app) {
+        Some(banner) => {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .split(chunks[1]);
+            f.render_widget(banner, rows[0]);
+            rows[1]
+        }
+        None => chunks[1],
+    };
+    draw_main(f, app, main);
     draw_footer(f, app, chunks[2]);
 
-    match app.mode {
+    app.hit_areas.modal = Some(match app.mode {
         AppMode::SecretDecode => secrets_view::draw_decode_modal(f, app),
+        AppMode::SecretCreate => secrets_view::draw_create_modal(f, app),
+        AppMode::SecretEditValue => {
+            let area = secrets_view::draw_decode_modal(f, app);
+            secrets_view::draw_edit_input(f, app);
+            area
+        }
+        AppMode::SecretExport | AppMode::SecretSaveRaw => {
+            let area = secrets_view::draw_decode_modal(f, app);
+            secrets_view::draw_export_input(f, app);
+            area
+        }
+        AppMode::SecretDiffInput => secrets_view::draw_diff_input(f, app),
+        AppMode::SecretDiff => secrets_view::draw_diff_modal(f, app),
+        AppMode::SecretUsage => secrets_view::draw_usage_modal(f, app),
+        AppMode::SplitInput => split_view::draw_input(f, app),
+        AppMode::ConfigMapView => configmaps_view::draw_view_modal(f, app),
+        AppMode::ValuePager
+        | AppMode::ValuePagerSearch
+        | AppMode::EditDiff
+        | AppMode::ApplyDiff => pager_view::draw(f, app),
         AppMode::ContextSelect | AppMode::NamespaceSelect | AppMode::StatusFilter => {
             popup_view::draw_popup(f, app)
         }
+        AppMode::NamespaceCreate => popup_view::draw_namespace_create(f, app),
+        AppMode::AccessReview => access_view::draw(f, app),
+        AppMode::NamespaceSummary => popup_view::draw_namespace_summary(f, app),
+        AppMode::MetricsView => metrics_view::draw(f, app),
+        AppMode::CommandInput => draw_command_input(f, app),
+        AppMode::ActionMenu => popup_view::draw_action_menu(f, app),
+        AppMode::PluginMenu => popup_view::draw_plugin_menu(f, app),
+        AppMode::LinkMenu => popup_view::draw_link_menu(f, app),
+        AppMode::EndpointMenu => popup_view::draw_endpoint_menu(f, app),
+        AppMode::ManifestExport => popup_view::draw_manifest_export(f, app),
         AppMode::ScaleInput => draw_scale_input(f, app),
         AppMode::Confirm => draw_confirm(f, app),
         AppMode::ShellView => shell_view::draw(f, app),
-        AppMode::DescribeView => describe_view::draw(f, app),
-        _ => {}
+        AppMode::DescribeView | AppMode::DescribeSearchInput => describe_view::draw(f, app),
+        _ => return,
+    });
+}
+
+/// The tab under column `x` of the tab bar.
+pub fn tab_at(x: u16) -> Option<ResourceType> {
+    let mut start = 0;
+    for tab in ResourceType::ALL {
+        let end = start + tab.title().len() as u16 + 2;
+        if x < end {
+            return (x >= start).then_some(tab);
+        }
+        start = end + 1;
     }
+    None
 }
 
 fn draw_header(f: &mut Frame, app: &App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(1), Constraint::Length(1)])
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
         .margin(0)
         .split(area);
 
@@ -56,31 +126,43 @@ fn draw_header(f: &mut Frame, app: &App, area: Rect) {
         .constraints([Constraint::Min(0), Constraint::Length(version_width)])
         .split(chunks[0]);
 
-    let titles = ["Pods", "Deployments", "Secrets"]
+    let titles = ResourceType::ALL
         .iter()
-        .map(|t| Line::from(Span::styled(*t, Style::default().fg(COLOR_TEXT))))
+        .map(|t| Line::from(Span::styled(t.title(), Style::default().fg(theme().text))))
         .collect::<Vec<Line>>();
 
     let tabs = Tabs::new(titles)
         .block(Block::default().borders(Borders::NONE))
-        .highlight_style(STYLE_HIGHLIGHT)
-        .select(match app.active_tab {
-            ResourceType::Pod => 0,
-            ResourceType::Deployment => 1,
-            ResourceType::Secret => 2,
-        });
+        .highlight_style(theme().highlight_style())
+        .select(
+            ResourceType::ALL
+                .iter()
+                .position(|tab| *tab == app.active_tab),
+        );
     f.render_widget(tabs, tab_row[0]);
 
-    let version = Paragraph::new(version_label)
-        .style(Style::default().fg(COLOR_VERSION));
+    let version = Paragraph::new(version_label).style(Style::default().fg(theme().muted));
     f.render_widget(version, tab_row[1]);
 
     let filter_part = if app.filter_query.is_empty() {
         String::new()
-    } else if app.mode == AppMode::FilterInput {
-        format!(" | Filter: {}_", app.filter_query)
     } else {
-        format!(" | Filter: {}", app.filter_query)
+        let cursor = if app.mode == AppMode::FilterInput {
+            "_"
+        } else {
+            ""
+        };
+        let mode = app.app_state.filter_mode;
+        let invalid = if NameMatcher::new(mode, &app.filter_query).is_valid() {
+            ""
+        } else {
+            ", invalid"
+        };
+        format!(
+            " | Filter ({}{invalid}): {}{cursor}",
+            mode.label(),
+            app.filter_query
+        )
     };
 
     let status_part = if app.status_filter.is_empty() {
@@ -91,61 +173,293 @@ fn draw_header(f: &mut Frame, app: &App, area: Rect) {
         format!(" | Status: {}", statuses.join(", "))
     };
 
+    let split_part = match &app.split {
+        Some(pane) => format!(
+            " | Split: {}/{}",
+            app.context_label(&pane.context),
+            pane.namespace
+        ),
+        None => String::new(),
+    };
+
     let info_text = format!(
-        " Ctx: {} | NS: {} | Items: {}{}{}",
-        app.current_context,
-        app.current_namespace,
+        " {} | Items: {}{}{}{}",
+        breadcrumb(app),
         app.filtered_items.len(),
         filter_part,
         status_part,
+        split_part,
     );
-    let info = Paragraph::new(info_text).style(STYLE_NORMAL);
+    let (dot, dot_color) = match app.cluster_reachable {
+        Some(true) if theme().accessible => ("✓", theme().running),
+        Some(false) if theme().accessible => ("✗", theme().error),
+        Some(true) => ("●", theme().running),
+        Some(false) => ("●", theme().error),
+        None => ("○", theme().muted),
+    };
+    let server = app.cluster_version.as_deref().unwrap_or("?");
+    let accent = app
+        .config
+        .context(&app.current_context)
+        .and_then(|c| c.accent());
+    let mut spans = vec![
+        Span::styled(format!(" {dot}"), Style::default().fg(dot_color)),
+        Span::styled(format!(" {server} |"), Style::default().fg(theme().muted)),
+        Span::raw(info_text),
+    ];
+    if app.read_only {
+        spans.push(Span::styled(
+            " [READ-ONLY]",
+            Style::default()
+                .fg(theme().error)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    let protected = app.protected();
+    if protected {
+        spans.push(Span::styled(
+            " [PROTECTED]",
+            Style::default()
+                .fg(theme().error)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        ));
+    }
+    // A protected context overrides its accent: the warning has to win.
+    let info = Paragraph::new(Line::from(spans)).style(match accent {
+        _ if protected => theme()
+            .normal()
+            .fg(theme().error)
+            .add_modifier(Modifier::BOLD),
+        Some(color) => theme().normal().fg(color).add_modifier(Modifier::BOLD),
+        None => theme().normal(),
+    });
     f.render_widget(info, chunks[1]);
+    draw_status_bar(f, app, chunks[2]);
+}
+
+/// A full-width warning while the API server is unreachable or the watch keeps failing.
+fn cluster_banner(app: &App) -> Option<Paragraph<'static>> {
+    let ago =
+        |since: std::time::Instant| crate::utils::format_secs(since.elapsed().as_secs() as i64);
+    let (text, color) = if app.cluster_reachable == Some(false) {
+        let since = app.cluster_unreachable_since.map(ago).unwrap_or_default();
+        let error: String = app
+            .cluster_error
+            .as_deref()
+            .unwrap_or_default()
+            .chars()
+            .take(60)
+            .collect();
+        (
+            format!(
+                " OFFLINE — API server unreachable for {since} ({error}); data shown may be stale, retrying"
+            ),
+            theme().error,
+        )
+    } else if let WatchHealth::Reconnecting { since, .. } = app.watch_health
+        && since.elapsed() >= std::time::Duration::from_secs(10)
+    {
+        (
+            format!(
+                " DEGRADED — API server answers but the watch has failed for {}; data shown may be stale",
+                ago(since)
+            ),
+            theme().pending,
+        )
+    } else {
+        return None;
+    };
+    Some(
+        Paragraph::new(text).style(
+            Style::default()
+                .fg(color)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        ),
+    )
+}
+
+/// Context, namespace and tab, then the resource and views drilled into.
+fn breadcrumb(app: &App) -> String {
+    let mut crumbs = vec![
+        format!(
+            "{}{}",
+            app.context_label(&app.current_context),
+            if app.context_missing {
+                " (removed from kubeconfig)"
+            } else {
+                ""
+            }
+        ),
+        app.current_namespace.clone(),
+        app.active_tab.title().to_string(),
+    ];
+    let views = app.mode_stack.iter().chain([&app.mode]);
+    let mut named_resource = false;
+    for mode in views {
+        let Some(label) = mode.view_label() else {
+            continue;
+        };
+        if mode.is_resource_view()
+            && !named_resource
+            && let Some(resource) = app.get_selected_resource()
+        {
+            crumbs.push(truncate_name(resource.name(), 24));
+            named_resource = true;
+        }
+        let label = match mode {
+            AppMode::ValuePager | AppMode::ValuePagerSearch if !app.pager_title.is_empty() => {
+                truncate_name(&app.pager_title, 24)
+            }
+            _ => label.to_string(),
+        };
+        if crumbs.last() != Some(&label) {
+            crumbs.push(label);
+        }
+    }
+    crumbs.join(" › ")
 }
 
-const SPINNER: &[char] = &['◐', '◓', '◑', '◒'];
+/// Pod phases, watch health, open streams and in-flight background operations.
+fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
+    let muted = Style::default().fg(theme().muted);
+    let mut spans = Vec::new();
+
+    if app.active_tab == ResourceType::Pod && !app.items.is_empty() {
+        let mut phases: std::collections::BTreeMap<&str, usize> = Default::default();
+        for item in &app.items {
+            if let crate::models::KubeResource::Pod(p) = item {
+                *phases.entry(App::pod_phase(p)).or_default() += 1;
+            }
+        }
+        spans.push(Span::styled(" Pods:", muted));
+        for (phase, count) in phases {
+            spans.push(Span::styled(
+                format!(" {count} {}", theme().phase_text(phase)),
+                Style::default().fg(theme().phase(phase)),
+            ));
+        }
+        spans.push(Span::styled(" |", muted));
+    }
+
+    let (watch, color) = match &app.watch_health {
+        WatchHealth::Syncing => ("syncing".to_string(), theme().pending),
+        WatchHealth::Live => ("live".to_string(), theme().running),
+        WatchHealth::Reconnecting {
+            attempts,
+            last_error,
+            ..
+        } => {
+            let msg: String = last_error.chars().take(40).collect();
+            (
+                format!("reconnecting… (attempt {attempts}: {msg})"),
+                theme().pending,
+            )
+        }
+        WatchHealth::Forbidden => ("forbidden".to_string(), theme().error),
+    };
+    spans.push(Span::styled(" Watch: ", muted));
+    spans.push(Span::styled(watch, Style::default().fg(color)));
+    if let Some(at) = app.last_watch_event {
+        let ago = crate::utils::format_secs(at.elapsed().as_secs() as i64);
+        spans.push(Span::styled(format!(" (updated {ago} ago)"), muted));
+    }
+
+    let mut streams = Vec::new();
+    if app.log_task.is_some() {
+        streams.push("logs");
+    }
+    if app.split.as_ref().is_some_and(|p| p.watcher.is_some()) {
+        streams.push("split");
+    }
+    if app.shell_session.is_some() {
+        streams.push("shell");
+    }
+    if !streams.is_empty() {
+        spans.push(Span::styled(" | Streams: ", muted));
+        spans.push(Span::raw(streams.join(", ")));
+    }
+
+    let tasks = app.tasks.labels();
+    if !tasks.is_empty() {
+        spans.push(Span::styled(" | Tasks: ", muted));
+        spans.push(Span::styled(
+            truncate_name(&tasks.join(", "), 60),
+            Style::default().fg(theme().pending),
+        ));
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
 
 fn draw_main(f: &mut Frame, app: &mut App, area: Rect) {
     if !matches!(app.mode, AppMode::LogView | AppMode::LogSearchInput)
         && app.is_loading
         && app.filtered_items.is_empty()
     {
-        let resource = match app.active_tab {
-            ResourceType::Pod => "pods",
-            ResourceType::Deployment => "deployments",
-            ResourceType::Secret => "secrets",
-        };
+        let resource = app.active_tab.plural();
         let elapsed = app
             .loading_since
             .map(|t| format!(" ({:.1}s)", t.elapsed().as_secs_f64()))
             .unwrap_or_default();
-        let spinner_idx = app
+        let ticks = app
             .loading_since
-            .map(|t| (t.elapsed().as_millis() / 250) as usize % SPINNER.len())
+            .map(|t| (t.elapsed().as_millis() / app.config.tick().as_millis()) as usize)
             .unwrap_or(0);
         let label = format!(
             " {} Loading {} in {}...{}",
-            SPINNER[spinner_idx], resource, app.current_namespace, elapsed,
+            symbols().spinner_frame(ticks),
+            resource,
+            app.current_namespace,
+            elapsed,
         );
-        let p = Paragraph::new(label)
-            .style(STYLE_NORMAL)
-            .block(Block::default().borders(Borders::ALL));
+        let p = Paragraph::new(label).style(theme().normal()).block(block());
         f.render_widget(p, area);
         return;
     }
+    let in_logs = matches!(app.mode, AppMode::LogView | AppMode::LogSearchInput);
+    let area = if app.split.is_some() && !in_logs {
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+        split_view::draw(f, app, panes[1]);
+        panes[0]
+    } else if app.app_state.detail_pane && !in_logs {
+        let width = app.detail_width();
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(100 - width),
+                Constraint::Percentage(width),
+            ])
+            .split(area);
+        detail_view::draw(f, app, panes[1]);
+        panes[0]
+    } else {
+        area
+    };
     match app.mode {
         AppMode::LogView | AppMode::LogSearchInput => logs_view::draw(f, app, area),
-        _ => match app.active_tab {
-            ResourceType::Pod => pods_view::draw(f, app, area),
-            ResourceType::Deployment => deployments_view::draw(f, app, area),
-            ResourceType::Secret => secrets_view::draw(f, app, area),
-        },
+        _ => {
+            app.hit_areas.table = area;
+            match app.active_tab {
+                ResourceType::Pod => pods_view::draw(f, app, area),
+                ResourceType::Deployment => deployments_view::draw(f, app, area),
+                ResourceType::Secret => secrets_view::draw(f, app, area),
+                ResourceType::ConfigMap => configmaps_view::draw(f, app, area),
+            }
+        }
     }
 }
 
 fn draw_footer(f: &mut Frame, app: &App, area: Rect) {
     if let Some(err) = &app.last_error {
-        let p = Paragraph::new(format!(" ERROR: {}", err))
+        let hint = if app.mode == AppMode::List {
+            " (E:Details)"
+        } else {
+            ""
+        };
+        let p = Paragraph::new(format!(" ERROR{hint}: {}", err))
             .style(Style::default().fg(ratatui::style::Color::Red));
         f.render_widget(p, area);
         return;
@@ -159,59 +473,157 @@ fn draw_footer(f: &mut Frame, app: &App, area: Rect) {
     let help = match app.mode {
         AppMode::List => match app.active_tab {
             ResourceType::Pod => {
-                "q:Quit /:Filter f:Status j/k:Nav g/G:Top/End Space:Sel ^a:All Tab:Next l:Logs s:Shell D:Del d:Desc e:Edit c:Ctx n:NS"
+                "q:Quit | ?:Help | /:Filter | f:Status | j/k:Nav | g/G:Top/End | Space:Sel | ^a:All | Tab:Next | l:Logs | s:Shell | D:Del | d:Desc | e:Edit | w:Wide | c:Ctx | n:NS | [/]:Cycle NS | -:Prev NS | |:Split | p:Detail"
             }
             ResourceType::Deployment => {
-                "q:Quit /:Filter j/k:Nav g/G:Top/End PgUp/PgDn Space:Sel ^a:All Tab:Next S:Scale r:Restart D:Del d:Desc e:Edit c:Ctx n:NS"
+                "q:Quit | ?:Help | /:Filter | j/k:Nav | g/G:Top/End | PgUp/PgDn | Space:Sel | ^a:All | Tab:Next | S:Scale | r:Restart | D:Del | d:Desc | e:Edit | w:Wide | c:Ctx | n:NS | [/]:Cycle NS | -:Prev NS | |:Split | p:Detail"
             }
             ResourceType::Secret => {
-                "q:Quit /:Filter j/k:Nav g/G:Top/End PgUp/PgDn Tab:Next Enter/x:Decode c:Ctx n:NS"
+                "q:Quit | ?:Help | /:Filter | j/k:Nav | g/G:Top/End | PgUp/PgDn | Tab:Next | Enter/x:Decode | a:New | =:Diff | u:Used by | c:Ctx | n:NS | [/]:Cycle NS | -:Prev NS | |:Split | p:Detail"
+            }
+            ResourceType::ConfigMap => {
+                "q:Quit | ?:Help | /:Filter | j/k:Nav | g/G:Top/End | PgUp/PgDn | Tab:Next | Enter/x:View | c:Ctx | n:NS | [/]:Cycle NS | -:Prev NS | |:Split | p:Detail"
             }
         },
-        AppMode::FilterInput => "Type to filter | Esc:Cancel | Enter:Confirm",
-        AppMode::SecretDecode => "j/k:Scroll | r:Reveal | c:Copy | q/Esc:Close",
-        AppMode::LogView => "j/k:Scroll | PgUp/PgDn | g/G:Top/Follow | /:Search n/N:Next/Prev | q/Esc:Back",
+        AppMode::FilterInput => {
+            "Type to filter | Tab:Substring/Fuzzy/Regex | Up/Down:History | Esc:Cancel | Enter:Confirm"
+        }
+        AppMode::SecretDecode => {
+            "j/k:Scroll | Enter/v:Pager | r:Reveal | c:Copy | C:Copy all | e:Edit | w:Export .env | h:Hex | s:Save raw | b:Base64 | q/Esc:Close"
+        }
+        AppMode::SecretEditValue => "Type new value | Enter:Save | ^e:$EDITOR | Esc:Cancel",
+        AppMode::SecretExport | AppMode::SecretSaveRaw => {
+            "Type file path | Enter:Write (mode 0600) | Esc:Cancel"
+        }
+        AppMode::SecretDiffInput => "namespace or context/namespace | Enter:Compare | Esc:Cancel",
+        AppMode::SecretDiff => "j/k:Scroll | r:Reveal | q/Esc:Close",
+        AppMode::SecretUsage => "j/k:Scroll | q/Esc:Close",
+        AppMode::SplitInput => "namespace or context/namespace | Enter:Open | Esc:Cancel",
+        AppMode::NamespaceCreate => "Type namespace name | Enter:Create | Esc:Back",
+        AppMode::AccessReview => "r:Re-check | q/Esc:Close",
+        AppMode::NamespaceSummary | AppMode::MetricsView => "r:Refresh | q/Esc:Close",
+        AppMode::CommandInput => {
+            "ns/ctx [name], pods, deploy, secrets, cm, apply [file], export [file], login [ctx], help, q | Tab:Complete | Up/Down:History | Enter:Run | Esc:Cancel"
+        }
+        AppMode::ConfigMapView => {
+            "j/k:Key | J/K/PgUp/PgDn:Scroll value | Enter/v:Pager | c:Copy | q/Esc:Close"
+        }
+        AppMode::ValuePager => {
+            "j/k:Scroll | PgUp/PgDn | g/G:Top/Bottom | /:Search | n/N:Next/Prev | c:Copy | |:$PAGER | q/Esc:Back"
+        }
+        AppMode::ValuePagerSearch => "Type to search | Enter:Confirm | Esc:Cancel",
+        AppMode::EditDiff => "y:Apply | e:Edit again | j/k:Scroll | PgUp/PgDn | q/Esc:Discard",
+        AppMode::ApplyDiff => "y:Apply | j/k:Scroll | PgUp/PgDn | g/G:Top/Bottom | q/Esc:Discard",
+        AppMode::LogView => {
+            "j/k:Scroll | PgUp/PgDn | g/G:Top/Follow | /:Search | n/N:Next/Prev | R:Reattach | |:$PAGER | q/Esc:Back"
+        }
         AppMode::LogSearchInput => "Type to search | Enter:Confirm | Esc:Cancel",
         AppMode::ScaleInput => "Enter replica count | Enter:Confirm | Esc:Cancel",
-        AppMode::Confirm => "y:Confirm | n/Esc:Cancel",
-        AppMode::DescribeView => "j/k:Scroll | PgUp/PgDn | g/G:Top/Bottom | q/Esc:Close",
-        AppMode::ShellView => if app.shell_title.starts_with("Edit") {
-            "Ctrl+Q:Close editor"
-        } else {
-            "Ctrl+Q:Close shell"
-        },
+        AppMode::Confirm => "y:Confirm | n/Esc:Cancel | c:Copy kubectl",
+        AppMode::DescribeView => {
+            "j/k:Scroll | PgUp/PgDn | g/G:Top/Bottom | /:Search | n/N:Next/Prev | a:Auto-refresh | |:$PAGER | q/Esc:Close"
+        }
+        AppMode::DescribeSearchInput => "Type to search | Enter:Confirm | Esc:Cancel",
+        AppMode::ShellView => "Ctrl+Q:Close shell",
+        AppMode::SecretCreate => {
+            "Tab/Up/Down:Field | Enter:Add key / load .env | ^d:Drop last | ^s:Create | Esc:Cancel"
+        }
         AppMode::StatusFilter => "j/k:Nav | Space:Toggle | a:All | Enter:Apply | Esc:Cancel",
-        AppMode::ContextSelect => "j/k:Nav | Enter:Select | Esc:Cancel",
+        AppMode::ActionMenu => "j/k:Nav | Enter or key:Run | c:Copy kubectl | Esc:Close",
+        AppMode::PluginMenu => "j/k:Nav | Enter or key:Run | Esc:Close",
+        AppMode::LinkMenu => "j/k:Nav | Enter:Open in browser | Esc:Close",
+        AppMode::EndpointMenu => "j/k:Nav | Enter:Open in browser | c:Copy URL | Esc:Close",
+        AppMode::ManifestExport => {
+            "Type directory | Tab:Toggle status/managedFields stripping | Enter:Write (mode 0600) | Esc:Cancel"
+        }
+        AppMode::ContextSelect => {
+            if app.context_typing {
+                "Type to filter | Up/Down:Nav | Enter:Select | Esc:Back"
+            } else {
+                "j/k:Nav | /:Search | Enter:Select | L:tsh login | Esc:Cancel"
+            }
+        }
         AppMode::NamespaceSelect => {
             if app.namespace_typing {
                 "Type namespace | Up/Down:Nav | Enter:Select | Esc:Back"
             } else {
-                "j/k:Nav | /:Search | Enter:Select | Esc:Cancel"
+                "j/k:Nav | /:Search | f:Pin/Unpin | a:New | D:Delete | Enter:Select | Esc:Cancel"
             }
         }
     };
-    let p = Paragraph::new(help).style(STYLE_NORMAL);
+    let p = Paragraph::new(fit_footer(help, area.width)).style(theme().normal());
+    f.render_widget(p, area);
+}
+
+/// Keeps as many whole ` | `-separated bindings of `help` as fit in `width`.
+fn fit_footer(help: &str, width: u16) -> String {
+    let width = width as usize;
+    if help.chars().count() <= width {
+        return help.to_string();
+    }
+    let mut out = String::new();
+    for entry in help.split(" | ") {
+        let candidate = if out.is_empty() {
+            entry.to_string()
+        } else {
+            format!("{out} | {entry}")
+        };
+        // Two columns stay free for the ` …` marker.
+        if candidate.chars().count() + 2 > width {
+            break;
+        }
+        out = candidate;
+    }
+    out.push_str(" …");
+    out
+}
+
+fn draw_command_input(f: &mut Frame, app: &App) -> Rect {
+    let area = centered_fixed_rect(60, 3, f.area());
+    f.render_widget(Clear, area);
+
+    let p = Paragraph::new(format!(":{}_", app.command_input))
+        .block(block().title("Command").style(theme().normal()))
+        .style(theme().normal());
+    f.render_widget(p, area);
+    area
+}
+
+/// The screen shown while the first client is being built.
+pub fn draw_connecting(f: &mut Frame, context: &str, ticks: usize) {
+    let text = format!(
+        "{} Connecting to {}...\n\nq/Esc:Quit",
+        symbols().spinner_frame(ticks),
+        if context.is_empty() {
+            "cluster"
+        } else {
+            context
+        },
+    );
+    let width = (text.lines().map(|l| l.chars().count()).max().unwrap_or(0) + 4) as u16;
+    let area = centered_fixed_rect(width, 5, f.area());
+    let p = Paragraph::new(text)
+        .block(block().style(theme().normal()))
+        .style(theme().normal());
     f.render_widget(p, area);
 }
 
-fn draw_scale_input(f: &mut Frame, app: &App) {
+fn draw_scale_input(f: &mut Frame, app: &App) -> Rect {
     let area = centered_fixed_rect(35, 5, f.area());
     f.render_widget(Clear, area);
 
     let text = format!("Replicas: {}_", app.scale_input);
     let p = Paragraph::new(text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Scale Deployment")
-                .style(STYLE_NORMAL),
-        )
-        .style(STYLE_NORMAL);
+        .block(block().title("Scale Deployment").style(theme().normal()))
+        .style(theme().normal());
     f.render_widget(p, area);
+    area
 }
 
-fn draw_confirm(f: &mut Frame, app: &App) {
-    let area = centered_fixed_rect(50, 9, f.area());
+fn draw_confirm(f: &mut Frame, app: &App) -> Rect {
+    let typed = app.typed_confirmation();
+    let height = if typed.is_some() { 12 } else { 9 };
+    let area = centered_fixed_rect(50, height, f.area());
     f.render_widget(Clear, area);
 
     let msg = app
@@ -219,14 +631,124 @@ fn draw_confirm(f: &mut Frame, app: &App) {
         .as_ref()
         .map(|a| a.message())
         .unwrap_or_else(|| "Confirm action?".to_string());
-    let text = format!("{}\n\n[y] Yes  [n] No", msg);
+    let has_kubectl = app
+        .pending_action
+        .as_ref()
+        .and_then(|a| a.kubectl(&app.current_context, &app.current_namespace))
+        .is_some();
+    let copy = match (has_kubectl, typed.is_some()) {
+        (false, _) => "",
+        (true, false) => "  [c] Copy kubectl",
+        (true, true) => "\n[Ctrl+Y] Copy kubectl",
+    };
+    let text = match typed {
+        Some(expected) if app.confirm_protected() => format!(
+            "{msg}\n\n'{expected}' is protected. Type the context name to confirm:\n> {}_\n\n[Enter] Confirm  [Esc] Cancel{copy}",
+            app.confirm_input
+        ),
+        Some(expected) => format!(
+            "{msg}\n\nType '{expected}' to confirm:\n> {}_\n\n[Enter] Confirm  [Esc] Cancel{copy}",
+            app.confirm_input
+        ),
+        None => format!("{}\n\n[y] Yes  [n] No{copy}", msg),
+    };
     let p = Paragraph::new(text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Confirm")
-                .style(STYLE_NORMAL),
-        )
-        .style(STYLE_NORMAL);
+        .wrap(ratatui::widgets::Wrap { trim: false })
+        .block(block().title("Confirm").style(theme().normal()))
+        .style(theme().normal());
     f.render_widget(p, area);
+    area
+}
+
+/// Rewrites each of `links` wrapped in an OSC 8 escape, which ratatui can't emit.
+pub fn write_hyperlinks(buffer: &Buffer, links: &[(Rect, String)]) -> std::io::Result<()> {
+    use crossterm::{
+        cursor::MoveTo,
+        queue,
+        style::{Attribute, Print, SetAttribute, SetBackgroundColor, SetForegroundColor},
+    };
+    use ratatui::backend::IntoCrossterm;
+    use std::io::Write;
+
+    let mut out = std::io::stdout();
+    for (area, url) in links {
+        queue!(
+            out,
+            MoveTo(area.x, area.y),
+            Print(format!("\x1b]8;;{url}\x1b\\"))
+        )?;
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, area.y)];
+            queue!(
+                out,
+                SetForegroundColor(cell.fg.into_crossterm()),
+                SetBackgroundColor(cell.bg.into_crossterm()),
+            )?;
+            if cell.modifier.contains(Modifier::BOLD) {
+                queue!(out, SetAttribute(Attribute::Bold))?;
+            }
+            if cell.modifier.contains(Modifier::REVERSED) {
+                queue!(out, SetAttribute(Attribute::Reverse))?;
+            }
+            queue!(out, Print(cell.symbol()), SetAttribute(Attribute::Reset))?;
+        }
+        queue!(out, Print("\x1b]8;;\x1b\\"))?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tab_at_maps_columns_to_tabs() {
+        // " Pods │ Deployments │ Secrets │ ConfigMaps "
+        assert_eq!(tab_at(0), Some(ResourceType::Pod));
+        assert_eq!(tab_at(5), Some(ResourceType::Pod));
+        assert_eq!(tab_at(6), None);
+        assert_eq!(tab_at(7), Some(ResourceType::Deployment));
+        assert_eq!(tab_at(21), Some(ResourceType::Secret));
+        assert_eq!(tab_at(31), Some(ResourceType::ConfigMap));
+        assert_eq!(tab_at(43), None);
+    }
+
+    #[test]
+    fn footer_keeps_whole_bindings() {
+        let help = "q:Quit | ?:Help | /:Filter | j/k:Nav";
+        assert_eq!(fit_footer(help, 80), help);
+        assert_eq!(fit_footer(help, 28), "q:Quit | ?:Help | /:Filter …");
+        assert_eq!(
+            fit_footer("n:NS | [/]:Cycle NS | -:Prev NS | u:Used by", 34),
+            "n:NS | [/]:Cycle NS | -:Prev NS …"
+        );
+        assert_eq!(
+            fit_footer("j/k:Nav | Enter:Select | Esc:Cancel", 26),
+            "j/k:Nav | Enter:Select …"
+        );
+    }
+
+    #[tokio::test]
+    async fn breadcrumb_follows_the_view_stack() {
+        use k8s_openapi::api::core::v1::Pod;
+        let mut app = App::new_test();
+        app.current_context = "prod".to_string();
+        app.current_namespace = "payments".to_string();
+        app.active_tab = ResourceType::Pod;
+        assert_eq!(breadcrumb(&app), "prod › payments › Pods");
+
+        let mut pod = Pod::default();
+        pod.metadata.name = Some("api-7f9c5d8b6-abcde-with-a-long-suffix".to_string());
+        app.filtered_items = vec![crate::models::KubeResource::Pod(std::sync::Arc::new(pod))];
+        app.table_state.select(Some(0));
+        app.push_mode(AppMode::LogView);
+        app.mode = AppMode::LogSearchInput;
+        assert_eq!(
+            breadcrumb(&app),
+            "prod › payments › Pods › api-7f9c5d8b6-abcde-wit… › Logs"
+        );
+
+        app.open_pager("Keybindings".to_string(), String::new());
+        assert!(breadcrumb(&app).ends_with("› Logs › Keybindings"));
+    }
 }
@@ -0,0 +1,144 @@
+use ratatui::symbols::border;
+use ratatui::widgets::{Block, BorderType, Borders};
+use std::sync::OnceLock;
+
+/// Borders and glyphs, chosen once at startup from the config file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbols {
+    pub border: BorderStyle,
+    /// Marks multi-selected rows.
+    pub marker: String,
+    /// Frames of the loading spinner, one character each.
+    pub spinner: Vec<char>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderStyle {
+    Plain,
+    Rounded,
+    /// `+`, `-` and `|` only.
+    Ascii,
+    None,
+}
+
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+impl Symbols {
+    pub fn unicode() -> Self {
+        Self {
+            border: BorderStyle::Plain,
+            marker: "●".to_string(),
+            spinner: vec!['◐', '◓', '◑', '◒'],
+        }
+    }
+
+    pub fn ascii() -> Self {
+        Self {
+            border: BorderStyle::Ascii,
+            marker: "*".to_string(),
+            spinner: vec!['|', '/', '-', '\\'],
+        }
+    }
+
+    /// Starts from `preset` (`unicode` or `ascii`) and applies the overrides.
+    pub fn from_config(
+        preset: Option<&str>,
+        border: Option<&str>,
+        marker: Option<&str>,
+        spinner: Option<&str>,
+    ) -> Result<Self, String> {
+        let mut symbols = match preset.unwrap_or("unicode") {
+            "unicode" => Self::unicode(),
+            "ascii" => Self::ascii(),
+            other => return Err(format!("unknown symbols preset '{other}'")),
+        };
+        if let Some(border) = border {
+            symbols.border = match border {
+                "plain" => BorderStyle::Plain,
+                "rounded" => BorderStyle::Rounded,
+                "ascii" => BorderStyle::Ascii,
+                "none" => BorderStyle::None,
+                other => return Err(format!("unknown border style '{other}'")),
+            };
+        }
+        if let Some(marker) = marker {
+            if marker.chars().count() != 1 {
+                return Err(format!("marker '{marker}' must be a single character"));
+            }
+            symbols.marker = marker.to_string();
+        }
+        if let Some(spinner) = spinner {
+            if spinner.is_empty() {
+                return Err("spinner needs at least one frame".to_string());
+            }
+            symbols.spinner = spinner.chars().collect();
+        }
+        Ok(symbols)
+    }
+
+    /// A block with the configured border around all sides.
+    pub fn block<'a>(&self) -> Block<'a> {
+        match self.border {
+            BorderStyle::Plain => Block::default().borders(Borders::ALL),
+            BorderStyle::Rounded => Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded),
+            BorderStyle::Ascii => Block::default()
+                .borders(Borders::ALL)
+                .border_set(ASCII_BORDER),
+            BorderStyle::None => Block::default(),
+        }
+    }
+
+    /// The spinner frame for `ticks` elapsed ticks.
+    pub fn spinner_frame(&self, ticks: usize) -> char {
+        self.spinner[ticks % self.spinner.len()]
+    }
+}
+
+static SYMBOLS: OnceLock<Symbols> = OnceLock::new();
+
+/// Installs the symbols; only the first call has an effect.
+pub fn init(symbols: Symbols) {
+    let _ = SYMBOLS.set(symbols);
+}
+
+/// The active symbols, Unicode until `init` is called.
+pub fn symbols() -> &'static Symbols {
+    SYMBOLS.get_or_init(Symbols::unicode)
+}
+
+/// Shorthand for `symbols().block()`.
+pub fn block<'a>() -> Block<'a> {
+    symbols().block()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_and_overrides() {
+        assert_eq!(
+            Symbols::from_config(None, None, None, None),
+            Ok(Symbols::unicode())
+        );
+        let s = Symbols::from_config(Some("ascii"), Some("rounded"), Some("x"), None).unwrap();
+        assert_eq!(s.border, BorderStyle::Rounded);
+        assert_eq!(s.marker, "x");
+        assert_eq!(s.spinner_frame(5), '/');
+        assert!(Symbols::from_config(Some("emoji"), None, None, None).is_err());
+        assert!(Symbols::from_config(None, Some("double"), None, None).is_err());
+        assert!(Symbols::from_config(None, None, Some("**"), None).is_err());
+        assert!(Symbols::from_config(None, None, None, Some("")).is_err());
+    }
+}
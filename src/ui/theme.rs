@@ -1,22 +1,198 @@
 use ratatui::style::{Color, Modifier, Style};
+use std::collections::HashMap;
+use std::sync::OnceLock;
 
-pub const COLOR_TEXT: Color = Color::White;
-pub const COLOR_HIGHLIGHT: Color = Color::Cyan;
-
-pub const COLOR_STATUS_RUNNING: Color = Color::Green;
-pub const COLOR_STATUS_PENDING: Color = Color::Yellow;
-pub const COLOR_STATUS_ERROR: Color = Color::Red;
-pub const COLOR_STATUS_TERMINATING: Color = Color::Magenta;
-pub const COLOR_STATUS_SUCCEEDED: Color = Color::Cyan;
-pub const COLOR_VERSION: Color = Color::DarkGray;
-
-pub const STYLE_NORMAL: Style = Style::new().fg(COLOR_TEXT);
-pub const STYLE_HIGHLIGHT: Style = Style::new()
-    .fg(Color::Black)
-    .bg(COLOR_HIGHLIGHT)
-    .add_modifier(Modifier::BOLD);
-
-pub const STYLE_SEARCH_MATCH: Style = Style::new()
-    .fg(Color::Black)
-    .bg(Color::Yellow)
-    .add_modifier(Modifier::BOLD);
+/// UI colors, chosen once at startup from the config file's `theme` section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub text: Color,
+    pub highlight: Color,
+    /// Text drawn on top of `highlight` (selected rows, active tab).
+    pub highlight_text: Color,
+    pub running: Color,
+    pub pending: Color,
+    pub error: Color,
+    pub terminating: Color,
+    pub succeeded: Color,
+    /// Secondary text: version, hints, separators.
+    pub muted: Color,
+    pub search_match: Color,
+    /// Marks states with symbols as well as color.
+    pub accessible: bool,
+}
+
+impl Theme {
+    pub const DARK: Theme = Theme {
+        text: Color::White,
+        highlight: Color::Cyan,
+        highlight_text: Color::Black,
+        running: Color::Green,
+        pending: Color::Yellow,
+        error: Color::Red,
+        terminating: Color::Magenta,
+        succeeded: Color::Cyan,
+        muted: Color::DarkGray,
+        search_match: Color::Yellow,
+        accessible: false,
+    };
+
+    pub const LIGHT: Theme = Theme {
+        text: Color::Black,
+        highlight: Color::Blue,
+        highlight_text: Color::White,
+        running: Color::Rgb(0x00, 0x87, 0x00),
+        pending: Color::Rgb(0xaf, 0x5f, 0x00),
+        error: Color::Rgb(0xd7, 0x00, 0x00),
+        terminating: Color::Magenta,
+        succeeded: Color::Blue,
+        muted: Color::Gray,
+        search_match: Color::Rgb(0xff, 0xd7, 0x00),
+        accessible: false,
+    };
+
+    /// The terminal's own colors only, for `NO_COLOR` and limited terminals.
+    pub const MONO: Theme = Theme {
+        text: Color::Reset,
+        highlight: Color::Reset,
+        highlight_text: Color::Reset,
+        running: Color::Reset,
+        pending: Color::Reset,
+        error: Color::Reset,
+        terminating: Color::Reset,
+        succeeded: Color::Reset,
+        muted: Color::Reset,
+        search_match: Color::Reset,
+        accessible: true,
+    };
+
+    /// Starts from `preset` (`dark`, `light` or `mono`) and applies per-color overrides.
+    pub fn from_config(
+        preset: Option<&str>,
+        colors: &HashMap<String, String>,
+    ) -> Result<Theme, String> {
+        let mut theme = match preset.unwrap_or("dark") {
+            "dark" => Self::DARK,
+            "light" => Self::LIGHT,
+            "mono" => Self::MONO,
+            other => return Err(format!("unknown theme preset '{other}'")),
+        };
+        for (name, value) in colors {
+            let color: Color = value
+                .parse()
+                .map_err(|_| format!("invalid color '{value}' for '{name}'"))?;
+            let slot = match name.as_str() {
+                "text" => &mut theme.text,
+                "highlight" => &mut theme.highlight,
+                "highlightText" => &mut theme.highlight_text,
+                "running" => &mut theme.running,
+                "pending" => &mut theme.pending,
+                "error" => &mut theme.error,
+                "terminating" => &mut theme.terminating,
+                "succeeded" => &mut theme.succeeded,
+                "muted" => &mut theme.muted,
+                "searchMatch" => &mut theme.search_match,
+                _ => return Err(format!("unknown theme color '{name}'")),
+            };
+            *slot = color;
+        }
+        Ok(theme)
+    }
+
+    /// Color for a pod phase as shown in tables and summaries.
+    pub fn phase(&self, phase: &str) -> Color {
+        match phase {
+            "Running" => self.running,
+            "Pending" => self.pending,
+            "Succeeded" => self.succeeded,
+            "Terminating" => self.terminating,
+            _ => self.error,
+        }
+    }
+
+    /// A pod phase as text, led by a symbol in accessible mode.
+    pub fn phase_text(&self, phase: &str) -> String {
+        if !self.accessible {
+            return phase.to_string();
+        }
+        let symbol = match phase {
+            "Running" => "✓",
+            "Pending" => "…",
+            "Succeeded" => "✔",
+            "Terminating" => "↓",
+            _ => "✗",
+        };
+        format!("{symbol} {phase}")
+    }
+
+    pub fn normal(&self) -> Style {
+        Style::new().fg(self.text)
+    }
+
+    pub fn highlight_style(&self) -> Style {
+        if self.accessible {
+            return Style::new().add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        }
+        Style::new()
+            .fg(self.highlight_text)
+            .bg(self.highlight)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    pub fn search_match_style(&self) -> Style {
+        if self.accessible {
+            return Style::new().add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        }
+        Style::new()
+            .fg(Color::Black)
+            .bg(self.search_match)
+            .add_modifier(Modifier::BOLD)
+    }
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Installs the theme; only the first call has an effect.
+pub fn init(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+/// The active theme, dark until `init` is called.
+pub fn theme() -> &'static Theme {
+    THEME.get_or_init(|| Theme::DARK)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preset_with_overrides() {
+        let colors = HashMap::from([
+            ("running".to_string(), "#00ff00".to_string()),
+            ("muted".to_string(), "gray".to_string()),
+        ]);
+        let theme = Theme::from_config(Some("light"), &colors).unwrap();
+        assert_eq!(theme.text, Color::Black);
+        assert_eq!(theme.running, Color::Rgb(0, 255, 0));
+        assert_eq!(theme.muted, Color::Gray);
+        assert_eq!(Theme::from_config(None, &HashMap::new()), Ok(Theme::DARK));
+    }
+
+    #[test]
+    fn rejects_unknown_names_and_colors() {
+        assert!(Theme::from_config(Some("solarized"), &HashMap::new()).is_err());
+        let bad_key = HashMap::from([("bogus".to_string(), "red".to_string())]);
+        assert!(Theme::from_config(None, &bad_key).is_err());
+        let bad_color = HashMap::from([("error".to_string(), "not-a-color".to_string())]);
+        assert!(Theme::from_config(None, &bad_color).is_err());
+    }
+
+    #[test]
+    fn accessible_mode_adds_symbols_and_drops_backgrounds() {
+        assert_eq!(Theme::DARK.phase_text("Running"), "Running");
+        assert_eq!(Theme::MONO.phase_text("Running"), "✓ Running");
+        assert_eq!(Theme::MONO.phase_text("Failed"), "✗ Failed");
+        assert_eq!(Theme::MONO.highlight_style().bg, None);
+        assert!(Theme::DARK.highlight_style().bg.is_some());
+    }
+}
@@ -0,0 +1,62 @@
+use crate::app::App;
+use crate::ui::components::centered_fixed_rect;
+use crate::ui::symbols::block;
+use crate::ui::theme::*;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::Style,
+    widgets::{Cell, Clear, Row, Table},
+};
+
+pub fn draw(f: &mut Frame, app: &App) -> Rect {
+    let height = app.access_checks.len() as u16 + 4;
+    let area = centered_fixed_rect(70, height, f.area());
+    f.render_widget(Clear, area);
+
+    let header = Row::new(
+        ["VERB", "RESOURCE", "ALLOWED"]
+            .iter()
+            .map(|h| Cell::from(*h).style(Style::default().fg(theme().highlight))),
+    )
+    .height(1)
+    .bottom_margin(1);
+
+    let rows = app.access_checks.iter().map(|check| {
+        let (label, color) = match &check.allowed {
+            Ok(true) => ("yes".to_string(), theme().running),
+            Ok(false) => ("no".to_string(), theme().error),
+            Err(e) => (format!("? {e}"), theme().pending),
+        };
+        let resource = if check.group.is_empty() {
+            check.target()
+        } else {
+            format!("{}.{}", check.target(), check.group)
+        };
+        Row::new(vec![
+            Cell::from(check.verb),
+            Cell::from(resource),
+            Cell::from(label).style(Style::default().fg(color)),
+        ])
+    });
+
+    let t = Table::new(
+        rows,
+        [
+            Constraint::Length(8),
+            Constraint::Length(28),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .block(
+        block()
+            .title(format!(
+                "Can I? (as current user in {})",
+                app.current_namespace
+            ))
+            .style(theme().normal()),
+    );
+    f.render_widget(t, area);
+    area
+}
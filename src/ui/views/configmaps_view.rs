@@ -0,0 +1,195 @@
+use crate::app::App;
+use crate::models::{ConfigValue, KubeResource};
+use crate::ui::components::{
+    centered_rect, custom_cells, drop_order_with_custom, fit_columns, keep_columns,
+    table_body_height, truncate_name, visible_rows, window_state,
+};
+use crate::ui::symbols::block;
+use crate::ui::theme::*;
+use k8s_openapi::api::core::v1::ConfigMap;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Cell, Clear, HighlightSpacing, Paragraph, Row, Table, Wrap},
+};
+
+pub const HEADERS: [&str; 3] = ["Name", "Data Count", "Age"];
+/// Dropped on narrow terminals, first one first: Age, then Data Count.
+const DROP_ORDER: [usize; 2] = [2, 1];
+
+/// Text of the `HEADERS` columns, for the table and for `:export`.
+pub fn columns(c: &ConfigMap) -> [String; 3] {
+    let count =
+        c.data.as_ref().map_or(0, |d| d.len()) + c.binary_data.as_ref().map_or(0, |d| d.len());
+    [
+        c.metadata.name.clone().unwrap_or_default(),
+        count.to_string(),
+        crate::utils::get_resource_age(c.metadata.creation_timestamp.as_ref()),
+    ]
+}
+
+pub fn draw(f: &mut Frame, app: &mut App, area: Rect) {
+    let custom = app.config.columns(app.active_tab);
+    let mut widths = vec![
+        Constraint::Fill(1),
+        Constraint::Length(12),
+        Constraint::Length(8),
+    ];
+    let fixed = widths.len();
+    widths.extend(custom.iter().map(|c| Constraint::Length(c.width())));
+    let drop = drop_order_with_custom(fixed, custom.len(), &DROP_ORDER);
+    let (keep, name_width) = fit_columns(&widths, &drop, area.width);
+
+    let header_cells = HEADERS
+        .into_iter()
+        .chain(custom.iter().map(|c| c.header.as_str()))
+        .map(|h| Cell::from(h).style(Style::default().fg(theme().highlight)))
+        .collect();
+    let header = Row::new(keep_columns(header_cells, &keep))
+        .style(theme().normal())
+        .height(1)
+        .bottom_margin(1);
+
+    app.viewport.table_rows = table_body_height(area);
+    let window = visible_rows(
+        app.filtered_items.len(),
+        app.table_state.selected(),
+        app.table_state.offset(),
+        app.viewport.table_rows,
+    );
+    let rows = app.filtered_items[window.clone()].iter().map(|item| {
+        let KubeResource::ConfigMap(c) = item else {
+            return Row::new(vec![Cell::from(item.name().to_owned())]).height(1);
+        };
+
+        let [name, count, age] = columns(c);
+
+        Row::new(keep_columns(
+            vec![
+                Cell::from(truncate_name(&name, name_width)),
+                Cell::from(count),
+                Cell::from(age),
+            ]
+            .into_iter()
+            .chain(custom_cells(item, custom))
+            .collect(),
+            &keep,
+        ))
+        .height(1)
+    });
+
+    let t = Table::new(rows, keep_columns(widths, &keep))
+        .header(header)
+        .block(block().title("ConfigMaps"))
+        .row_highlight_style(theme().highlight_style())
+        .highlight_symbol("> ")
+        .highlight_spacing(HighlightSpacing::Always);
+
+    if app.filtered_items.is_empty() && !app.is_loading {
+        let msg = if app.last_error.is_some() {
+            "" // error shown in footer
+        } else if app.filter_query.is_empty() && app.config.field_selector(app.active_tab).is_some()
+        {
+            "No configmaps match the configured field selector"
+        } else if app.filter_query.is_empty() {
+            "No configmaps in this namespace"
+        } else {
+            "No configmaps match filter"
+        };
+        let empty = Paragraph::new(msg)
+            .style(theme().normal())
+            .block(block().title("ConfigMaps"));
+        f.render_widget(empty, area);
+    } else {
+        let mut state = window_state(&mut app.table_state, &window);
+        f.render_stateful_widget(t, area, &mut state);
+    }
+}
+
+fn value_summary(value: &ConfigValue) -> String {
+    match value {
+        ConfigValue::Text(text) => {
+            let first = text.lines().next().unwrap_or_default();
+            let lines = text.lines().count();
+            if lines > 1 {
+                format!("{first} … ({lines} lines)")
+            } else {
+                first.to_owned()
+            }
+        }
+        ConfigValue::Binary(bytes) => format!("<binary, {} bytes>", bytes.len()),
+    }
+}
+
+pub fn draw_view_modal(f: &mut Frame, app: &mut App) -> Rect {
+    let area = centered_rect(70, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let title = format!("ConfigMap {}", app.configmap_name);
+    let Some(entries) = &app.configmap_entries else {
+        return area;
+    };
+
+    if entries.is_empty() {
+        let p = Paragraph::new("No data in configmap.")
+            .block(block().title(title).style(theme().normal()))
+            .style(theme().normal());
+        f.render_widget(p, area);
+        return area;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area);
+
+    let header = Row::new(vec![
+        Cell::from("KEY").style(Style::default().fg(theme().highlight)),
+        Cell::from("VALUE").style(Style::default().fg(theme().highlight)),
+    ])
+    .height(1)
+    .bottom_margin(1);
+
+    let rows: Vec<Row> = entries
+        .iter()
+        .map(|(k, v)| Row::new(vec![Cell::from(k.as_str()), Cell::from(value_summary(v))]))
+        .collect();
+
+    let selected = entries.get(app.configmap_scroll);
+    let (detail_title, detail) = match selected {
+        Some((key, ConfigValue::Text(text))) => (key.clone(), text.clone()),
+        Some((key, ConfigValue::Binary(bytes))) => (
+            format!("{key} (binaryData)"),
+            format!(
+                "<{} bytes of binary data; c copies it base64-encoded>",
+                bytes.len()
+            ),
+        ),
+        None => (String::new(), String::new()),
+    };
+
+    app.configmap_table_state.select(Some(app.configmap_scroll));
+
+    let t = Table::new(
+        rows,
+        [Constraint::Percentage(30), Constraint::Percentage(70)],
+    )
+    .header(header)
+    .block(block().title(title).style(theme().normal()))
+    .row_highlight_style(
+        Style::default()
+            .fg(theme().highlight)
+            .add_modifier(Modifier::BOLD),
+    )
+    .highlight_symbol("> ");
+    f.render_stateful_widget(t, chunks[0], &mut app.configmap_table_state);
+
+    let p = Paragraph::new(detail)
+        .style(theme().normal())
+        .wrap(Wrap { trim: false })
+        .scroll((app.configmap_value_scroll, 0))
+        .block(block().title(detail_title).style(theme().normal()));
+    f.render_widget(p, chunks[1]);
+    area
+}
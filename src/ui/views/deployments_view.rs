@@ -1,30 +1,138 @@
 use crate::app::App;
 use crate::models::KubeResource;
+use crate::ui::components::{
+    custom_cells, drop_order_with_custom, fit_columns, keep_columns, name_column_width,
+    scroll_columns, table_body_height, truncate_name, visible_rows, window_state,
+};
+use crate::ui::symbols::{block, symbols};
 use crate::ui::theme::*;
+use k8s_openapi::api::apps::v1::Deployment;
 use ratatui::{
     Frame,
     layout::{Constraint, Rect},
     style::{Modifier, Style},
-    widgets::{Block, Borders, Cell, HighlightSpacing, Paragraph, Row, Table},
+    widgets::{Cell, HighlightSpacing, Paragraph, Row, Table},
 };
 
+pub const HEADERS: [&str; 6] = ["", "Name", "Ready", "Up-to-date", "Available", "Age"];
+pub const WIDE_HEADERS: [&str; 3] = ["Containers", "Images", "Selector"];
+/// Marker and name stay in place when scrolling sideways.
+const PINNED: usize = 2;
+/// Dropped on narrow terminals, first one first: Age, then Up-to-date.
+const DROP_ORDER: [usize; 2] = [5, 3];
+
+/// Text of the `HEADERS` columns after the marker.
+pub fn columns(d: &Deployment) -> [String; 5] {
+    let status = d.status.as_ref();
+    let count = |field: fn(&k8s_openapi::api::apps::v1::DeploymentStatus) -> Option<i32>| {
+        status.and_then(field).unwrap_or(0)
+    };
+    [
+        d.metadata.name.clone().unwrap_or_default(),
+        format!("{}/{}", count(|s| s.ready_replicas), count(|s| s.replicas)),
+        count(|s| s.updated_replicas).to_string(),
+        count(|s| s.available_replicas).to_string(),
+        crate::utils::get_resource_age(d.metadata.creation_timestamp.as_ref()),
+    ]
+}
+
+/// Text of the `WIDE_HEADERS` columns.
+pub fn wide_columns(d: &Deployment) -> [String; 3] {
+    let spec = d.spec.as_ref();
+    let containers = spec
+        .and_then(|s| s.template.spec.as_ref())
+        .map(|s| s.containers.as_slice())
+        .unwrap_or_default();
+    let selector: Vec<String> = spec
+        .and_then(|s| s.selector.match_labels.as_ref())
+        .iter()
+        .flat_map(|labels| labels.iter().map(|(k, v)| format!("{k}={v}")))
+        .collect();
+    [
+        containers
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>()
+            .join(","),
+        containers
+            .iter()
+            .filter_map(|c| c.image.as_deref())
+            .collect::<Vec<_>>()
+            .join(","),
+        selector.join(","),
+    ]
+}
+
 pub fn draw(f: &mut Frame, app: &mut App, area: Rect) {
-    let header_cells = ["", "Name", "Ready", "Up-to-date", "Available", "Age"]
+    let wide_headers: &[&str] = if app.wide { &WIDE_HEADERS } else { &[] };
+    let custom = app.config.columns(app.active_tab);
+    let scrollable = HEADERS.len() + custom.len() + wide_headers.len() - PINNED;
+    app.column_offset = if app.wide {
+        app.column_offset.min(scrollable - 1)
+    } else {
+        0
+    };
+    let offset = app.column_offset;
+
+    let mut widths = vec![
+        Constraint::Length(2),
+        if app.wide {
+            Constraint::Length(name_column_width(
+                app.filtered_items.iter().map(|i| i.name()),
+            ))
+        } else {
+            Constraint::Fill(1)
+        },
+        Constraint::Length(10),
+        Constraint::Length(12),
+        Constraint::Length(10),
+        Constraint::Length(8),
+    ];
+    widths.extend(custom.iter().map(|c| Constraint::Length(c.width())));
+    // Wide mode scrolls sideways instead of dropping columns.
+    let (keep, name_width) = if app.wide {
+        widths.extend([
+            Constraint::Length(20),
+            Constraint::Length(40),
+            Constraint::Fill(1),
+        ]);
+        (vec![true; widths.len()], u16::MAX)
+    } else {
+        let drop = drop_order_with_custom(HEADERS.len(), custom.len(), &DROP_ORDER);
+        fit_columns(&widths, &drop, area.width)
+    };
+
+    let header_cells = HEADERS
         .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(COLOR_HIGHLIGHT)));
+        .copied()
+        .chain(custom.iter().map(|c| c.header.as_str()))
+        .chain(wide_headers.iter().copied())
+        .map(|h| Cell::from(h).style(Style::default().fg(theme().highlight)))
+        .collect();
 
-    let header = Row::new(header_cells)
-        .style(STYLE_NORMAL)
-        .height(1)
-        .bottom_margin(1);
+    let header = Row::new(scroll_columns(
+        keep_columns(header_cells, &keep),
+        PINNED,
+        offset,
+    ))
+    .style(theme().normal())
+    .height(1)
+    .bottom_margin(1);
 
-    let rows: Vec<Row> = app
-        .filtered_items
+    app.viewport.table_rows = table_body_height(area);
+    let window = visible_rows(
+        app.filtered_items.len(),
+        app.table_state.selected(),
+        app.table_state.offset(),
+        app.viewport.table_rows,
+    );
+    let rows: Vec<Row> = app.filtered_items[window.clone()]
         .iter()
         .enumerate()
-        .map(|(idx, item)| {
+        .map(|(i, item)| {
+            let idx = window.start + i;
             let marker = if app.selected_indices.contains(&idx) {
-                "●"
+                symbols().marker.as_str()
             } else {
                 " "
             };
@@ -33,69 +141,72 @@ pub fn draw(f: &mut Frame, app: &mut App, area: Rect) {
                 return Row::new(vec![Cell::from(marker), Cell::from(item.name().to_owned())]);
             };
 
-            let name = d.metadata.name.as_deref().unwrap_or_default();
-            let status = d.status.as_ref();
-            let replicas = status.map_or(0, |s| s.replicas.unwrap_or(0));
-            let ready = status.map_or(0, |s| s.ready_replicas.unwrap_or(0));
-            let updated = status.map_or(0, |s| s.updated_replicas.unwrap_or(0));
-            let available = status.map_or(0, |s| s.available_replicas.unwrap_or(0));
-            let age = crate::utils::get_resource_age(d.metadata.creation_timestamp.as_ref());
+            let [name, ready, updated, available, age] = columns(d);
 
             let marker_style = if app.selected_indices.contains(&idx) {
-                Style::default().fg(COLOR_STATUS_RUNNING)
+                Style::default().fg(theme().running)
             } else {
-                STYLE_NORMAL
+                theme().normal()
             };
 
-            Row::new(vec![
+            let mut cells = vec![
                 Cell::from(marker).style(marker_style),
-                Cell::from(name.to_owned()).style(STYLE_NORMAL.add_modifier(Modifier::BOLD)),
-                Cell::from(format!("{}/{}", ready, replicas)),
-                Cell::from(updated.to_string()),
-                Cell::from(available.to_string()),
+                Cell::from(truncate_name(&name, name_width))
+                    .style(theme().normal().add_modifier(Modifier::BOLD)),
+                Cell::from(ready),
+                Cell::from(updated),
+                Cell::from(available),
                 Cell::from(age),
-            ])
-            .height(1)
-            .style(STYLE_NORMAL)
+            ];
+            cells.extend(custom_cells(item, custom));
+            if app.wide {
+                cells.extend(wide_columns(d).map(Cell::from));
+            }
+            Row::new(scroll_columns(keep_columns(cells, &keep), PINNED, offset))
+                .height(1)
+                .style(theme().normal())
         })
         .collect();
 
-    let title = if app.selected_indices.is_empty() {
+    let mut title = if app.selected_indices.is_empty() {
         "Deployments".to_string()
     } else {
         format!("Deployments ({} selected)", app.selected_indices.len())
     };
+    if app.visual_anchor.is_some() {
+        title.push_str(" -- VISUAL --");
+    }
+    if app.wide {
+        title.push_str(&format!(" [wide {}/{} ←/→]", offset + 1, scrollable));
+    }
 
     let t = Table::new(
         rows,
-        [
-            Constraint::Length(2),
-            Constraint::Fill(1),
-            Constraint::Length(10),
-            Constraint::Length(12),
-            Constraint::Length(10),
-            Constraint::Length(8),
-        ],
+        scroll_columns(keep_columns(widths, &keep), PINNED, offset),
     )
     .header(header)
-    .block(Block::default().borders(Borders::ALL).title(title.clone()))
-    .row_highlight_style(STYLE_HIGHLIGHT)
+    .block(block().title(title.clone()))
+    .row_highlight_style(theme().highlight_style())
     .highlight_symbol("> ")
     .highlight_spacing(HighlightSpacing::Always);
 
     if app.filtered_items.is_empty() && !app.is_loading {
         let msg = if app.last_error.is_some() {
             ""
+        } else if app.filter_query.is_empty() && app.config.field_selector(app.active_tab).is_some()
+        {
+            "No deployments match the configured field selector"
         } else if app.filter_query.is_empty() {
             "No deployments in this namespace"
         } else {
             "No deployments match filter"
         };
         let empty = Paragraph::new(msg)
-            .style(STYLE_NORMAL)
-            .block(Block::default().borders(Borders::ALL).title(title));
+            .style(theme().normal())
+            .block(block().title(title));
         f.render_widget(empty, area);
     } else {
-        f.render_stateful_widget(t, area, &mut app.table_state);
+        let mut state = window_state(&mut app.table_state, &window);
+        f.render_stateful_widget(t, area, &mut state);
     }
 }
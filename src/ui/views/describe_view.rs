@@ -1,34 +1,61 @@
-use crate::app::App;
+use crate::app::{App, DESCRIBE_REFRESH_INTERVAL};
+use crate::models::AppMode;
 use crate::ui::components::centered_rect;
+use crate::ui::symbols::block;
 use crate::ui::theme::*;
+use crate::ui::views::logs_view::highlight_line;
 use ratatui::{
     Frame,
+    layout::Rect,
     text::Line,
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Clear, Paragraph},
 };
 
-pub fn draw(f: &mut Frame, app: &App) {
+pub fn draw(f: &mut Frame, app: &mut App) -> Rect {
     let area = centered_rect(90, 90, f.area());
     f.render_widget(Clear, area);
 
-    let lines: Vec<Line> = app.describe_content.iter().map(Line::raw).collect();
+    let temp;
+    let query_lower = if app.mode == AppMode::DescribeSearchInput {
+        temp = app.describe_search_input.to_ascii_lowercase();
+        temp.as_str()
+    } else {
+        app.describe_query.as_str()
+    };
+    let lines: Vec<Line> = app
+        .describe_content
+        .iter()
+        .map(|l| highlight_line(l, query_lower))
+        .collect();
 
     let total_lines = lines.len() as u16;
     let visible_height = area.height.saturating_sub(2);
+    app.viewport.describe_rows = visible_height as usize;
 
     let scroll = (app.describe_scroll as u16).min(total_lines.saturating_sub(visible_height));
 
-    let title = format!("Describe [{} lines]", app.describe_content.len(),);
+    let search_label = if app.mode == AppMode::DescribeSearchInput {
+        format!(" /{}_", app.describe_search_input)
+    } else if !app.describe_query.is_empty() {
+        format!(" /{}", app.describe_query)
+    } else {
+        String::new()
+    };
+    let refresh_label = if app.describe_auto_refresh {
+        format!(" [auto-refresh {}s]", DESCRIBE_REFRESH_INTERVAL.as_secs())
+    } else {
+        String::new()
+    };
+    let title = format!(
+        "Describe [{} lines]{refresh_label}{search_label}",
+        app.describe_content.len(),
+    );
 
     let paragraph = Paragraph::new(lines)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(title)
-                .style(STYLE_NORMAL),
-        )
-        .style(STYLE_NORMAL)
+        .block(block().title(title).style(theme().normal()))
+        .style(theme().normal())
         .scroll((scroll, 0));
 
     f.render_widget(paragraph, area);
+    area
 }
@@ -0,0 +1,28 @@
+use crate::app::App;
+use crate::ui::symbols::block;
+use crate::ui::theme::*;
+use ratatui::{Frame, layout::Rect, style::Style, text::Line, widgets::Paragraph};
+
+/// Details of the selected row, redrawn from the watched object.
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let block = block().title("Detail").style(theme().normal());
+    let Some(item) = app.get_selected_resource() else {
+        f.render_widget(Paragraph::new(" No selection").block(block), area);
+        return;
+    };
+
+    let lines: Vec<Line> = crate::utils::resource_details(item)
+        .into_iter()
+        .map(|l| {
+            if l.ends_with(':') && !l.starts_with(' ') {
+                Line::styled(l, Style::default().fg(theme().highlight))
+            } else {
+                Line::raw(l)
+            }
+        })
+        .collect();
+    f.render_widget(
+        Paragraph::new(lines).block(block).style(theme().normal()),
+        area,
+    );
+}
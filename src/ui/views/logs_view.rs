@@ -1,14 +1,15 @@
 use crate::app::App;
-use crate::models::AppMode;
+use crate::models::{AppMode, LogStreamEnd};
+use crate::ui::symbols::block;
 use crate::ui::theme::*;
 use ratatui::{
     Frame,
     layout::Rect,
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::Paragraph,
 };
 
-fn highlight_line<'a>(text: &'a str, needle_lower: &str) -> Line<'a> {
+pub(crate) fn highlight_line<'a>(text: &'a str, needle_lower: &str) -> Line<'a> {
     if needle_lower.is_empty() {
         return Line::raw(text);
     }
@@ -28,7 +29,7 @@ fn highlight_line<'a>(text: &'a str, needle_lower: &str) -> Line<'a> {
             }
             spans.push(Span::styled(
                 &text[abs..abs + needle_len],
-                STYLE_SEARCH_MATCH,
+                theme().search_match_style(),
             ));
             start = abs + needle_len;
         } else {
@@ -48,6 +49,7 @@ fn highlight_line<'a>(text: &'a str, needle_lower: &str) -> Line<'a> {
 pub fn draw(f: &mut Frame, app: &mut App, area: Rect) {
     let total_lines = app.log_buffer.len();
     let visible_height = area.height.saturating_sub(2) as usize;
+    app.viewport.log_rows = visible_height;
 
     let (scroll_offset, mode_label) = match app.log_scroll_offset {
         None => (total_lines.saturating_sub(visible_height), "FOLLOWING"),
@@ -84,14 +86,22 @@ pub fn draw(f: &mut Frame, app: &mut App, area: Rect) {
     } else {
         String::new()
     };
+    let end_label = match &app.log_end {
+        None => String::new(),
+        Some(LogStreamEnd::Ended) => " [stream ended]".to_string(),
+        Some(LogStreamEnd::PodTerminated {
+            replacement: Some(name),
+        }) => format!(" [pod terminated, R: follow {name}]"),
+        Some(LogStreamEnd::PodTerminated { replacement: None }) => " [pod terminated]".to_string(),
+    };
     let title = format!(
-        "Logs [{} lines] [{}]{}{}",
-        total_lines, mode_label, history_label, search_label,
+        "Logs {} [{} lines] [{}]{}{}{}",
+        app.log_pod_name, total_lines, mode_label, end_label, history_label, search_label,
     );
 
     let paragraph = Paragraph::new(lines)
-        .block(Block::default().borders(Borders::ALL).title(title))
-        .style(STYLE_NORMAL);
+        .block(block().title(title))
+        .style(theme().normal());
 
     f.render_widget(paragraph, area);
 }
@@ -105,7 +115,7 @@ mod tests {
     }
 
     fn is_highlighted(span: &Span) -> bool {
-        span.style == STYLE_SEARCH_MATCH
+        span.style == theme().search_match_style()
     }
 
     #[test]
@@ -139,7 +149,10 @@ mod tests {
     #[test]
     fn multiple_matches() {
         let line = highlight_line("err foo err bar err", "err");
-        assert_eq!(span_texts(&line), vec!["err", " foo ", "err", " bar ", "err"]);
+        assert_eq!(
+            span_texts(&line),
+            vec!["err", " foo ", "err", " bar ", "err"]
+        );
         assert!(is_highlighted(&line.spans[0]));
         assert!(!is_highlighted(&line.spans[1]));
         assert!(is_highlighted(&line.spans[2]));
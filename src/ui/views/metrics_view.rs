@@ -0,0 +1,104 @@
+use crate::app::App;
+use crate::models::MetricSeries;
+use crate::ui::symbols::block;
+use crate::ui::theme::*;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Chart, Clear, Dataset, GraphType, Paragraph},
+};
+
+/// Compact value for axis labels and titles: `1234`, `12.5`, `0.042`.
+fn format_value(v: f64) -> String {
+    match v.abs() {
+        v if v >= 100.0 => format!("{v:.0}"),
+        v if v >= 1.0 => format!("{v:.2}"),
+        _ => format!("{v:.3}"),
+    }
+}
+
+pub fn draw(f: &mut Frame, app: &App) -> Rect {
+    let area = f.area();
+    let area = Rect {
+        height: area.height.saturating_sub(1),
+        ..area
+    };
+    f.render_widget(Clear, area);
+    let title = format!(
+        "Metrics {} [last {}m]",
+        app.metrics_target,
+        crate::prometheus::RANGE_SECS / 60
+    );
+    let outer = block().title(title).style(theme().normal());
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let Some(series) = &app.metrics else {
+        f.render_widget(
+            Paragraph::new("Querying Prometheus…").style(theme().normal()),
+            inner,
+        );
+        return area;
+    };
+    let rows = Layout::vertical(vec![Constraint::Fill(1); series.len().max(1)]).split(inner);
+    for (s, row) in series.iter().zip(rows.iter()) {
+        draw_series(f, s, *row);
+    }
+    area
+}
+
+fn draw_series(f: &mut Frame, series: &MetricSeries, area: Rect) {
+    let points = match &series.points {
+        Ok(points) if !points.is_empty() => points,
+        Ok(_) => {
+            let p = Paragraph::new("no data")
+                .style(Style::default().fg(theme().muted))
+                .block(block().title(series.title.as_str()));
+            f.render_widget(p, area);
+            return;
+        }
+        Err(e) => {
+            let p = Paragraph::new(e.as_str())
+                .style(Style::default().fg(theme().error))
+                .block(block().title(series.title.as_str()));
+            f.render_widget(p, area);
+            return;
+        }
+    };
+    let max = points.iter().map(|(_, v)| *v).fold(0.0, f64::max);
+    let top = if max > 0.0 { max * 1.1 } else { 1.0 };
+    let last = points.last().map_or(0.0, |(_, v)| *v);
+    let range = crate::prometheus::RANGE_SECS as f64 / 60.0;
+    let title = Line::from(vec![
+        Span::raw(format!("{} ", series.title)),
+        Span::styled(format_value(last), Style::default().fg(theme().highlight)),
+        Span::styled(
+            format!("  max {}", format_value(max)),
+            Style::default().fg(theme().muted),
+        ),
+    ]);
+    let dataset = Dataset::default()
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(theme().running))
+        .data(points);
+    let chart = Chart::new(vec![dataset])
+        .block(block().title(title))
+        .style(theme().normal())
+        .x_axis(
+            Axis::default()
+                .bounds([-range, 0.0])
+                .labels([format!("-{range:.0}m"), "now".to_string()])
+                .style(Style::default().fg(theme().muted)),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, top])
+                .labels(["0".to_string(), format_value(top)])
+                .style(Style::default().fg(theme().muted)),
+        );
+    f.render_widget(chart, area);
+}
@@ -1,7 +1,13 @@
+pub mod access_view;
+pub mod configmaps_view;
 pub mod deployments_view;
 pub mod describe_view;
+pub mod detail_view;
 pub mod logs_view;
+pub mod metrics_view;
+pub mod pager_view;
 pub mod pods_view;
 pub mod popup_view;
 pub mod secrets_view;
 pub mod shell_view;
+pub mod split_view;
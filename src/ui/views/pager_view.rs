@@ -0,0 +1,75 @@
+use crate::app::App;
+use crate::models::AppMode;
+use crate::ui::symbols::block;
+use crate::ui::theme::*;
+use crate::ui::views::logs_view::highlight_line;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Clear, Paragraph},
+};
+
+/// Everything but the footer row, so key hints stay visible.
+pub fn pager_area(full: Rect) -> Rect {
+    Rect {
+        height: full.height.saturating_sub(1),
+        ..full
+    }
+}
+
+pub fn draw(f: &mut Frame, app: &mut App) -> Rect {
+    let area = pager_area(f.area());
+    f.render_widget(Clear, area);
+
+    let width = area.width.saturating_sub(2) as usize;
+    let visible = area.height.saturating_sub(2) as usize;
+    app.viewport.pager = (width, visible);
+    let rows = crate::utils::wrap_rows(&app.pager_text, width);
+    let scroll = app.pager_scroll.min(rows.len().saturating_sub(1));
+
+    let temp;
+    let query_lower = if app.mode == AppMode::ValuePagerSearch {
+        temp = app.pager_search_input.to_ascii_lowercase();
+        temp.as_str()
+    } else {
+        app.pager_query.as_str()
+    };
+    let end = (scroll + visible).min(rows.len());
+    let diff = matches!(app.mode, AppMode::EditDiff | AppMode::ApplyDiff);
+    let lines: Vec<Line> = rows[scroll..end]
+        .iter()
+        .map(|r| {
+            let line = highlight_line(r, query_lower);
+            match r.as_bytes().first() {
+                Some(b'+') if diff => line.style(Style::default().fg(theme().running)),
+                Some(b'-' | b'!') if diff => line.style(Style::default().fg(theme().error)),
+                Some(b'=') if diff => line.style(Style::default().fg(theme().highlight)),
+                _ => line,
+            }
+        })
+        .collect();
+
+    let search_label = if app.mode == AppMode::ValuePagerSearch {
+        format!(" /{}_", app.pager_search_input)
+    } else if !app.pager_query.is_empty() {
+        format!(" /{}", app.pager_query)
+    } else {
+        String::new()
+    };
+    let title = format!(
+        "{} [{}-{}/{} rows]{}",
+        app.pager_title,
+        scroll + 1,
+        end,
+        rows.len(),
+        search_label
+    );
+
+    let paragraph = Paragraph::new(lines)
+        .block(block().title(title).style(theme().normal()))
+        .style(theme().normal());
+    f.render_widget(paragraph, area);
+    area
+}
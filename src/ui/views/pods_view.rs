@@ -1,29 +1,134 @@
 use crate::app::App;
 use crate::models::KubeResource;
+use crate::ui::components::{
+    custom_cells, drop_order_with_custom, fit_columns, keep_columns, name_column_width,
+    scroll_columns, table_body_height, truncate_name, visible_rows, window_state,
+};
+use crate::ui::symbols::{block, symbols};
 use crate::ui::theme::*;
+use k8s_openapi::api::core::v1::Pod;
 use ratatui::{
     Frame,
     layout::{Constraint, Rect},
     style::Style,
-    widgets::{Block, Borders, Cell, HighlightSpacing, Paragraph, Row, Table},
+    widgets::{Cell, HighlightSpacing, Paragraph, Row, Table},
 };
 
+pub const HEADERS: [&str; 6] = ["", "Name", "Ready", "Status", "Restarts", "Age"];
+pub const WIDE_HEADERS: [&str; 4] = ["IP", "Node", "QoS", "Images"];
+/// Marker and name stay in place when scrolling sideways.
+const PINNED: usize = 2;
+/// Dropped on narrow terminals, first one first: Age, then Restarts.
+const DROP_ORDER: [usize; 2] = [5, 4];
+
+/// Text of the `HEADERS` columns after the marker.
+pub fn columns(p: &Pod) -> [String; 5] {
+    let status = p.status.as_ref();
+    let container_statuses = status
+        .and_then(|s| s.container_statuses.as_deref())
+        .unwrap_or_default();
+    let restarts: i32 = container_statuses.iter().map(|cs| cs.restart_count).sum();
+    let ready = container_statuses.iter().filter(|cs| cs.ready).count();
+    let total = p.spec.as_ref().map_or(0, |s| s.containers.len());
+    [
+        p.metadata.name.clone().unwrap_or_default(),
+        format!("{ready}/{total}"),
+        status.and_then(|s| s.phase.clone()).unwrap_or_default(),
+        restarts.to_string(),
+        crate::utils::get_resource_age(p.metadata.creation_timestamp.as_ref()),
+    ]
+}
+
+/// Text of the `WIDE_HEADERS` columns.
+pub fn wide_columns(p: &Pod) -> [String; 4] {
+    let status = p.status.as_ref();
+    let images: Vec<&str> = p
+        .spec
+        .iter()
+        .flat_map(|s| &s.containers)
+        .filter_map(|c| c.image.as_deref())
+        .collect();
+    [
+        status.and_then(|s| s.pod_ip.clone()).unwrap_or_default(),
+        p.spec
+            .as_ref()
+            .and_then(|s| s.node_name.clone())
+            .unwrap_or_default(),
+        status.and_then(|s| s.qos_class.clone()).unwrap_or_default(),
+        images.join(","),
+    ]
+}
+
 pub fn draw(f: &mut Frame, app: &mut App, area: Rect) {
-    let header_cells = ["", "Name", "Ready", "Status", "Restarts", "Age"]
+    let wide_headers: &[&str] = if app.wide { &WIDE_HEADERS } else { &[] };
+    let custom = app.config.columns(app.active_tab);
+    let scrollable = HEADERS.len() + custom.len() + wide_headers.len() - PINNED;
+    app.column_offset = if app.wide {
+        app.column_offset.min(scrollable - 1)
+    } else {
+        0
+    };
+    let offset = app.column_offset;
+
+    let mut widths = vec![
+        Constraint::Length(2),
+        if app.wide {
+            Constraint::Length(name_column_width(
+                app.filtered_items.iter().map(|i| i.name()),
+            ))
+        } else {
+            Constraint::Fill(1)
+        },
+        Constraint::Length(8),
+        // Room for the accessible mode's state symbol.
+        Constraint::Length(if theme().accessible { 14 } else { 12 }),
+        Constraint::Length(10),
+        Constraint::Length(8),
+    ];
+    widths.extend(custom.iter().map(|c| Constraint::Length(c.width())));
+    // Wide mode scrolls sideways instead of dropping columns.
+    let (keep, name_width) = if app.wide {
+        widths.extend([
+            Constraint::Length(16),
+            Constraint::Length(20),
+            Constraint::Length(11),
+            Constraint::Fill(1),
+        ]);
+        (vec![true; widths.len()], u16::MAX)
+    } else {
+        let drop = drop_order_with_custom(HEADERS.len(), custom.len(), &DROP_ORDER);
+        fit_columns(&widths, &drop, area.width)
+    };
+    let header_cells = HEADERS
         .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(COLOR_HIGHLIGHT)));
-    let header = Row::new(header_cells)
-        .style(STYLE_NORMAL)
-        .height(1)
-        .bottom_margin(1);
+        .copied()
+        .chain(custom.iter().map(|c| c.header.as_str()))
+        .chain(wide_headers.iter().copied())
+        .map(|h| Cell::from(h).style(Style::default().fg(theme().highlight)))
+        .collect();
+    let header = Row::new(scroll_columns(
+        keep_columns(header_cells, &keep),
+        PINNED,
+        offset,
+    ))
+    .style(theme().normal())
+    .height(1)
+    .bottom_margin(1);
 
-    let rows: Vec<Row> = app
-        .filtered_items
+    app.viewport.table_rows = table_body_height(area);
+    let window = visible_rows(
+        app.filtered_items.len(),
+        app.table_state.selected(),
+        app.table_state.offset(),
+        app.viewport.table_rows,
+    );
+    let rows: Vec<Row> = app.filtered_items[window.clone()]
         .iter()
         .enumerate()
-        .map(|(idx, item)| {
+        .map(|(i, item)| {
+            let idx = window.start + i;
             let marker = if app.selected_indices.contains(&idx) {
-                "●"
+                symbols().marker.as_str()
             } else {
                 " "
             };
@@ -33,94 +138,72 @@ pub fn draw(f: &mut Frame, app: &mut App, area: Rect) {
                     .height(1);
             };
 
-            let name = p.metadata.name.as_deref().unwrap_or_default();
-            let status_obj = p.status.as_ref();
-            let phase = status_obj
-                .and_then(|s| s.phase.as_deref())
-                .unwrap_or_default();
-
-            let restarts: i32 = status_obj
-                .and_then(|s| {
-                    s.container_statuses
-                        .as_ref()
-                        .map(|c| c.iter().map(|cs| cs.restart_count).sum())
-                })
-                .unwrap_or(0);
-
-            let ready_count = status_obj
-                .and_then(|s| {
-                    s.container_statuses
-                        .as_ref()
-                        .map(|c| c.iter().filter(|cs| cs.ready).count())
-                })
-                .unwrap_or(0);
-
-            let total_containers = p.spec.as_ref().map(|s| s.containers.len()).unwrap_or(0);
-
-            let age = crate::utils::get_resource_age(p.metadata.creation_timestamp.as_ref());
-
-            let status_style = match phase {
-                "Running" => Style::default().fg(COLOR_STATUS_RUNNING),
-                "Pending" => Style::default().fg(COLOR_STATUS_PENDING),
-                "Succeeded" => Style::default().fg(COLOR_STATUS_SUCCEEDED),
-                "Terminating" => Style::default().fg(COLOR_STATUS_TERMINATING),
-                _ => Style::default().fg(COLOR_STATUS_ERROR),
-            };
+            let [name, ready, phase, restarts, age] = columns(p);
+            let status_style = Style::default().fg(theme().phase(&phase));
 
             let marker_style = if app.selected_indices.contains(&idx) {
-                Style::default().fg(COLOR_STATUS_RUNNING)
+                Style::default().fg(theme().running)
             } else {
-                STYLE_NORMAL
+                theme().normal()
             };
 
-            Row::new(vec![
+            let mut cells = vec![
                 Cell::from(marker).style(marker_style),
-                Cell::from(name.to_owned()),
-                Cell::from(format!("{}/{}", ready_count, total_containers)),
-                Cell::from(phase.to_owned()).style(status_style),
-                Cell::from(restarts.to_string()),
+                Cell::from(truncate_name(&name, name_width)),
+                Cell::from(ready),
+                Cell::from(theme().phase_text(&phase)).style(status_style),
+                Cell::from(restarts),
                 Cell::from(age),
-            ])
-            .height(1)
+            ];
+            cells.extend(custom_cells(item, custom));
+            if app.wide {
+                cells.extend(wide_columns(p).map(Cell::from));
+            }
+            Row::new(scroll_columns(keep_columns(cells, &keep), PINNED, offset)).height(1)
         })
         .collect();
 
-    let title = if app.selected_indices.is_empty() {
+    let mut title = if app.selected_indices.is_empty() {
         "Pods".to_string()
     } else {
         format!("Pods ({} selected)", app.selected_indices.len())
     };
+    if app.visual_anchor.is_some() {
+        title.push_str(" -- VISUAL --");
+    }
+    if app.wide {
+        title.push_str(&format!(" [wide {}/{} ←/→]", offset + 1, scrollable));
+    }
 
     let t = Table::new(
         rows,
-        [
-            Constraint::Length(2),
-            Constraint::Fill(1),
-            Constraint::Length(8),
-            Constraint::Length(12),
-            Constraint::Length(10),
-            Constraint::Length(8),
-        ],
+        scroll_columns(keep_columns(widths, &keep), PINNED, offset),
     )
     .header(header)
-    .block(Block::default().borders(Borders::ALL).title(title.clone()))
-    .row_highlight_style(STYLE_HIGHLIGHT)
+    .block(block().title(title.clone()))
+    .row_highlight_style(theme().highlight_style())
     .highlight_symbol("> ")
     .highlight_spacing(HighlightSpacing::Always);
 
     if app.filtered_items.is_empty() && !app.is_loading {
         let msg = if app.last_error.is_some() {
             ""
+        } else if app.filter_query.is_empty()
+            && app.status_filter.is_empty()
+            && app.config.field_selector(app.active_tab).is_some()
+        {
+            "No pods match the configured field selector"
         } else if app.filter_query.is_empty() && app.status_filter.is_empty() {
             "No pods in this namespace"
         } else {
             "No pods match filter"
         };
         let empty = Paragraph::new(msg)
-            .style(STYLE_NORMAL)
-            .block(Block::default().borders(Borders::ALL).title(title));
+            .style(theme().normal())
+            .block(block().title(title));
         f.render_widget(empty, area);
     } else {
-        f.render_stateful_widget(t, area, &mut app.table_state);
+        let mut state = window_state(&mut app.table_state, &window);
+        f.render_stateful_widget(t, area, &mut state);
     }
 }
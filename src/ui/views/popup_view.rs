@@ -1,16 +1,18 @@
 use crate::app::App;
+use crate::k8s::teleport::LoginStatus;
 use crate::models::AppMode;
 use crate::ui::components::{centered_fixed_rect, centered_rect};
+use crate::ui::symbols::{block, symbols};
 use crate::ui::theme::*;
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
     style::Style,
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    widgets::{Clear, List, ListItem, Paragraph},
 };
 
-pub fn draw_popup(f: &mut Frame, app: &mut App) {
+pub fn draw_popup(f: &mut Frame, app: &mut App) -> Rect {
     match app.mode {
         AppMode::ContextSelect | AppMode::NamespaceSelect => {
             let area = centered_rect(50, 50, f.area());
@@ -20,36 +22,99 @@ pub fn draw_popup(f: &mut Frame, app: &mut App) {
                 AppMode::NamespaceSelect => draw_namespace_popup(f, app, area),
                 _ => {}
             }
+            area
         }
         AppMode::StatusFilter => draw_status_filter_popup(f, app),
-        _ => {}
+        _ => Rect::default(),
     }
 }
 
 fn draw_context_popup(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
+    let now = jiff::Timestamp::now();
     let list_items: Vec<ListItem> = app
-        .available_contexts
+        .filtered_contexts
         .iter()
         .map(|ctx| {
-            let label = if *ctx == app.current_context {
-                format!("{ctx} (current)")
-            } else {
-                ctx.clone()
+            let mut spans = match app.config.context_alias(ctx) {
+                Some(alias) => vec![
+                    Span::raw(alias.to_string()),
+                    Span::styled(format!("  {ctx}"), Style::default().fg(theme().muted)),
+                ],
+                None => vec![Span::raw(ctx.clone())],
             };
-            ListItem::new(Span::raw(label))
+            if *ctx == app.current_context {
+                spans.push(Span::raw(" (current)"));
+            }
+            if let Some(status) = app.teleport_status.get(ctx) {
+                let color = match status {
+                    LoginStatus::ValidUntil(_) => theme().muted,
+                    LoginStatus::Expired | LoginStatus::LoggedOut => theme().error,
+                };
+                spans.push(Span::styled(
+                    format!("  {}", status.label(now)),
+                    Style::default().fg(color),
+                ));
+            }
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
-    let list = List::new(list_items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Select Context"),
-        )
-        .highlight_style(STYLE_HIGHLIGHT)
-        .highlight_symbol(">> ");
+    if app.context_typing {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(area);
+
+        let input = Paragraph::new(format!("{}_", app.context_input))
+            .block(block().title("Search context").style(theme().normal()))
+            .style(theme().normal());
+        f.render_widget(input, chunks[0]);
+
+        let list = List::new(list_items)
+            .block(block())
+            .highlight_style(theme().highlight_style())
+            .highlight_symbol(">> ");
+
+        f.render_stateful_widget(list, chunks[1], &mut app.popup_state);
+    } else {
+        let list = List::new(list_items)
+            .block(block().title("Select Context"))
+            .highlight_style(theme().highlight_style())
+            .highlight_symbol(">> ");
+
+        f.render_stateful_widget(list, area, &mut app.popup_state);
+    }
+}
 
-    f.render_stateful_widget(list, area, &mut app.popup_state);
+/// Favorites first with a star, then a rule and the other namespaces.
+fn namespace_items(app: &App) -> Vec<ListItem<'static>> {
+    let favorites = app.app_state.get_favorites(&app.current_context);
+    let pinned = app
+        .filtered_namespaces
+        .iter()
+        .take_while(|ns| favorites.contains(ns))
+        .count();
+    app.filtered_namespaces
+        .iter()
+        .enumerate()
+        .map(|(i, ns)| {
+            if i >= pinned {
+                return ListItem::new(Span::raw(format!("  {ns}")));
+            }
+            let line = Line::from(vec![
+                Span::styled("★ ", Style::default().fg(theme().pending)),
+                Span::raw(ns.clone()),
+            ]);
+            if i + 1 == pinned && pinned < app.filtered_namespaces.len() {
+                ListItem::new(vec![
+                    line,
+                    Line::styled("──────", Style::default().fg(theme().muted)),
+                ])
+            } else {
+                ListItem::new(line)
+            }
+        })
+        .collect()
 }
 
 fn draw_namespace_popup(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
@@ -62,57 +127,136 @@ fn draw_namespace_popup(f: &mut Frame, app: &mut App, area: ratatui::layout::Rec
         let input_text = format!("{}_", app.namespace_input);
         let input = Paragraph::new(input_text)
             .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("Type namespace")
-                    .style(STYLE_NORMAL),
+                block()
+                    .title(format!(
+                        "Type namespace ({}, Tab cycles)",
+                        app.app_state.filter_mode.label()
+                    ))
+                    .style(theme().normal()),
             )
-            .style(STYLE_NORMAL);
+            .style(theme().normal());
         f.render_widget(input, chunks[0]);
 
-        let list_items: Vec<ListItem> = app
-            .filtered_namespaces
-            .iter()
-            .map(|i| ListItem::new(Span::raw(i)))
-            .collect();
+        let list_items = namespace_items(app);
 
         let list = List::new(list_items)
-            .block(Block::default().borders(Borders::ALL))
-            .highlight_style(STYLE_HIGHLIGHT)
+            .block(block())
+            .highlight_style(theme().highlight_style())
             .highlight_symbol(">> ");
 
         f.render_stateful_widget(list, chunks[1], &mut app.popup_state);
     } else {
-        let list_items: Vec<ListItem> = app
-            .filtered_namespaces
-            .iter()
-            .map(|i| ListItem::new(Span::raw(i)))
-            .collect();
+        let list_items = namespace_items(app);
 
         let list = List::new(list_items)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("Select Namespace"),
-            )
-            .highlight_style(STYLE_HIGHLIGHT)
+            .block(block().title("Select Namespace"))
+            .highlight_style(theme().highlight_style())
             .highlight_symbol(">> ");
 
         f.render_stateful_widget(list, area, &mut app.popup_state);
     }
 }
 
-fn status_color(phase: &str) -> ratatui::style::Color {
-    match phase {
-        "Running" => COLOR_STATUS_RUNNING,
-        "Pending" => COLOR_STATUS_PENDING,
-        "Succeeded" => COLOR_STATUS_SUCCEEDED,
-        "Terminating" => COLOR_STATUS_TERMINATING,
-        _ => COLOR_STATUS_ERROR,
+pub fn draw_namespace_create(f: &mut Frame, app: &App) -> Rect {
+    let area = centered_fixed_rect(50, 3, f.area());
+    f.render_widget(Clear, area);
+
+    let p = Paragraph::new(format!("{}_", app.namespace_create_input))
+        .block(block().title("New Namespace").style(theme().normal()))
+        .style(theme().normal());
+    f.render_widget(p, area);
+    area
+}
+
+pub fn draw_manifest_export(f: &mut Frame, app: &App) -> Rect {
+    let area = centered_fixed_rect(60, 3, f.area());
+    f.render_widget(Clear, area);
+
+    let count = app.selected_indices.len().max(1);
+    let how = if app.export_clean {
+        "cleaned for re-applying"
+    } else {
+        "as on the server"
+    };
+    let title = format!("Export {count} manifest(s) to directory ({how})");
+    let p = Paragraph::new(format!("{}_", app.export_path_input))
+        .block(block().title(title).style(theme().normal()))
+        .style(theme().normal());
+    f.render_widget(p, area);
+    area
+}
+
+pub fn draw_namespace_summary(f: &mut Frame, app: &App) -> Rect {
+    let Some(summary) = &app.namespace_summary else {
+        return Rect::default();
+    };
+    let mut lines = vec![Line::styled("Pods", Style::default().fg(theme().highlight))];
+    if summary.pods_by_status.is_empty() {
+        lines.push(Line::raw("  none"));
+    }
+    for (phase, count) in &summary.pods_by_status {
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                format!("{:<14}", theme().phase_text(phase)),
+                Style::default().fg(theme().phase(phase)),
+            ),
+            Span::raw(count.to_string()),
+        ]));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::from(vec![
+        Span::styled("Deployments ", Style::default().fg(theme().highlight)),
+        Span::raw(format!(
+            "{} ({} fully ready)",
+            summary.deployments, summary.deployments_ready
+        )),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Requests    ", Style::default().fg(theme().highlight)),
+        Span::raw(format!(
+            "cpu {}, memory {}",
+            crate::utils::format_cpu_millis(summary.cpu_requests_millis),
+            crate::utils::format_bytes(summary.memory_requests_bytes)
+        )),
+    ]));
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "Quotas",
+        Style::default().fg(theme().highlight),
+    ));
+    if summary.quotas.is_empty() {
+        lines.push(Line::raw("  none"));
     }
+    for q in &summary.quotas {
+        let (pct, color) = match q.ratio {
+            Some(r) if r >= 0.9 => (format!("{:>4.0}%", r * 100.0), theme().error),
+            Some(r) if r >= 0.7 => (format!("{:>4.0}%", r * 100.0), theme().pending),
+            Some(r) => (format!("{:>4.0}%", r * 100.0), theme().running),
+            None => ("    ?".to_string(), theme().muted),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(pct, Style::default().fg(color)),
+            Span::raw(format!(
+                "  {}/{}  {} of {}",
+                q.quota, q.resource, q.used, q.hard
+            )),
+        ]));
+    }
+
+    let height = (lines.len() as u16 + 2).min(f.area().height);
+    let area = centered_fixed_rect(60, height, f.area());
+    f.render_widget(Clear, area);
+    let p = Paragraph::new(lines).block(
+        block()
+            .title(format!("Namespace '{}'", app.current_namespace))
+            .style(theme().normal()),
+    );
+    f.render_widget(p, area);
+    area
 }
 
-fn draw_status_filter_popup(f: &mut Frame, app: &mut App) {
+fn draw_status_filter_popup(f: &mut Frame, app: &mut App) -> Rect {
     let h = (app.status_filter_items.len() as u16 + 2).max(4);
     let area = centered_fixed_rect(40, h, f.area());
     f.render_widget(Clear, area);
@@ -123,30 +267,168 @@ fn draw_status_filter_popup(f: &mut Frame, app: &mut App) {
         .enumerate()
         .map(|(i, (phase, count))| {
             let marker = if app.status_filter_selected.contains(&i) {
-                "●"
+                symbols().marker.as_str()
             } else {
                 " "
             };
             let line = Line::from(vec![
+                Span::styled(format!("{marker} "), Style::default().fg(theme().running)),
                 Span::styled(
-                    format!("{marker} "),
-                    Style::default().fg(COLOR_STATUS_RUNNING),
+                    theme().phase_text(phase),
+                    Style::default().fg(theme().phase(phase)),
                 ),
-                Span::styled(phase.as_str(), Style::default().fg(status_color(phase))),
-                Span::styled(format!(" ({count})"), STYLE_NORMAL),
+                Span::styled(format!(" ({count})"), theme().normal()),
             ]);
             ListItem::new(line)
         })
         .collect();
 
     let list = List::new(list_items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Filter by Status"),
-        )
-        .highlight_style(STYLE_HIGHLIGHT)
+        .block(block().title("Filter by Status"))
+        .highlight_style(theme().highlight_style())
         .highlight_symbol(">> ");
 
     f.render_stateful_widget(list, area, &mut app.status_filter_state);
+    area
+}
+
+pub fn draw_action_menu(f: &mut Frame, app: &mut App) -> Rect {
+    let actions = crate::keymap::resource_actions(app.active_tab);
+    let plugins = app.menu_kubectl_plugins().to_vec();
+    let height = (actions.len() + plugins.len()) as u16 + 2;
+    let area = centered_fixed_rect(44, height, f.area());
+    f.render_widget(Clear, area);
+
+    let list_items: Vec<ListItem> = actions
+        .iter()
+        .map(|(key, label)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{key}  "), Style::default().fg(theme().highlight)),
+                Span::styled(*label, theme().normal()),
+            ]))
+        })
+        .chain(plugins.iter().map(|plugin| {
+            ListItem::new(Line::from(vec![
+                Span::raw("   "),
+                Span::styled(format!("kubectl {plugin}"), theme().normal()),
+            ]))
+        }))
+        .collect();
+    let name = app
+        .get_selected_resource()
+        .map(|r| r.name().to_owned())
+        .unwrap_or_default();
+
+    let list = List::new(list_items)
+        .block(block().title(name))
+        .highlight_style(theme().highlight_style())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, area, &mut app.action_menu_state);
+    area
+}
+
+/// The `O` popup. Each URL is also recorded for `write_hyperlinks`.
+pub fn draw_endpoint_menu(f: &mut Frame, app: &mut App) -> Rect {
+    let width = app
+        .endpoints
+        .iter()
+        .map(|e| e.url.chars().count() + e.source.chars().count() + 7)
+        .max()
+        .unwrap_or(0)
+        .clamp(44, 100) as u16;
+    let area = centered_fixed_rect(width, app.endpoints.len() as u16 + 2, f.area());
+    f.render_widget(Clear, area);
+
+    let list_items: Vec<ListItem> = app
+        .endpoints
+        .iter()
+        .map(|e| {
+            ListItem::new(Line::from(vec![
+                Span::styled(e.url.clone(), theme().normal()),
+                Span::styled(
+                    format!("  {}", e.source),
+                    Style::default().fg(theme().muted),
+                ),
+            ]))
+        })
+        .collect();
+    let name = app
+        .get_selected_resource()
+        .map(|r| format!("Endpoints: {}", r.name()))
+        .unwrap_or_default();
+    let outer = block().title(name);
+    let inner = outer.inner(area);
+    let list = List::new(list_items)
+        .block(outer)
+        .highlight_style(theme().highlight_style())
+        .highlight_symbol(">> ");
+    f.render_stateful_widget(list, area, &mut app.endpoint_menu_state);
+
+    let offset = app.endpoint_menu_state.offset();
+    let rows = app
+        .endpoints
+        .iter()
+        .skip(offset)
+        .take(inner.height as usize);
+    for (row, e) in rows.enumerate() {
+        // After the highlight symbol's column.
+        let x = inner.x + 3;
+        let width = (e.url.chars().count() as u16).min(inner.right().saturating_sub(x));
+        let link = Rect::new(x, inner.y + row as u16, width, 1);
+        app.drawn_links.push((link, e.url.clone()));
+    }
+    area
+}
+
+pub fn draw_link_menu(f: &mut Frame, app: &mut App) -> Rect {
+    let links = app.config.links(&app.current_context, app.active_tab);
+    let area = centered_fixed_rect(44, links.len() as u16 + 2, f.area());
+    f.render_widget(Clear, area);
+
+    let list_items: Vec<ListItem> = links
+        .iter()
+        .map(|link| ListItem::new(Span::styled(link.name.clone(), theme().normal())))
+        .collect();
+    let name = app
+        .get_selected_resource()
+        .map(|r| format!("Open: {}", r.name()))
+        .unwrap_or_default();
+
+    let list = List::new(list_items)
+        .block(block().title(name))
+        .highlight_style(theme().highlight_style())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, area, &mut app.link_menu_state);
+    area
+}
+
+pub fn draw_plugin_menu(f: &mut Frame, app: &mut App) -> Rect {
+    let plugins = app.config.plugins(app.active_tab, app.read_only);
+    let area = centered_fixed_rect(44, plugins.len() as u16 + 2, f.area());
+    f.render_widget(Clear, area);
+
+    let list_items: Vec<ListItem> = plugins
+        .iter()
+        .map(|(_, plugin)| {
+            let key = plugin.key.map_or(' ', |k| k);
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{key}  "), Style::default().fg(theme().highlight)),
+                Span::styled(plugin.name.clone(), theme().normal()),
+            ]))
+        })
+        .collect();
+    let name = app
+        .get_selected_resource()
+        .map(|r| format!("Plugins: {}", r.name()))
+        .unwrap_or_default();
+
+    let list = List::new(list_items)
+        .block(block().title(name))
+        .highlight_style(theme().highlight_style())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, area, &mut app.plugin_menu_state);
+    area
 }
@@ -1,133 +1,565 @@
-use crate::app::App;
-use crate::models::KubeResource;
-use crate::ui::components::centered_rect;
+use crate::app::{App, secret_owner};
+use crate::cert::{CertInfo, EXPIRY_WARNING_DAYS};
+use crate::models::{AppMode, ConfigValue, DiffStatus, KubeResource, SecretFormField};
+use crate::ui::components::{
+    centered_fixed_rect, centered_rect, custom_cells, drop_order_with_custom, fit_columns,
+    keep_columns, table_body_height, truncate_name, visible_rows, window_state,
+};
+use crate::ui::symbols::block;
 use crate::ui::theme::*;
+use base64::prelude::*;
+use k8s_openapi::api::core::v1::Secret;
 use ratatui::{
     Frame,
-    layout::{Constraint, Rect},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
-    widgets::{Block, Borders, Cell, Clear, HighlightSpacing, Row, Table},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, HighlightSpacing, Paragraph, Row, Table},
 };
 
+pub const HEADERS: [&str; 5] = ["Name", "Type", "Managed By", "Data Count", "Age"];
+/// Dropped on narrow terminals, first one first: Age, Data Count, Managed By.
+const DROP_ORDER: [usize; 3] = [4, 3, 2];
+
+/// Text of the `HEADERS` columns, for the table and for `:export`.
+pub fn columns(s: &Secret) -> [String; 5] {
+    [
+        s.metadata.name.clone().unwrap_or_default(),
+        s.type_.clone().unwrap_or_default(),
+        secret_owner(s)
+            .map(|o| o.kind)
+            .unwrap_or_default()
+            .to_string(),
+        s.data.as_ref().map_or(0, |d| d.len()).to_string(),
+        crate::utils::get_resource_age(s.metadata.creation_timestamp.as_ref()),
+    ]
+}
+
 pub fn draw(f: &mut Frame, app: &mut App, area: Rect) {
-    let header_cells = ["Name", "Type", "Data Count", "Age"]
-        .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(COLOR_HIGHLIGHT)));
-    let header = Row::new(header_cells)
-        .style(STYLE_NORMAL)
+    let custom = app.config.columns(app.active_tab);
+    let mut widths = vec![
+        Constraint::Fill(1),
+        Constraint::Length(25),
+        Constraint::Length(15),
+        Constraint::Length(12),
+        Constraint::Length(8),
+    ];
+    let fixed = widths.len();
+    widths.extend(custom.iter().map(|c| Constraint::Length(c.width())));
+    let drop = drop_order_with_custom(fixed, custom.len(), &DROP_ORDER);
+    let (keep, name_width) = fit_columns(&widths, &drop, area.width);
+
+    let header_cells = HEADERS
+        .into_iter()
+        .chain(custom.iter().map(|c| c.header.as_str()))
+        .map(|h| Cell::from(h).style(Style::default().fg(theme().highlight)))
+        .collect();
+    let header = Row::new(keep_columns(header_cells, &keep))
+        .style(theme().normal())
         .height(1)
         .bottom_margin(1);
 
-    let rows = app.filtered_items.iter().map(|item| {
+    app.viewport.table_rows = table_body_height(area);
+    let window = visible_rows(
+        app.filtered_items.len(),
+        app.table_state.selected(),
+        app.table_state.offset(),
+        app.viewport.table_rows,
+    );
+    let rows = app.filtered_items[window.clone()].iter().map(|item| {
         let KubeResource::Secret(s) = item else {
             return Row::new(vec![Cell::from(item.name().to_owned())]).height(1);
         };
 
-        let name = s.metadata.name.as_deref().unwrap_or_default();
-        let type_ = s.type_.as_deref().unwrap_or_default();
-        let count = s.data.as_ref().map(|d| d.len()).unwrap_or(0);
-        let age = crate::utils::get_resource_age(s.metadata.creation_timestamp.as_ref());
+        let [name, type_, managed_by, count, age] = columns(s);
 
-        Row::new(vec![
-            Cell::from(name.to_owned()),
-            Cell::from(type_.to_owned()),
-            Cell::from(count.to_string()),
-            Cell::from(age),
-        ])
+        Row::new(keep_columns(
+            vec![
+                Cell::from(truncate_name(&name, name_width)),
+                Cell::from(type_),
+                Cell::from(managed_by).style(Style::default().fg(theme().pending)),
+                Cell::from(count),
+                Cell::from(age),
+            ]
+            .into_iter()
+            .chain(custom_cells(item, custom))
+            .collect(),
+            &keep,
+        ))
         .height(1)
     });
 
-    let t = Table::new(
-        rows,
-        [
-            Constraint::Fill(1),
-            Constraint::Length(25),
-            Constraint::Length(12),
-            Constraint::Length(8),
-        ],
-    )
-    .header(header)
-    .block(Block::default().borders(Borders::ALL).title("Secrets"))
-    .row_highlight_style(STYLE_HIGHLIGHT)
-    .highlight_symbol("> ")
-    .highlight_spacing(HighlightSpacing::Always);
+    let t = Table::new(rows, keep_columns(widths, &keep))
+        .header(header)
+        .block(block().title("Secrets"))
+        .row_highlight_style(theme().highlight_style())
+        .highlight_symbol("> ")
+        .highlight_spacing(HighlightSpacing::Always);
 
     if app.filtered_items.is_empty() && !app.is_loading {
         let msg = if app.last_error.is_some() {
             "" // error shown in footer
+        } else if app.filter_query.is_empty() && app.config.field_selector(app.active_tab).is_some()
+        {
+            "No secrets match the configured field selector"
         } else if app.filter_query.is_empty() {
             "No secrets in this namespace"
         } else {
             "No secrets match filter"
         };
         let empty = ratatui::widgets::Paragraph::new(msg)
-            .style(STYLE_NORMAL)
-            .block(Block::default().borders(Borders::ALL).title("Secrets"));
+            .style(theme().normal())
+            .block(block().title("Secrets"));
         f.render_widget(empty, area);
     } else {
-        f.render_stateful_widget(t, area, &mut app.table_state);
+        let mut state = window_state(&mut app.table_state, &window);
+        f.render_stateful_widget(t, area, &mut state);
     }
 }
 
-pub fn draw_decode_modal(f: &mut Frame, app: &mut App) {
+pub fn draw_decode_modal(f: &mut Frame, app: &mut App) -> Rect {
     let area = centered_rect(60, 60, f.area());
     f.render_widget(Clear, area);
 
     let Some(decoded) = &app.selected_secret_decoded else {
-        return;
+        return area;
     };
 
+    let mut title = vec![Span::raw("Decoded Secret")];
+    if let Some(owner) = &app.secret_owner {
+        let status = app
+            .secret_owner_status
+            .as_deref()
+            .unwrap_or("checking sync…");
+        title.push(Span::styled(
+            format!(" [managed by {} '{}': {status}]", owner.kind, owner.name),
+            Style::default().fg(theme().pending),
+        ));
+    }
+    let title = Line::from(title);
+
     if decoded.is_empty() {
         let p = ratatui::widgets::Paragraph::new("No data in secret.")
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("Decoded Secret")
-                    .style(STYLE_NORMAL),
-            )
-            .style(STYLE_NORMAL);
+            .block(block().title(title.clone()).style(theme().normal()))
+            .style(theme().normal());
         f.render_widget(p, area);
-        return;
+        return area;
     }
 
-    let header = Row::new(vec![
-        Cell::from("KEY").style(Style::default().fg(COLOR_HIGHLIGHT)),
-        Cell::from("VALUE").style(Style::default().fg(COLOR_HIGHLIGHT)),
-    ])
-    .height(1)
-    .bottom_margin(1);
+    let area = match &app.secret_certs {
+        Some(certs) => {
+            let lines = cert_lines(certs);
+            let height = (lines.len() as u16 + 2).min(area.height / 2);
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(height), Constraint::Min(0)])
+                .split(area);
+            let p =
+                Paragraph::new(lines).block(block().title("Certificate").style(theme().normal()));
+            f.render_widget(p, chunks[0]);
+            chunks[1]
+        }
+        None => area,
+    };
+    let is_tls = app.secret_certs.is_some();
 
+    let mut header_cells = vec![
+        Cell::from("KEY").style(Style::default().fg(theme().highlight)),
+        Cell::from("VALUE").style(Style::default().fg(theme().highlight)),
+    ];
+    if app.secret_show_base64 {
+        header_cells.push(Cell::from("BASE64").style(Style::default().fg(theme().highlight)));
+    }
+    let header = Row::new(header_cells).height(1).bottom_margin(1);
+
+    let mask = |s: String| {
+        if app.secret_revealed {
+            s
+        } else {
+            "********".to_owned()
+        }
+    };
     let rows: Vec<Row> = decoded
         .iter()
         .map(|(k, v)| {
-            let display_val = if app.secret_revealed {
-                v.as_str().to_owned()
-            } else {
-                "********".to_owned()
+            let value = match v {
+                ConfigValue::Binary(bytes) => format!("<binary, {} bytes>", bytes.len()),
+                ConfigValue::Text(text) if is_tls && text.starts_with("-----BEGIN") => {
+                    format!("<PEM, {} lines>", text.lines().count())
+                }
+                ConfigValue::Text(text) => text.clone(),
             };
-            Row::new(vec![Cell::from(k.as_str()), Cell::from(display_val)])
+            let mut cells = vec![Cell::from(k.as_str()), Cell::from(mask(value))];
+            if app.secret_show_base64 {
+                cells.push(Cell::from(mask(BASE64_STANDARD.encode(v.bytes()))));
+            }
+            Row::new(cells)
         })
         .collect();
 
     app.secret_table_state.select(Some(app.secret_scroll));
 
+    let widths = if app.secret_show_base64 {
+        vec![
+            Constraint::Percentage(25),
+            Constraint::Percentage(35),
+            Constraint::Percentage(40),
+        ]
+    } else {
+        vec![Constraint::Percentage(30), Constraint::Percentage(70)]
+    };
+    let t = Table::new(rows, widths)
+        .header(header)
+        .block(block().title(title).style(theme().normal()))
+        .row_highlight_style(
+            Style::default()
+                .fg(theme().highlight)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+
+    f.render_stateful_widget(t, area, &mut app.secret_table_state);
+    area
+}
+
+fn cert_lines(certs: &Result<Vec<CertInfo>, String>) -> Vec<Line<'static>> {
+    let certs = match certs {
+        Ok(certs) => certs,
+        Err(e) => {
+            return vec![Line::styled(
+                format!("Could not parse tls.crt: {e}"),
+                Style::default().fg(theme().error),
+            )];
+        }
+    };
+    let now = jiff::Timestamp::now();
+    let label = Style::default().fg(theme().highlight);
+    let mut lines = Vec::new();
+    for (i, cert) in certs.iter().enumerate() {
+        if certs.len() > 1 {
+            let role = if i == 0 { "leaf" } else { "chain" };
+            lines.push(Line::styled(
+                format!("[{}/{}] {role}", i + 1, certs.len()),
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
+        }
+        let days = cert.days_left(now);
+        let (expiry, style) = if cert.is_expired(now) {
+            (
+                format!("EXPIRED {}d ago", -days),
+                Style::default()
+                    .fg(theme().error)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else if days < EXPIRY_WARNING_DAYS {
+            (
+                format!("{days}d left"),
+                Style::default().fg(theme().pending),
+            )
+        } else {
+            (
+                format!("{days}d left"),
+                Style::default().fg(theme().running),
+            )
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Subject: ", label),
+            Span::raw(cert.subject.clone()),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("Issuer:  ", label),
+            Span::raw(cert.issuer.clone()),
+        ]));
+        if i == 0 {
+            let sans = if cert.sans.is_empty() {
+                "-".to_string()
+            } else {
+                cert.sans.join(", ")
+            };
+            lines.push(Line::from(vec![
+                Span::styled("SANs:    ", label),
+                Span::raw(sans),
+            ]));
+        }
+        lines.push(Line::from(vec![
+            Span::styled("Valid:   ", label),
+            Span::raw(format!(
+                "{} .. {} ",
+                cert.not_before.strftime("%Y-%m-%d"),
+                cert.not_after.strftime("%Y-%m-%d")
+            )),
+            Span::styled(format!("({expiry})"), style),
+        ]));
+    }
+    lines
+}
+
+pub fn draw_create_modal(f: &mut Frame, app: &App) -> Rect {
+    let area = centered_rect(60, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let form = &app.secret_form;
+    let block = block()
+        .title(format!("New Secret in {}", app.current_namespace))
+        .style(theme().normal());
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Name
+            Constraint::Length(1), // Type
+            Constraint::Length(1), // Key
+            Constraint::Length(1), // Value
+            Constraint::Length(1), // .env file
+            Constraint::Length(1),
+            Constraint::Min(0), // Entries
+        ])
+        .split(inner);
+
+    let fields = [
+        (SecretFormField::Name, "Name", form.name.as_str()),
+        (SecretFormField::Type, "Type", form.type_.as_str()),
+        (SecretFormField::Key, "Key", form.key.as_str()),
+        (SecretFormField::Value, "Value", form.value.as_str()),
+        (
+            SecretFormField::EnvFile,
+            "Load .env",
+            form.env_file.as_str(),
+        ),
+    ];
+    for (i, (field, label, value)) in fields.into_iter().enumerate() {
+        let focused = form.field == field;
+        let cursor = if focused { "_" } else { "" };
+        let label_style = if focused {
+            Style::default()
+                .fg(theme().highlight)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            theme().normal()
+        };
+        let line = Line::from(vec![
+            Span::styled(format!("{label:>10}: "), label_style),
+            Span::styled(format!("{value}{cursor}"), theme().normal()),
+        ]);
+        f.render_widget(Paragraph::new(line), chunks[i]);
+    }
+
+    let rows: Vec<Row> = form
+        .entries
+        .iter()
+        .map(|(k, _)| Row::new(vec![Cell::from(k.as_str()), Cell::from("********")]))
+        .collect();
     let t = Table::new(
         rows,
-        [Constraint::Percentage(30), Constraint::Percentage(70)],
+        [Constraint::Percentage(40), Constraint::Percentage(60)],
+    )
+    .header(
+        Row::new(vec![
+            Cell::from("KEY").style(Style::default().fg(theme().highlight)),
+            Cell::from("VALUE").style(Style::default().fg(theme().highlight)),
+        ])
+        .height(1),
     )
-    .header(header)
     .block(
         Block::default()
-            .borders(Borders::ALL)
-            .title("Decoded Secret")
-            .style(STYLE_NORMAL),
+            .borders(Borders::TOP)
+            .title(format!("Data ({} keys)", form.entries.len())),
+    );
+    f.render_widget(t, chunks[6]);
+    area
+}
+
+pub fn draw_edit_input(f: &mut Frame, app: &App) -> Rect {
+    let area = centered_fixed_rect(60, 3, f.area());
+    f.render_widget(Clear, area);
+
+    let key = app
+        .selected_secret_decoded
+        .as_ref()
+        .and_then(|d| d.get(app.secret_scroll))
+        .map(|(k, _)| k.as_str())
+        .unwrap_or_default();
+    let p = Paragraph::new(format!("{}_", app.secret_edit_input))
+        .block(
+            block()
+                .title(format!("Edit '{key}'"))
+                .style(theme().normal()),
+        )
+        .style(theme().normal());
+    f.render_widget(p, area);
+    area
+}
+
+pub fn draw_export_input(f: &mut Frame, app: &App) -> Rect {
+    let area = centered_fixed_rect(60, 3, f.area());
+    f.render_widget(Clear, area);
+
+    let title = if app.mode == AppMode::SecretSaveRaw {
+        let key = app
+            .selected_secret_decoded
+            .as_ref()
+            .and_then(|d| d.get(app.secret_scroll))
+            .map(|(k, _)| k.as_str())
+            .unwrap_or_default();
+        format!("Save raw bytes of '{key}' to")
+    } else {
+        "Export as .env".to_string()
+    };
+    let p = Paragraph::new(format!("{}_", app.export_path_input))
+        .block(block().title(title).style(theme().normal()))
+        .style(theme().normal());
+    f.render_widget(p, area);
+    area
+}
+
+pub fn draw_diff_input(f: &mut Frame, app: &App) -> Rect {
+    let area = centered_fixed_rect(60, 3, f.area());
+    f.render_widget(Clear, area);
+
+    let name = app
+        .get_selected_resource()
+        .map(|r| r.name())
+        .unwrap_or_default();
+    let p = Paragraph::new(format!("{}_", app.secret_diff_input))
+        .block(
+            block()
+                .title(format!("Compare '{name}' with (ns or ctx/ns)"))
+                .style(theme().normal()),
+        )
+        .style(theme().normal());
+    f.render_widget(p, area);
+    area
+}
+
+pub fn draw_diff_modal(f: &mut Frame, app: &mut App) -> Rect {
+    let area = centered_rect(80, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let mask = |v: &Option<String>| match v {
+        None => "-".to_string(),
+        Some(_) if !app.secret_revealed => "********".to_string(),
+        Some(v) => v.clone(),
+    };
+
+    let header = Row::new(
+        ["", "KEY", "LOCAL", "REMOTE"]
+            .iter()
+            .map(|h| Cell::from(*h).style(Style::default().fg(theme().highlight))),
+    )
+    .height(1)
+    .bottom_margin(1);
+
+    let rows: Vec<Row> = app
+        .secret_diff
+        .iter()
+        .map(|row| {
+            let (marker, color) = match row.status {
+                DiffStatus::Same => ("=", theme().text),
+                DiffStatus::Changed => ("~", theme().pending),
+                DiffStatus::Added => ("+", theme().running),
+                DiffStatus::Removed => ("-", theme().error),
+            };
+            Row::new(vec![
+                Cell::from(marker),
+                Cell::from(row.key.as_str()),
+                Cell::from(mask(&row.left)),
+                Cell::from(mask(&row.right)),
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+
+    let changed = app
+        .secret_diff
+        .iter()
+        .filter(|r| r.status != DiffStatus::Same)
+        .count();
+    let title = format!(
+        "Diff {} [{} of {} keys differ]",
+        app.secret_diff_target,
+        changed,
+        app.secret_diff.len()
+    );
+
+    app.secret_table_state.select(Some(app.secret_scroll));
+    let t = Table::new(
+        rows,
+        [
+            Constraint::Length(2),
+            Constraint::Percentage(30),
+            Constraint::Percentage(35),
+            Constraint::Percentage(35),
+        ],
+    )
+    .header(header)
+    .block(block().title(title).style(theme().normal()))
+    .row_highlight_style(Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED))
+    .highlight_symbol("> ");
+
+    f.render_stateful_widget(t, area, &mut app.secret_table_state);
+    area
+}
+
+pub fn draw_usage_modal(f: &mut Frame, app: &mut App) -> Rect {
+    let area = centered_rect(80, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let title = format!(
+        "'{}' used by {} resource(s)",
+        app.secret_usage_name,
+        app.secret_usage.len()
+    );
+    if app.secret_usage.is_empty() {
+        let p = Paragraph::new(format!(
+            "No pods or deployments in {} reference this secret.",
+            app.current_namespace
+        ))
+        .block(block().title(title).style(theme().normal()))
+        .style(theme().normal());
+        f.render_widget(p, area);
+        return area;
+    }
+
+    let header = Row::new(
+        ["KIND", "NAME", "REFERENCES"]
+            .iter()
+            .map(|h| Cell::from(*h).style(Style::default().fg(theme().highlight))),
     )
+    .height(1)
+    .bottom_margin(1);
+
+    let rows: Vec<Row> = app
+        .secret_usage
+        .iter()
+        .map(|c| {
+            Row::new(vec![
+                Cell::from(c.kind),
+                Cell::from(c.name.as_str()),
+                Cell::from(c.refs.join(", ")),
+            ])
+        })
+        .collect();
+
+    app.secret_table_state.select(Some(app.secret_usage_scroll));
+    let t = Table::new(
+        rows,
+        [
+            Constraint::Length(12),
+            Constraint::Percentage(35),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .block(block().title(title).style(theme().normal()))
     .row_highlight_style(
         Style::default()
-            .fg(COLOR_HIGHLIGHT)
+            .fg(theme().highlight)
             .add_modifier(Modifier::BOLD),
     )
     .highlight_symbol("> ");
 
     f.render_stateful_widget(t, area, &mut app.secret_table_state);
+    area
 }
@@ -1,19 +1,21 @@
 use crate::app::App;
 use crate::ui::components::centered_rect;
+use crate::ui::symbols::block;
 use crate::ui::theme::*;
 use ratatui::{
     Frame,
+    layout::Rect,
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Clear, Paragraph},
 };
 
-pub fn draw(f: &mut Frame, app: &App) {
+pub fn draw(f: &mut Frame, app: &App) -> Rect {
     let area = centered_rect(80, 80, f.area());
     f.render_widget(Clear, area);
 
     let Some(session) = &app.shell_session else {
-        return;
+        return area;
     };
 
     let screen = session.parser.screen();
@@ -69,13 +71,11 @@ pub fn draw(f: &mut Frame, app: &App) {
     } else {
         format!("{} (Ctrl+Q to close)", app.shell_title)
     };
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .title(title)
-        .style(STYLE_NORMAL);
+    let block = block().title(title).style(theme().normal());
 
     let paragraph = Paragraph::new(lines).block(block);
     f.render_widget(paragraph, area);
+    area
 }
 
 fn convert_color(c: vt100::Color) -> ratatui::style::Color {
@@ -0,0 +1,133 @@
+use crate::app::App;
+use crate::models::KubeResource;
+use crate::ui::components::centered_fixed_rect;
+use crate::ui::symbols::block;
+use crate::ui::theme::*;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    widgets::{Cell, Clear, Paragraph, Row, Table, TableState},
+};
+
+/// Kind-specific summary columns.
+fn summary(item: &KubeResource) -> (String, String) {
+    match item {
+        KubeResource::Pod(p) => {
+            let restarts: i32 = p
+                .status
+                .as_ref()
+                .and_then(|s| s.container_statuses.as_ref())
+                .map_or(0, |cs| cs.iter().map(|c| c.restart_count).sum());
+            (App::pod_phase(p).to_owned(), restarts.to_string())
+        }
+        KubeResource::Deployment(d) => {
+            let status = d.status.as_ref();
+            let replicas = status.map_or(0, |s| s.replicas.unwrap_or(0));
+            let ready = status.map_or(0, |s| s.ready_replicas.unwrap_or(0));
+            let image = d
+                .spec
+                .as_ref()
+                .and_then(|s| s.template.spec.as_ref())
+                .and_then(|s| s.containers.first())
+                .and_then(|c| c.image.clone())
+                .unwrap_or_default();
+            (format!("{ready}/{replicas}"), image)
+        }
+        KubeResource::Secret(s) => (
+            s.type_.clone().unwrap_or_default(),
+            s.data.as_ref().map_or(0, |d| d.len()).to_string(),
+        ),
+        KubeResource::ConfigMap(c) => (
+            String::new(),
+            (c.data.as_ref().map_or(0, |d| d.len())
+                + c.binary_data.as_ref().map_or(0, |d| d.len()))
+            .to_string(),
+        ),
+    }
+}
+
+fn headers(item: Option<&KubeResource>) -> [&'static str; 3] {
+    match item {
+        Some(KubeResource::Pod(_)) => ["Name", "Status", "Restarts"],
+        Some(KubeResource::Deployment(_)) => ["Name", "Ready", "Image"],
+        Some(KubeResource::Secret(_)) => ["Name", "Type", "Data"],
+        Some(KubeResource::ConfigMap(_)) => ["Name", "", "Data"],
+        None => ["Name", "", ""],
+    }
+}
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let Some(pane) = &app.split else {
+        return;
+    };
+    let title = format!("{}/{}", app.context_label(&pane.context), pane.namespace);
+
+    if let Some(err) = &pane.error {
+        let p = Paragraph::new(err.as_str())
+            .style(Style::default().fg(theme().error))
+            .block(block().title(title));
+        f.render_widget(p, area);
+        return;
+    }
+
+    let items = app.filter_items(&pane.items);
+    let selected_name = app.get_selected_resource().map(|r| r.name());
+    let local_names: std::collections::HashSet<&str> = app.items.iter().map(|i| i.name()).collect();
+
+    let header = Row::new(
+        headers(items.first().copied())
+            .iter()
+            .map(|h| Cell::from(*h).style(Style::default().fg(theme().highlight))),
+    )
+    .height(1)
+    .bottom_margin(1);
+
+    // Names missing from the main pane are tinted so drift stands out.
+    let rows = items.iter().map(|item| {
+        let (a, b) = summary(item);
+        let style = if local_names.contains(item.name()) {
+            theme().normal()
+        } else {
+            Style::default().fg(theme().pending)
+        };
+        Row::new(vec![
+            Cell::from(item.name().to_owned()),
+            Cell::from(a),
+            Cell::from(b),
+        ])
+        .style(style)
+    });
+
+    let t = Table::new(
+        rows,
+        [
+            Constraint::Fill(2),
+            Constraint::Length(12),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .block(block().title(format!("{title} [{}]", items.len())))
+    .row_highlight_style(theme().highlight_style().add_modifier(Modifier::BOLD))
+    .highlight_symbol("> ");
+
+    let mut state = TableState::default();
+    state.select(selected_name.and_then(|n| items.iter().position(|i| i.name() == n)));
+    f.render_stateful_widget(t, area, &mut state);
+}
+
+pub fn draw_input(f: &mut Frame, app: &App) -> Rect {
+    let area = centered_fixed_rect(60, 3, f.area());
+    f.render_widget(Clear, area);
+
+    let p = Paragraph::new(format!("{}_", app.split_input))
+        .block(
+            block()
+                .title("Split view: compare with (ns or ctx/ns)")
+                .style(theme().normal()),
+        )
+        .style(theme().normal());
+    f.render_widget(p, area);
+    area
+}
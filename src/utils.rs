@@ -1,25 +1,1271 @@
 use jiff::Timestamp;
+use k8s_openapi::ByteString;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+use std::collections::BTreeMap;
 
 pub fn get_resource_age(timestamp: Option<&Time>) -> String {
     match timestamp {
         Some(time) => {
             let now = Timestamp::now();
             let duration = now.duration_since(time.0);
-            let secs = duration.as_secs();
-
-            if secs >= 86400 {
-                format!("{}d", secs / 86400)
-            } else if secs >= 3600 {
-                format!("{}h", secs / 3600)
-            } else if secs >= 60 {
-                format!("{}m", secs / 60)
+            format_secs(duration.as_secs())
+        }
+        None => "?".to_string(),
+    }
+}
+
+/// One step of a custom column path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathStep {
+    Key(String),
+    Index(usize),
+    /// `[*]`: every element of an array (or value of an object).
+    All,
+}
+
+/// Parses a JSONPath-like expression such as `.spec.containers[*].image`.
+pub fn parse_json_path(path: &str) -> Result<Vec<PathStep>, String> {
+    let trimmed = path.trim();
+    let inner = trimmed
+        .strip_prefix('{')
+        .and_then(|p| p.strip_suffix('}'))
+        .unwrap_or(trimmed);
+    let mut chars = inner.chars().peekable();
+    let mut steps = Vec::new();
+    let invalid = || format!("invalid path '{path}'");
+    if chars.peek().is_some_and(|c| *c != '.' && *c != '[') {
+        // Tolerate a missing leading dot.
+        steps.push(PathStep::Key(String::new()));
+    }
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => steps.push(PathStep::Key(String::new())),
+            '[' => {
+                let mut inside = String::new();
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    }
+                    inside.push(c);
+                }
+                let step = if inside == "*" {
+                    PathStep::All
+                } else if let Ok(i) = inside.parse() {
+                    PathStep::Index(i)
+                } else {
+                    let key = inside
+                        .strip_prefix('\'')
+                        .and_then(|k| k.strip_suffix('\''))
+                        .or_else(|| inside.strip_prefix('"').and_then(|k| k.strip_suffix('"')))
+                        .ok_or_else(invalid)?;
+                    PathStep::Key(key.to_string())
+                };
+                steps.push(step);
+            }
+            ']' => return Err(invalid()),
+            c => match steps.last_mut() {
+                Some(PathStep::Key(key)) => key.push(c),
+                _ => return Err(invalid()),
+            },
+        }
+    }
+    let empty_key = |s: &PathStep| matches!(s, PathStep::Key(k) if k.is_empty());
+    if steps.is_empty() || steps.iter().any(empty_key) {
+        return Err(invalid());
+    }
+    Ok(steps)
+}
+
+/// Every value `steps` reach in `value`, as text joined by commas.
+pub fn eval_json_path(value: &serde_json::Value, steps: &[PathStep]) -> String {
+    let mut current = vec![value];
+    for step in steps {
+        current = current
+            .into_iter()
+            .flat_map(|v| -> Vec<&serde_json::Value> {
+                match (step, v) {
+                    (PathStep::Key(k), serde_json::Value::Object(m)) => {
+                        m.get(k).into_iter().collect()
+                    }
+                    (PathStep::Index(i), serde_json::Value::Array(a)) => {
+                        a.get(*i).into_iter().collect()
+                    }
+                    (PathStep::All, serde_json::Value::Array(a)) => a.iter().collect(),
+                    (PathStep::All, serde_json::Value::Object(m)) => m.values().collect(),
+                    _ => Vec::new(),
+                }
+            })
+            .collect();
+    }
+    current
+        .into_iter()
+        .filter(|v| !v.is_null())
+        .map(|v| match v {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parses `30s`, `10m`, `2h` or `1d` (a bare number is seconds) into seconds.
+pub fn parse_duration_secs(text: &str) -> Option<i64> {
+    let text = text.trim();
+    let (number, unit) = match text.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => text.split_at(i),
+        None => (text, "s"),
+    };
+    let n: i64 = number.parse().ok()?;
+    let scale = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return None,
+    };
+    n.checked_mul(scale)
+}
+
+/// A duration in its largest whole unit: `42s`, `5m`, `3h`, `2d`.
+pub fn format_secs(secs: i64) -> String {
+    if secs >= 86400 {
+        format!("{}d", secs / 86400)
+    } else if secs >= 3600 {
+        format!("{}h", secs / 3600)
+    } else if secs >= 60 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+pub fn expand_home(path: &str) -> std::path::PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .map(|h| h.join(rest))
+            .unwrap_or_else(|| std::path::PathBuf::from(path)),
+        None => std::path::PathBuf::from(path),
+    }
+}
+
+/// Replaces `$VAR` in `text` with its value from `vars`, taking the longest name.
+pub fn expand_placeholders(text: &str, vars: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find('$') {
+        out.push_str(&rest[..i]);
+        let after = &rest[i + 1..];
+        let len = after
+            .find(|c: char| !(c.is_ascii_uppercase() || c == '_'))
+            .unwrap_or(after.len());
+        match vars.iter().find(|(k, _)| *k == &after[..len]) {
+            Some((_, value)) if len > 0 => out.push_str(value),
+            _ => {
+                out.push('$');
+                out.push_str(&after[..len]);
+            }
+        }
+        rest = &after[len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The `kubectl` plugins on `path`, named the way kubectl resolves them.
+pub fn find_kubectl_plugins(path: &std::ffi::OsStr) -> Vec<String> {
+    let mut plugins = Vec::new();
+    for dir in std::env::split_paths(path) {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let file_name = entry.file_name();
+            let Some(name) = file_name.to_str() else {
+                continue;
+            };
+            let name = match cfg!(windows) {
+                true => name.strip_suffix(".exe").unwrap_or(name),
+                false => name,
+            };
+            let Some(plugin) = name.strip_prefix("kubectl-").filter(|p| !p.is_empty()) else {
+                continue;
+            };
+            if !is_executable(&entry.path()) {
+                continue;
+            }
+            let words: Vec<String> = plugin.split('-').map(|w| w.replace('_', "-")).collect();
+            let command = words.join(" ");
+            if !plugins.contains(&command) {
+                plugins.push(command);
+            }
+        }
+    }
+    plugins.sort();
+    plugins
+}
+
+fn is_executable(path: &std::path::Path) -> bool {
+    let Ok(meta) = std::fs::metadata(path) else {
+        return false;
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        meta.is_file() && meta.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        meta.is_file()
+    }
+}
+
+/// `rows` as CSV or, with a tab separator, as TSV.
+pub fn delimited(rows: &[Vec<String>], separator: char) -> String {
+    let field = |f: &String| match separator {
+        '\t' => f.replace(['\t', '\n', '\r'], " "),
+        _ if f.contains([separator, '"', '\n', '\r']) => format!("\"{}\"", f.replace('"', "\"\"")),
+        _ => f.clone(),
+    };
+    rows.iter()
+        .map(|row| {
+            let fields: Vec<String> = row.iter().map(field).collect();
+            format!("{}\n", fields.join(&separator.to_string()))
+        })
+        .collect()
+}
+
+/// The `kubectl` command line equivalent to an action, quoted for a shell.
+pub fn kubectl_line(context: &str, namespace: Option<&str>, args: &[String]) -> String {
+    let mut words = vec!["kubectl", "--context", context];
+    if let Some(ns) = namespace {
+        words.extend(["-n", ns]);
+    }
+    words.extend(args.iter().map(String::as_str));
+    let plain = |w: &str| {
+        !w.is_empty()
+            && w.chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c))
+    };
+    words
+        .into_iter()
+        .map(|w| match plain(w) {
+            true => w.into(),
+            false => shlex::try_quote(w).unwrap_or_else(|_| w.into()),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Percent-encodes everything but RFC 3986 unreserved characters.
+pub fn percent_encode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for b in text.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(b as char)
+            }
+            _ => out.push_str(&format!("%{b:02X}")),
+        }
+    }
+    out
+}
+
+/// The platform's command for opening `url` in the default browser.
+pub fn browser_command(url: &str) -> tokio::process::Command {
+    let mut cmd = if cfg!(target_os = "macos") {
+        tokio::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.args(["/C", "start", ""]);
+        cmd
+    } else {
+        tokio::process::Command::new("xdg-open")
+    };
+    cmd.arg(url)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    cmd
+}
+
+/// Opens `initial` in `$VISUAL`/`$EDITOR` and returns the saved contents.
+pub fn edit_in_editor(initial: &str, suffix: &str) -> std::io::Result<String> {
+    let path = std::env::temp_dir().join(format!(
+        "kr-edit-{}-{}{suffix}",
+        std::process::id(),
+        Timestamp::now().as_millisecond()
+    ));
+    let mut opts = std::fs::OpenOptions::new();
+    opts.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        opts.mode(0o600);
+    }
+    {
+        use std::io::Write;
+        let mut file = opts.open(&path)?;
+        file.write_all(initial.as_bytes())?;
+    }
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut parts = shlex::split(&editor).unwrap_or_else(|| vec![editor.clone()]);
+    if parts.is_empty() {
+        parts.push("vi".to_string());
+    }
+    let status = std::process::Command::new(&parts[0])
+        .args(&parts[1..])
+        .arg(&path)
+        .status();
+    let result = match status {
+        Ok(s) if s.success() => std::fs::read_to_string(&path),
+        Ok(s) => Err(std::io::Error::other(format!("editor exited with {s}"))),
+        Err(e) => Err(e),
+    };
+    let _ = std::fs::remove_file(&path);
+    result
+}
+
+/// Pipes `text` into `$PAGER` (falling back to `less`) and waits for it.
+pub fn page(text: &str) -> std::io::Result<()> {
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| "less".to_string());
+    let parts = shlex::split(&pager).unwrap_or_else(|| vec![pager.clone()]);
+    let Some((program, args)) = parts.split_first() else {
+        return Err(std::io::Error::other("PAGER is empty"));
+    };
+    let mut child = std::process::Command::new(program)
+        .args(args)
+        .stdin(std::process::Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        use std::io::Write;
+        // Quitting before the end closes the pipe; that isn't an error.
+        match stdin.write_all(text.as_bytes()) {
+            Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => return Err(e),
+            _ => {}
+        }
+    }
+    match child.wait()? {
+        s if s.success() => Ok(()),
+        s => Err(std::io::Error::other(format!("pager exited with {s}"))),
+    }
+}
+
+/// Hard-wraps `text` into rows of at most `width` characters.
+pub fn wrap_rows(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+    for line in text.split('\n') {
+        let line = line
+            .strip_suffix('\r')
+            .unwrap_or(line)
+            .replace('\t', "    ");
+        let chars: Vec<char> = line.chars().collect();
+        if chars.is_empty() {
+            rows.push(String::new());
+            continue;
+        }
+        rows.extend(chars.chunks(width).map(|c| c.iter().collect::<String>()));
+    }
+    rows
+}
+
+/// `hexdump -C` style dump: offset, 16 hex bytes, printable ASCII.
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for (i, chunk) in bytes.chunks(16).enumerate() {
+        let mut hex = String::with_capacity(49);
+        for (j, b) in chunk.iter().enumerate() {
+            if j == 8 {
+                hex.push(' ');
+            }
+            hex.push_str(&format!("{b:02x} "));
+        }
+        let ascii: String = chunk
+            .iter()
+            .map(|&b| {
+                if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        out.push_str(&format!("{:08x}  {hex:<49} |{ascii}|\n", i * 16));
+    }
+    out.push_str(&format!("{:08x}", bytes.len()));
+    out
+}
+
+/// OSC52 "set clipboard" escape for `text`; an empty `text` clears it.
+pub fn osc52_sequence(text: &str, in_tmux: bool) -> String {
+    use base64::prelude::*;
+    let seq = format!("\x1b]52;c;{}\x07", BASE64_STANDARD.encode(text));
+    if in_tmux {
+        format!("\x1bPtmux;\x1b{seq}\x1b\\")
+    } else {
+        seq
+    }
+}
+
+/// An object as a YAML manifest; `clean` drops what the API server assigns.
+pub fn manifest_yaml(
+    mut value: serde_json::Value,
+    clean: bool,
+) -> Result<String, serde_yaml::Error> {
+    if clean && let Some(obj) = value.as_object_mut() {
+        obj.remove("status");
+        if let Some(meta) = obj.get_mut("metadata").and_then(|m| m.as_object_mut()) {
+            for field in [
+                "uid",
+                "resourceVersion",
+                "generation",
+                "creationTimestamp",
+                "deletionTimestamp",
+                "deletionGracePeriodSeconds",
+                "managedFields",
+                "ownerReferences",
+                "selfLink",
+            ] {
+                meta.remove(field);
+            }
+            if let Some(annotations) = meta.get_mut("annotations").and_then(|a| a.as_object_mut()) {
+                annotations.remove("kubectl.kubernetes.io/last-applied-configuration");
+                annotations.remove("deployment.kubernetes.io/revision");
+                if annotations.is_empty() {
+                    meta.remove("annotations");
+                }
+            }
+        }
+    }
+    serde_yaml::to_string(&value)
+}
+
+/// Secret and ConfigMap keys: alphanumerics, `-`, `_` and `.`.
+pub fn is_valid_data_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= 253
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+pub fn parse_env_file(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut entries = Vec::new();
+    for (lineno, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!("line {}: expected KEY=value", lineno + 1));
+        };
+        let key = key.trim();
+        if !is_valid_data_key(key) {
+            return Err(format!("line {}: invalid key '{key}'", lineno + 1));
+        }
+        let value = value.trim();
+        let value = if let Some(quoted) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"'))
+        {
+            unescape_double_quoted(quoted)
+        } else {
+            value
+                .strip_prefix('\'')
+                .and_then(|v| v.strip_suffix('\''))
+                .unwrap_or(value)
+                .to_string()
+        };
+        entries.push((key.to_string(), value));
+    }
+    Ok(entries)
+}
+
+fn unescape_double_quoted(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Renders entries as `KEY=value` lines, quoting where needed.
+pub fn format_env_file(entries: &[(String, String)]) -> String {
+    let mut out = String::new();
+    for (key, value) in entries {
+        let plain = !value.is_empty()
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_./:@+,%".contains(c));
+        if plain {
+            out.push_str(&format!("{key}={value}\n"));
+        } else {
+            let escaped = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+                .replace('\t', "\\t");
+            out.push_str(&format!("{key}=\"{escaped}\"\n"));
+        }
+    }
+    out
+}
+
+/// Renders entries as `export KEY='value'` lines safe to paste into a POSIX shell.
+pub fn format_shell_exports(entries: &[(String, String)]) -> String {
+    entries
+        .iter()
+        .map(|(key, value)| {
+            let mut name: String = key
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+            if name.starts_with(|c: char| c.is_ascii_digit()) {
+                name.insert(0, '_');
+            }
+            format!("export {name}='{}'\n", value.replace('\'', "'\\''"))
+        })
+        .collect()
+}
+
+/// Writes `contents` to `path` readable only by the current user.
+pub fn write_private_file(path: &std::path::Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut opts = std::fs::OpenOptions::new();
+    opts.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        opts.mode(0o600);
+    }
+    let mut file = opts.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    std::io::Write::write_all(&mut file, contents)
+}
+
+/// URLs that reach the pods labelled `labels` through ingresses and services.
+pub fn workload_endpoints(
+    labels: &std::collections::BTreeMap<String, String>,
+    services: &[k8s_openapi::api::core::v1::Service],
+    ingresses: &[k8s_openapi::api::networking::v1::Ingress],
+) -> Vec<crate::models::Endpoint> {
+    use crate::models::Endpoint;
+    let selecting: Vec<_> = services
+        .iter()
+        .filter(|s| {
+            s.spec
+                .as_ref()
+                .and_then(|spec| spec.selector.as_ref())
+                .is_some_and(|sel| {
+                    !sel.is_empty() && sel.iter().all(|(k, v)| labels.get(k) == Some(v))
+                })
+        })
+        .collect();
+    let names: Vec<&str> = selecting
+        .iter()
+        .filter_map(|s| s.metadata.name.as_deref())
+        .collect();
+    let mut endpoints: Vec<Endpoint> = Vec::new();
+    let mut push = |source: String, url: String| {
+        if !endpoints.iter().any(|e| e.url == url) {
+            endpoints.push(Endpoint { source, url });
+        }
+    };
+
+    for ingress in ingresses {
+        let Some(spec) = &ingress.spec else {
+            continue;
+        };
+        let routes_to = |backend: &k8s_openapi::api::networking::v1::IngressBackend| {
+            backend
+                .service
+                .as_ref()
+                .is_some_and(|s| names.contains(&s.name.as_str()))
+        };
+        let by_default = spec.default_backend.as_ref().is_some_and(routes_to);
+        let tls_hosts: Vec<&str> = spec
+            .tls
+            .iter()
+            .flatten()
+            .flat_map(|t| t.hosts.iter().flatten())
+            .map(String::as_str)
+            .collect();
+        let source = format!(
+            "ingress {}",
+            ingress.metadata.name.as_deref().unwrap_or_default()
+        );
+        for rule in spec.rules.iter().flatten() {
+            // A wildcard host names no single site to open.
+            let Some(host) = rule.host.as_deref().filter(|h| !h.starts_with('*')) else {
+                continue;
+            };
+            let paths = rule.http.iter().flat_map(|h| &h.paths);
+            let path = match paths.into_iter().find(|p| routes_to(&p.backend)) {
+                Some(p) => p
+                    .path
+                    .as_deref()
+                    .filter(|p| is_plain_path(p))
+                    .unwrap_or("/"),
+                None if by_default => "/",
+                None => continue,
+            };
+            let scheme = match tls_hosts.iter().any(|t| host_matches(t, host)) {
+                true => "https",
+                false => "http",
+            };
+            push(source.clone(), format!("{scheme}://{host}{path}"));
+        }
+    }
+
+    for service in selecting {
+        let name = service.metadata.name.as_deref().unwrap_or_default();
+        let spec = service.spec.as_ref();
+        let balancers = service
+            .status
+            .iter()
+            .filter_map(|s| s.load_balancer.as_ref())
+            .flat_map(|lb| lb.ingress.iter().flatten())
+            .filter_map(|i| i.hostname.clone().or_else(|| i.ip.clone()))
+            .map(|host| (host, "load balancer"));
+        let external = spec
+            .and_then(|s| s.external_ips.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|host| (host, "external IP"));
+        for (host, how) in balancers.chain(external) {
+            let host = match host.contains(':') {
+                true => format!("[{host}]"),
+                false => host,
+            };
+            for port in spec.and_then(|s| s.ports.as_ref()).into_iter().flatten() {
+                let tls = port.port == 443
+                    || port.name.as_deref().is_some_and(|n| n.contains("https"))
+                    || port.app_protocol.as_deref() == Some("https");
+                let url = match (tls, port.port) {
+                    (true, 443) => format!("https://{host}/"),
+                    (false, 80) => format!("http://{host}/"),
+                    (true, p) => format!("https://{host}:{p}/"),
+                    (false, p) => format!("http://{host}:{p}/"),
+                };
+                push(format!("service {name} ({how})"), url);
+            }
+        }
+    }
+    endpoints
+}
+
+/// Whether an ingress path can go into a URL as is.
+fn is_plain_path(path: &str) -> bool {
+    path.starts_with('/')
+        && path
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/-_.~".contains(c))
+}
+
+/// Whether a TLS host entry such as `*.example.com` covers `host`.
+fn host_matches(pattern: &str, host: &str) -> bool {
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host
+            .split_once('.')
+            .is_some_and(|(_, rest)| rest.eq_ignore_ascii_case(domain)),
+        None => pattern.eq_ignore_ascii_case(host),
+    }
+}
+
+/// Describes every way `spec` pulls in the secret `name`.
+pub fn secret_refs_in_pod_spec(
+    spec: &k8s_openapi::api::core::v1::PodSpec,
+    name: &str,
+) -> Vec<String> {
+    let mut refs = Vec::new();
+    let containers = spec
+        .init_containers
+        .iter()
+        .flatten()
+        .chain(spec.containers.iter());
+    for c in containers {
+        for env in c.env.iter().flatten() {
+            let key_ref = env
+                .value_from
+                .as_ref()
+                .and_then(|v| v.secret_key_ref.as_ref());
+            if let Some(sel) = key_ref
+                && sel.name == name
+            {
+                refs.push(format!("env {} ({})", env.name, c.name));
+            }
+        }
+        for from in c.env_from.iter().flatten() {
+            if from.secret_ref.as_ref().is_some_and(|s| s.name == name) {
+                refs.push(format!("envFrom ({})", c.name));
+            }
+        }
+    }
+    for vol in spec.volumes.iter().flatten() {
+        let direct = vol.secret.as_ref().and_then(|s| s.secret_name.as_deref()) == Some(name);
+        let projected = vol
+            .projected
+            .as_ref()
+            .and_then(|p| p.sources.as_ref())
+            .is_some_and(|sources| {
+                sources
+                    .iter()
+                    .any(|s| s.secret.as_ref().is_some_and(|s| s.name == name))
+            });
+        if direct || projected {
+            refs.push(format!("volume {}", vol.name));
+        }
+    }
+    if spec
+        .image_pull_secrets
+        .iter()
+        .flatten()
+        .any(|r| r.name == name)
+    {
+        refs.push("imagePullSecrets".to_string());
+    }
+    refs
+}
+
+/// Summarises the `Ready`/`Synced` condition of an ExternalSecret or SealedSecret.
+pub fn owner_sync_status(obj: &serde_json::Value) -> String {
+    let conditions = obj
+        .pointer("/status/conditions")
+        .and_then(|c| c.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let Some(cond) = conditions.iter().find(|c| {
+        matches!(
+            c.get("type").and_then(|t| t.as_str()),
+            Some("Ready" | "Synced")
+        )
+    }) else {
+        return "no status reported".to_string();
+    };
+    let field = |k: &str| cond.get(k).and_then(|v| v.as_str()).unwrap_or_default();
+    let mut out = format!("{}: {}", field("type"), field("status"));
+    if !field("reason").is_empty() {
+        out.push_str(&format!(" ({})", field("reason")));
+    }
+    if field("status") != "True" && !field("message").is_empty() {
+        out.push_str(&format!(" — {}", field("message")));
+    }
+    out
+}
+
+/// Parses a Kubernetes quantity (`250m`, `128Mi`, `2G`) into base units.
+pub fn parse_quantity(s: &str) -> Option<f64> {
+    const SUFFIXES: [(&str, f64); 13] = [
+        ("Ki", 1024.0),
+        ("Mi", 1_048_576.0),
+        ("Gi", 1_073_741_824.0),
+        ("Ti", 1_099_511_627_776.0),
+        ("Pi", 1_125_899_906_842_624.0),
+        ("Ei", 1_152_921_504_606_846_976.0),
+        ("m", 1e-3),
+        ("k", 1e3),
+        ("M", 1e6),
+        ("G", 1e9),
+        ("T", 1e12),
+        ("P", 1e15),
+        ("E", 1e18),
+    ];
+    let s = s.trim();
+    SUFFIXES
+        .iter()
+        .find_map(|(suffix, mult)| {
+            s.strip_suffix(suffix)
+                .and_then(|n| n.parse::<f64>().ok())
+                .map(|n| n * mult)
+        })
+        .or_else(|| s.parse::<f64>().ok())
+}
+
+pub fn format_cpu_millis(millis: u64) -> String {
+    if millis >= 1000 && millis.is_multiple_of(100) {
+        format!("{}", millis as f64 / 1000.0)
+    } else {
+        format!("{millis}m")
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "Ki", "Mi", "Gi", "Ti"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes}B")
+    } else {
+        format!("{value:.1}{}", UNITS[unit])
+    }
+}
+
+/// Builds the namespace summary from listed pods, deployments and quotas.
+pub fn summarize_namespace(
+    pods: &[k8s_openapi::api::core::v1::Pod],
+    deployments: &[k8s_openapi::api::apps::v1::Deployment],
+    quotas: &[k8s_openapi::api::core::v1::ResourceQuota],
+) -> crate::models::NamespaceSummary {
+    use crate::models::{NamespaceSummary, QuotaUsage};
+    let mut by_status: std::collections::BTreeMap<String, usize> = Default::default();
+    let mut cpu = 0.0;
+    let mut memory = 0.0;
+    for pod in pods {
+        let phase = crate::app::App::pod_phase(pod);
+        *by_status.entry(phase.to_string()).or_default() += 1;
+        if matches!(phase, "Succeeded" | "Failed") {
+            continue;
+        }
+        let requests = pod
+            .spec
+            .iter()
+            .flat_map(|s| &s.containers)
+            .filter_map(|c| c.resources.as_ref()?.requests.as_ref());
+        for req in requests {
+            cpu += req
+                .get("cpu")
+                .and_then(|q| parse_quantity(&q.0))
+                .unwrap_or(0.0);
+            memory += req
+                .get("memory")
+                .and_then(|q| parse_quantity(&q.0))
+                .unwrap_or(0.0);
+        }
+    }
+
+    let deployments_ready = deployments
+        .iter()
+        .filter(|d| {
+            let desired = d.spec.as_ref().and_then(|s| s.replicas).unwrap_or(1);
+            let ready = d
+                .status
+                .as_ref()
+                .and_then(|s| s.ready_replicas)
+                .unwrap_or(0);
+            ready >= desired
+        })
+        .count();
+
+    let mut quota_rows = Vec::new();
+    for quota in quotas {
+        let name = quota.metadata.name.clone().unwrap_or_default();
+        let Some(status) = &quota.status else {
+            continue;
+        };
+        let used = status.used.clone().unwrap_or_default();
+        for (resource, hard) in status.hard.iter().flatten() {
+            let used = used.get(resource).map(|q| q.0.clone()).unwrap_or_default();
+            let ratio = match (parse_quantity(&used), parse_quantity(&hard.0)) {
+                (Some(u), Some(h)) if h > 0.0 => Some(u / h),
+                _ => None,
+            };
+            quota_rows.push(QuotaUsage {
+                quota: name.clone(),
+                resource: resource.clone(),
+                used,
+                hard: hard.0.clone(),
+                ratio,
+            });
+        }
+    }
+
+    NamespaceSummary {
+        pods_by_status: by_status.into_iter().collect(),
+        deployments: deployments.len(),
+        deployments_ready,
+        cpu_requests_millis: (cpu * 1000.0).round() as u64,
+        memory_requests_bytes: memory.round() as u64,
+        quotas: quota_rows,
+    }
+}
+
+/// Line diff of `old` to `new` with `context` unchanged lines around each change.
+pub fn diff_lines(old: &str, new: &str, context: usize) -> Vec<String> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    // Only the middle between a common prefix and suffix needs the LCS table.
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (a, b) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
             } else {
-                format!("{secs}s")
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    // (tag, line): ' ' unchanged, '-' removed, '+' added.
+    let mut ops: Vec<(char, &str)> = old[..prefix].iter().map(|l| (' ', *l)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            ops.push((' ', a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', a[i]));
+            i += 1;
+        } else {
+            ops.push(('+', b[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[old.len() - suffix..].iter().map(|l| (' ', *l)));
+
+    let changed: Vec<usize> = (0..ops.len()).filter(|&k| ops[k].0 != ' ').collect();
+    let mut out = Vec::new();
+    let mut shown_to = 0;
+    for &k in &changed {
+        let start = k.saturating_sub(context).max(shown_to);
+        if start > shown_to {
+            out.push("…".to_string());
+        }
+        let end = (k + context + 1).min(ops.len());
+        for (tag, line) in &ops[start..end] {
+            out.push(format!("{tag} {line}"));
+        }
+        shown_to = shown_to.max(end);
+    }
+    out
+}
+
+/// Compares two secrets' `data` key by key on the raw bytes.
+pub fn diff_secret_data(
+    left: &BTreeMap<String, ByteString>,
+    right: &BTreeMap<String, ByteString>,
+) -> Vec<crate::models::SecretDiffRow> {
+    use crate::models::{DiffStatus, SecretDiffRow};
+    let text = |v: &ByteString| match std::str::from_utf8(&v.0) {
+        Ok(text) => text.to_string(),
+        Err(_) => format!("<binary, {} bytes>", v.0.len()),
+    };
+    let keys: std::collections::BTreeSet<&String> = left.keys().chain(right.keys()).collect();
+    keys.into_iter()
+        .map(|key| {
+            let l = left.get(key);
+            let r = right.get(key);
+            let status = match (l, r) {
+                (Some(a), Some(b)) if a == b => DiffStatus::Same,
+                (Some(_), Some(_)) => DiffStatus::Changed,
+                (None, Some(_)) => DiffStatus::Added,
+                _ => DiffStatus::Removed,
+            };
+            SecretDiffRow {
+                key: key.clone(),
+                status,
+                left: l.map(text),
+                right: r.map(text),
+            }
+        })
+        .collect()
+}
+
+/// Plain-text details for the detail pane; secret values are never included.
+pub fn resource_details(item: &crate::models::KubeResource) -> Vec<String> {
+    use crate::models::KubeResource;
+    let meta = match item {
+        KubeResource::Pod(p) => &p.metadata,
+        KubeResource::Deployment(d) => &d.metadata,
+        KubeResource::Secret(s) => &s.metadata,
+        KubeResource::ConfigMap(c) => &c.metadata,
+    };
+    let mut lines = vec![
+        format!("Name:       {}", item.name()),
+        format!(
+            "Namespace:  {}",
+            meta.namespace.as_deref().unwrap_or_default()
+        ),
+        format!(
+            "Age:        {}",
+            get_resource_age(meta.creation_timestamp.as_ref())
+        ),
+    ];
+
+    match item {
+        KubeResource::Pod(p) => {
+            let spec = p.spec.as_ref();
+            let status = p.status.as_ref();
+            lines.push(format!("Status:     {}", crate::app::App::pod_phase(p)));
+            lines.push(format!(
+                "Node:       {}",
+                spec.and_then(|s| s.node_name.as_deref()).unwrap_or("-")
+            ));
+            lines.push(format!(
+                "IP:         {}",
+                status.and_then(|s| s.pod_ip.as_deref()).unwrap_or("-")
+            ));
+            lines.push(String::new());
+            lines.push("Containers:".to_string());
+            for c in spec.map(|s| s.containers.as_slice()).unwrap_or_default() {
+                let cs = status
+                    .and_then(|s| s.container_statuses.as_ref())
+                    .and_then(|all| all.iter().find(|cs| cs.name == c.name));
+                let state =
+                    cs.and_then(|cs| cs.state.as_ref())
+                        .map_or("Unknown".to_string(), |st| {
+                            if let Some(w) = &st.waiting {
+                                format!("Waiting ({})", w.reason.as_deref().unwrap_or("-"))
+                            } else if let Some(t) = &st.terminated {
+                                format!("Terminated ({})", t.reason.as_deref().unwrap_or("-"))
+                            } else {
+                                "Running".to_string()
+                            }
+                        });
+                lines.push(format!("  {}", c.name));
+                lines.push(format!(
+                    "    Image:    {}",
+                    c.image.as_deref().unwrap_or("-")
+                ));
+                lines.push(format!("    State:    {state}"));
+                lines.push(format!(
+                    "    Ready:    {}  Restarts: {}",
+                    cs.is_some_and(|cs| cs.ready),
+                    cs.map_or(0, |cs| cs.restart_count)
+                ));
+            }
+            if let Some(conditions) = status.and_then(|s| s.conditions.as_ref()) {
+                lines.push(String::new());
+                lines.push("Conditions:".to_string());
+                for c in conditions {
+                    lines.push(format!("  {:<16} {}", c.type_, c.status));
+                }
             }
         }
-        None => "?".to_string(),
+        KubeResource::Deployment(d) => {
+            let status = d.status.as_ref();
+            let spec = d.spec.as_ref();
+            lines.push(format!(
+                "Replicas:   {} desired | {} updated | {} ready | {} available",
+                spec.and_then(|s| s.replicas).unwrap_or(1),
+                status.and_then(|s| s.updated_replicas).unwrap_or(0),
+                status.and_then(|s| s.ready_replicas).unwrap_or(0),
+                status.and_then(|s| s.available_replicas).unwrap_or(0),
+            ));
+            lines.push(format!(
+                "Strategy:   {}",
+                spec.and_then(|s| s.strategy.as_ref())
+                    .and_then(|s| s.type_.as_deref())
+                    .unwrap_or("RollingUpdate")
+            ));
+            lines.push(String::new());
+            lines.push("Containers:".to_string());
+            for c in spec
+                .and_then(|s| s.template.spec.as_ref())
+                .map(|s| s.containers.as_slice())
+                .unwrap_or_default()
+            {
+                lines.push(format!(
+                    "  {}: {}",
+                    c.name,
+                    c.image.as_deref().unwrap_or("-")
+                ));
+            }
+            if let Some(conditions) = status.and_then(|s| s.conditions.as_ref()) {
+                lines.push(String::new());
+                lines.push("Conditions:".to_string());
+                for c in conditions {
+                    lines.push(format!(
+                        "  {:<16} {} {}",
+                        c.type_,
+                        c.status,
+                        c.reason.as_deref().unwrap_or_default()
+                    ));
+                }
+            }
+        }
+        KubeResource::Secret(s) => {
+            lines.push(format!(
+                "Type:       {}",
+                s.type_.as_deref().unwrap_or("Opaque")
+            ));
+            lines.push(String::new());
+            lines.push("Data:".to_string());
+            for (key, value) in s.data.iter().flatten() {
+                lines.push(format!("  {key}: {} bytes", value.0.len()));
+            }
+        }
+        KubeResource::ConfigMap(c) => {
+            lines.push(String::new());
+            lines.push("Data:".to_string());
+            for (key, value) in c.data.iter().flatten() {
+                lines.push(format!("  {key}: {} bytes", value.len()));
+            }
+            for (key, value) in c.binary_data.iter().flatten() {
+                lines.push(format!("  {key}: {} bytes (binary)", value.0.len()));
+            }
+        }
+    }
+
+    if let Some(labels) = meta.labels.as_ref().filter(|l| !l.is_empty()) {
+        lines.push(String::new());
+        lines.push("Labels:".to_string());
+        for (k, v) in labels {
+            lines.push(format!("  {k}={v}"));
+        }
+    }
+    lines
+}
+
+/// Full text of an error for the detail pager.
+pub fn error_detail(message: &str) -> String {
+    let field = |pattern: &str| {
+        regex::Regex::new(pattern)
+            .ok()?
+            .captures(message)
+            .map(|c| c[1].to_string())
+    };
+    let code = field(r"\bcode: (\d+)");
+    let reason = field(r#"\breason: "([^"]*)""#);
+    let mut lines = Vec::new();
+    if let (Some(code), Some(reason)) = (code, reason) {
+        lines.push(format!("API status: {code} {reason}"));
+        lines.push(String::new());
+    }
+    lines.push(message.to_string());
+    lines.join("\n")
+}
+
+/// One line per resource of a bulk action, failures first.
+pub fn format_bulk_report(results: &[(String, Result<(), String>)]) -> String {
+    let mut lines: Vec<String> = results
+        .iter()
+        .filter_map(|(name, r)| r.as_ref().err().map(|e| format!("FAILED  {name}: {e}")))
+        .collect();
+    lines.extend(
+        results
+            .iter()
+            .filter(|(_, r)| r.is_ok())
+            .map(|(name, _)| format!("OK      {name}")),
+    );
+    lines.join("\n")
+}
+
+/// Matches names against a filter query, case-insensitively.
+pub struct NameMatcher {
+    mode: crate::models::FilterMode,
+    query: Vec<char>,
+    /// Compiled query in regex mode; `None` if it does not parse.
+    regex: Option<regex::Regex>,
+}
+
+impl NameMatcher {
+    pub fn new(mode: crate::models::FilterMode, query: &str) -> Self {
+        let regex = (mode == crate::models::FilterMode::Regex)
+            .then(|| {
+                regex::RegexBuilder::new(query)
+                    .case_insensitive(true)
+                    .build()
+                    .ok()
+            })
+            .flatten();
+        Self {
+            mode,
+            query: query.to_lowercase().chars().collect(),
+            regex,
+        }
+    }
+
+    /// False for a regex query that does not parse (it matches nothing).
+    pub fn is_valid(&self) -> bool {
+        self.mode != crate::models::FilterMode::Regex || self.regex.is_some()
+    }
+
+    /// Whether results should be reordered by score.
+    pub fn ranks(&self) -> bool {
+        self.mode == crate::models::FilterMode::Fuzzy && !self.query.is_empty()
+    }
+
+    pub fn score(&self, name: &str) -> Option<i64> {
+        if self.query.is_empty() {
+            return Some(0);
+        }
+        if let Some(re) = &self.regex {
+            return re.is_match(name).then_some(0);
+        }
+        let name: Vec<char> = name.to_lowercase().chars().collect();
+        match self.mode {
+            crate::models::FilterMode::Substring => name
+                .windows(self.query.len())
+                .any(|w| w == self.query.as_slice())
+                .then_some(0),
+            crate::models::FilterMode::Fuzzy => fuzzy_score(&self.query, &name),
+            crate::models::FilterMode::Regex => None,
+        }
+    }
+}
+
+const FUZZY_MATCH: i64 = 16;
+const FUZZY_BOUNDARY: i64 = 8;
+const FUZZY_CONSECUTIVE: i64 = 8;
+
+/// fzf-style score of `query` as a subsequence of `name`, if it is one.
+fn fuzzy_score(query: &[char], name: &[char]) -> Option<i64> {
+    if query.len() > name.len() {
+        return None;
+    }
+    let bonus = |j: usize| {
+        let boundary = j == 0 || matches!(name[j - 1], '-' | '_' | '.' | '/' | ':');
+        FUZZY_MATCH + if boundary { FUZZY_BOUNDARY } else { 0 }
+    };
+    // prev[j]: best score with the previous query char matched at name[j].
+    let mut prev: Vec<Option<i64>> = name
+        .iter()
+        .enumerate()
+        .map(|(j, &c)| (c == query[0]).then(|| bonus(j)))
+        .collect();
+    for &q in &query[1..] {
+        let mut cur = vec![None; name.len()];
+        // Best `prev[k] + k` over k < j - 1, so a gap costs `j - k - 1`.
+        let mut best_gap: Option<i64> = None;
+        for j in 1..name.len() {
+            if j >= 2
+                && let Some(p) = prev[j - 2]
+            {
+                let v = p + (j as i64 - 2);
+                best_gap = Some(best_gap.map_or(v, |b| b.max(v)));
+            }
+            if name[j] != q {
+                continue;
+            }
+            let adjacent = prev[j - 1].map(|p| p + FUZZY_CONSECUTIVE);
+            let gapped = best_gap.map(|b| b - j as i64 + 1);
+            cur[j] = adjacent.max(gapped).map(|s| s + bonus(j));
+        }
+        prev = cur;
     }
+    prev.into_iter().flatten().max()
 }
 
 #[cfg(test)]
@@ -83,4 +1329,550 @@ mod tests {
         let t = time_ago(SignedDuration::from_hours(24));
         assert_eq!(get_resource_age(Some(&t)), "1d");
     }
+
+    #[test]
+    fn parse_env_file_handles_quotes_comments_and_export() {
+        let text = "# comment\nA=1\nexport B=\"two words\"\n\nC='x=y'\n";
+        let entries = parse_env_file(text).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                ("A".to_string(), "1".to_string()),
+                ("B".to_string(), "two words".to_string()),
+                ("C".to_string(), "x=y".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_env_file_rejects_missing_equals() {
+        let err = parse_env_file("A=1\nBROKEN\n").unwrap_err();
+        assert!(err.contains("line 2"));
+    }
+
+    #[test]
+    fn data_key_validation() {
+        assert!(is_valid_data_key("tls.crt"));
+        assert!(is_valid_data_key("DB_PASSWORD"));
+        assert!(!is_valid_data_key("bad key"));
+        assert!(!is_valid_data_key(""));
+    }
+
+    #[test]
+    fn format_env_file_round_trips() {
+        let entries = vec![
+            ("PLAIN".to_string(), "abc-123".to_string()),
+            ("SPACED".to_string(), "two words".to_string()),
+            ("MULTI".to_string(), "line1\nline2 \"q\" \\".to_string()),
+            ("EMPTY".to_string(), String::new()),
+        ];
+        let text = format_env_file(&entries);
+        assert!(text.starts_with("PLAIN=abc-123\n"));
+        assert_eq!(parse_env_file(&text).unwrap(), entries);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_private_file_sets_0600() {
+        use std::os::unix::fs::PermissionsExt;
+        let path = std::env::temp_dir().join(format!("kr-private-{}", std::process::id()));
+        write_private_file(&path, b"x").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn manifest_yaml_strips_server_fields_when_clean() {
+        let value = serde_json::json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": {
+                "name": "app",
+                "uid": "1234",
+                "resourceVersion": "42",
+                "annotations": {"kubectl.kubernetes.io/last-applied-configuration": "{}"},
+                "labels": {"app": "web"},
+            },
+            "data": {"k": "v"},
+            "status": {},
+        });
+        let clean = manifest_yaml(value.clone(), true).unwrap();
+        assert_eq!(
+            clean,
+            "apiVersion: v1\ndata:\n  k: v\nkind: ConfigMap\nmetadata:\n  labels:\n    app: web\n  name: app\n"
+        );
+        let full = manifest_yaml(value, false).unwrap();
+        assert!(full.contains("resourceVersion: '42'") && full.contains("status: {}"));
+    }
+
+    #[test]
+    fn expand_placeholders_takes_the_longest_name() {
+        let vars = [("NAME", "web"), ("NAMESPACE", "prod")];
+        assert_eq!(
+            expand_placeholders("$NAMESPACE/$NAME:$HOME $ $name", &vars),
+            "prod/web:$HOME $ $name"
+        );
+    }
+
+    #[test]
+    fn diff_lines_shows_changes_with_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh";
+        let new = "a\nb\nC\nd\ne\nf\ng\nh\ni";
+        assert_eq!(
+            diff_lines(old, new, 1),
+            ["…", "  b", "- c", "+ C", "  d", "…", "  h", "+ i"]
+        );
+        assert!(diff_lines(old, old, 3).is_empty());
+    }
+
+    #[test]
+    fn diff_secret_data_classifies_keys() {
+        use crate::models::DiffStatus;
+        let pair = |k: &str, v: &str| (k.to_string(), ByteString(v.as_bytes().to_vec()));
+        let left = BTreeMap::from([pair("same", "1"), pair("changed", "a"), pair("gone", "x")]);
+        let right = BTreeMap::from([pair("same", "1"), pair("changed", "b"), pair("new", "y")]);
+        let statuses: Vec<(String, DiffStatus)> = diff_secret_data(&left, &right)
+            .into_iter()
+            .map(|r| (r.key, r.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("changed".to_string(), DiffStatus::Changed),
+                ("gone".to_string(), DiffStatus::Removed),
+                ("new".to_string(), DiffStatus::Added),
+                ("same".to_string(), DiffStatus::Same),
+            ]
+        );
+    }
+
+    #[test]
+    fn format_shell_exports_quotes_values() {
+        let entries = vec![
+            ("A".to_string(), "plain".to_string()),
+            ("B".to_string(), "it's $HOME".to_string()),
+            ("tls.crt".to_string(), "x".to_string()),
+        ];
+        assert_eq!(
+            format_shell_exports(&entries),
+            "export A='plain'\nexport B='it'\\''s $HOME'\nexport tls_crt='x'\n"
+        );
+    }
+
+    #[test]
+    fn wrap_rows_splits_long_lines_and_keeps_blank_ones() {
+        assert_eq!(
+            wrap_rows("abcdef\n\n\tx", 4),
+            vec!["abcd", "ef", "", "    ", "x"]
+        );
+        assert_eq!(wrap_rows("", 10), vec![""]);
+    }
+
+    #[test]
+    fn secret_refs_in_pod_spec_finds_env_volumes_and_pull_secrets() {
+        let spec: k8s_openapi::api::core::v1::PodSpec = serde_json::from_value(serde_json::json!({
+            "initContainers": [{
+                "name": "init",
+                "envFrom": [{ "secretRef": { "name": "db" } }]
+            }],
+            "containers": [{
+                "name": "app",
+                "env": [
+                    { "name": "PASSWORD", "valueFrom": { "secretKeyRef": { "name": "db", "key": "pw" } } },
+                    { "name": "OTHER", "valueFrom": { "secretKeyRef": { "name": "other", "key": "x" } } },
+                    { "name": "PLAIN", "value": "1" }
+                ]
+            }],
+            "volumes": [
+                { "name": "creds", "secret": { "secretName": "db" } },
+                { "name": "bundle", "projected": { "sources": [{ "secret": { "name": "db" } }] } },
+                { "name": "unrelated", "secret": { "secretName": "other" } }
+            ],
+            "imagePullSecrets": [{ "name": "db" }]
+        }))
+        .unwrap();
+        assert_eq!(
+            secret_refs_in_pod_spec(&spec, "db"),
+            vec![
+                "envFrom (init)",
+                "env PASSWORD (app)",
+                "volume creds",
+                "volume bundle",
+                "imagePullSecrets",
+            ]
+        );
+        assert!(secret_refs_in_pod_spec(&spec, "missing").is_empty());
+    }
+
+    #[test]
+    fn hex_dump_matches_hexdump_c_layout() {
+        let bytes: Vec<u8> = (0x41..0x41 + 18).chain([0x00, 0xff]).collect();
+        assert_eq!(
+            hex_dump(&bytes),
+            "00000000  41 42 43 44 45 46 47 48  49 4a 4b 4c 4d 4e 4f 50  |ABCDEFGHIJKLMNOP|\n\
+             00000010  51 52 00 ff                                       |QR..|\n\
+             00000014"
+        );
+        assert_eq!(hex_dump(&[]), "00000000");
+    }
+
+    #[test]
+    fn osc52_sequence_encodes_and_wraps_for_tmux() {
+        assert_eq!(osc52_sequence("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            osc52_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+        assert_eq!(osc52_sequence("", false), "\x1b]52;c;\x07");
+    }
+
+    #[test]
+    fn owner_sync_status_reads_ready_or_synced_condition() {
+        let ready = serde_json::json!({ "status": { "conditions": [
+            { "type": "Ready", "status": "True", "reason": "SecretSynced", "message": "ok" }
+        ]}});
+        assert_eq!(owner_sync_status(&ready), "Ready: True (SecretSynced)");
+        let failing = serde_json::json!({ "status": { "conditions": [
+            { "type": "Synced", "status": "False", "message": "no key could decrypt" }
+        ]}});
+        assert_eq!(
+            owner_sync_status(&failing),
+            "Synced: False — no key could decrypt"
+        );
+        assert_eq!(
+            owner_sync_status(&serde_json::json!({})),
+            "no status reported"
+        );
+    }
+
+    #[test]
+    fn parse_quantity_suffixes() {
+        assert_eq!(parse_quantity("250m"), Some(0.25));
+        assert_eq!(parse_quantity("2"), Some(2.0));
+        assert_eq!(parse_quantity("1.5"), Some(1.5));
+        assert_eq!(parse_quantity("128Mi"), Some(128.0 * 1024.0 * 1024.0));
+        assert_eq!(parse_quantity("1G"), Some(1e9));
+        assert_eq!(parse_quantity("1e3"), Some(1000.0));
+        assert_eq!(parse_quantity("2Ei"), Some(2.0 * 1024f64.powi(6)));
+        assert_eq!(parse_quantity("10x"), None);
+    }
+
+    #[test]
+    fn format_quantities() {
+        assert_eq!(format_cpu_millis(250), "250m");
+        assert_eq!(format_cpu_millis(1500), "1.5");
+        assert_eq!(format_cpu_millis(1250), "1250m");
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(3 * 1024 * 1024 / 2), "1.5Mi");
+    }
+
+    #[test]
+    fn summarize_namespace_counts_and_requests() {
+        use k8s_openapi::api::apps::v1::Deployment;
+        use k8s_openapi::api::core::v1::{Pod, ResourceQuota};
+        let pod = |phase: &str, cpu: &str, mem: &str| -> Pod {
+            serde_json::from_value(serde_json::json!({
+                "spec": {"containers": [{"name": "c", "resources": {"requests": {"cpu": cpu, "memory": mem}}}]},
+                "status": {"phase": phase}
+            }))
+            .unwrap()
+        };
+        let pods = vec![
+            pod("Running", "250m", "128Mi"),
+            pod("Running", "1", "1Gi"),
+            pod("Succeeded", "4", "4Gi"),
+        ];
+        let deployments: Vec<Deployment> = vec![
+            serde_json::from_value(serde_json::json!({"spec": {"replicas": 2, "selector": {}, "template": {}}, "status": {"readyReplicas": 2}})).unwrap(),
+            serde_json::from_value(serde_json::json!({"spec": {"replicas": 3, "selector": {}, "template": {}}, "status": {"readyReplicas": 1}})).unwrap(),
+        ];
+        let quotas: Vec<ResourceQuota> = vec![
+            serde_json::from_value(serde_json::json!({
+                "metadata": {"name": "compute"},
+                "status": {"hard": {"requests.cpu": "4"}, "used": {"requests.cpu": "1250m"}}
+            }))
+            .unwrap(),
+        ];
+
+        let summary = summarize_namespace(&pods, &deployments, &quotas);
+        assert_eq!(
+            summary.pods_by_status,
+            vec![("Running".to_string(), 2), ("Succeeded".to_string(), 1)]
+        );
+        assert_eq!(summary.deployments, 2);
+        assert_eq!(summary.deployments_ready, 1);
+        assert_eq!(summary.cpu_requests_millis, 1250);
+        assert_eq!(summary.memory_requests_bytes, 1152 * 1024 * 1024);
+        assert_eq!(summary.quotas.len(), 1);
+        assert_eq!(summary.quotas[0].resource, "requests.cpu");
+        assert_eq!(summary.quotas[0].ratio, Some(0.3125));
+    }
+
+    #[test]
+    fn resource_details_show_secret_sizes_not_values() {
+        use crate::models::KubeResource;
+        use k8s_openapi::ByteString;
+        use k8s_openapi::api::core::v1::Secret;
+        let secret = Secret {
+            metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
+                name: Some("db".to_string()),
+                namespace: Some("prod".to_string()),
+                ..Default::default()
+            },
+            data: Some(
+                [("password".to_string(), ByteString(b"hunter2".to_vec()))]
+                    .into_iter()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        let lines = resource_details(&KubeResource::Secret(std::sync::Arc::new(secret)));
+        assert!(lines.iter().any(|l| l == "Namespace:  prod"));
+        assert!(lines.iter().any(|l| l == "  password: 7 bytes"));
+        assert!(!lines.iter().any(|l| l.contains("hunter2")));
+    }
+
+    fn fuzzy(query: &str, name: &str) -> Option<i64> {
+        NameMatcher::new(crate::models::FilterMode::Fuzzy, query).score(name)
+    }
+
+    #[test]
+    fn fuzzy_matches_subsequences_only() {
+        assert!(fuzzy("apsv", "api-server").is_some());
+        assert!(fuzzy("API", "api-server").is_some());
+        assert!(fuzzy("vsa", "api-server").is_none());
+        assert!(fuzzy("", "anything").is_some());
+    }
+
+    #[test]
+    fn fuzzy_prefers_consecutive_and_word_starts() {
+        let contiguous = fuzzy("api", "api-server").unwrap();
+        let scattered = fuzzy("api", "a-pod-init").unwrap();
+        assert!(contiguous > scattered);
+
+        let boundary = fuzzy("ws", "web-server").unwrap();
+        assert!(boundary > fuzzy("ws", "wxxxs").unwrap());
+    }
+
+    #[test]
+    fn substring_matcher_ignores_case() {
+        let m = NameMatcher::new(crate::models::FilterMode::Substring, "Serv");
+        assert_eq!(m.score("api-server"), Some(0));
+        assert_eq!(m.score("apsv"), None);
+        assert!(!m.ranks());
+    }
+
+    #[test]
+    fn regex_matcher_handles_alternation_and_bad_patterns() {
+        let m = NameMatcher::new(crate::models::FilterMode::Regex, "^API-(foo|bar)");
+        assert!(m.is_valid());
+        assert_eq!(m.score("api-foo-7d9f"), Some(0));
+        assert_eq!(m.score("api-baz"), None);
+        assert_eq!(m.score("x-api-bar"), None);
+
+        let bad = NameMatcher::new(crate::models::FilterMode::Regex, "api-(");
+        assert!(!bad.is_valid());
+        assert_eq!(bad.score("api-"), None);
+    }
+
+    #[test]
+    fn bulk_report_lists_failures_first() {
+        let results = vec![
+            ("a".to_string(), Ok(())),
+            ("b".to_string(), Err("forbidden".to_string())),
+            ("c".to_string(), Ok(())),
+        ];
+        assert_eq!(
+            format_bulk_report(&results),
+            "FAILED  b: forbidden\nOK      a\nOK      c"
+        );
+    }
+
+    #[test]
+    fn error_detail_extracts_api_status() {
+        let msg = r#"Delete 'web' failed: ApiError: pods "web" is forbidden: denied (Status { status: Some(Failure), code: 403, message: "denied", metadata: None, reason: "Forbidden", details: None })"#;
+        let detail = error_detail(msg);
+        assert!(detail.starts_with("API status: 403 Forbidden\n\n"));
+        assert!(detail.ends_with(msg));
+        assert_eq!(
+            error_detail("Describe failed: timeout"),
+            "Describe failed: timeout"
+        );
+    }
+
+    #[test]
+    fn json_path_parses_keys_indexes_and_wildcards() {
+        assert_eq!(
+            parse_json_path("{.metadata.labels['app.kubernetes.io/name']}").unwrap(),
+            vec![
+                PathStep::Key("metadata".into()),
+                PathStep::Key("labels".into()),
+                PathStep::Key("app.kubernetes.io/name".into()),
+            ]
+        );
+        assert_eq!(
+            parse_json_path("spec.containers[0]").unwrap(),
+            vec![
+                PathStep::Key("spec".into()),
+                PathStep::Key("containers".into()),
+                PathStep::Index(0),
+            ]
+        );
+        assert!(parse_json_path("").is_err());
+        assert!(parse_json_path(".spec..name").is_err());
+        assert!(parse_json_path(".labels[app]").is_err());
+    }
+
+    #[test]
+    fn json_path_evaluates_against_objects() {
+        let value = serde_json::json!({
+            "spec": {
+                "nodeName": "node-1",
+                "containers": [{"image": "nginx"}, {"image": "envoy"}],
+                "replicas": 3
+            }
+        });
+        let eval = |p: &str| eval_json_path(&value, &parse_json_path(p).unwrap());
+        assert_eq!(eval(".spec.nodeName"), "node-1");
+        assert_eq!(eval(".spec.containers[*].image"), "nginx,envoy");
+        assert_eq!(eval(".spec.containers[1].image"), "envoy");
+        assert_eq!(eval(".spec.replicas"), "3");
+        assert_eq!(eval(".spec.missing"), "");
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration_secs("10m"), Some(600));
+        assert_eq!(parse_duration_secs("45"), Some(45));
+        assert_eq!(parse_duration_secs("1d"), Some(86400));
+        assert_eq!(parse_duration_secs("m"), None);
+        assert_eq!(parse_duration_secs("5w"), None);
+    }
+
+    #[test]
+    fn delimited_quotes_csv_and_flattens_tsv() {
+        let rows = vec![
+            vec!["Name".to_string(), "Images".to_string()],
+            vec!["api".to_string(), "a:1,b:2".to_string()],
+            vec!["say \"hi\"".to_string(), "x\ty".to_string()],
+        ];
+        assert_eq!(
+            delimited(&rows, ','),
+            "Name,Images\napi,\"a:1,b:2\"\n\"say \"\"hi\"\"\",x\ty\n"
+        );
+        assert_eq!(
+            delimited(&rows, '\t'),
+            "Name\tImages\napi\ta:1,b:2\nsay \"hi\"\tx y\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn finds_executable_kubectl_plugins_on_path() {
+        use std::os::unix::fs::PermissionsExt;
+        let root = std::env::temp_dir().join(format!("kr-plugins-{}", std::process::id()));
+        let (first, second) = (root.join("a"), root.join("b"));
+        for dir in [&first, &second] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        let file = |dir: &std::path::Path, name: &str, mode: u32| {
+            let path = dir.join(name);
+            std::fs::write(&path, "#!/bin/sh\n").unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+        };
+        file(&first, "kubectl-view_secret", 0o755);
+        file(&first, "kubectl-", 0o755);
+        file(&first, "kubectl-notes", 0o644);
+        file(&first, "kr", 0o755);
+        file(&second, "kubectl-view_secret", 0o755);
+        file(&second, "kubectl-foo-bar", 0o755);
+
+        let path = std::env::join_paths([&first, &second, &root.join("missing")]).unwrap();
+        let plugins = find_kubectl_plugins(&path);
+        let _ = std::fs::remove_dir_all(&root);
+        assert_eq!(plugins, ["foo bar", "view-secret"]);
+    }
+
+    #[test]
+    fn endpoints_follow_ingresses_and_services_to_the_pods() {
+        use k8s_openapi::api::{core::v1::Service, networking::v1::Ingress};
+        let service = |name: &str, app: &str, extra: serde_json::Value| -> Service {
+            let mut value = serde_json::json!({
+                "metadata": {"name": name},
+                "spec": {"selector": {"app": app}, "ports": [{"port": 443, "name": "https"}]},
+            });
+            if let Some(status) = extra.get("status") {
+                value["status"] = status.clone();
+            }
+            serde_json::from_value(value).unwrap()
+        };
+        let services = [
+            service(
+                "web",
+                "web",
+                serde_json::json!({
+                    "status": {"loadBalancer": {"ingress": [{"ip": "203.0.113.7"}]}},
+                }),
+            ),
+            service("other", "other", serde_json::json!({})),
+        ];
+        let ingress: Ingress = serde_json::from_value(serde_json::json!({
+            "metadata": {"name": "public"},
+            "spec": {
+                "tls": [{"hosts": ["*.example.com"]}],
+                "rules": [
+                    {"host": "shop.example.com", "http": {"paths": [
+                        {"path": "/store", "pathType": "Prefix",
+                         "backend": {"service": {"name": "web", "port": {"number": 443}}}},
+                    ]}},
+                    {"host": "plain.example.org", "http": {"paths": [
+                        {"path": "/(.*)", "pathType": "ImplementationSpecific",
+                         "backend": {"service": {"name": "web", "port": {"number": 443}}}},
+                    ]}},
+                    {"host": "admin.example.com", "http": {"paths": [
+                        {"path": "/", "pathType": "Prefix",
+                         "backend": {"service": {"name": "other", "port": {"number": 80}}}},
+                    ]}},
+                ],
+            },
+        }))
+        .unwrap();
+        let labels = [("app".to_string(), "web".to_string())].into();
+
+        let urls: Vec<(String, String)> = workload_endpoints(&labels, &services, &[ingress])
+            .into_iter()
+            .map(|e| (e.source, e.url))
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                (
+                    "ingress public".into(),
+                    "https://shop.example.com/store".into()
+                ),
+                ("ingress public".into(), "http://plain.example.org/".into()),
+                (
+                    "service web (load balancer)".into(),
+                    "https://203.0.113.7/".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_secret_data_compares_binary_values_by_bytes() {
+        use crate::models::DiffStatus;
+        let binary =
+            |bytes: &[u8]| BTreeMap::from([("store.p12".to_string(), ByteString(bytes.to_vec()))]);
+        let rows = diff_secret_data(
+            &binary(&[0x30, 0x82, 0xff]),
+            &binary(&[0x30, 0x82, 0xfe, 0x00]),
+        );
+        assert_eq!(rows[0].status, DiffStatus::Changed);
+        assert_eq!(rows[0].left.as_deref(), Some("<binary, 3 bytes>"));
+        assert_eq!(rows[0].right.as_deref(), Some("<binary, 4 bytes>"));
+        let rows = diff_secret_data(&binary(&[0xff]), &binary(&[0xff]));
+        assert_eq!(rows[0].status, DiffStatus::Same);
+    }
 }
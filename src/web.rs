@@ -0,0 +1,40 @@
+//! HTTP(S) requests that bypass the API server: Prometheus by URL and
+//! webhooks.
+
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use std::time::Duration;
+
+/// How long a request may take, response body included.
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Sends `request`, returning the status and the body as text.
+pub async fn send(
+    request: http::Request<Full<Bytes>>,
+) -> Result<(http::StatusCode, String), String> {
+    tokio::time::timeout(SEND_TIMEOUT, exchange(request))
+        .await
+        .unwrap_or_else(|_| Err(format!("timed out after {}s", SEND_TIMEOUT.as_secs())))
+}
+
+async fn exchange(
+    request: http::Request<Full<Bytes>>,
+) -> Result<(http::StatusCode, String), String> {
+    let connector = hyper_rustls::HttpsConnectorBuilder::new()
+        .with_native_roots()
+        .map_err(|e| e.to_string())?
+        .https_or_http()
+        .enable_http1()
+        .build();
+    let client = hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
+        .build::<_, Full<Bytes>>(connector);
+    let response = client.request(request).await.map_err(|e| e.to_string())?;
+    let status = response.status();
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .map_err(|e| e.to_string())?
+        .to_bytes();
+    Ok((status, String::from_utf8_lossy(&body).into_owned()))
+}